@@ -1,6 +1,6 @@
 import test from 'ava'
 
-import { newLineageProof, newEveProof, Peer, selectCoins, mintStore, oracleSpend, addFee, masterPublicKeyToWalletSyntheticKey, masterPublicKeyToFirstPuzzleHash, masterSecretKeyToWalletSyntheticSecretKey, secretKeyToPublicKey, puzzleHashToAddress, addressToPuzzleHash, adminDelegatedPuzzleFromKey, writerDelegatedPuzzleFromKey, oracleDelegatedPuzzle, signCoinSpends, getCoinId, updateStoreMetadata, updateStoreOwnership, meltStore } from '../index.js';
+import { newLineageProof, newEveProof, Peer, selectCoins, mintStore, oracleSpend, addFee, masterPublicKeyToWalletSyntheticKey, masterPublicKeyToFirstPuzzleHash, masterSecretKeyToWalletSyntheticSecretKey, secretKeyToPublicKey, puzzleHashToAddress, addressToPuzzleHash, adminDelegatedPuzzleFromKey, writerDelegatedPuzzleFromKey, oracleDelegatedPuzzle, signCoinSpends, getCoinId, updateStoreMetadata, updateStoreOwnership, meltStore, syntheticKeyToPuzzleHash, Simulator, StoreSpendKind, StoreCache, DataTree, verifyInclusionProof, verifyExclusionProof, DataTreeChangeKind, TransactionBuilder, getMainnetGenesisChallenge, mintStoreWithFeePolicy, updateStoreMetadataWithFeePolicy, getCost, bumpFee, SpendStatusKind, DriverError, ConversionError, TransactionRejectedError, UnsignedTransaction, SigningError, signCoinSpendsWithSigner, decodeSpendIntent, SpendActionKind, validateSpendBundle, ValidationError, ConnectionError } from '../index.js';
 
 
 test('exports', (t) => {
@@ -25,4 +25,461 @@ test('exports', (t) => {
   t.assert(updateStoreOwnership);
   t.assert(meltStore);
   t.assert(Peer);
+  t.assert(Simulator);
+  t.assert(DataTree);
+})
+
+test('store lifecycle on a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 7));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+
+  const broadcast = async (coinSpends) => {
+    const sig = signCoinSpends(coinSpends, [secretKey], false);
+    t.is(await peer.broadcastSpend(coinSpends, [sig]), '');
+  };
+
+  const coin = simulator.newCoin(puzzleHash, 1000n);
+  const minted = mintStore(syntheticKey, [coin], Buffer.alloc(32, 1), 'label', null, null, puzzleHash, [], 10n);
+  await broadcast(minted.coinSpends);
+
+  const updated = updateStoreMetadata(minted.newStore, Buffer.alloc(32, 2), null, null, null, syntheticKey, null, null);
+  await broadcast(updated.coinSpends);
+
+  const synced = await peer.syncStoreFromLauncherId(minted.newStore.launcherId, null, simulator.genesisChallenge(), true);
+  t.deepEqual(synced.rootHashes, [Buffer.alloc(32, 1), Buffer.alloc(32, 2)]);
+  t.deepEqual(synced.latestStore.metadata.rootHash, Buffer.alloc(32, 2));
+  t.deepEqual(synced.history.map((entry) => entry.kind), [StoreSpendKind.Launch, StoreSpendKind.Owner]);
+  t.deepEqual(synced.history[1].previousMetadata.rootHash, Buffer.alloc(32, 1));
+  t.is(synced.history[1].headerHash.equals(simulator.headerHash(synced.history[1].height)), true);
+
+  const spent = peer.waitForCoinToBeSpent(getCoinId(synced.latestStore.coin), null, simulator.genesisChallenge());
+  await broadcast(meltStore(synced.latestStore, syntheticKey));
+  t.deepEqual(await spent, getCoinId(synced.latestStore.coin));
+  t.is(await peer.getPeak(), simulator.height());
+})
+
+test('watching a store on a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 7));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+
+  const broadcast = async (coinSpends) => {
+    const sig = signCoinSpends(coinSpends, [secretKey], false);
+    t.is(await peer.broadcastSpend(coinSpends, [sig]), '');
+  };
+
+  const coin = simulator.newCoin(puzzleHash, 1n);
+  const minted = mintStore(syntheticKey, [coin], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+  await broadcast(minted.coinSpends);
+
+  const watcher = await peer.watchStore(minted.newStore.launcherId);
+  t.deepEqual((await watcher.next()).store.metadata.rootHash, Buffer.alloc(32, 1));
+
+  // Leaving a `for await` loop early cancels the watcher.
+  const iterated = await peer.watchStore(minted.newStore.launcherId);
+  for await (const current of iterated) {
+    t.deepEqual(current.store.metadata.rootHash, Buffer.alloc(32, 1));
+    break;
+  }
+  t.is(await iterated.next(), null);
+
+  const updated = updateStoreMetadata(minted.newStore, Buffer.alloc(32, 2), null, null, null, syntheticKey, null, null);
+  await broadcast(updated.coinSpends);
+
+  const update = await watcher.next();
+  t.deepEqual(update.store.metadata.rootHash, Buffer.alloc(32, 2));
+  t.is(update.height, simulator.height());
+
+  await broadcast(meltStore(update.store, syntheticKey));
+  t.is(await watcher.next(), null);
+})
+
+test('caching a store on a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+  const cache = new StoreCache(null);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 7));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+
+  const broadcast = async (coinSpends) => {
+    const sig = signCoinSpends(coinSpends, [secretKey], false);
+    t.is(await peer.broadcastSpend(coinSpends, [sig]), '');
+  };
+
+  const coin = simulator.newCoin(puzzleHash, 1n);
+  const minted = mintStore(syntheticKey, [coin], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+  await broadcast(minted.coinSpends);
+  const launcherId = minted.newStore.launcherId;
+
+  t.is(cache.get(launcherId), null);
+  await peer.syncCachedStore(cache, launcherId, false);
+  t.deepEqual(cache.launcherIds(), [launcherId]);
+
+  const updated = updateStoreMetadata(minted.newStore, Buffer.alloc(32, 2), null, null, null, syntheticKey, null, null);
+  await broadcast(updated.coinSpends);
+
+  const synced = await peer.syncCachedStore(cache, launcherId, true);
+  t.deepEqual(synced.rootHashes, [Buffer.alloc(32, 1), Buffer.alloc(32, 2)]);
+  t.deepEqual(cache.get(launcherId).rootHashes, synced.rootHashes);
+})
+
+test('computing root hashes with a data tree', (t) => {
+  const tree = new DataTree();
+  t.deepEqual(tree.rootHash(), Buffer.alloc(32));
+
+  tree.insert(Buffer.from('a'), Buffer.from('1'));
+  const oneLeaf = tree.rootHash();
+  t.notDeepEqual(oneLeaf, Buffer.alloc(32));
+
+  t.is(tree.insert(Buffer.from('b'), Buffer.from('2')), null);
+  t.deepEqual(tree.get(Buffer.from('b')), Buffer.from('2'));
+  t.is(tree.len(), 2);
+
+  t.deepEqual(tree.delete(Buffer.from('b')), Buffer.from('2'));
+  t.deepEqual(tree.rootHash(), oneLeaf);
+  t.deepEqual(new DataTree(tree.entries()).rootHash(), oneLeaf);
+
+  t.deepEqual(tree.upsert(Buffer.from('a'), Buffer.from('2')), Buffer.from('1'));
+  t.deepEqual(tree.get(Buffer.from('a')), Buffer.from('2'));
+  t.is(tree.upsert(Buffer.from('b'), Buffer.from('2')), null);
+})
+
+test('proving keys against a root hash', (t) => {
+  const tree = new DataTree([
+    { key: Buffer.from('a'), value: Buffer.from('1') },
+    { key: Buffer.from('b'), value: Buffer.from('2') },
+    { key: Buffer.from('c'), value: Buffer.from('3') },
+  ]);
+  const rootHash = tree.rootHash();
+
+  const inclusion = tree.proveInclusion(Buffer.from('b'));
+  t.assert(verifyInclusionProof(inclusion, rootHash, Buffer.from('b'), Buffer.from('2')));
+  t.assert(!verifyInclusionProof(inclusion, rootHash, Buffer.from('b'), Buffer.from('3')));
+  t.is(tree.proveExclusion(Buffer.from('b')), null);
+
+  const exclusion = tree.proveExclusion(Buffer.from('d'));
+  t.assert(verifyExclusionProof(exclusion, rootHash, Buffer.from('d')));
+  t.assert(!verifyExclusionProof(exclusion, Buffer.alloc(32), Buffer.from('d')));
+  t.is(tree.proveInclusion(Buffer.from('d')), null);
+})
+
+test('diffing and applying deltas between data trees', (t) => {
+  const pairs = [...Array(50).keys()].map((i) => ({ key: Buffer.from(`key${i}`), value: Buffer.from(`value${i}`) }));
+  const oldTree = new DataTree(pairs);
+  const newTree = new DataTree(pairs);
+  newTree.delete(Buffer.from('key1'));
+  newTree.insert(Buffer.from('key2'), Buffer.from('updated'));
+  newTree.insert(Buffer.from('new'), Buffer.from('value'));
+
+  const changes = oldTree.diff(newTree);
+  t.deepEqual(changes.map((change) => [change.kind, change.key.toString()]), [
+    [DataTreeChangeKind.Delete, 'key1'],
+    [DataTreeChangeKind.Update, 'key2'],
+    [DataTreeChangeKind.Insert, 'new'],
+  ]);
+  t.deepEqual(changes[1].previousValue, Buffer.from('value2'));
+
+  const updatedTree = oldTree.applyDelta(oldTree.delta(newTree));
+  t.deepEqual(updatedTree.rootHash(), newTree.rootHash());
+  t.deepEqual(updatedTree.diff(newTree), []);
+})
+
+test('building one transaction for several stores on a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 8));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+
+  const broadcast = async (coinSpends) => {
+    const sig = signCoinSpends(coinSpends, [secretKey], false);
+    t.is(await peer.broadcastSpend(coinSpends, [sig]), '');
+  };
+
+  const stores = [];
+  for (const i of [1, 2]) {
+    const minted = mintStore(syntheticKey, [simulator.newCoin(puzzleHash, 1n)], Buffer.alloc(32, i), null, null, null, puzzleHash, [], 0n);
+    await broadcast(minted.coinSpends);
+    stores.push(minted.newStore);
+  }
+
+  const builder = new TransactionBuilder(syntheticKey);
+  builder.updateStoreMetadata(stores[0], Buffer.alloc(32, 3), null, null, null, syntheticKey, null, null);
+  builder.updateStoreMetadata(stores[1], Buffer.alloc(32, 4), null, null, null, syntheticKey, null, null);
+  builder.sendXch({ puzzleHash: Buffer.alloc(32, 9), amount: 100n, memos: [] });
+  builder.setFee(10n);
+  t.is(builder.requiredAmount(), 110n);
+
+  const transaction = builder.build([simulator.newCoin(puzzleHash, 1000n)], false);
+  t.is(transaction.change, 890n);
+  t.is(transaction.newStores.length, 2);
+  t.assert(transaction.requiredSignatures.length > 0);
+  await broadcast(transaction.coinSpends);
+
+  const synced = await peer.syncStore(stores[1], null, getMainnetGenesisChallenge(), false);
+  t.deepEqual(synced.latestStore.metadata.rootHash, Buffer.alloc(32, 4));
+})
+
+test('paying fees that cover the cost on a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 9));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const coins = [simulator.newCoin(puzzleHash, 1000000000000n), simulator.newCoin(puzzleHash, 1000000000000n)];
+
+  t.is((await peer.feePolicyForTargetTime(60n)).mojosPerCost, 0n);
+
+  const minted = mintStoreWithFeePolicy(syntheticKey, coins, Buffer.alloc(32, 1), null, null, null, puzzleHash, [], { mojosPerCost: 5n }, false);
+  t.is(minted.cost, getCost(minted.coinSpends, false));
+  t.assert(minted.fee >= minted.cost * 5n);
+  const sig = signCoinSpends(minted.coinSpends, [secretKey], false);
+  t.is(await peer.broadcastSpend(minted.coinSpends, [sig]), '');
+
+  const builder = new TransactionBuilder(syntheticKey);
+  builder.updateStoreMetadata(minted.newStore, Buffer.alloc(32, 2), null, null, null, syntheticKey, null, null);
+  const transaction = builder.buildWithFeePolicy([coins[1]], false, { fixed: 42n });
+  t.is(transaction.fee, 42n);
+  t.assert(transaction.cost > 0n);
+
+  const updated = updateStoreMetadataWithFeePolicy(minted.newStore, Buffer.alloc(32, 2), null, null, null, syntheticKey, null, null, syntheticKey, [simulator.newCoin(puzzleHash, 1000000000n)], { mojosPerCost: 5n }, false);
+  t.is(updated.cost, getCost(updated.coinSpends, false));
+  t.assert(updated.fee >= updated.cost * 5n);
+  const updateSig = signCoinSpends(updated.coinSpends, [secretKey], false);
+  t.is(await peer.broadcastSpend(updated.coinSpends, [updateSig]), '');
+})
+
+test('bumping the fee of a pending transaction on a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 10));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const coins = [simulator.newCoin(puzzleHash, 1000000000000n), simulator.newCoin(puzzleHash, 1000000000000n)];
+
+  const pending = mintStore(syntheticKey, [coins[0]], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+  const bumped = bumpFee(pending.coinSpends, syntheticKey, [coins[1]], { fixed: 0n }, false);
+  t.is(bumped.previousFee, 0n);
+  t.assert(bumped.fee >= 10000000n);
+  t.is(bumped.cost, getCost(bumped.coinSpends, false));
+
+  const sig = signCoinSpends(bumped.coinSpends, [secretKey], false);
+  const error = await t.throwsAsync(peer.broadcastFeeBump({ ...bumped, fee: bumped.previousFee }, [sig]), { instanceOf: TransactionRejectedError });
+  t.is(error.mempoolError, 'MEMPOOL_CONFLICT');
+  t.assert(Buffer.isBuffer(await peer.broadcastFeeBump(bumped, [sig])));
+})
+
+test('tracking a broadcast spend on a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 11));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const coin = simulator.newCoin(puzzleHash, 1000000n);
+
+  const minted = mintStore(syntheticKey, [coin], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+  const conflicting = mintStore(syntheticKey, [coin], Buffer.alloc(32, 2), null, null, null, puzzleHash, [], 0n);
+  const sig = signCoinSpends(minted.coinSpends, [secretKey], false);
+  t.is(await peer.broadcastSpend(minted.coinSpends, [sig]), '');
+  const height = simulator.height();
+
+  const tracker = await peer.trackSpend(minted.coinSpends, 3, null);
+  const statuses = [];
+  for await (const status of tracker) {
+    statuses.push(status);
+    if (status.kind === SpendStatusKind.Pending) {
+      simulator.farmBlocks(2);
+    }
+  }
+  t.deepEqual(statuses.map((status) => status.kind), [SpendStatusKind.Pending, SpendStatusKind.Confirmed]);
+  t.is(statuses[1].height, height);
+
+  const conflicted = await (await peer.trackSpend(conflicting.coinSpends, null, null)).next();
+  t.is(conflicted.kind, SpendStatusKind.Conflicted);
+  t.deepEqual(conflicted.coinId, getCoinId(coin));
+})
+
+test('throwing typed errors', async (t) => {
+  const coin = { parentCoinInfo: Buffer.alloc(31), puzzleHash: Buffer.alloc(32), amount: 1n };
+  const error = t.throws(() => getCoinId(coin), { instanceOf: ConversionError, code: 'INVALID_LENGTH' });
+  t.assert(error instanceof DriverError);
+  t.is(error.argument, 'coin.parentCoinInfo');
+  t.is(error.expectedLength, 32);
+  t.is(error.actualLength, 31);
+
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 12));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const minted = mintStore(syntheticKey, [simulator.newCoin(puzzleHash, 1000000n)], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+
+  const rejected = await t.throwsAsync(peer.sendSpend(minted.coinSpends, []), { instanceOf: TransactionRejectedError, code: 'TRANSACTION_REJECTED' });
+  t.is(rejected.status, 3);
+  t.assert(rejected.mempoolError);
+
+  const sig = signCoinSpends(minted.coinSpends, [secretKey], false);
+  t.is((await peer.sendSpend(minted.coinSpends, [sig])).length, 32);
+})
+
+test('signing an unsigned transaction offline', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 13));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const minted = mintStore(syntheticKey, [simulator.newCoin(puzzleHash, 1000000n)], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+
+  const unsigned = new UnsignedTransaction(minted.coinSpends, false, 'Mint a store');
+  t.deepEqual(unsigned.missingSignatures(), [syntheticKey]);
+  const missing = t.throws(() => unsigned.aggregatedSignature(), { instanceOf: SigningError, code: 'MISSING_SIGNATURES' });
+  t.deepEqual(missing.publicKeys, [syntheticKey]);
+
+  const offline = UnsignedTransaction.fromBytes(unsigned.toBytes());
+  t.is(offline.intent(), 'Mint a store');
+  t.falsy(offline.requiredSignatures()[0].signature);
+  t.is(offline.sign([secretKey]), 1);
+
+  t.is(unsigned.importSignatures(offline.toBytes()), 1);
+  t.deepEqual(unsigned.missingSignatures(), []);
+  t.is((await peer.sendSpend(unsigned.coinSpends(), [unsigned.aggregatedSignature()])).length, 32);
+
+  const tampered = unsigned.toBytes();
+  tampered[tampered.length - 1] ^= 1;
+  t.throws(() => UnsignedTransaction.fromBytes(tampered), { instanceOf: SigningError });
+})
+
+test('decoding what coin spends do', (t) => {
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 15));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const coin = { parentCoinInfo: Buffer.alloc(32, 1), puzzleHash, amount: 1000n };
+  const minted = mintStore(syntheticKey, [coin], Buffer.alloc(32, 1), 'label', null, null, puzzleHash, [], 10n);
+
+  const launch = decodeSpendIntent(minted.coinSpends);
+  t.is(launch.fee, 10n);
+  const launched = launch.spends.find((spend) => spend.kind === SpendActionKind.LaunchStore);
+  t.deepEqual(launched.launcherId, minted.newStore.launcherId);
+  t.is(launched.metadata.label, 'label');
+  const payment = launch.spends.find((spend) => spend.kind === SpendActionKind.Payment).payment;
+  t.deepEqual(payment.syntheticKey, syntheticKey);
+  t.is(payment.change, 989n);
+
+  const updated = updateStoreMetadata(minted.newStore, Buffer.alloc(32, 2), 'new label', null, null, syntheticKey, null, null);
+  const [update] = decodeSpendIntent(updated.coinSpends).spends;
+  t.is(update.kind, SpendActionKind.UpdateStore);
+  t.is(update.storeSpendKind, StoreSpendKind.Owner);
+  t.deepEqual(update.previousMetadata.rootHash, Buffer.alloc(32, 1));
+  t.deepEqual(update.metadata.rootHash, Buffer.alloc(32, 2));
+  t.deepEqual(update.ownerPuzzleHash, puzzleHash);
+  t.falsy(update.delegatedPuzzles);
+
+  const [melt] = decodeSpendIntent(meltStore(updated.newStore, syntheticKey)).spends;
+  t.is(melt.kind, SpendActionKind.MeltStore);
+})
+
+test('signing with a signer callback', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 14));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const minted = mintStore(syntheticKey, [simulator.newCoin(puzzleHash, 1000000n)], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+
+  // Stands in for a remote signer, which only answers for the keys it holds.
+  const remote = new UnsignedTransaction(minted.coinSpends, false, '');
+  remote.sign([secretKey]);
+  const signer = async (publicKey, message) =>
+    remote.requiredSignatures().find((request) => request.publicKey.equals(publicKey) && request.message.equals(message))?.signature ?? null;
+
+  const signature = await signCoinSpendsWithSigner(minted.coinSpends, signer, false);
+  t.deepEqual(signature, signCoinSpends(minted.coinSpends, [secretKey], false));
+
+  const missing = await t.throwsAsync(signCoinSpendsWithSigner(minted.coinSpends, () => null, false), { instanceOf: SigningError, code: 'MISSING_SIGNATURES' });
+  t.deepEqual(missing.publicKeys, [syntheticKey]);
+  await t.throwsAsync(signCoinSpendsWithSigner(minted.coinSpends, () => { throw new Error('device locked'); }, false), { instanceOf: SigningError, code: 'SIGNER_FAILED' });
+
+  t.is((await peer.sendSpend(minted.coinSpends, [signature])).length, 32);
+})
+
+test('validating a spend bundle before broadcasting', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 15));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+  const coin = simulator.newCoin(puzzleHash, 1000000n);
+  const minted = mintStore(syntheticKey, [coin], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 10n);
+  const sig = signCoinSpends(minted.coinSpends, [secretKey], false);
+
+  const conditions = await peer.validateSpendBundle(minted.coinSpends, [sig], false);
+  t.is(conditions.cost, validateSpendBundle(minted.coinSpends, [sig], false).cost);
+  t.is(conditions.fee, 10n);
+  t.assert(conditions.removals.some((removal) => getCoinId(removal).equals(getCoinId(coin))));
+  t.assert(conditions.additions.some((addition) => getCoinId(addition).equals(minted.newStore.coin.parentCoinInfo)));
+  t.is(conditions.spends.length, minted.coinSpends.length);
+
+  const invalid = t.throws(() => validateSpendBundle(minted.coinSpends, [], false), { instanceOf: ValidationError, code: 'INVALID_SPEND_BUNDLE' });
+  t.is(invalid.validationError, 'BadAggregateSignature');
+
+  await peer.sendSpend(minted.coinSpends, [sig]);
+  const spent = await t.throwsAsync(peer.validateSpendBundle(minted.coinSpends, [sig], false), { instanceOf: ValidationError, code: 'COIN_ALREADY_SPENT' });
+  t.deepEqual(spent.coinId, getCoinId(coin));
+})
+
+test('reconnecting to a simulator', async (t) => {
+  const simulator = new Simulator();
+  const peer = await Peer.fromSimulator(simulator);
+
+  const secretKey = masterSecretKeyToWalletSyntheticSecretKey(Buffer.alloc(32, 16));
+  const syntheticKey = secretKeyToPublicKey(secretKey);
+  const puzzleHash = syntheticKeyToPuzzleHash(syntheticKey);
+
+  const waitForSpend = (coin) => peer.waitForCoinToBeSpent(getCoinId(coin), null, simulator.genesisChallenge());
+  // Gives the peer time to subscribe to the coin before its connection drops.
+  const settle = () => new Promise((resolve) => setTimeout(resolve, 50));
+
+  // A coin spent while the peer is disconnected is found when it resubscribes.
+  const coin = simulator.newCoin(puzzleHash, 1000n);
+  const spent = waitForSpend(coin);
+  await settle();
+  simulator.refuseConnections(2);
+  simulator.disconnect();
+  const minted = mintStore(syntheticKey, [coin], Buffer.alloc(32, 1), null, null, null, puzzleHash, [], 0n);
+  t.is(await peer.broadcastSpend(minted.coinSpends, [signCoinSpends(minted.coinSpends, [secretKey], false)]), '');
+  t.deepEqual(await spent, getCoinId(coin));
+  t.assert(peer.isConnected());
+
+  // A coin that can't be subscribed to again fails its waiters instead of leaving them hanging.
+  const unsubscribed = waitForSpend(simulator.newCoin(puzzleHash, 1000n));
+  await settle();
+  simulator.failRequests(1);
+  simulator.disconnect();
+  await t.throwsAsync(unsubscribed, { instanceOf: ConnectionError, code: 'DISCONNECTED' });
+  t.assert(peer.isConnected());
+
+  // Once every reconnection attempt fails, everything waiting on the peer fails.
+  const stranded = waitForSpend(simulator.newCoin(puzzleHash, 1000n));
+  await settle();
+  simulator.refuseConnections(5);
+  simulator.disconnect();
+  await t.throwsAsync(stranded, { instanceOf: ConnectionError, code: 'DISCONNECTED' });
+  t.is(peer.isConnected(), false);
 })
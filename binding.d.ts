@@ -0,0 +1,1649 @@
+/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by NAPI-RS */
+
+/**
+ * Represents a coin on the Chia blockchain.
+ *
+ * @property {Buffer} parentCoinInfo - Parent coin name/id.
+ * @property {Buffer} puzzleHash - Puzzle hash.
+ * @property {BigInt} amount - Coin amount.
+ */
+export interface Coin {
+  parentCoinInfo: Buffer
+  puzzleHash: Buffer
+  amount: bigint
+}
+/**
+ * Represents a full coin state on the Chia blockchain.
+ *
+ * @property {Coin} coin - The coin.
+ * @property {Buffer} spentHeight - The height the coin was spent at, if it was spent.
+ * @property {Buffer} createdHeight - The height the coin was created at.
+ */
+export interface CoinState {
+  coin: Coin
+  spentHeight?: bigint
+  createdHeight?: bigint
+}
+/**
+ * Represents a coin spend on the Chia blockchain.
+ *
+ * @property {Coin} coin - The coin being spent.
+ * @property {Buffer} puzzleReveal - The puzzle of the coin being spent.
+ * @property {Buffer} solution - The solution.
+ */
+export interface CoinSpend {
+  coin: Coin
+  puzzleReveal: Buffer
+  solution: Buffer
+}
+/**
+ * Represents a lineage proof that can be used to spend a singleton.
+ *
+ * @property {Buffer} parentParentCoinInfo - Parent coin's parent coin info/name/ID.
+ * @property {Buffer} parentInnerPuzzleHash - Parent coin's inner puzzle hash.
+ * @property {BigInt} parentAmount - Parent coin's amount.
+ */
+export interface LineageProof {
+  parentParentCoinInfo: Buffer
+  parentInnerPuzzleHash: Buffer
+  parentAmount: bigint
+}
+/**
+ * Represents an eve proof that can be used to spend a singleton. Parent coin is the singleton launcher.
+ *
+ * @property {Buffer} parentParentCoinInfo - Parent coin's name.
+ * @property {BigInt} parentAmount - Parent coin's amount.
+ */
+export interface EveProof {
+  parentParentCoinInfo: Buffer
+  parentAmount: bigint
+}
+/**
+ * Represents a proof (either eve or lineage) that can be used to spend a singleton. Use `new_lineage_proof` or `new_eve_proof` to create a new proof.
+ *
+ * @property {Option<LineageProof>} lineageProof - The lineage proof, if this is a lineage proof.
+ * @property {Option<EveProof>} eveProof - The eve proof, if this is an eve proof.
+ */
+export interface Proof {
+  lineageProof?: LineageProof
+  eveProof?: EveProof
+}
+/**
+ * Represents a mirror coin with a potentially morphed launcher id.
+ *
+ * @property {Coin} coin - The coin.
+ * @property {Buffer} p2PuzzleHash - The puzzle hash that owns the server coin.
+ * @property {Array<string>} memoUrls - The memo URLs that serve the data store being mirrored.
+ */
+export interface ServerCoin {
+  coin: Coin
+  p2PuzzleHash: Buffer
+  memoUrls: Array<string>
+}
+/**
+ * Describes a Chia network. Use `getMainnetNetwork` or `getTestnet11Network` for the public networks.
+ *
+ * @property {string} networkId - Network id sent in the peer handshake (e.g., 'mainnet', 'testnet11' or 'simulator0').
+ * @property {Buffer} genesisChallenge - Genesis challenge of the network.
+ * @property {Option<Buffer>} aggSigMe - AGG_SIG_ME additional data. Defaults to the genesis challenge.
+ * @property {string} addressPrefix - Address prefix (e.g., 'xch').
+ * @property {number} defaultPort - Default full node port.
+ * @property {Option<Array<string>>} dnsIntroducers - Host names of the DNS introducers used for peer discovery.
+ * @property {Option<Array<string>>} introducers - Host names of the introducer nodes used for peer discovery.
+ */
+export interface Network {
+  networkId: string
+  genesisChallenge: Buffer
+  aggSigMe?: Buffer
+  addressPrefix: string
+  defaultPort: number
+  dnsIntroducers?: Array<string>
+  introducers?: Array<string>
+}
+/**
+ * Creates a new lineage proof.
+ *
+ * @param {LineageProof} lineageProof - The lineage proof.
+ * @returns {Proof} The new proof.
+ */
+export declare function newLineageProof(lineageProof: LineageProof): Proof
+/**
+ * Creates a new eve proof.
+ *
+ * @param {EveProof} eveProof - The eve proof.
+ * @returns {Proof} The new proof.
+ */
+export declare function newEveProof(eveProof: EveProof): Proof
+/**
+ * Represents metadata for a data store.
+ *
+ * @property {Buffer} rootHash - Root hash.
+ * @property {Option<String>} label - Label (optional).
+ * @property {Option<String>} description - Description (optional).
+ * @property {Option<BigInt>} bytes - Size of the store in bytes (optional).
+ */
+export interface DataStoreMetadata {
+  rootHash: Buffer
+  label?: string
+  description?: string
+  bytes?: bigint
+}
+/**
+ * Represents information about a delegated puzzle. Note that this struct can represent all three types of delegated puzzles, but only represents one at a time.
+ *
+ * @property {Option<Buffer>} adminInnerPuzzleHash - Admin inner puzzle hash, if this is an admin delegated puzzle.
+ * @property {Option<Buffer>} writerInnerPuzzleHash - Writer inner puzzle hash, if this is a writer delegated puzzle.
+ * @property {Option<Buffer>} oraclePaymentPuzzleHash - Oracle payment puzzle hash, if this is an oracle delegated puzzle.
+ * @property {Option<BigInt>} oracleFee - Oracle fee, if this is an oracle delegated puzzle.
+ */
+export interface DelegatedPuzzle {
+  adminInnerPuzzleHash?: Buffer
+  writerInnerPuzzleHash?: Buffer
+  oraclePaymentPuzzleHash?: Buffer
+  oracleFee?: bigint
+}
+/**
+ * Represents information about a data store. This information can be used to spend the store. It is recommended that this struct is stored in a database to avoid syncing it every time.
+ *
+ * @property {Coin} coin - The coin associated with the data store.
+ * @property {Buffer} launcherId - The store's launcher/singleton ID.
+ * @property {Proof} proof - Proof that can be used to spend this store.
+ * @property {DataStoreMetadata} metadata - This store's metadata.
+ * @property {Buffer} ownerPuzzleHash - The puzzle hash of the owner puzzle.
+ * @property {Vec<DelegatedPuzzle>} delegatedPuzzles - This store's delegated puzzles. An empty list usually indicates a 'vanilla' store.
+ */
+export interface DataStore {
+  coin: Coin
+  launcherId: Buffer
+  proof: Proof
+  metadata: DataStoreMetadata
+  ownerPuzzleHash: Buffer
+  delegatedPuzzles: Array<DelegatedPuzzle>
+}
+/**
+ *
+ * @property {Vec<CoinSpend>} coinSpends - Coin spends that can be used to spend the provided store.
+ * @property {DataStore} newStore - New data store information after the spend is confirmed.
+ */
+export interface SuccessResponse {
+  coinSpends: Array<CoinSpend>
+  newStore: DataStore
+}
+/**
+ * Represents a response from synchronizing a store.
+ *
+ * @property {DataStore} latestStore - Latest data store information.
+ * @property {Option<Vec<Buffer>>} rootHashes - When synced with whistory, this list will contain all of the store's previous root hashes. Otherwise null.
+ * @property {Option<Vec<BigInt>>} rootHashesTimestamps - Timestamps of the root hashes (see `rootHashes`).
+ * @property {Option<Vec<StoreHistoryEntry>>} history - The spends that set the root hashes (see `rootHashes`), one entry per root hash.
+ * @property {u32} latestHeight - Latest sync height.
+ * @property {Option<StoreReorg>} reorg - Set if the block sync started from is no longer part of the chain. In that case, `rootHashes` and `history` start where `knownHistory` started, or at the store's launch.
+ */
+export interface SyncStoreResponse {
+  latestStore: DataStore
+  rootHashes?: Array<Buffer>
+  rootHashesTimestamps?: Array<bigint>
+  history?: Array<StoreHistoryEntry>
+  latestHeight: number
+  reorg?: StoreReorg
+}
+/** What authorized a store spend. */
+export const enum StoreSpendKind {
+  Launch = 'Launch',
+  Owner = 'Owner',
+  Admin = 'Admin',
+  Writer = 'Writer',
+  Oracle = 'Oracle'
+}
+/**
+ * Represents a confirmed spend of a store.
+ *
+ * @property {StoreSpendKind} kind - What authorized the spend.
+ * @property {Option<DelegatedPuzzle>} delegatedPuzzle - The delegated puzzle that authorized the spend. Null for launches and owner spends.
+ * @property {Buffer} coinId - ID of the spent coin (the launcher or the store's previous coin).
+ * @property {u32} height - Height of the block the spend was confirmed in.
+ * @property {Buffer} headerHash - Header hash of that block.
+ * @property {BigInt} timestamp - Timestamp of that block.
+ * @property {Option<DataStoreMetadata>} previousMetadata - Metadata before the spend. Null for launches.
+ * @property {DataStoreMetadata} metadata - Metadata after the spend.
+ * @property {Option<Buffer>} previousOwnerPuzzleHash - Owner puzzle hash before the spend. Null for launches.
+ * @property {Buffer} ownerPuzzleHash - Owner puzzle hash after the spend.
+ * @property {Option<Vec<DelegatedPuzzle>>} previousDelegatedPuzzles - Delegated puzzles before the spend. Null for launches.
+ * @property {Vec<DelegatedPuzzle>} delegatedPuzzles - Delegated puzzles after the spend.
+ */
+export interface StoreHistoryEntry {
+  kind: StoreSpendKind
+  delegatedPuzzle?: DelegatedPuzzle
+  coinId: Buffer
+  height: number
+  headerHash: Buffer
+  timestamp: bigint
+  previousMetadata?: DataStoreMetadata
+  metadata: DataStoreMetadata
+  previousOwnerPuzzleHash?: Buffer
+  ownerPuzzleHash: Buffer
+  previousDelegatedPuzzles?: Array<DelegatedPuzzle>
+  delegatedPuzzles: Array<DelegatedPuzzle>
+}
+/**
+ * Represents a reorg detected while synchronizing a store. The store is synced again from the newest state of `knownHistory` that is still part of the chain.
+ *
+ * @property {Option<u32>} forkHeight - Height of the newest known state that is still part of the chain. Null if there is none and the store was synced again from its launcher.
+ * @property {Vec<Buffer>} orphanedRootHashes - Root hashes of `knownHistory` that are no longer part of the chain, oldest first. Spends that were included again on the new chain aren't listed.
+ */
+export interface StoreReorg {
+  forkHeight?: number
+  orphanedRootHashes: Array<Buffer>
+}
+/**
+ * Represents the result of synchronizing one store of a batch.
+ *
+ * @property {Buffer} launcherId - The store's launcher/singleton ID.
+ * @property {Option<SyncStoreResponse>} response - The sync store response. Null if the store couldn't be synced.
+ * @property {Option<String>} error - Why the store couldn't be synced. Null if it was synced.
+ */
+export interface BatchSyncResult {
+  launcherId: Buffer
+  response?: SyncStoreResponse
+  error?: string
+}
+/**
+ * Represents a new state of a watched store.
+ *
+ * @property {DataStore} store - The store's new state.
+ * @property {u32} height - Height of the block the state was confirmed in.
+ * @property {BigInt} timestamp - Timestamp of that block.
+ */
+export interface StoreUpdate {
+  store: DataStore
+  height: number
+  timestamp: bigint
+}
+/**
+ * Represents a response containing unspent coins.
+ *
+ * @property {Vec<Coin>} coins - Unspent coins.
+ * @property {u32} lastHeight - Last height.
+ * @property {Buffer} lastHeaderHash - Last header hash.
+ */
+export interface UnspentCoinsResponse {
+  coins: Array<Coin>
+  lastHeight: number
+  lastHeaderHash: Buffer
+}
+/**
+ * Represents a response containing possible launcher ids for datastores.
+ *
+ * @property {Vec<Buffer>} launcher_ids - Launcher ids of coins that might be datastores.
+ * @property {u32} lastHeight - Last height.
+ * @property {Buffer} lastHeaderHash - Last header hash.
+ */
+export interface PossibleLaunchersResponse {
+  launcherIds: Array<Buffer>
+  lastHeight: number
+  lastHeaderHash: Buffer
+}
+/** Where a tracked spend bundle stands. */
+export const enum SpendStatusKind {
+  Pending = 'Pending',
+  Confirmed = 'Confirmed',
+  Conflicted = 'Conflicted',
+  Dropped = 'Dropped'
+}
+/**
+ * A status of a tracked spend bundle.
+ *
+ * @property {SpendStatusKind} kind - Whether the bundle is pending, confirmed, conflicted or dropped.
+ * @property {Option<u32>} height - Height of the block the bundle (or the conflicting spend) is in. Null if the bundle is pending or dropped.
+ * @property {Option<Buffer>} coinId - The coin spent by a conflicting spend. Null unless the bundle is conflicted.
+ */
+export interface SpendStatus {
+  kind: SpendStatusKind
+  height?: number
+  coinId?: Buffer
+}
+/**
+ * Finds full nodes through the network's DNS introducers, or by asking its introducers if DNS doesn't return any nodes.
+ *
+ * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+ * @param {Tls} tls - TLS connector, used to talk to introducers.
+ * @returns {Promise<Vec<String>>} URIs of the discovered nodes (e.g., ['1.2.3.4:8444']).
+ */
+export declare function discoverPeers(network: boolean | Network, tls: Tls): Promise<Array<string>>
+/**
+ * A key/value pair in a data tree.
+ *
+ * @property {Buffer} key - Key.
+ * @property {Buffer} value - Value.
+ */
+export interface KeyValue {
+  key: Buffer
+  value: Buffer
+}
+/** How a key changed between two data trees. */
+export const enum DataTreeChangeKind {
+  Insert = 'Insert',
+  Delete = 'Delete',
+  Update = 'Update'
+}
+/**
+ * A key that changed between two data trees.
+ *
+ * @property {DataTreeChangeKind} kind - Whether the key was inserted, deleted or given a new value.
+ * @property {Buffer} key - Key.
+ * @property {Option<Buffer>} previousValue - Value in the old tree, or null if the key was inserted.
+ * @property {Option<Buffer>} value - Value in the new tree, or null if the key was deleted.
+ */
+export interface DataTreeChange {
+  kind: DataTreeChangeKind
+  key: Buffer
+  previousValue?: Buffer
+  value?: Buffer
+}
+/**
+ * How the fee of a transaction is determined. Exactly one of the fields must be set. Use `Peer.feePolicyForTargetTime` to get a policy for a confirmation time.
+ *
+ * @property {Option<BigInt>} fixed - A fixed fee, regardless of the transaction's cost.
+ * @property {Option<BigInt>} mojosPerCost - A fee of this many mojos per unit of the transaction's CLVM cost.
+ */
+export interface FeePolicy {
+  fixed?: bigint
+  mojosPerCost?: bigint
+}
+/**
+ * A driver response for a spend whose fee covers its own cost.
+ *
+ * @property {Vec<CoinSpend>} coinSpends - Coin spends that can be used to spend the provided store.
+ * @property {DataStore} newStore - New data store information after the spend is confirmed.
+ * @property {BigInt} fee - Fee paid.
+ * @property {BigInt} cost - CLVM cost of the coin spends.
+ */
+export interface PricedSuccessResponse {
+  coinSpends: Array<CoinSpend>
+  newStore: DataStore
+  fee: bigint
+  cost: bigint
+}
+/**
+ * A pending transaction rebuilt with a higher fee.
+ *
+ * @property {Vec<CoinSpend>} coinSpends - The new coin spends. They have to be signed again before they're broadcast.
+ * @property {BigInt} fee - New fee.
+ * @property {BigInt} cost - New CLVM cost.
+ * @property {BigInt} previousFee - Fee of the pending transaction.
+ * @property {BigInt} previousCost - CLVM cost of the pending transaction.
+ */
+export interface FeeBump {
+  coinSpends: Array<CoinSpend>
+  fee: bigint
+  cost: bigint
+  previousFee: bigint
+  previousCost: bigint
+}
+/**
+ * A signature a transaction needs.
+ *
+ * @property {Buffer} publicKey - Public key that has to sign.
+ * @property {Buffer} message - Final message to sign, including any additional data.
+ */
+export interface RequiredSignature {
+  publicKey: Buffer
+  message: Buffer
+}
+/**
+ * A transaction built by a `TransactionBuilder`.
+ *
+ * @property {Vec<CoinSpend>} coinSpends - Coin spends of the whole transaction, to be signed and broadcast together.
+ * @property {Vec<DataStore>} newStores - States of the stores after the transaction, in the order they were first used. Melted stores are left out.
+ * @property {Vec<Coin>} selectedCoins - Wallet coins spent by the transaction.
+ * @property {BigInt} change - Change sent back to the wallet.
+ * @property {BigInt} fee - Fee paid, including the mojos of melted stores.
+ * @property {BigInt} cost - CLVM cost of the transaction.
+ * @property {Vec<RequiredSignature>} requiredSignatures - Signatures the transaction needs.
+ */
+export interface Transaction {
+  coinSpends: Array<CoinSpend>
+  newStores: Array<DataStore>
+  selectedCoins: Array<Coin>
+  change: bigint
+  fee: bigint
+  cost: bigint
+  requiredSignatures: Array<RequiredSignature>
+}
+/**
+ * A signature an unsigned transaction needs.
+ *
+ * @property {Buffer} publicKey - Public key that has to sign.
+ * @property {Buffer} message - Final message to sign, including any additional data.
+ * @property {Option<Buffer>} signature - The signature, once it's been added.
+ */
+export interface SignatureRequest {
+  publicKey: Buffer
+  message: Buffer
+  signature?: Buffer
+}
+/** What a decoded coin spend does. */
+export const enum SpendActionKind {
+  LaunchStore = 'LaunchStore',
+  UpdateStore = 'UpdateStore',
+  MeltStore = 'MeltStore',
+  PayOracle = 'PayOracle',
+  Payment = 'Payment',
+  Unknown = 'Unknown'
+}
+/**
+ * A coin created by a standard coin spend.
+ *
+ * @property {Buffer} puzzleHash - Puzzle hash of the coin.
+ * @property {BigInt} amount - Amount of the coin.
+ * @property {Vec<Buffer>} memos - Memos of the coin, e.g. its hint.
+ */
+export interface PaymentOutput {
+  puzzleHash: Buffer
+  amount: bigint
+  memos: Array<Buffer>
+}
+/**
+ * A server coin created by a standard coin spend.
+ *
+ * @property {Buffer} hint - The hint of the server coin, usually the original or morphed launcher id.
+ * @property {BigInt} amount - Amount of the server coin.
+ * @property {Vec<String>} memoUrls - The URLs the server coin advertises.
+ */
+export interface ServerCoinOutput {
+  hint: Buffer
+  amount: bigint
+  memoUrls: Array<string>
+}
+/**
+ * A decoded standard coin spend.
+ *
+ * @property {Buffer} syntheticKey - Synthetic key of the spent coin.
+ * @property {Vec<PaymentOutput>} outputs - Coins created for other puzzle hashes, except server coins.
+ * @property {Vec<ServerCoinOutput>} serverCoins - Server coins created.
+ * @property {BigInt} change - Amount sent back to the spent coin's puzzle hash.
+ */
+export interface Payment {
+  syntheticKey: Buffer
+  outputs: Array<PaymentOutput>
+  serverCoins: Array<ServerCoinOutput>
+  change: bigint
+}
+/**
+ * What a coin spend does, decoded by running its puzzle.
+ *
+ * @property {Coin} coin - The spent coin.
+ * @property {SpendActionKind} kind - What the spend does.
+ * @property {Vec<Coin>} additions - Coins the spend creates.
+ * @property {BigInt} reservedFee - Fee the spend reserves with RESERVE_FEE conditions.
+ * @property {Option<Buffer>} launcherId - The store's launcher id. Null unless the spend launches, updates, melts or pays the oracle of a store.
+ * @property {Option<StoreSpendKind>} storeSpendKind - What authorized a store update. Null for other spends.
+ * @property {Option<DelegatedPuzzle>} delegatedPuzzle - The delegated puzzle that authorized a store update. Null for other spends and owner spends.
+ * @property {Option<DataStoreMetadata>} previousMetadata - Metadata before a store update. Null for other spends.
+ * @property {Option<DataStoreMetadata>} metadata - Metadata of a launched store, or after a store update. Null for other spends.
+ * @property {Option<Buffer>} previousOwnerPuzzleHash - Owner puzzle hash before a store update. Null for other spends.
+ * @property {Option<Buffer>} ownerPuzzleHash - Owner puzzle hash of a launched or melted store, or after a store update. Null for other spends.
+ * @property {Option<Vec<DelegatedPuzzle>>} delegatedPuzzles - Delegated puzzles of a launched store, or after a store update that replaces them. Null for other spends and updates that keep them.
+ * @property {Option<Buffer>} oraclePuzzleHash - Puzzle hash the oracle fee is paid to. Null unless the spend pays a store's oracle.
+ * @property {Option<BigInt>} oracleFee - The oracle fee. Null unless the spend pays a store's oracle.
+ * @property {Option<Payment>} payment - The decoded standard coin spend. Null for other spends.
+ */
+export interface DecodedSpend {
+  coin: Coin
+  kind: SpendActionKind
+  additions: Array<Coin>
+  reservedFee: bigint
+  launcherId?: Buffer
+  storeSpendKind?: StoreSpendKind
+  delegatedPuzzle?: DelegatedPuzzle
+  previousMetadata?: DataStoreMetadata
+  metadata?: DataStoreMetadata
+  previousOwnerPuzzleHash?: Buffer
+  ownerPuzzleHash?: Buffer
+  delegatedPuzzles?: Array<DelegatedPuzzle>
+  oraclePuzzleHash?: Buffer
+  oracleFee?: bigint
+  payment?: Payment
+}
+/**
+ * What a set of coin spends does, decoded by running their puzzles.
+ *
+ * @property {Vec<DecodedSpend>} spends - One entry per coin spend, in the same order.
+ * @property {BigInt} fee - Amount of the spent coins minus the amount of the created coins, i.e. what the farmer gets.
+ */
+export interface SpendIntent {
+  spends: Array<DecodedSpend>
+  fee: bigint
+}
+/**
+ * Decodes what coin spends do, e.g. to review a transaction before signing it. Each spend is decoded on its own from its puzzle and solution, without looking up any coins.
+ *
+ * @param {Vec<CoinSpend>} coinSpends - The coin spends to decode.
+ * @returns {SpendIntent} What each coin spend does and the fee.
+ */
+export declare function decodeSpendIntent(coinSpends: Array<CoinSpend>): SpendIntent
+/**
+ * The conditions of a coin spend, as checked by the mempool.
+ *
+ * @property {Coin} coin - The spent coin.
+ * @property {Vec<Coin>} additions - Coins the spend creates.
+ * @property {Option<u32>} heightRelative - Blocks that must pass after the coin was created (ASSERT_HEIGHT_RELATIVE).
+ * @property {Option<BigInt>} secondsRelative - Seconds that must pass after the coin was created (ASSERT_SECONDS_RELATIVE).
+ * @property {Option<u32>} beforeHeightRelative - Blocks after the coin was created before which the spend must be included (ASSERT_BEFORE_HEIGHT_RELATIVE).
+ * @property {Option<BigInt>} beforeSecondsRelative - Seconds after the coin was created before which the spend must be included (ASSERT_BEFORE_SECONDS_RELATIVE).
+ * @property {Option<u32>} birthHeight - Height the coin must have been created at (ASSERT_MY_BIRTH_HEIGHT).
+ * @property {Option<BigInt>} birthSeconds - Timestamp the coin must have been created at (ASSERT_MY_BIRTH_SECONDS).
+ */
+export interface SpendConditions {
+  coin: Coin
+  additions: Array<Coin>
+  heightRelative?: number
+  secondsRelative?: bigint
+  beforeHeightRelative?: number
+  beforeSecondsRelative?: bigint
+  birthHeight?: number
+  birthSeconds?: bigint
+}
+/**
+ * The conditions of a validated spend bundle.
+ *
+ * @property {Vec<SpendConditions>} spends - One entry per coin spend.
+ * @property {Vec<Coin>} additions - Coins the bundle creates.
+ * @property {Vec<Coin>} removals - Coins the bundle spends.
+ * @property {BigInt} fee - Amount of the removals minus the amount of the additions.
+ * @property {BigInt} reserveFee - Minimum fee the spends reserve with RESERVE_FEE conditions.
+ * @property {u32} heightAbsolute - Height after which the bundle can be included (ASSERT_HEIGHT_ABSOLUTE).
+ * @property {BigInt} secondsAbsolute - Timestamp after which the bundle can be included (ASSERT_SECONDS_ABSOLUTE).
+ * @property {Option<u32>} beforeHeightAbsolute - Height before which the bundle must be included (ASSERT_BEFORE_HEIGHT_ABSOLUTE).
+ * @property {Option<BigInt>} beforeSecondsAbsolute - Timestamp before which the bundle must be included (ASSERT_BEFORE_SECONDS_ABSOLUTE).
+ * @property {BigInt} cost - Cost of the bundle.
+ */
+export interface SpendBundleConditions {
+  spends: Array<SpendConditions>
+  additions: Array<Coin>
+  removals: Array<Coin>
+  fee: bigint
+  reserveFee: bigint
+  heightAbsolute: number
+  secondsAbsolute: bigint
+  beforeHeightAbsolute?: number
+  beforeSecondsAbsolute?: bigint
+  cost: bigint
+}
+/**
+ * Validates a spend bundle like the mempool would, without looking up its coins: runs the puzzles, checks their conditions, including announcements and concurrent spend assertions, and verifies the aggregated signature. Throws a `ValidationError` with code `INVALID_SPEND_BUNDLE` if the bundle is invalid. Use `Peer.validateSpendBundle` to also check that the coins are unspent.
+ *
+ * @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+ * @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+ * @param {Option<bool | Network>} network - Network whose consensus constants are used. True for testnet11, false for mainnet, or a network descriptor. Defaults to mainnet.
+ * @returns {SpendBundleConditions} The conditions of the bundle.
+ */
+export declare function validateSpendBundle(coinSpends: Array<CoinSpend>, sigs: Array<Buffer>, network?: boolean | Network | undefined | null): SpendBundleConditions
+/**
+ * Selects coins using the knapsack algorithm.
+ *
+ * @param {Vec<Coin>} allCoins - Array of available coins (coins to select from).
+ * @param {BigInt} totalAmount - Amount needed for the transaction, including fee.
+ * @returns {Vec<Coin>} Array of selected coins.
+ */
+export declare function selectCoins(allCoins: Array<Coin>, totalAmount: bigint): Array<Coin>
+/** An output puzzle hash and amount. */
+export interface Output {
+  puzzleHash: Buffer
+  amount: bigint
+  memos: Array<Buffer>
+}
+/**
+ * Sends XCH to a given set of puzzle hashes.
+ *
+ * @param {Buffer} syntheticKey - The synthetic key used by the wallet.
+ * @param {Vec<Coin>} selectedCoins - Coins to be spent, as retured by `select_coins`.
+ * @param {Vec<Output>} outputs - The output amounts to create.
+ * @param {BigInt} fee - The fee to use for the transaction.
+ */
+export declare function sendXch(syntheticKey: Buffer, selectedCoins: Array<Coin>, outputs: Array<Output>, fee: bigint): Array<CoinSpend>
+/**
+ * Adds an offset to a launcher id to make it deterministically unique from the original.
+ *
+ * @param {Buffer} launcherId - The original launcher id.
+ * @param {BigInt} offset - The offset to add.
+ */
+export declare function morphLauncherId(launcherId: Buffer, offset: bigint): Buffer
+/** The new server coin and coin spends to create it. */
+export interface NewServerCoin {
+  serverCoin: ServerCoin
+  coinSpends: Array<CoinSpend>
+}
+/**
+ * Creates a new mirror coin with the given URLs.
+ *
+ * @param {Buffer} syntheticKey - The synthetic key used by the wallet.
+ * @param {Vec<Coin>} selectedCoins - Coins to be used for minting, as retured by `select_coins`. Note that, besides the fee, 1 mojo will be used to create the mirror coin.
+ * @param {Buffer} hint - The hint for the mirror coin, usually the original or morphed launcher id.
+ * @param {Vec<String>} uris - The URIs of the mirrors.
+ * @param {BigInt} amount - The amount to use for the created coin.
+ * @param {BigInt} fee - The fee to use for the transaction.
+ */
+export declare function createServerCoin(syntheticKey: Buffer, selectedCoins: Array<Coin>, hint: Buffer, uris: Array<string>, amount: bigint, fee: bigint): NewServerCoin
+/**
+ * Mints a new datastore.
+ *
+ * @param {Buffer} minterSyntheticKey - Minter synthetic key.
+ * @param {Vec<Coin>} selectedCoins - Coins to be used for minting, as retured by `select_coins`. Note that, besides the fee, 1 mojo will be used to create the new store.
+ * @param {Buffer} rootHash - Root hash of the store.
+ * @param {Option<String>} label - Store label (optional).
+ * @param {Option<String>} description - Store description (optional).
+ * @param {Option<BigInt>} bytes - Store size in bytes (optional).
+ * @param {Buffer} ownerPuzzleHash - Owner puzzle hash.
+ * @param {Vec<DelegatedPuzzle>} delegatedPuzzles - Delegated puzzles.
+ * @param {BigInt} fee - Fee to use for the transaction. Total amount - 1 - fee will be sent back to the minter.
+ * @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+ */
+export declare function mintStore(minterSyntheticKey: Buffer, selectedCoins: Array<Coin>, rootHash: Buffer, label: string | undefined | null, description: string | undefined | null, bytes: bigint | undefined | null, ownerPuzzleHash: Buffer, delegatedPuzzles: Array<DelegatedPuzzle>, fee: bigint): SuccessResponse
+/**
+ * Mints a new datastore like `mintStore`, selecting coins and raising the fee until it covers the cost of the mint.
+ *
+ * @param {Buffer} minterSyntheticKey - Minter synthetic key.
+ * @param {Vec<Coin>} coins - Wallet coins to select from.
+ * @param {Buffer} rootHash - Root hash of the store.
+ * @param {Option<String>} label - Store label (optional).
+ * @param {Option<String>} description - Store description (optional).
+ * @param {Option<BigInt>} bytes - Store size in bytes (optional).
+ * @param {Buffer} ownerPuzzleHash - Owner puzzle hash.
+ * @param {Vec<DelegatedPuzzle>} delegatedPuzzles - Delegated puzzles.
+ * @param {FeePolicy} feePolicy - How the fee is determined.
+ * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the cost.
+ * @returns {PricedSuccessResponse} The coin spends, information about the new datastore, and the fee and cost of the mint.
+ */
+export declare function mintStoreWithFeePolicy(minterSyntheticKey: Buffer, coins: Array<Coin>, rootHash: Buffer, label: string | undefined | null, description: string | undefined | null, bytes: bigint | undefined | null, ownerPuzzleHash: Buffer, delegatedPuzzles: Array<DelegatedPuzzle>, feePolicy: FeePolicy, network: boolean | Network): PricedSuccessResponse
+/**
+ * Spends a store in oracle mode.
+ *
+ * @param {Buffer} spenderSyntheticKey - Spender synthetic key.
+ * @param {Vec<Coin>} selectedCoins - Selected coins, as returned by `select_coins`.
+ * @param {DataStore} store - Up-to-daye store information.
+ * @param {BigInt} fee - Transaction fee to use.
+ * @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+ */
+export declare function oracleSpend(spenderSyntheticKey: Buffer, selectedCoins: Array<Coin>, store: DataStore, fee: bigint): SuccessResponse
+/**
+ * Rebuilds a pending transaction with a higher fee, so that it replaces the pending one in the mempool. Every coin of the pending transaction is spent again, and the fee grows by at least 0.00001 XCH and to a higher fee per cost, as full nodes require. The extra fee is taken out of the wallet's change; if there isn't enough, fee coins are spent too.
+ *
+ * @param {Vec<CoinSpend>} coinSpends - Coin spends of the pending transaction.
+ * @param {Buffer} syntheticKey - Synthetic key of the wallet that pays the fee.
+ * @param {Vec<Coin>} feeCoins - Wallet coins that can be spent if the change doesn't cover the fee.
+ * @param {FeePolicy} feePolicy - How the new fee is determined. The replacement rules apply on top of it.
+ * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute fees and costs.
+ * @returns {FeeBump} The new coin spends, to be signed and broadcast with `Peer.broadcastFeeBump`, with the old and new fee and cost.
+ */
+export declare function bumpFee(coinSpends: Array<CoinSpend>, syntheticKey: Buffer, feeCoins: Array<Coin>, feePolicy: FeePolicy, network: boolean | Network): FeeBump
+/**
+ * Adds a fee to any transaction. Change will be sent to spender.
+ *
+ * @param {Buffer} spenderSyntheticKey - Synthetic key of spender.
+ * @param {Vec<Coin>} selectedCoins - Selected coins, as returned by `select_coins`.
+ * @param {Vec<Buffer>} assertCoinIds - IDs of coins that need to be spent for the fee to be paid. Usually all coin ids in the original transaction.
+ * @param {BigInt} fee - Fee to add.
+ * @returns {Vec<CoinSpend>} The coin spends to be added to the original transaction.
+ */
+export declare function addFee(spenderSyntheticKey: Buffer, selectedCoins: Array<Coin>, assertCoinIds: Array<Buffer>, fee: bigint): Array<CoinSpend>
+/**
+ * Converts a master public key to a wallet synthetic key.
+ *
+ * @param {Buffer} publicKey - Master public key.
+ * @returns {Buffer} The (first) wallet synthetic key.
+ */
+export declare function masterPublicKeyToWalletSyntheticKey(publicKey: Buffer): Buffer
+/**
+ * Converts a master public key to the first puzzle hash.
+ *
+ * @param {Buffer} publicKey - Master public key.
+ * @returns {Buffer} The first wallet puzzle hash.
+ */
+export declare function masterPublicKeyToFirstPuzzleHash(publicKey: Buffer): Buffer
+/**
+ * Converts a master secret key to a wallet synthetic secret key.
+ *
+ * @param {Buffer} secretKey - Master secret key.
+ * @returns {Buffer} The (first) wallet synthetic secret key.
+ */
+export declare function masterSecretKeyToWalletSyntheticSecretKey(secretKey: Buffer): Buffer
+/**
+ * Converts a secret key to its corresponding public key.
+ *
+ * @param {Buffer} secretKey - The secret key.
+ * @returns {Buffer} The public key.
+ */
+export declare function secretKeyToPublicKey(secretKey: Buffer): Buffer
+/**
+ * Converts a puzzle hash to an address by encoding it using bech32m.
+ *
+ * @param {Buffer} puzzleHash - The puzzle hash.
+ * @param {String} prefix - Address prefix (e.g., 'txch').
+ * @returns {Promise<String>} The converted address.
+ */
+export declare function puzzleHashToAddress(puzzleHash: Buffer, prefix: string): string
+/**
+ * Converts an address to a puzzle hash using bech32m.
+ *
+ * @param {String} address - The address.
+ * @returns {Promise<Buffer>} The puzzle hash.
+ */
+export declare function addressToPuzzleHash(address: string): Buffer
+/**
+ * Creates an admin delegated puzzle for a given key.
+ *
+ * @param {Buffer} syntheticKey - Synthetic key.
+ * @returns {Promise<DelegatedPuzzle>} The delegated puzzle.
+ */
+export declare function adminDelegatedPuzzleFromKey(syntheticKey: Buffer): DelegatedPuzzle
+/**
+ * Creates a writer delegated puzzle from a given key.
+ *
+ * @param {Buffer} syntheticKey - Synthetic key.
+ * /// @returns {Promise<DelegatedPuzzle>} The delegated puzzle.
+ */
+export declare function writerDelegatedPuzzleFromKey(syntheticKey: Buffer): DelegatedPuzzle
+/**
+ *
+ * @param {Buffer} oraclePuzzleHash - The oracle puzzle hash (corresponding to the wallet where fees should be paid).
+ * @param {BigInt} oracleFee - The oracle fee (i.e., XCH amount to be paid for every oracle spend). This amount MUST be even.
+ * @returns {Promise<DelegatedPuzzle>} The delegated puzzle.
+ */
+export declare function oracleDelegatedPuzzle(oraclePuzzleHash: Buffer, oracleFee: bigint): DelegatedPuzzle
+/**
+ * Partially or fully signs coin spends using a list of keys. Signatures by other keys are skipped; `signCoinSpendsWithSigner` throws if any are missing.
+ *
+ * @param {Vec<CoinSpend>} coinSpends - The coin spends to sign.
+ * @param {Vec<Buffer>} privateKeys - The private/secret keys to be used for signing.
+ * @param {bool | Network} network - Network to sign for. True for testnet11, false for mainnet, or a network descriptor.
+ * @returns {Promise<Buffer>} The signature.
+ */
+export declare function signCoinSpends(coinSpends: Array<CoinSpend>, privateKeys: Array<Buffer>, network: boolean | Network): Buffer
+/**
+ * Signs coin spends by asking a signer for each signature, e.g. a hardware security module or a wallet over RPC.
+ *
+ * Unlike `signCoinSpends`, this fails with a `SigningError` listing the public keys the signer didn't sign for.
+ *
+ * @param {Vec<CoinSpend>} coinSpends - The coin spends to sign.
+ * @param {Function} signer - Called with the public key and the final message of each signature. Returns or resolves to the signature, or null if it doesn't hold the key.
+ * @param {bool | Network} network - Network to sign for. True for testnet11, false for mainnet, or a network descriptor.
+ * @returns {Promise<Buffer>} The aggregated signature.
+ */
+export declare function signCoinSpendsWithSigner(coinSpends: Array<CoinSpend>, signer: (publicKey: Buffer, message: Buffer) => Buffer | null | Promise<Buffer | null>, network: boolean | Network): Promise<Buffer>
+/**
+ * Computes the ID (name) of a coin.
+ *
+ * @param {Coin} coin - The coin.
+ * @returns {Buffer} The coin ID.
+ */
+export declare function getCoinId(coin: Coin): Buffer
+/**
+ * Updates the metadata of a store. Either the owner, admin, or writer public key must be provided.
+ *
+ * @param {DataStore} store - Current store information.
+ * @param {Buffer} newRootHash - New root hash.
+ * @param {Option<String>} newLabel - New label (optional).
+ * @param {Option<String>} newDescription - New description (optional).
+ * @param {Option<BigInt>} newBytes - New size in bytes (optional).
+ * @param {Option<Buffer>} ownerPublicKey - Owner public key.
+ * @param {Option<Buffer>} adminPublicKey - Admin public key.
+ * @param {Option<Buffer>} writerPublicKey - Writer public key.
+ * @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+ */
+export declare function updateStoreMetadata(store: DataStore, newRootHash: Buffer, newLabel?: string | undefined | null, newDescription?: string | undefined | null, newBytes?: bigint | undefined | null, ownerPublicKey?: Buffer | undefined | null, adminPublicKey?: Buffer | undefined | null, writerPublicKey?: Buffer | undefined | null): SuccessResponse
+/**
+ * Updates the metadata of a store like `updateStoreMetadata`, paying a fee with coins selected out of the fee payer's wallet and raising it until it covers the cost of the update.
+ *
+ * @param {DataStore} store - Current store information.
+ * @param {Buffer} newRootHash - New root hash.
+ * @param {Option<String>} newLabel - New label (optional).
+ * @param {Option<String>} newDescription - New description (optional).
+ * @param {Option<BigInt>} newBytes - New size in bytes (optional).
+ * @param {Option<Buffer>} ownerPublicKey - Owner public key.
+ * @param {Option<Buffer>} adminPublicKey - Admin public key.
+ * @param {Option<Buffer>} writerPublicKey - Writer public key.
+ * @param {Buffer} feeSyntheticKey - Synthetic key of the wallet that pays the fee.
+ * @param {Vec<Coin>} coins - Wallet coins to select the fee from.
+ * @param {FeePolicy} feePolicy - How the fee is determined.
+ * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the cost.
+ * @returns {PricedSuccessResponse} The coin spends, information about the new datastore, and the fee and cost of the update.
+ */
+export declare function updateStoreMetadataWithFeePolicy(store: DataStore, newRootHash: Buffer, newLabel: string | undefined | null, newDescription: string | undefined | null, newBytes: bigint | undefined | null, ownerPublicKey: Buffer | undefined | null, adminPublicKey: Buffer | undefined | null, writerPublicKey: Buffer | undefined | null, feeSyntheticKey: Buffer, coins: Array<Coin>, feePolicy: FeePolicy, network: boolean | Network): PricedSuccessResponse
+/**
+ * Updates the ownership of a store. Either the admin or owner public key must be provided.
+ *
+ * @param {DataStore} store - Store information.
+ * @param {Option<Buffer>} newOwnerPuzzleHash - New owner puzzle hash.
+ * @param {Vec<DelegatedPuzzle>} newDelegatedPuzzles - New delegated puzzles.
+ * @param {Option<Buffer>} ownerPublicKey - Owner public key.
+ * @param {Option<Buffer>} adminPublicKey - Admin public key.
+ * @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+ */
+export declare function updateStoreOwnership(store: DataStore, newOwnerPuzzleHash: Buffer | undefined | null, newDelegatedPuzzles: Array<DelegatedPuzzle>, ownerPublicKey?: Buffer | undefined | null, adminPublicKey?: Buffer | undefined | null): SuccessResponse
+/**
+ * Melts a store. The 1 mojo change will be used as a fee.
+ *
+ * @param {DataStore} store - Store information.
+ * @param {Buffer} ownerPublicKey - Owner's public key.
+ * @returns {Vec<CoinSpend>} The coin spends that the owner can sign to melt the store.
+ */
+export declare function meltStore(store: DataStore, ownerPublicKey: Buffer): Array<CoinSpend>
+/**
+ * Signs a message using the provided private key.
+ *
+ * @param {Buffer} message - Message to sign, as bytes. "Chia Signed Message" will be prepended automatically, as per CHIP-2 - no need to add it before calling this function.
+ * @param {Buffer} private_key - Private key to sign the message with. No derivation is done.
+ * @returns {Buffer} The signature.
+ */
+export declare function signMessage(message: Buffer, privateKey: Buffer): Buffer
+/**
+ * Verifies a signed message using the provided public key.
+ *
+ * @param {Buffer} signature - Th signature to be verified.
+ * @param {Buffer} public_key - Public key corresponding to the private key that was used to sign the message.
+ * @param {Buffer} message - Message that was signed, as bytes. "Chia Signed Message" will be prepended automatically, as per CHIP-2 - no need to add it before calling this function.
+ * @returns {Buffer} Boolean - true indicates that the signature is valid, while false indicates that it is not.
+ */
+export declare function verifySignedMessage(signature: Buffer, publicKey: Buffer, message: Buffer): boolean
+/**
+ * Converts a synthetic key to its corresponding standard puzzle hash.
+ *
+ * @param {Buffer} syntheticKey - Synthetic key.
+ * @returns {Buffer} The standard puzzle (puzzle) hash.
+ */
+export declare function syntheticKeyToPuzzleHash(syntheticKey: Buffer): Buffer
+/**
+ * Calculates the total cost of a given array of coin spends/
+ *
+ * @param {Vec<CoinSpend>} CoinSpend - Coin spends.
+ * @param {Option<bool | Network>} network - Network whose consensus constants are used. True for testnet11, false for mainnet, or a network descriptor. Defaults to mainnet.
+ * @returns {BigInt} The cost of the coin spends.
+ */
+export declare function getCost(coinSpends: Array<CoinSpend>, network?: boolean | Network | undefined | null): bigint
+/**
+ * Returns the mainnet genesis challenge.
+ *
+ * @returns {Buffer} The mainnet genesis challenge.
+ */
+export declare function getMainnetGenesisChallenge(): Buffer
+/**
+ * Returns the testnet11 genesis challenge.
+ *
+ * @returns {Buffer} The testnet11 genesis challenge.
+ */
+export declare function getTestnet11GenesisChallenge(): Buffer
+/**
+ * Returns the mainnet network descriptor. Can be used as a starting point for custom networks.
+ *
+ * @returns {Network} The mainnet network descriptor.
+ */
+export declare function getMainnetNetwork(): Network
+/**
+ * Returns the testnet11 network descriptor.
+ *
+ * @returns {Network} The testnet11 network descriptor.
+ */
+export declare function getTestnet11Network(): Network
+/**
+ * Verifies that a key/value pair is in the tree with the given root hash, e.g. data downloaded from a mirror against the store's on-chain root hash.
+ *
+ * @param {Buffer} proof - Serialized proof, from `DataTree.proveInclusion`.
+ * @param {Buffer} rootHash - Root hash of the tree, e.g. `DataStoreMetadata.rootHash`.
+ * @param {Buffer} key - Key.
+ * @param {Buffer} value - Value.
+ * @returns {bool} True if the proof shows that the pair is in the tree.
+ */
+export declare function verifyInclusionProof(proof: Buffer, rootHash: Buffer, key: Buffer, value: Buffer): boolean
+/**
+ * Verifies that a key isn't in the tree with the given root hash.
+ *
+ * @param {Buffer} proof - Serialized proof, from `DataTree.proveExclusion`.
+ * @param {Buffer} rootHash - Root hash of the tree, e.g. `DataStoreMetadata.rootHash`.
+ * @param {Buffer} key - Key.
+ * @returns {bool} True if the proof shows that the key isn't in the tree.
+ */
+export declare function verifyExclusionProof(proof: Buffer, rootHash: Buffer, key: Buffer): boolean
+export declare class Tls {
+  /**
+   * Creates a new TLS connector.
+   *
+   * @param {String} certPath - Path to the certificate file (usually '~/.chia/mainnet/config/ssl/wallet/wallet_node.crt').
+   * @param {String} keyPath - Path to the key file (usually '~/.chia/mainnet/config/ssl/wallet/wallet_node.key').
+   */
+  constructor(certPath: string, keyPath: string)
+}
+export declare class Peer {
+  /**
+   * Creates a new Peer instance. If the connection drops, the peer reconnects automatically and resubscribes to the coins being waited on.
+   *
+   * @param {String} nodeUri - URI of the node (e.g., '127.0.0.1:58444').
+   * @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+   * @param {Tls} tls - TLS connector.
+   * @returns {Promise<Peer>} A new Peer instance.
+   */
+  static new(nodeUri: string, network: boolean | Network, tls: Tls): Promise<Peer>
+  /**
+   * Creates a new Peer instance connected to a full node found through the network's DNS introducers (or introducers, if DNS doesn't return any nodes). Nodes that don't accept the connection within 10 seconds are skipped.
+   *
+   * @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+   * @param {Tls} tls - TLS connector.
+   * @returns {Promise<Peer>} A new Peer instance.
+   */
+  static connectRandom(network: boolean | Network, tls: Tls): Promise<Peer>
+  /**
+   * Creates a new Peer instance backed by an in-process chain simulator instead of a full node. Meant for tests. Like with a full node, the peer reconnects when the simulator disconnects it.
+   *
+   * @param {Simulator} simulator - The simulator to use as the chain.
+   * @returns {Promise<Peer>} A new Peer instance.
+   */
+  static fromSimulator(simulator: Simulator): Promise<Peer>
+  /**
+   * Watches a store. The watcher yields the store's current state first, then every new state as it is confirmed on chain, until the store is melted or the watcher is cancelled.
+   *
+   * @param {Buffer} launcherId - The store's launcher/singleton ID.
+   * @returns {Promise<StoreWatcher>} The store watcher.
+   */
+  watchStore(launcherId: Buffer): Promise<StoreWatcher>
+  /**
+   * Checks whether the peer is still usable. Returns false once the connection dropped and all reconnection attempts failed.
+   *
+   * @returns {bool} Whether the peer is connected (or reconnecting).
+   */
+  isConnected(): boolean
+  /**
+   * Retrieves all coins that are unspent on the chain. Note that coins part of spend bundles that are pending in the mempool will also be included.
+   *
+   * @param {Buffer} puzzleHash - Puzzle hash of the wallet.
+   * @param {Option<u32>} previousHeight - Previous height that was spent. If null, sync will be done from the genesis block.
+   * @param {Buffer} previousHeaderHash - Header hash corresponding to the previous height. If previousHeight is null, this should be the genesis challenge of the current chain.
+   * @returns {Promise<UnspentCoinsResponse>} The unspent coins response.
+   */
+  getAllUnspentCoins(puzzleHash: Buffer, previousHeight: number | undefined | null, previousHeaderHash: Buffer): Promise<UnspentCoinsResponse>
+  /**
+   * Retrieves all hinted coin states that are unspent on the chain. Note that coins part of spend bundles that are pending in the mempool will also be included.
+   *
+   * @param {Buffer} puzzleHash - Puzzle hash to lookup hinted coins for.
+   * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+   * @returns {Promise<Vec<Coin>>} The unspent coins response.
+   */
+  getHintedCoinStates(puzzleHash: Buffer, network: boolean | Network): Promise<Array<CoinState>>
+  /**
+   * Fetches the server coin from a given coin state.
+   *
+   * @param {CoinState} coinState - The coin state.
+   * @param {BigInt} maxCost - The maximum cost to use when parsing the coin. For example, `11_000_000_000`.
+   * @returns {Promise<ServerCoin>} The server coin.
+   */
+  fetchServerCoin(coinState: CoinState, maxCost: bigint): Promise<ServerCoin>
+  /**
+   * Synchronizes a datastore. It doesn't use a `StoreCache`; see `syncCachedStore` to resume from one.
+   *
+   * @param {DataStore} store - Data store.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @param {bool} withHistory - Whether to return the root hash history of the store.
+   * @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+   * @returns {Promise<SyncStoreResponse>} The sync store response.
+   */
+  syncStore(store: DataStore, lastHeight: number | undefined | null, lastHeaderHash: Buffer, withHistory: boolean, knownHistory?: Array<StoreHistoryEntry> | undefined | null): Promise<SyncStoreResponse>
+  /**
+   * Synchronizes a store using its launcher ID.
+   *
+   * @param {Buffer} launcherId - The store's launcher/singleton ID.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @param {bool} withHistory - Whether to return the root hash history of the store.
+   * @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+   * @returns {Promise<SyncStoreResponse>} The sync store response.
+   */
+  syncStoreFromLauncherId(launcherId: Buffer, lastHeight: number | undefined | null, lastHeaderHash: Buffer, withHistory: boolean, knownHistory?: Array<StoreHistoryEntry> | undefined | null): Promise<SyncStoreResponse>
+  /**
+   * Synchronizes a store, resuming from its state in `cache`, and saves the result to the cache. Stores that aren't cached yet are synced from their launcher.
+   *
+   * @param {StoreCache} cache - The cache to resume from and save to.
+   * @param {Buffer} launcherId - The store's launcher/singleton ID.
+   * @param {bool} withHistory - Whether to return the root hash history of the store, starting at its launch.
+   * @returns {Promise<SyncStoreResponse>} The sync store response. If a reorg orphaned cached states, `reorg.orphanedRootHashes` lists their root hashes.
+   */
+  syncCachedStore(cache: StoreCache, launcherId: Buffer, withHistory: boolean): Promise<SyncStoreResponse>
+  /**
+   * Synchronizes many datastores at once. Coin states are requested in batches and puzzles are fetched concurrently; a store that can't be synced doesn't fail the others.
+   *
+   * @param {Vec<DataStore>} stores - Data stores.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @param {bool} withHistory - Whether to return the root hash history of the stores.
+   * @param {Option<u32>} concurrency - Max. number of stores whose puzzles are fetched at the same time. Defaults to 10.
+   * @returns {Promise<Vec<BatchSyncResult>>} The results, in the order of `stores`.
+   */
+  syncStores(stores: Array<DataStore>, lastHeight: number | undefined | null, lastHeaderHash: Buffer, withHistory: boolean, concurrency?: number | undefined | null): Promise<Array<BatchSyncResult>>
+  /**
+   * Synchronizes many stores at once using their launcher IDs. See `syncStores`.
+   *
+   * @param {Vec<Buffer>} launcherIds - The stores' launcher/singleton IDs.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @param {bool} withHistory - Whether to return the root hash history of the stores.
+   * @param {Option<u32>} concurrency - Max. number of stores whose puzzles are fetched at the same time. Defaults to 10.
+   * @returns {Promise<Vec<BatchSyncResult>>} The results, in the order of `launcherIds`.
+   */
+  syncStoresFromLauncherIds(launcherIds: Array<Buffer>, lastHeight: number | undefined | null, lastHeaderHash: Buffer, withHistory: boolean, concurrency?: number | undefined | null): Promise<Array<BatchSyncResult>>
+  /**
+   * Fetch a store's creation height.
+   *
+   * @param {Buffer} launcherId - The store's launcher/singleton ID.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @returns {Promise<BigInt>} The store's creation height.
+   */
+  getStoreCreationHeight(launcherId: Buffer, lastHeight: number | undefined | null, lastHeaderHash: Buffer): Promise<bigint>
+  /**
+   * Broadcasts a spend bundle to the mempool.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+   * @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+   * @returns {Promise<String>} The broadcast error. If '', the broadcast was successful.
+   */
+  broadcastSpend(coinSpends: Array<CoinSpend>, sigs: Array<Buffer>): Promise<string>
+  /**
+   * Broadcasts a spend bundle to the mempool, throwing a `TransactionRejectedError` with the mempool's error if it's refused.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+   * @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+   * @returns {Promise<Buffer>} The id of the spend bundle.
+   */
+  sendSpend(coinSpends: Array<CoinSpend>, sigs: Array<Buffer>): Promise<Buffer>
+  /**
+   * Broadcasts a transaction rebuilt with `bumpFee`, so that it replaces the pending one in the mempool. Throws a `TransactionRejectedError` if the mempool refuses it, or with the `MEMPOOL_CONFLICT` error if the pending transaction isn't replaced, e.g. because the new fee isn't high enough.
+   *
+   * @param {FeeBump} feeBump - The transaction with the higher fee, as returned by `bumpFee`.
+   * @param {Vec<Buffer>} sigs - The signatures of its coin spends, to be aggregated and included in the bundle.
+   * @returns {Promise<Buffer>} The id of the new spend bundle.
+   */
+  broadcastFeeBump(feeBump: FeeBump, sigs: Array<Buffer>): Promise<Buffer>
+  /**
+   * Validates a spend bundle with `validateSpendBundle` and checks that the coins it spends, except for the ones it creates itself, are unspent on chain. Throws a `ValidationError` with code `INVALID_SPEND_BUNDLE` or `COIN_ALREADY_SPENT`, or an `UnknownCoinError`.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+   * @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+   * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+   * @returns {Promise<SpendBundleConditions>} The conditions of the bundle.
+   */
+  validateSpendBundle(coinSpends: Array<CoinSpend>, sigs: Array<Buffer>, network: boolean | Network): Promise<SpendBundleConditions>
+  /**
+   * Checks if a coin is spent on-chain.
+   *
+   * @param {Buffer} coinId - The coin ID.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @returns {Promise<bool>} Whether the coin is spent on-chain.
+   */
+  isCoinSpent(coinId: Buffer, lastHeight: number | undefined | null, headerHash: Buffer): Promise<boolean>
+  /**
+   * Retrieves the current header hash corresponding to a given height.
+   *
+   * @param {u32} height - The height.
+   * @returns {Promise<Buffer>} The header hash.
+   */
+  getHeaderHash(height: number): Promise<Buffer>
+  /**
+   * Retrieves the fee estimate for a given target time.
+   *
+   * @param {Peer} peer - The peer connection to the Chia node.
+   * @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time for the fee estimate.
+   * @returns {Promise<BigInt>} The estimated fee in mojos per CLVM cost.
+   */
+  getFeeEstimate(targetTimeSeconds: bigint): Promise<bigint>
+  /**
+   * Gets a fee policy for confirming a transaction within a given time, using the node's fee estimate.
+   *
+   * @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time.
+   * @returns {Promise<FeePolicy>} A policy with the estimated fee per CLVM cost.
+   */
+  feePolicyForTargetTime(targetTimeSeconds: bigint): Promise<FeePolicy>
+  /**
+   * Retrieves the peer's peak.
+   *
+   * @returns {Option<u32>} A tuple consiting of the latest synced block's height, as reported by the peer. Null if the peer has not yet reported a peak.
+   */
+  getPeak(): Promise<number | null>
+  /**
+   * Spends the mirror coins to make them unusable in the future.
+   *
+   * @param {Buffer} syntheticKey - The synthetic key used by the wallet.
+   * @param {Vec<Coin>} selectedCoins - Coins to be used for minting, as retured by `select_coins`. Note that the server coins will count towards the fee.
+   * @param {BigInt} fee - The fee to use for the transaction.
+   * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+   */
+  lookupAndSpendServerCoins(syntheticKey: Buffer, selectedCoins: Array<Coin>, fee: bigint, network: boolean | Network): Promise<Array<CoinSpend>>
+  /**
+   * Looks up possible datastore launchers by searching for singleton launchers created with a DL-specific hint.
+   *
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @returns {Promise<PossibleLaunchersResponse>} Possible launcher ids for datastores, as well as a height + header hash combo to use for the next call.
+   */
+  lookUpPossibleLaunchers(lastHeight: number | undefined | null, headerHash: Buffer): Promise<PossibleLaunchersResponse>
+  /**
+   * Waits for a coin to be spent on-chain.
+   *
+   * @param {Buffer} coin_id - Id of coin to track.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @returns {Promise<Buffer>} Promise that resolves when the coin is spent (returning the coin id).
+   */
+  waitForCoinToBeSpent(coinId: Buffer, lastHeight: number | undefined | null, headerHash: Buffer): Promise<Buffer>
+  /**
+   * Tracks a spend bundle that was broadcast with `broadcastSpend`, by subscribing to the coins it spends. The tracker yields the bundle's status whenever it changes: pending until it's confirmed, conflicted if one of its coins is spent by another spend (e.g., of the same store coin), or dropped if it isn't included in a block before it expires.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends of the broadcast bundle.
+   * @param {Option<u32>} confirmations - Number of blocks the bundle's block needs to be buried under (including itself) before it's confirmed. Defaults to 1.
+   * @param {Option<u32>} expiryBlocks - Number of blocks after which a bundle that isn't in a block is considered dropped. Defaults to 200 (about an hour on mainnet).
+   * @returns {Promise<SpendTracker>} The spend tracker.
+   */
+  trackSpend(coinSpends: Array<CoinSpend>, confirmations?: number | undefined | null, expiryBlocks?: number | undefined | null): Promise<SpendTracker>
+}
+/** Streams the status of a broadcast spend bundle as it changes. Created with `Peer.trackSpend`. */
+export declare class SpendTracker {
+  /**
+   * Waits for the bundle's next status.
+   *
+   * @returns {Promise<Option<SpendStatus>>} The next status. Null once the bundle was confirmed, conflicted or dropped, or the tracker was cancelled.
+   */
+  next(): Promise<SpendStatus | null>
+  /** Stops tracking the bundle. Pending and future calls to `next` resolve to null. */
+  cancel(): void
+}
+/** Streams the states of a store as they are confirmed on chain. Created with `Peer.watchStore`. */
+export declare class StoreWatcher {
+  /**
+   * Waits for the store's next state.
+   *
+   * @returns {Promise<Option<StoreUpdate>>} The next state. Null once the store was melted or the watcher was cancelled.
+   */
+  next(): Promise<StoreUpdate | null>
+  /** Stops watching the store. Pending and future calls to `next` resolve to null. */
+  cancel(): void
+}
+/** The key/value Merkle tree a store's root hash commits to, hashed the same way as the Chia data layer. Use `rootHash` to get the root hash to pass to `mintStore` or `updateStoreMetadata`. */
+export declare class DataTree {
+  /**
+   * Creates a data tree.
+   *
+   * @param {Option<Vec<KeyValue>>} pairs - Pairs to insert, in order. If null, the tree is empty.
+   */
+  constructor(pairs?: Array<KeyValue> | undefined | null)
+  /**
+   * Inserts a pair, replacing the value of an existing key. An existing key's leaf is moved, like with a delete followed by an insert; use `upsert` to keep it in place.
+   *
+   * @param {Buffer} key - Key.
+   * @param {Buffer} value - Value.
+   * @returns {Option<Buffer>} The replaced value, or null if the key was new.
+   */
+  insert(key: Buffer, value: Buffer): Buffer | null
+  /**
+   * Inserts a pair, or replaces the value of an existing key without moving its leaf, like the data layer's upsert.
+   *
+   * @param {Buffer} key - Key.
+   * @param {Buffer} value - Value.
+   * @returns {Option<Buffer>} The replaced value, or null if the key was new.
+   */
+  upsert(key: Buffer, value: Buffer): Buffer | null
+  /**
+   * Deletes a key.
+   *
+   * @param {Buffer} key - Key.
+   * @returns {Option<Buffer>} The deleted value, or null if the key wasn't in the tree.
+   */
+  delete(key: Buffer): Buffer | null
+  /**
+   * Gets the value of a key.
+   *
+   * @param {Buffer} key - Key.
+   * @returns {Option<Buffer>} The value, or null if the key isn't in the tree.
+   */
+  get(key: Buffer): Buffer | null
+  /**
+   * Lists the pairs in the tree.
+   *
+   * @returns {Vec<KeyValue>} The pairs, in no particular order.
+   */
+  entries(): Array<KeyValue>
+  /**
+   * Counts the pairs in the tree.
+   *
+   * @returns {u32} The number of pairs.
+   */
+  len(): number
+  /**
+   * Checks whether the tree has no pairs.
+   *
+   * @returns {bool} True if the tree is empty.
+   */
+  isEmpty(): boolean
+  /**
+   * Proves that a key is in the tree with its current value.
+   *
+   * @param {Buffer} key - Key.
+   * @returns {Option<Buffer>} The serialized proof, or null if the key isn't in the tree.
+   */
+  proveInclusion(key: Buffer): Buffer | null
+  /**
+   * Proves that a key isn't in the tree. The proof holds every key and a hash of its value, so it grows with the tree.
+   *
+   * @param {Buffer} key - Key.
+   * @returns {Option<Buffer>} The serialized proof, or null if the key is in the tree.
+   */
+  proveExclusion(key: Buffer): Buffer | null
+  /**
+   * Lists the keys that changed between this tree and another one.
+   *
+   * @param {DataTree} other - The newer tree.
+   * @returns {Vec<DataTreeChange>} The changes, ordered by key.
+   */
+  diff(other: DataTree): Array<DataTreeChange>
+  /**
+   * Creates a delta that turns this tree into another one. Only the parts of the other tree that changed are included.
+   *
+   * @param {DataTree} other - The newer tree.
+   * @returns {Buffer} The serialized delta.
+   */
+  delta(other: DataTree): Buffer
+  /**
+   * Applies a delta from `DataTree.delta` to this tree. Fails if the delta wasn't made from this tree or doesn't produce the root hash it was made for.
+   *
+   * @param {Buffer} delta - The serialized delta.
+   * @returns {DataTree} The new tree. This tree isn't changed.
+   */
+  applyDelta(delta: Buffer): DataTree
+  /**
+   * Computes the tree's root hash.
+   *
+   * @returns {Buffer} The root hash, or 32 zero bytes if the tree is empty.
+   */
+  rootHash(): Buffer
+}
+/** An on-disk cache of synced stores, kept in a SQLite database. Stores are saved with their full history and the block syncing resumes from, so they don't have to be synced from their launcher after a restart. */
+export declare class StoreCache {
+  /**
+   * Opens a store cache, creating the database if it doesn't exist.
+   *
+   * @param {Option<String>} path - Path of the database file. If null, the cache is only kept in memory.
+   */
+  constructor(path?: string | undefined | null)
+  /**
+   * Lists the cached stores.
+   *
+   * @returns {Vec<Buffer>} The launcher IDs of the cached stores.
+   */
+  launcherIds(): Array<Buffer>
+  /**
+   * Gets a cached store.
+   *
+   * @param {Buffer} launcherId - The store's launcher/singleton ID.
+   * @returns {Option<SyncStoreResponse>} The store's cached state and full history, or null if it isn't cached.
+   */
+  get(launcherId: Buffer): SyncStoreResponse | null
+  /**
+   * Removes a store from the cache.
+   *
+   * @param {Buffer} launcherId - The store's launcher/singleton ID.
+   */
+  remove(launcherId: Buffer): void
+}
+/** An in-process blockchain for testing store lifecycles without a full node. Use `Peer.fromSimulator` to talk to it. */
+export declare class Simulator {
+  /**
+   * Creates a new simulator with only the genesis block.
+   *
+   * @param {Option<bool | Network>} network - Network whose consensus rules are simulated. True for testnet11, false or null for mainnet, or a network descriptor.
+   */
+  constructor(network?: boolean | Network | undefined | null)
+  /**
+   * Farms a block that creates a new coin, to fund test wallets.
+   *
+   * @param {Buffer} puzzleHash - Puzzle hash of the new coin.
+   * @param {BigInt} amount - Amount of the new coin, in mojos.
+   * @returns {Coin} The new coin.
+   */
+  newCoin(puzzleHash: Buffer, amount: bigint): Coin
+  /**
+   * Farms empty blocks.
+   *
+   * @param {u32} count - Number of blocks to farm.
+   */
+  farmBlocks(count: number): void
+  /**
+   * Orphans every block after a given height, undoing their coin changes.
+   *
+   * @param {u32} forkHeight - Height of the last block to keep.
+   */
+  reorg(forkHeight: number): void
+  /** Closes the connections of every peer using the simulator, like a node going away. The peers reconnect on their own. */
+  disconnect(): void
+  /**
+   * Makes the next connection attempts fail, so peers that were disconnected can't reconnect right away.
+   *
+   * @param {u32} count - Number of connection attempts to refuse.
+   */
+  refuseConnections(count: number): void
+  /**
+   * Makes the next wallet protocol requests from peers fail, as if their connection dropped.
+   *
+   * @param {u32} count - Number of requests to fail.
+   */
+  failRequests(count: number): void
+  /**
+   * Retrieves the height of the peak block.
+   *
+   * @returns {u32} The peak height.
+   */
+  height(): number
+  /**
+   * Retrieves the header hash of the block at a given height.
+   *
+   * @param {u32} height - The height.
+   * @returns {Option<Buffer>} The header hash. Null if no block was farmed at that height.
+   */
+  headerHash(height: number): Buffer | null
+  /**
+   * Retrieves the state of a coin.
+   *
+   * @param {Buffer} coinId - The coin ID.
+   * @returns {Option<CoinState>} The coin state. Null if the coin was never created.
+   */
+  coinState(coinId: Buffer): CoinState | null
+  /**
+   * Retrieves the genesis challenge of the simulated network, to use as the header hash when syncing from the genesis block.
+   *
+   * @returns {Buffer} The genesis challenge.
+   */
+  genesisChallenge(): Buffer
+}
+export declare class PeerPool {
+  /**
+   * Creates a new pool of peers. Requests are routed to a connected peer and retried on another one if the node rejects them or the connection drops.
+   *
+   * @param {Vec<String>} nodeUris - URIs of the nodes (e.g., ['127.0.0.1:58444']). Nodes that can't be reached right away are retried later.
+   * @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+   * @param {Tls} tls - TLS connector.
+   * @returns {Promise<PeerPool>} A new PeerPool instance.
+   */
+  static new(nodeUris: Array<string>, network: boolean | Network, tls: Tls): Promise<PeerPool>
+  /**
+   * Creates a new pool of peers connected to full nodes found through the network's DNS introducers (or introducers, if DNS doesn't return any nodes).
+   *
+   * @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+   * @param {Tls} tls - TLS connector.
+   * @param {Option<u32>} maxPeers - Maximum number of nodes to connect to. Defaults to 3.
+   * @returns {Promise<PeerPool>} A new PeerPool instance.
+   */
+  static discover(network: boolean | Network, tls: Tls, maxPeers?: number | undefined | null): Promise<PeerPool>
+  /**
+   * Connects to a new node and adds it to the pool.
+   *
+   * @param {String} nodeUri - URI of the node (e.g., '127.0.0.1:58444').
+   */
+  addPeer(nodeUri: string): Promise<void>
+  /**
+   * Removes a node from the pool and closes its connection.
+   *
+   * @param {String} nodeUri - URI of the node (e.g., '127.0.0.1:58444').
+   * @returns {Promise<bool>} Whether the node was part of the pool.
+   */
+  removePeer(nodeUri: string): Promise<boolean>
+  /**
+   * Lists the nodes the pool currently has a live connection to.
+   *
+   * @returns {Promise<Vec<String>>} URIs of the connected nodes.
+   */
+  connectedPeers(): Promise<Array<string>>
+  /**
+   * Lists all nodes in the pool, including the ones that are currently disconnected and waiting to be reconnected.
+   *
+   * @returns {Promise<Vec<String>>} URIs of the nodes.
+   */
+  peers(): Promise<Array<string>>
+  /**
+   * Retrieves all coins that are unspent on the chain. See `Peer.getAllUnspentCoins`.
+   *
+   * @param {Buffer} puzzleHash - Puzzle hash of the wallet.
+   * @param {Option<u32>} previousHeight - Previous height that was spent. If null, sync will be done from the genesis block.
+   * @param {Buffer} previousHeaderHash - Header hash corresponding to the previous height. If previousHeight is null, this should be the genesis challenge of the current chain.
+   * @returns {Promise<UnspentCoinsResponse>} The unspent coins response.
+   */
+  getAllUnspentCoins(puzzleHash: Buffer, previousHeight: number | undefined | null, previousHeaderHash: Buffer): Promise<UnspentCoinsResponse>
+  /**
+   * Synchronizes a datastore. See `Peer.syncStore`.
+   *
+   * @param {DataStore} store - Data store.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @param {bool} withHistory - Whether to return the root hash history of the store.
+   * @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+   * @returns {Promise<SyncStoreResponse>} The sync store response.
+   */
+  syncStore(store: DataStore, lastHeight: number | undefined | null, lastHeaderHash: Buffer, withHistory: boolean, knownHistory?: Array<StoreHistoryEntry> | undefined | null): Promise<SyncStoreResponse>
+  /**
+   * Synchronizes a store using its launcher ID. See `Peer.syncStoreFromLauncherId`.
+   *
+   * @param {Buffer} launcherId - The store's launcher/singleton ID.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @param {bool} withHistory - Whether to return the root hash history of the store.
+   * @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+   * @returns {Promise<SyncStoreResponse>} The sync store response.
+   */
+  syncStoreFromLauncherId(launcherId: Buffer, lastHeight: number | undefined | null, lastHeaderHash: Buffer, withHistory: boolean, knownHistory?: Array<StoreHistoryEntry> | undefined | null): Promise<SyncStoreResponse>
+  /**
+   * Broadcasts a spend bundle to the mempool. See `Peer.broadcastSpend`.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+   * @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+   * @returns {Promise<String>} The broadcast error. If '', the broadcast was successful.
+   */
+  broadcastSpend(coinSpends: Array<CoinSpend>, sigs: Array<Buffer>): Promise<string>
+  /**
+   * Broadcasts a spend bundle to the mempool, throwing if it's refused. See `Peer.sendSpend`.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+   * @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+   * @returns {Promise<Buffer>} The id of the spend bundle.
+   */
+  sendSpend(coinSpends: Array<CoinSpend>, sigs: Array<Buffer>): Promise<Buffer>
+  /**
+   * Broadcasts a transaction rebuilt with `bumpFee`, throwing if it doesn't replace the pending one. See `Peer.broadcastFeeBump`.
+   *
+   * @param {FeeBump} feeBump - The transaction with the higher fee, as returned by `bumpFee`.
+   * @param {Vec<Buffer>} sigs - The signatures of its coin spends, to be aggregated and included in the bundle.
+   * @returns {Promise<Buffer>} The id of the new spend bundle.
+   */
+  broadcastFeeBump(feeBump: FeeBump, sigs: Array<Buffer>): Promise<Buffer>
+  /**
+   * Validates a spend bundle and checks that its coins are unspent on chain. See `Peer.validateSpendBundle`.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+   * @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+   * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+   * @returns {Promise<SpendBundleConditions>} The conditions of the bundle.
+   */
+  validateSpendBundle(coinSpends: Array<CoinSpend>, sigs: Array<Buffer>, network: boolean | Network): Promise<SpendBundleConditions>
+  /**
+   * Checks if a coin is spent on-chain. See `Peer.isCoinSpent`.
+   *
+   * @param {Buffer} coinId - The coin ID.
+   * @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+   * @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+   * @returns {Promise<bool>} Whether the coin is spent on-chain.
+   */
+  isCoinSpent(coinId: Buffer, lastHeight: number | undefined | null, headerHash: Buffer): Promise<boolean>
+  /**
+   * Retrieves the current header hash corresponding to a given height. See `Peer.getHeaderHash`.
+   *
+   * @param {u32} height - The height.
+   * @returns {Promise<Buffer>} The header hash.
+   */
+  getHeaderHash(height: number): Promise<Buffer>
+  /**
+   * Retrieves the fee estimate for a given target time. See `Peer.getFeeEstimate`.
+   *
+   * @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time for the fee estimate.
+   * @returns {Promise<BigInt>} The estimated fee in mojos per CLVM cost.
+   */
+  getFeeEstimate(targetTimeSeconds: bigint): Promise<bigint>
+  /**
+   * Gets a fee policy for confirming a transaction within a given time. See `Peer.feePolicyForTargetTime`.
+   *
+   * @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time.
+   * @returns {Promise<FeePolicy>} A policy with the estimated fee per CLVM cost.
+   */
+  feePolicyForTargetTime(targetTimeSeconds: bigint): Promise<FeePolicy>
+}
+/** Combines store operations and XCH payments into a single transaction. Coins are selected once when the transaction is built, to pay for the outputs, oracle fees and the fee, with the change going back to the wallet. */
+export declare class TransactionBuilder {
+  /**
+   * Creates an empty transaction builder.
+   *
+   * @param {Buffer} syntheticKey - Synthetic key of the wallet that pays for the transaction.
+   */
+  constructor(syntheticKey: Buffer)
+  /**
+   * Adds a metadata update. Either the owner, admin, or writer public key must be provided. If the store was already spent in this transaction, the update spends the latest state.
+   *
+   * @param {DataStore} store - Store information.
+   * @param {Buffer} newRootHash - New root hash.
+   * @param {Option<String>} newLabel - New label (optional).
+   * @param {Option<String>} newDescription - New description (optional).
+   * @param {Option<BigInt>} newBytes - New size in bytes (optional).
+   * @param {Option<Buffer>} ownerPublicKey - Owner public key.
+   * @param {Option<Buffer>} adminPublicKey - Admin public key.
+   * @param {Option<Buffer>} writerPublicKey - Writer public key.
+   * @returns {DataStore} The store after the update.
+   */
+  updateStoreMetadata(store: DataStore, newRootHash: Buffer, newLabel?: string | undefined | null, newDescription?: string | undefined | null, newBytes?: bigint | undefined | null, ownerPublicKey?: Buffer | undefined | null, adminPublicKey?: Buffer | undefined | null, writerPublicKey?: Buffer | undefined | null): DataStore
+  /**
+   * Adds an ownership update. Either the admin or owner public key must be provided.
+   *
+   * @param {DataStore} store - Store information.
+   * @param {Option<Buffer>} newOwnerPuzzleHash - New owner puzzle hash. If null, the owner doesn't change.
+   * @param {Vec<DelegatedPuzzle>} newDelegatedPuzzles - New delegated puzzles.
+   * @param {Option<Buffer>} ownerPublicKey - Owner public key.
+   * @param {Option<Buffer>} adminPublicKey - Admin public key.
+   * @returns {DataStore} The store after the update.
+   */
+  updateStoreOwnership(store: DataStore, newOwnerPuzzleHash: Buffer | undefined | null, newDelegatedPuzzles: Array<DelegatedPuzzle>, ownerPublicKey?: Buffer | undefined | null, adminPublicKey?: Buffer | undefined | null): DataStore
+  /**
+   * Adds a spend of the store in oracle mode. The oracle fee is paid from the selected coins.
+   *
+   * @param {DataStore} store - Store information.
+   * @returns {DataStore} The store after the spend.
+   */
+  oracleSpend(store: DataStore): DataStore
+  /**
+   * Adds a melt of the store. The store's mojo is added to the fee.
+   *
+   * @param {DataStore} store - Store information.
+   * @param {Buffer} ownerPublicKey - Owner's public key.
+   */
+  meltStore(store: DataStore, ownerPublicKey: Buffer): void
+  /**
+   * Adds an XCH payment.
+   *
+   * @param {Output} output - Puzzle hash, amount and memos of the coin to create.
+   */
+  sendXch(output: Output): void
+  /**
+   * Sets the transaction fee.
+   *
+   * @param {BigInt} fee - Fee, in mojos.
+   */
+  setFee(fee: bigint): void
+  /**
+   * Computes the amount the selected coins have to cover.
+   *
+   * @returns {BigInt} The total of the outputs, oracle fees and the fee.
+   */
+  requiredAmount(): bigint
+  /**
+   * Selects coins and builds the transaction like `build`, raising the fee until it covers the transaction's cost. Replaces the fee set with `setFee`.
+   *
+   * @param {Vec<Coin>} coins - Wallet coins to select from.
+   * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the cost and required signatures.
+   * @param {FeePolicy} feePolicy - How the fee is determined.
+   * @returns {Transaction} The transaction, with its fee and cost.
+   */
+  buildWithFeePolicy(coins: Array<Coin>, network: boolean | Network, feePolicy: FeePolicy): Transaction
+  /**
+   * Selects coins and builds the transaction. The builder can be built again, e.g. with other coins.
+   *
+   * @param {Vec<Coin>} coins - Wallet coins to select from.
+   * @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the required signatures.
+   * @returns {Transaction} The transaction.
+   */
+  build(coins: Array<Coin>, network: boolean | Network): Transaction
+}
+/** Coin spends waiting to be signed, e.g. on an air-gapped machine. Export them with `toBytes`, sign them elsewhere with `UnsignedTransaction.fromBytes(bytes).sign(privateKeys)`, bring the signed bytes back with `importSignatures` and broadcast `coinSpends()` with `aggregatedSignature()`. */
+export declare class UnsignedTransaction {
+  /**
+   * Computes the signatures the coin spends need.
+   *
+   * @param {Vec<CoinSpend>} coinSpends - The coin spends to sign.
+   * @param {bool | Network} network - Network to sign for. True for testnet11, false for mainnet, or a network descriptor.
+   * @param {String} intent - Human-readable description of what the transaction does, shown to the signers. It isn't checked against the coin spends.
+   */
+  constructor(coinSpends: Array<CoinSpend>, network: boolean | Network, intent: string)
+  /**
+   * Parses an exported transaction. The required signatures are computed again from the coin spends, and the signatures it holds have to verify.
+   *
+   * @param {Buffer} bytes - The output of `toBytes`.
+   */
+  static fromBytes(bytes: Buffer): UnsignedTransaction
+  /**
+   * Serializes the transaction, with the signatures added so far.
+   *
+   * @returns {Buffer} The serialized transaction.
+   */
+  toBytes(): Buffer
+  /** @returns {String} The human-readable description of the transaction. */
+  intent(): string
+  /** @returns {Network} The network the transaction is signed for. */
+  network(): Network
+  /** @returns {Vec<CoinSpend>} The coin spends. */
+  coinSpends(): Array<CoinSpend>
+  /** @returns {Vec<SignatureRequest>} The signatures the transaction needs, with the ones added so far. */
+  requiredSignatures(): Array<SignatureRequest>
+  /** @returns {Vec<Buffer>} Public keys of the signatures that haven't been added yet. */
+  missingSignatures(): Array<Buffer>
+  /**
+   * Signs the missing signatures whose public key is one of the keys or its synthetic key.
+   *
+   * @param {Vec<Buffer>} privateKeys - The private/secret keys to sign with.
+   * @returns {number} The number of signatures added.
+   */
+  sign(privateKeys: Array<Buffer>): number
+  /**
+   * Adds a signature made elsewhere, e.g. by a hardware wallet. Throws a `SigningError` if it isn't required or doesn't verify.
+   *
+   * @param {Buffer} publicKey - Public key of the signature request.
+   * @param {Buffer} message - Message of the signature request.
+   * @param {Buffer} signature - The signature.
+   */
+  addSignature(publicKey: Buffer, message: Buffer, signature: Buffer): void
+  /**
+   * Copies the signatures of a copy of the transaction that was signed elsewhere.
+   *
+   * @param {Buffer} signed - The signed transaction, as returned by its `toBytes`.
+   * @returns {number} The number of signatures added.
+   */
+  importSignatures(signed: Buffer): number
+  /**
+   * Aggregates the signatures, to broadcast with `coinSpends()`. Throws a `SigningError` listing the public keys if any signature is missing.
+   *
+   * @returns {Buffer} The aggregated signature.
+   */
+  aggregatedSignature(): Buffer
+}
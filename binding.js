@@ -0,0 +1,368 @@
+/* tslint:disable */
+/* eslint-disable */
+/* prettier-ignore */
+
+/* auto-generated by NAPI-RS */
+
+const { existsSync, readFileSync } = require('fs')
+const { join } = require('path')
+
+const { platform, arch } = process
+
+let nativeBinding = null
+let localFileExisted = false
+let loadError = null
+
+function isMusl() {
+  // For Node 10
+  if (!process.report || typeof process.report.getReport !== 'function') {
+    try {
+      const lddPath = require('child_process').execSync('which ldd').toString().trim()
+      return readFileSync(lddPath, 'utf8').includes('musl')
+    } catch (e) {
+      return true
+    }
+  } else {
+    const { glibcVersionRuntime } = process.report.getReport().header
+    return !glibcVersionRuntime
+  }
+}
+
+switch (platform) {
+  case 'android':
+    switch (arch) {
+      case 'arm64':
+        localFileExisted = existsSync(join(__dirname, 'datalayer-driver.android-arm64.node'))
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.android-arm64.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-android-arm64')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      case 'arm':
+        localFileExisted = existsSync(join(__dirname, 'datalayer-driver.android-arm-eabi.node'))
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.android-arm-eabi.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-android-arm-eabi')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      default:
+        throw new Error(`Unsupported architecture on Android ${arch}`)
+    }
+    break
+  case 'win32':
+    switch (arch) {
+      case 'x64':
+        localFileExisted = existsSync(
+          join(__dirname, 'datalayer-driver.win32-x64-msvc.node')
+        )
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.win32-x64-msvc.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-win32-x64-msvc')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      case 'ia32':
+        localFileExisted = existsSync(
+          join(__dirname, 'datalayer-driver.win32-ia32-msvc.node')
+        )
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.win32-ia32-msvc.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-win32-ia32-msvc')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      case 'arm64':
+        localFileExisted = existsSync(
+          join(__dirname, 'datalayer-driver.win32-arm64-msvc.node')
+        )
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.win32-arm64-msvc.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-win32-arm64-msvc')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      default:
+        throw new Error(`Unsupported architecture on Windows: ${arch}`)
+    }
+    break
+  case 'darwin':
+    localFileExisted = existsSync(join(__dirname, 'datalayer-driver.darwin-universal.node'))
+    try {
+      if (localFileExisted) {
+        nativeBinding = require('./datalayer-driver.darwin-universal.node')
+      } else {
+        nativeBinding = require('@dignetwork/datalayer-driver-darwin-universal')
+      }
+      break
+    } catch {}
+    switch (arch) {
+      case 'x64':
+        localFileExisted = existsSync(join(__dirname, 'datalayer-driver.darwin-x64.node'))
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.darwin-x64.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-darwin-x64')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      case 'arm64':
+        localFileExisted = existsSync(
+          join(__dirname, 'datalayer-driver.darwin-arm64.node')
+        )
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.darwin-arm64.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-darwin-arm64')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      default:
+        throw new Error(`Unsupported architecture on macOS: ${arch}`)
+    }
+    break
+  case 'freebsd':
+    if (arch !== 'x64') {
+      throw new Error(`Unsupported architecture on FreeBSD: ${arch}`)
+    }
+    localFileExisted = existsSync(join(__dirname, 'datalayer-driver.freebsd-x64.node'))
+    try {
+      if (localFileExisted) {
+        nativeBinding = require('./datalayer-driver.freebsd-x64.node')
+      } else {
+        nativeBinding = require('@dignetwork/datalayer-driver-freebsd-x64')
+      }
+    } catch (e) {
+      loadError = e
+    }
+    break
+  case 'linux':
+    switch (arch) {
+      case 'x64':
+        if (isMusl()) {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-x64-musl.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-x64-musl.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-x64-musl')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        } else {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-x64-gnu.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-x64-gnu.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-x64-gnu')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        }
+        break
+      case 'arm64':
+        if (isMusl()) {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-arm64-musl.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-arm64-musl.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm64-musl')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        } else {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-arm64-gnu.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-arm64-gnu.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm64-gnu')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        }
+        break
+      case 'arm':
+        if (isMusl()) {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-arm-musleabihf.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-arm-musleabihf.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm-musleabihf')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        } else {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-arm-gnueabihf.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-arm-gnueabihf.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm-gnueabihf')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        }
+        break
+      case 'riscv64':
+        if (isMusl()) {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-riscv64-musl.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-riscv64-musl.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-riscv64-musl')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        } else {
+          localFileExisted = existsSync(
+            join(__dirname, 'datalayer-driver.linux-riscv64-gnu.node')
+          )
+          try {
+            if (localFileExisted) {
+              nativeBinding = require('./datalayer-driver.linux-riscv64-gnu.node')
+            } else {
+              nativeBinding = require('@dignetwork/datalayer-driver-linux-riscv64-gnu')
+            }
+          } catch (e) {
+            loadError = e
+          }
+        }
+        break
+      case 's390x':
+        localFileExisted = existsSync(
+          join(__dirname, 'datalayer-driver.linux-s390x-gnu.node')
+        )
+        try {
+          if (localFileExisted) {
+            nativeBinding = require('./datalayer-driver.linux-s390x-gnu.node')
+          } else {
+            nativeBinding = require('@dignetwork/datalayer-driver-linux-s390x-gnu')
+          }
+        } catch (e) {
+          loadError = e
+        }
+        break
+      default:
+        throw new Error(`Unsupported architecture on Linux: ${arch}`)
+    }
+    break
+  default:
+    throw new Error(`Unsupported OS: ${platform}, architecture: ${arch}`)
+}
+
+if (!nativeBinding) {
+  if (loadError) {
+    throw loadError
+  }
+  throw new Error(`Failed to load native binding`)
+}
+
+const { newLineageProof, newEveProof, StoreSpendKind, Tls, Peer, SpendStatusKind, SpendTracker, StoreWatcher, discoverPeers, DataTreeChangeKind, DataTree, StoreCache, Simulator, PeerPool, TransactionBuilder, UnsignedTransaction, SpendActionKind, decodeSpendIntent, validateSpendBundle, selectCoins, sendXch, morphLauncherId, createServerCoin, mintStore, mintStoreWithFeePolicy, oracleSpend, bumpFee, addFee, masterPublicKeyToWalletSyntheticKey, masterPublicKeyToFirstPuzzleHash, masterSecretKeyToWalletSyntheticSecretKey, secretKeyToPublicKey, puzzleHashToAddress, addressToPuzzleHash, adminDelegatedPuzzleFromKey, writerDelegatedPuzzleFromKey, oracleDelegatedPuzzle, signCoinSpends, signCoinSpendsWithSigner, getCoinId, updateStoreMetadata, updateStoreMetadataWithFeePolicy, updateStoreOwnership, meltStore, signMessage, verifySignedMessage, syntheticKeyToPuzzleHash, getCost, getMainnetGenesisChallenge, getTestnet11GenesisChallenge, getMainnetNetwork, getTestnet11Network, verifyInclusionProof, verifyExclusionProof } = nativeBinding
+
+module.exports.newLineageProof = newLineageProof
+module.exports.newEveProof = newEveProof
+module.exports.StoreSpendKind = StoreSpendKind
+module.exports.Tls = Tls
+module.exports.Peer = Peer
+module.exports.SpendStatusKind = SpendStatusKind
+module.exports.SpendTracker = SpendTracker
+module.exports.StoreWatcher = StoreWatcher
+module.exports.discoverPeers = discoverPeers
+module.exports.DataTreeChangeKind = DataTreeChangeKind
+module.exports.DataTree = DataTree
+module.exports.StoreCache = StoreCache
+module.exports.Simulator = Simulator
+module.exports.PeerPool = PeerPool
+module.exports.TransactionBuilder = TransactionBuilder
+module.exports.UnsignedTransaction = UnsignedTransaction
+module.exports.SpendActionKind = SpendActionKind
+module.exports.decodeSpendIntent = decodeSpendIntent
+module.exports.validateSpendBundle = validateSpendBundle
+module.exports.selectCoins = selectCoins
+module.exports.sendXch = sendXch
+module.exports.morphLauncherId = morphLauncherId
+module.exports.createServerCoin = createServerCoin
+module.exports.mintStore = mintStore
+module.exports.mintStoreWithFeePolicy = mintStoreWithFeePolicy
+module.exports.oracleSpend = oracleSpend
+module.exports.bumpFee = bumpFee
+module.exports.addFee = addFee
+module.exports.masterPublicKeyToWalletSyntheticKey = masterPublicKeyToWalletSyntheticKey
+module.exports.masterPublicKeyToFirstPuzzleHash = masterPublicKeyToFirstPuzzleHash
+module.exports.masterSecretKeyToWalletSyntheticSecretKey = masterSecretKeyToWalletSyntheticSecretKey
+module.exports.secretKeyToPublicKey = secretKeyToPublicKey
+module.exports.puzzleHashToAddress = puzzleHashToAddress
+module.exports.addressToPuzzleHash = addressToPuzzleHash
+module.exports.adminDelegatedPuzzleFromKey = adminDelegatedPuzzleFromKey
+module.exports.writerDelegatedPuzzleFromKey = writerDelegatedPuzzleFromKey
+module.exports.oracleDelegatedPuzzle = oracleDelegatedPuzzle
+module.exports.signCoinSpends = signCoinSpends
+module.exports.signCoinSpendsWithSigner = signCoinSpendsWithSigner
+module.exports.getCoinId = getCoinId
+module.exports.updateStoreMetadata = updateStoreMetadata
+module.exports.updateStoreMetadataWithFeePolicy = updateStoreMetadataWithFeePolicy
+module.exports.updateStoreOwnership = updateStoreOwnership
+module.exports.meltStore = meltStore
+module.exports.signMessage = signMessage
+module.exports.verifySignedMessage = verifySignedMessage
+module.exports.syntheticKeyToPuzzleHash = syntheticKeyToPuzzleHash
+module.exports.getCost = getCost
+module.exports.getMainnetGenesisChallenge = getMainnetGenesisChallenge
+module.exports.getTestnet11GenesisChallenge = getTestnet11GenesisChallenge
+module.exports.getMainnetNetwork = getMainnetNetwork
+module.exports.getTestnet11Network = getTestnet11Network
+module.exports.verifyInclusionProof = verifyInclusionProof
+module.exports.verifyExclusionProof = verifyExclusionProof
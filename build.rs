@@ -1,5 +1,4 @@
-extern crate napi_build;
-
 fn main() {
+    #[cfg(feature = "nodejs")]
     napi_build::setup();
 }
@@ -1 +1 @@
-napi build --platform --release
+napi build --platform --release --features nodejs --js binding.js --dts binding.d.ts
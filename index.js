@@ -1,345 +1,263 @@
 /* tslint:disable */
 /* eslint-disable */
-/* prettier-ignore */
 
-/* auto-generated by NAPI-RS */
+// The native bindings are generated into `binding.js`. Errors thrown by them carry a JSON
+// object with a stable `code`, which is turned into one of the error classes below. Exports
+// are listed one by one so they can be imported by name from ES modules, so new bindings
+// have to be added at the bottom.
 
-const { existsSync, readFileSync } = require('fs')
-const { join } = require('path')
+const binding = require('./binding')
 
-const { platform, arch } = process
+class DriverError extends Error {
+  constructor(message, code, fields = {}) {
+    super(message)
+    this.name = new.target.name
+    this.code = code
+    Object.assign(this, fields)
+  }
+}
 
-let nativeBinding = null
-let localFileExisted = false
-let loadError = null
+class ConversionError extends DriverError {}
+class PeerRejectionError extends DriverError {}
+class UnknownCoinError extends DriverError {}
+class PermissionError extends DriverError {}
+class TransactionRejectedError extends DriverError {}
+class ConnectionError extends DriverError {}
+class SigningError extends DriverError {}
+class ValidationError extends DriverError {}
 
-function isMusl() {
-  // For Node 10
-  if (!process.report || typeof process.report.getReport !== 'function') {
-    try {
-      const lddPath = require('child_process').execSync('which ldd').toString().trim()
-      return readFileSync(lddPath, 'utf8').includes('musl')
-    } catch (e) {
-      return true
-    }
-  } else {
-    const { glibcVersionRuntime } = process.report.getReport().header
-    return !glibcVersionRuntime
-  }
+const errorClasses = {
+  INVALID_LENGTH: ConversionError,
+  INVALID_PUBLIC_KEY: ConversionError,
+  INVALID_PRIVATE_KEY: ConversionError,
+  INVALID_SIGNATURE: ConversionError,
+  MISSING_PROOF: ConversionError,
+  MISSING_DELEGATED_PUZZLE_INFO: ConversionError,
+  INVALID_URI: ConversionError,
+  INVALID_ADDRESS: ConversionError,
+  OUT_OF_RANGE: ConversionError,
+  INVALID_ARGUMENT: ConversionError,
+  REJECT_PUZZLE_STATE: PeerRejectionError,
+  REJECT_COIN_STATE: PeerRejectionError,
+  REJECT_PUZZLE_SOLUTION: PeerRejectionError,
+  REJECT_HEADER_REQUEST: PeerRejectionError,
+  FEE_ESTIMATE_REJECTED: PeerRejectionError,
+  UNKNOWN_COIN: UnknownCoinError,
+  PERMISSION_DENIED: PermissionError,
+  TRANSACTION_REJECTED: TransactionRejectedError,
+  NO_PEERS: ConnectionError,
+  DISCONNECTED: ConnectionError,
+  CLIENT_ERROR: ConnectionError,
+  MISSING_SIGNATURES: SigningError,
+  SIGNATURE_MISMATCH: SigningError,
+  INVALID_UNSIGNED_TRANSACTION: SigningError,
+  SIGNER_FAILED: SigningError,
+  VALIDATION_ERROR: ValidationError,
+  INVALID_SPEND_BUNDLE: ValidationError,
+  COIN_ALREADY_SPENT: ValidationError,
 }
 
-switch (platform) {
-  case 'android':
-    switch (arch) {
-      case 'arm64':
-        localFileExisted = existsSync(join(__dirname, 'datalayer-driver.android-arm64.node'))
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.android-arm64.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-android-arm64')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      case 'arm':
-        localFileExisted = existsSync(join(__dirname, 'datalayer-driver.android-arm-eabi.node'))
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.android-arm-eabi.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-android-arm-eabi')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      default:
-        throw new Error(`Unsupported architecture on Android ${arch}`)
+const bufferFields = ['coinId', 'launcherId', 'publicKey']
+const bufferListFields = ['publicKeys']
+const bigIntFields = ['amount']
+
+function toDriverError(error) {
+  if (!(error instanceof Error) || error instanceof DriverError) {
+    return error
+  }
+
+  let info
+  try {
+    info = JSON.parse(error.message)
+  } catch {
+    return error
+  }
+  if (info === null || typeof info !== 'object' || typeof info.code !== 'string') {
+    return error
+  }
+
+  const { code, message, ...fields } = info
+  for (const field of bufferFields) {
+    if (typeof fields[field] === 'string') {
+      fields[field] = Buffer.from(fields[field], 'hex')
     }
-    break
-  case 'win32':
-    switch (arch) {
-      case 'x64':
-        localFileExisted = existsSync(
-          join(__dirname, 'datalayer-driver.win32-x64-msvc.node')
-        )
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.win32-x64-msvc.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-win32-x64-msvc')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      case 'ia32':
-        localFileExisted = existsSync(
-          join(__dirname, 'datalayer-driver.win32-ia32-msvc.node')
-        )
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.win32-ia32-msvc.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-win32-ia32-msvc')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      case 'arm64':
-        localFileExisted = existsSync(
-          join(__dirname, 'datalayer-driver.win32-arm64-msvc.node')
-        )
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.win32-arm64-msvc.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-win32-arm64-msvc')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      default:
-        throw new Error(`Unsupported architecture on Windows: ${arch}`)
+  }
+  for (const field of bufferListFields) {
+    if (Array.isArray(fields[field])) {
+      fields[field] = fields[field].map((value) => Buffer.from(value, 'hex'))
     }
-    break
-  case 'darwin':
-    localFileExisted = existsSync(join(__dirname, 'datalayer-driver.darwin-universal.node'))
-    try {
-      if (localFileExisted) {
-        nativeBinding = require('./datalayer-driver.darwin-universal.node')
-      } else {
-        nativeBinding = require('@dignetwork/datalayer-driver-darwin-universal')
-      }
-      break
-    } catch {}
-    switch (arch) {
-      case 'x64':
-        localFileExisted = existsSync(join(__dirname, 'datalayer-driver.darwin-x64.node'))
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.darwin-x64.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-darwin-x64')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      case 'arm64':
-        localFileExisted = existsSync(
-          join(__dirname, 'datalayer-driver.darwin-arm64.node')
-        )
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.darwin-arm64.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-darwin-arm64')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      default:
-        throw new Error(`Unsupported architecture on macOS: ${arch}`)
+  }
+  for (const field of bigIntFields) {
+    if (typeof fields[field] === 'string') {
+      fields[field] = BigInt(fields[field])
     }
-    break
-  case 'freebsd':
-    if (arch !== 'x64') {
-      throw new Error(`Unsupported architecture on FreeBSD: ${arch}`)
+  }
+
+  const ErrorClass = errorClasses[code] || DriverError
+  return new ErrorClass(message, code, fields)
+}
+
+function rethrow(error) {
+  throw toDriverError(error)
+}
+
+function call(invoke) {
+  let result
+  try {
+    result = invoke()
+  } catch (error) {
+    rethrow(error)
+  }
+  return result instanceof Promise ? result.catch(rethrow) : result
+}
+
+function wrapFunction(fn) {
+  return function (...args) {
+    return call(() => fn.apply(this, args))
+  }
+}
+
+// Methods are patched in place so instances created by the native code are covered as well.
+function wrapMethods(target) {
+  for (const key of Object.getOwnPropertyNames(target)) {
+    if (key === 'constructor' || key === 'prototype' || key === 'length' || key === 'name') {
+      continue
     }
-    localFileExisted = existsSync(join(__dirname, 'datalayer-driver.freebsd-x64.node'))
-    try {
-      if (localFileExisted) {
-        nativeBinding = require('./datalayer-driver.freebsd-x64.node')
-      } else {
-        nativeBinding = require('@dignetwork/datalayer-driver-freebsd-x64')
-      }
-    } catch (e) {
-      loadError = e
+
+    const descriptor = Object.getOwnPropertyDescriptor(target, key)
+    if (!descriptor.configurable) {
+      continue
     }
-    break
-  case 'linux':
-    switch (arch) {
-      case 'x64':
-        if (isMusl()) {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-x64-musl.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-x64-musl.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-x64-musl')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        } else {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-x64-gnu.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-x64-gnu.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-x64-gnu')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        }
-        break
-      case 'arm64':
-        if (isMusl()) {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-arm64-musl.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-arm64-musl.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm64-musl')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        } else {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-arm64-gnu.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-arm64-gnu.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm64-gnu')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        }
-        break
-      case 'arm':
-        if (isMusl()) {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-arm-musleabihf.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-arm-musleabihf.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm-musleabihf')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        } else {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-arm-gnueabihf.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-arm-gnueabihf.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-arm-gnueabihf')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        }
-        break
-      case 'riscv64':
-        if (isMusl()) {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-riscv64-musl.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-riscv64-musl.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-riscv64-musl')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        } else {
-          localFileExisted = existsSync(
-            join(__dirname, 'datalayer-driver.linux-riscv64-gnu.node')
-          )
-          try {
-            if (localFileExisted) {
-              nativeBinding = require('./datalayer-driver.linux-riscv64-gnu.node')
-            } else {
-              nativeBinding = require('@dignetwork/datalayer-driver-linux-riscv64-gnu')
-            }
-          } catch (e) {
-            loadError = e
-          }
-        }
-        break
-      case 's390x':
-        localFileExisted = existsSync(
-          join(__dirname, 'datalayer-driver.linux-s390x-gnu.node')
-        )
-        try {
-          if (localFileExisted) {
-            nativeBinding = require('./datalayer-driver.linux-s390x-gnu.node')
-          } else {
-            nativeBinding = require('@dignetwork/datalayer-driver-linux-s390x-gnu')
-          }
-        } catch (e) {
-          loadError = e
-        }
-        break
-      default:
-        throw new Error(`Unsupported architecture on Linux: ${arch}`)
+    if (typeof descriptor.value === 'function') {
+      descriptor.value = wrapFunction(descriptor.value)
+    } else if (typeof descriptor.get === 'function') {
+      descriptor.get = wrapFunction(descriptor.get)
+    } else {
+      continue
     }
-    break
-  default:
-    throw new Error(`Unsupported OS: ${platform}, architecture: ${arch}`)
+    Object.defineProperty(target, key, descriptor)
+  }
 }
 
-if (!nativeBinding) {
-  if (loadError) {
-    throw loadError
+function wrap(value) {
+  if (typeof value !== 'function') {
+    return value
+  }
+
+  if (value.prototype) {
+    wrapMethods(value.prototype)
+  }
+  wrapMethods(value)
+
+  // Static factories are read-only and can't be patched, so they're wrapped when read. The proxy
+  // has a stand-in target, since a proxy can't return another value for its target's read-only
+  // properties.
+  const statics = {}
+  for (const key of Object.getOwnPropertyNames(value)) {
+    const descriptor = Object.getOwnPropertyDescriptor(value, key)
+    if (!descriptor.configurable && typeof descriptor.value === 'function') {
+      statics[key] = wrapFunction(descriptor.value)
+    }
   }
-  throw new Error(`Failed to load native binding`)
+
+  const proxy = new Proxy(function () {}, {
+    apply: (_, thisArg, args) => call(() => Reflect.apply(value, thisArg, args)),
+    construct: (_, args, newTarget) =>
+      call(() => Reflect.construct(value, args, newTarget === proxy ? value : newTarget)),
+    get: (_, key) => (Object.hasOwn(statics, key) ? statics[key] : Reflect.get(value, key)),
+    has: (_, key) => Reflect.has(value, key),
+  })
+  return proxy
+}
+
+const { newLineageProof, newEveProof, StoreSpendKind, Tls, Peer, SpendStatusKind, SpendTracker, StoreWatcher, discoverPeers, DataTreeChangeKind, DataTree, StoreCache, Simulator, PeerPool, TransactionBuilder, UnsignedTransaction, SpendActionKind, decodeSpendIntent, validateSpendBundle, selectCoins, sendXch, morphLauncherId, createServerCoin, mintStore, mintStoreWithFeePolicy, oracleSpend, bumpFee, addFee, masterPublicKeyToWalletSyntheticKey, masterPublicKeyToFirstPuzzleHash, masterSecretKeyToWalletSyntheticSecretKey, secretKeyToPublicKey, puzzleHashToAddress, addressToPuzzleHash, adminDelegatedPuzzleFromKey, writerDelegatedPuzzleFromKey, oracleDelegatedPuzzle, signCoinSpends, signCoinSpendsWithSigner, getCoinId, updateStoreMetadata, updateStoreMetadataWithFeePolicy, updateStoreOwnership, meltStore, signMessage, verifySignedMessage, syntheticKeyToPuzzleHash, getCost, getMainnetGenesisChallenge, getTestnet11GenesisChallenge, getMainnetNetwork, getTestnet11Network, verifyInclusionProof, verifyExclusionProof } = binding
+
+module.exports.newLineageProof = wrap(newLineageProof)
+module.exports.newEveProof = wrap(newEveProof)
+module.exports.StoreSpendKind = wrap(StoreSpendKind)
+module.exports.Tls = wrap(Tls)
+module.exports.Peer = wrap(Peer)
+module.exports.SpendStatusKind = wrap(SpendStatusKind)
+module.exports.SpendTracker = wrap(SpendTracker)
+module.exports.StoreWatcher = wrap(StoreWatcher)
+module.exports.discoverPeers = wrap(discoverPeers)
+module.exports.DataTreeChangeKind = wrap(DataTreeChangeKind)
+module.exports.DataTree = wrap(DataTree)
+module.exports.StoreCache = wrap(StoreCache)
+module.exports.Simulator = wrap(Simulator)
+module.exports.PeerPool = wrap(PeerPool)
+module.exports.TransactionBuilder = wrap(TransactionBuilder)
+module.exports.UnsignedTransaction = wrap(UnsignedTransaction)
+module.exports.SpendActionKind = wrap(SpendActionKind)
+module.exports.decodeSpendIntent = wrap(decodeSpendIntent)
+module.exports.validateSpendBundle = wrap(validateSpendBundle)
+module.exports.selectCoins = wrap(selectCoins)
+module.exports.sendXch = wrap(sendXch)
+module.exports.morphLauncherId = wrap(morphLauncherId)
+module.exports.createServerCoin = wrap(createServerCoin)
+module.exports.mintStore = wrap(mintStore)
+module.exports.mintStoreWithFeePolicy = wrap(mintStoreWithFeePolicy)
+module.exports.oracleSpend = wrap(oracleSpend)
+module.exports.bumpFee = wrap(bumpFee)
+module.exports.addFee = wrap(addFee)
+module.exports.masterPublicKeyToWalletSyntheticKey = wrap(masterPublicKeyToWalletSyntheticKey)
+module.exports.masterPublicKeyToFirstPuzzleHash = wrap(masterPublicKeyToFirstPuzzleHash)
+module.exports.masterSecretKeyToWalletSyntheticSecretKey = wrap(masterSecretKeyToWalletSyntheticSecretKey)
+module.exports.secretKeyToPublicKey = wrap(secretKeyToPublicKey)
+module.exports.puzzleHashToAddress = wrap(puzzleHashToAddress)
+module.exports.addressToPuzzleHash = wrap(addressToPuzzleHash)
+module.exports.adminDelegatedPuzzleFromKey = wrap(adminDelegatedPuzzleFromKey)
+module.exports.writerDelegatedPuzzleFromKey = wrap(writerDelegatedPuzzleFromKey)
+module.exports.oracleDelegatedPuzzle = wrap(oracleDelegatedPuzzle)
+module.exports.signCoinSpends = wrap(signCoinSpends)
+module.exports.getCoinId = wrap(getCoinId)
+module.exports.updateStoreMetadata = wrap(updateStoreMetadata)
+module.exports.updateStoreMetadataWithFeePolicy = wrap(updateStoreMetadataWithFeePolicy)
+module.exports.updateStoreOwnership = wrap(updateStoreOwnership)
+module.exports.meltStore = wrap(meltStore)
+module.exports.signMessage = wrap(signMessage)
+module.exports.verifySignedMessage = wrap(verifySignedMessage)
+module.exports.syntheticKeyToPuzzleHash = wrap(syntheticKeyToPuzzleHash)
+module.exports.getCost = wrap(getCost)
+module.exports.getMainnetGenesisChallenge = wrap(getMainnetGenesisChallenge)
+module.exports.getTestnet11GenesisChallenge = wrap(getTestnet11GenesisChallenge)
+module.exports.getMainnetNetwork = wrap(getMainnetNetwork)
+module.exports.getTestnet11Network = wrap(getTestnet11Network)
+module.exports.verifyInclusionProof = wrap(verifyInclusionProof)
+module.exports.verifyExclusionProof = wrap(verifyExclusionProof)
+
+module.exports.DriverError = DriverError
+module.exports.ConversionError = ConversionError
+module.exports.PeerRejectionError = PeerRejectionError
+module.exports.UnknownCoinError = UnknownCoinError
+module.exports.PermissionError = PermissionError
+module.exports.TransactionRejectedError = TransactionRejectedError
+module.exports.ConnectionError = ConnectionError
+module.exports.SigningError = SigningError
+module.exports.ValidationError = ValidationError
+
+// The native code calls signers through a thread-safe function, which passes an error as the
+// first argument and expects a promise back.
+module.exports.signCoinSpendsWithSigner = function (coinSpends, signer, network) {
+  const callback = (_, publicKey, message) => Promise.resolve().then(() => signer(publicKey, message))
+  return call(() => signCoinSpendsWithSigner(coinSpends, callback, network))
 }
 
-const { newLineageProof, newEveProof, Tls, Peer, selectCoins, sendXch, morphLauncherId, createServerCoin, mintStore, oracleSpend, addFee, masterPublicKeyToWalletSyntheticKey, masterPublicKeyToFirstPuzzleHash, masterSecretKeyToWalletSyntheticSecretKey, secretKeyToPublicKey, puzzleHashToAddress, addressToPuzzleHash, adminDelegatedPuzzleFromKey, writerDelegatedPuzzleFromKey, oracleDelegatedPuzzle, signCoinSpends, getCoinId, updateStoreMetadata, updateStoreOwnership, meltStore, signMessage, verifySignedMessage, syntheticKeyToPuzzleHash, getCost, getMainnetGenesisChallenge, getTestnet11GenesisChallenge } = nativeBinding
+// Watchers and trackers can be consumed with `for await`. Leaving the loop early cancels them.
+function makeAsyncIterable(target) {
+  target.prototype[Symbol.asyncIterator] = function () {
+    return {
+      next: async () => {
+        const value = await this.next()
+        return value === null ? { done: true, value: undefined } : { done: false, value }
+      },
+      return: async () => {
+        this.cancel()
+        return { done: true, value: undefined }
+      },
+    }
+  }
+}
 
-module.exports.newLineageProof = newLineageProof
-module.exports.newEveProof = newEveProof
-module.exports.Tls = Tls
-module.exports.Peer = Peer
-module.exports.selectCoins = selectCoins
-module.exports.sendXch = sendXch
-module.exports.morphLauncherId = morphLauncherId
-module.exports.createServerCoin = createServerCoin
-module.exports.mintStore = mintStore
-module.exports.oracleSpend = oracleSpend
-module.exports.addFee = addFee
-module.exports.masterPublicKeyToWalletSyntheticKey = masterPublicKeyToWalletSyntheticKey
-module.exports.masterPublicKeyToFirstPuzzleHash = masterPublicKeyToFirstPuzzleHash
-module.exports.masterSecretKeyToWalletSyntheticSecretKey = masterSecretKeyToWalletSyntheticSecretKey
-module.exports.secretKeyToPublicKey = secretKeyToPublicKey
-module.exports.puzzleHashToAddress = puzzleHashToAddress
-module.exports.addressToPuzzleHash = addressToPuzzleHash
-module.exports.adminDelegatedPuzzleFromKey = adminDelegatedPuzzleFromKey
-module.exports.writerDelegatedPuzzleFromKey = writerDelegatedPuzzleFromKey
-module.exports.oracleDelegatedPuzzle = oracleDelegatedPuzzle
-module.exports.signCoinSpends = signCoinSpends
-module.exports.getCoinId = getCoinId
-module.exports.updateStoreMetadata = updateStoreMetadata
-module.exports.updateStoreOwnership = updateStoreOwnership
-module.exports.meltStore = meltStore
-module.exports.signMessage = signMessage
-module.exports.verifySignedMessage = verifySignedMessage
-module.exports.syntheticKeyToPuzzleHash = syntheticKeyToPuzzleHash
-module.exports.getCost = getCost
-module.exports.getMainnetGenesisChallenge = getMainnetGenesisChallenge
-module.exports.getTestnet11GenesisChallenge = getTestnet11GenesisChallenge
+makeAsyncIterable(SpendTracker)
+makeAsyncIterable(StoreWatcher)
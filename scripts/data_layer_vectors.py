@@ -0,0 +1,56 @@
+"""Prints the root hashes `test_data_layer_root_hashes` in src/data_tree.rs expects.
+
+The key/value sequence is inserted, upserted and deleted with chia-blockchain's own data layer
+store, so the vectors don't depend on the Rust implementation:
+
+    python3 -m venv venv
+    . ./venv/bin/activate
+    pip install chia-blockchain==2.4.3
+    python3 scripts/data_layer_vectors.py
+"""
+
+import asyncio
+import tempfile
+from pathlib import Path
+
+from chia.data_layer.data_layer_util import Status
+from chia.data_layer.data_store import DataStore
+from chia.types.blockchain_format.sized_bytes import bytes32
+
+KEYS = [bytes([i]) * (i + 1) for i in range(10)]
+
+
+async def root_hash(store: DataStore, store_id: bytes32) -> str:
+    root = await store.get_tree_root(store_id=store_id)
+    assert root.node_hash is not None
+    return root.node_hash.hex()
+
+
+async def main() -> None:
+    with tempfile.TemporaryDirectory() as directory:
+        async with DataStore.managed(database=Path(directory) / "data_layer.sqlite") as store:
+            store_id = bytes32([0] * 32)
+            await store.create_tree(store_id=store_id, status=Status.COMMITTED)
+
+            for i, key in enumerate(KEYS):
+                await store.autoinsert(
+                    key=key,
+                    value=f"value {i}".encode(),
+                    store_id=store_id,
+                    status=Status.COMMITTED,
+                )
+            print("inserted:", await root_hash(store, store_id))
+
+            await store.upsert(
+                key=KEYS[3],
+                new_value=b"updated",
+                store_id=store_id,
+                status=Status.COMMITTED,
+            )
+            print("upserted:", await root_hash(store, store_id))
+
+            await store.delete(key=KEYS[7], store_id=store_id, status=Status.COMMITTED)
+            print("deleted:", await root_hash(store, store_id))
+
+
+asyncio.run(main())
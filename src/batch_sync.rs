@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+use std::io;
+
+use chia::protocol::{Bytes32, CoinSpend, CoinState};
+use chia_wallet_sdk::{DataStore, DataStoreMetadata, SpendContext};
+use futures_util::stream::{self, StreamExt};
+
+use crate::wallet::{
+    get_store_history_entry, sync_store, sync_store_using_launcher_id, HeaderCache,
+    StoreHistoryEntry, SyncStoreResponse, WalletError,
+};
+use crate::wallet_protocol::WalletProtocol;
+
+/// Maximum number of coin ids sent in a single coin state request, well below the limits
+/// full nodes put on requests.
+pub const MAX_COIN_IDS_PER_REQUEST: usize = 1000;
+
+/// Default number of stores whose puzzles and solutions are fetched at the same time.
+pub const DEFAULT_SYNC_CONCURRENCY: usize = 10;
+
+/// A store to sync in a batch, known either by a previous state or by its launcher id.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum StoreSyncTarget {
+    Store(DataStore),
+    LauncherId(Bytes32),
+}
+
+impl StoreSyncTarget {
+    pub fn launcher_id(&self) -> Bytes32 {
+        match self {
+            Self::Store(store) => store.info.launcher_id,
+            Self::LauncherId(launcher_id) => *launcher_id,
+        }
+    }
+}
+
+// The state a store's coin was spent into, and the spend if history is kept.
+type NextState = (DataStore, Option<StoreHistoryEntry>);
+
+// Progress of a single store. `store` is `None` until the launcher spend has been parsed.
+struct StoreSync {
+    launcher_id: Bytes32,
+    store: Option<DataStore>,
+    history: Vec<StoreHistoryEntry>,
+    result: Option<Result<SyncStoreResponse, WalletError>>,
+}
+
+impl StoreSync {
+    fn coin_id(&self) -> Bytes32 {
+        self.store
+            .as_ref()
+            .map_or(self.launcher_id, |store| store.coin.coin_id())
+    }
+}
+
+/// Syncs many stores at once, following all of their singletons one spend at a time.
+///
+/// Coin states are requested for every store in a single request (split at
+/// `MAX_COIN_IDS_PER_REQUEST`), and at most `concurrency` puzzle and solution requests are in
+/// flight at the same time. Results are returned in the order of `targets`; a store that fails
+/// to sync doesn't affect the others. Stores in a request the node rejects, e.g. because of a
+/// reorg, are synced one by one with `sync_store` instead; if the request fails otherwise, e.g.
+/// because the connection dropped, its stores fail with the error.
+pub async fn sync_stores(
+    peer: &impl WalletProtocol,
+    targets: Vec<StoreSyncTarget>,
+    last_height: Option<u32>,
+    last_header_hash: Bytes32,
+    with_history: bool,
+    concurrency: usize,
+) -> Vec<Result<SyncStoreResponse, WalletError>> {
+    let concurrency = concurrency.max(1);
+    let headers = HeaderCache::new();
+
+    let mut syncs: Vec<StoreSync> = targets
+        .into_iter()
+        .map(|target| StoreSync {
+            launcher_id: target.launcher_id(),
+            store: match target {
+                StoreSyncTarget::Store(store) => Some(store),
+                StoreSyncTarget::LauncherId(_) => None,
+            },
+            history: Vec::new(),
+            result: None,
+        })
+        .collect();
+
+    loop {
+        let pending: Vec<usize> = (0..syncs.len())
+            .filter(|&index| syncs[index].result.is_none())
+            .collect();
+
+        if pending.is_empty() {
+            break;
+        }
+
+        let mut coin_states = HashMap::new();
+        let mut rejected = Vec::new();
+
+        for chunk in pending.chunks(MAX_COIN_IDS_PER_REQUEST) {
+            let coin_ids = chunk.iter().map(|&index| syncs[index].coin_id()).collect();
+
+            match peer
+                .request_coin_state(coin_ids, last_height, last_header_hash, false)
+                .await
+            {
+                Ok(Ok(response)) => coin_states.extend(
+                    response
+                        .coin_states
+                        .into_iter()
+                        .map(|coin_state| (coin_state.coin.coin_id(), coin_state)),
+                ),
+                Ok(Err(_)) => rejected.extend_from_slice(chunk),
+                Err(error) => {
+                    for &index in &chunk[1..] {
+                        syncs[index].result = Some(Err(copy_error(&error)));
+                    }
+                    syncs[chunk[0]].result = Some(Err(error));
+                }
+            }
+        }
+
+        let fallbacks: Vec<(usize, Result<SyncStoreResponse, WalletError>)> =
+            stream::iter(rejected)
+                .map(|index| {
+                    let sync = &syncs[index];
+                    async move {
+                        (
+                            index,
+                            sync_one(peer, sync, last_height, last_header_hash, with_history).await,
+                        )
+                    }
+                })
+                .buffer_unordered(concurrency)
+                .collect()
+                .await;
+
+        for (index, result) in fallbacks {
+            syncs[index].result = Some(result);
+        }
+
+        let mut spent = Vec::new();
+
+        for index in pending {
+            let sync = &mut syncs[index];
+
+            if sync.result.is_some() {
+                continue;
+            }
+
+            let Some(coin_state) = coin_states.get(&sync.coin_id()).copied() else {
+                sync.result = Some(Err(WalletError::UnknownCoin(sync.coin_id())));
+                continue;
+            };
+
+            if let Some(spent_height) = coin_state.spent_height {
+                spent.push((index, coin_state, spent_height));
+                continue;
+            }
+
+            // A launcher is spent in the block it's created in, so only stores end up here.
+            sync.result = Some(match (sync.store.take(), coin_state.created_height) {
+                (Some(store), Some(latest_height)) => Ok(SyncStoreResponse::new(
+                    store,
+                    latest_height,
+                    with_history.then(|| std::mem::take(&mut sync.history)),
+                )),
+                _ => Err(WalletError::UnknownCoin(coin_state.coin.coin_id())),
+            });
+        }
+
+        let next_states: Vec<(usize, Result<NextState, WalletError>)> = stream::iter(spent)
+            .map(|(index, coin_state, spent_height)| {
+                let sync = &syncs[index];
+                let headers = &headers;
+                async move {
+                    (
+                        index,
+                        next_state(peer, headers, sync, coin_state, spent_height, with_history)
+                            .await,
+                    )
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+
+        for (index, next_state) in next_states {
+            let sync = &mut syncs[index];
+
+            match next_state {
+                Ok((store, entry)) => {
+                    if let Some(entry) = entry {
+                        sync.history.push(entry);
+                    }
+                    sync.store = Some(store);
+                }
+                Err(error) => sync.result = Some(Err(error)),
+            }
+        }
+    }
+
+    syncs
+        .into_iter()
+        .map(|sync| sync.result)
+        .collect::<Option<Vec<_>>>()
+        .expect("every store is synced")
+}
+
+// Parses the state a store's current coin (or launcher) was spent into.
+async fn next_state(
+    peer: &impl WalletProtocol,
+    headers: &HeaderCache,
+    sync: &StoreSync,
+    coin_state: CoinState,
+    spent_height: u32,
+    with_history: bool,
+) -> Result<NextState, WalletError> {
+    let puzzle_and_solution_req = peer
+        .request_puzzle_and_solution(coin_state.coin.coin_id(), spent_height)
+        .await?
+        .map_err(WalletError::RejectPuzzleSolution)?;
+
+    let cs = CoinSpend {
+        coin: coin_state.coin,
+        puzzle_reveal: puzzle_and_solution_req.puzzle,
+        solution: puzzle_and_solution_req.solution,
+    };
+
+    let mut ctx = SpendContext::new(); // just to run puzzles more easily
+
+    let new_store = DataStore::<DataStoreMetadata>::from_spend(
+        &mut ctx.allocator,
+        &cs,
+        sync.store
+            .as_ref()
+            .map_or(&[], |store| &store.info.delegated_puzzles),
+    )?
+    .ok_or(WalletError::Parse)?;
+
+    if !with_history {
+        return Ok((new_store, None));
+    }
+
+    let entry = get_store_history_entry(
+        peer,
+        headers,
+        &cs,
+        spent_height,
+        sync.store.as_ref(),
+        &new_store,
+    )
+    .await?;
+
+    Ok((new_store, Some(entry)))
+}
+
+// Syncs a single store from where the batch got it to, keeping the history synced so far.
+async fn sync_one(
+    peer: &impl WalletProtocol,
+    sync: &StoreSync,
+    last_height: Option<u32>,
+    last_header_hash: Bytes32,
+    with_history: bool,
+) -> Result<SyncStoreResponse, WalletError> {
+    let Some(store) = &sync.store else {
+        return sync_store_using_launcher_id(
+            peer,
+            sync.launcher_id,
+            last_height,
+            last_header_hash,
+            with_history,
+            &sync.history,
+        )
+        .await;
+    };
+
+    let mut response = sync_store(
+        peer,
+        store,
+        last_height,
+        last_header_hash,
+        with_history,
+        &sync.history,
+    )
+    .await?;
+
+    if response.reorg.is_none() {
+        if let Some(history) = response.history.as_mut() {
+            history.splice(0..0, sync.history.iter().cloned());
+        }
+        if let Some(root_hash_history) = response.root_hash_history.as_mut() {
+            root_hash_history.splice(
+                0..0,
+                sync.history
+                    .iter()
+                    .map(|entry| (entry.info.metadata.root_hash, entry.timestamp)),
+            );
+        }
+    }
+
+    Ok(response)
+}
+
+// Errors can't be cloned, so the other stores of a request that failed get a copy that keeps
+// the error's kind and message.
+fn copy_error(error: &WalletError) -> WalletError {
+    match error {
+        WalletError::Io(error) => io::Error::new(error.kind(), error.to_string()).into(),
+        WalletError::NoPeers => WalletError::NoPeers,
+        WalletError::Disconnected => WalletError::Disconnected,
+        error => io::Error::other(error.to_string()).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use chia::puzzles::standard::StandardArgs;
+    use chia::puzzles::DeriveSynthetic;
+
+    use super::*;
+    use crate::network::TargetNetwork;
+    use crate::simulator::ChainSimulator;
+    use crate::wallet::{mint_store, update_store_metadata, DataStoreInnerSpend};
+
+    #[tokio::test]
+    async fn test_sync_stores() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[3; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        let mut stores = Vec::new();
+
+        for i in 0..3 {
+            let coin = sim.new_coin(puzzle_hash, 1);
+            let minted = mint_store(
+                synthetic_key,
+                vec![coin],
+                Bytes32::new([i; 32]),
+                None,
+                None,
+                None,
+                puzzle_hash,
+                vec![],
+                0,
+            )?;
+            sim.sign_and_push(&sk, minted.coin_spends);
+
+            let mut store = minted.new_datastore;
+            for update in 0..i {
+                let updated = update_store_metadata(
+                    store,
+                    Bytes32::new([i * 10 + update; 32]),
+                    None,
+                    None,
+                    None,
+                    DataStoreInnerSpend::Owner(synthetic_key),
+                )?;
+                sim.sign_and_push(&sk, updated.coin_spends);
+                store = updated.new_datastore;
+            }
+            stores.push(store);
+        }
+
+        let targets = vec![
+            StoreSyncTarget::LauncherId(stores[0].info.launcher_id),
+            StoreSyncTarget::LauncherId(Bytes32::new([42; 32])),
+            StoreSyncTarget::LauncherId(stores[1].info.launcher_id),
+            StoreSyncTarget::Store(stores[2].clone()),
+        ];
+
+        let results = sync_stores(
+            &sim,
+            targets,
+            None,
+            sim.network().genesis_challenge,
+            true,
+            2,
+        )
+        .await;
+
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[1], Err(WalletError::UnknownCoin(_))));
+
+        for (result, store) in [&results[0], &results[2], &results[3]]
+            .into_iter()
+            .zip(&stores)
+        {
+            let response = result.as_ref().unwrap();
+            assert_eq!(response.latest_store.coin, store.coin);
+            assert_eq!(
+                response.latest_store.info.metadata.root_hash,
+                store.info.metadata.root_hash
+            );
+        }
+
+        let history = |index: usize| -> Vec<Bytes32> {
+            results[index]
+                .as_ref()
+                .unwrap()
+                .root_hash_history
+                .clone()
+                .unwrap()
+                .into_iter()
+                .map(|(root_hash, _)| root_hash)
+                .collect()
+        };
+
+        // Launcher syncs start at the launch, store syncs after the given state.
+        assert_eq!(history(0), vec![Bytes32::new([0; 32])]);
+        assert_eq!(
+            history(2),
+            vec![Bytes32::new([1; 32]), Bytes32::new([10; 32])]
+        );
+        assert!(history(3).is_empty());
+
+        // A request that fails isn't retried store by store.
+        sim.fail_requests(1);
+        let results = sync_stores(
+            &sim,
+            stores.into_iter().map(StoreSyncTarget::Store).collect(),
+            None,
+            sim.network().genesis_challenge,
+            false,
+            2,
+        )
+        .await;
+        assert_eq!(results.len(), 3);
+        for result in results {
+            assert!(matches!(
+                result,
+                Err(WalletError::Io(error)) if error.kind() == io::ErrorKind::ConnectionReset
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,573 @@
+use chia::bls::PublicKey;
+use chia::protocol::{Bytes32, Coin, SpendBundle};
+use chia::puzzles::standard::StandardArgs;
+use chia::traits::Streamable;
+use clap::Args;
+use datalayer_driver::server_coin::MirrorArgs;
+use datalayer_driver::transaction::TransactionBuilder;
+use datalayer_driver::{
+    create_server_coin, fetch_server_coin, get_unspent_coin_states, mint_store, select_coins,
+    send_spend_bundle, sign_with_signer, spend_server_coins, sync_store_using_launcher_id,
+    DataStore, DataStoreInnerSpend, DelegatedPuzzle, KeySigner, TargetNetwork, WalletError,
+    WalletProtocol,
+};
+use serde_json::{json, Map, Value};
+
+use crate::keyring::WalletKey;
+use crate::{output, parse, CliError};
+
+/// Cost limit when running the parent spend of a server coin to read its URLs.
+const MAX_SERVER_COIN_COST: u64 = 11_000_000_000;
+
+/// Options shared by the commands that create a transaction.
+#[derive(Debug, Clone, Default, Args)]
+pub struct SpendOptions {
+    /// Transaction fee in mojos.
+    #[arg(long, default_value_t = 0)]
+    pub fee: u64,
+
+    /// Print the signed spend bundle instead of broadcasting it. It can be sent later with `broadcast`.
+    #[arg(long)]
+    pub no_broadcast: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct MintArgs {
+    /// Root hash of the store's data.
+    #[arg(long, value_parser = parse::bytes32)]
+    pub root_hash: Bytes32,
+
+    #[arg(long)]
+    pub label: Option<String>,
+
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// Size of the store's data in bytes.
+    #[arg(long)]
+    pub bytes: Option<u64>,
+
+    /// Owner address or puzzle hash. Defaults to the wallet's first address.
+    #[arg(long, value_parser = parse::puzzle_hash)]
+    pub owner: Option<Bytes32>,
+
+    /// Synthetic key of an admin, who can update the store and its writers.
+    #[arg(long = "admin", value_parser = parse::public_key)]
+    pub admins: Vec<PublicKey>,
+
+    /// Synthetic key of a writer, who can update the store's metadata.
+    #[arg(long = "writer", value_parser = parse::public_key)]
+    pub writers: Vec<PublicKey>,
+
+    /// Oracle as `<puzzle hash>:<fee>`. Anyone can spend the store by paying the (even) fee to the puzzle hash.
+    #[arg(long = "oracle", value_parser = parse::oracle)]
+    pub oracles: Vec<(Bytes32, u64)>,
+
+    #[command(flatten)]
+    pub spend: SpendOptions,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct UpdateRootArgs {
+    #[arg(value_parser = parse::bytes32)]
+    pub launcher_id: Bytes32,
+
+    #[arg(value_parser = parse::bytes32)]
+    pub root_hash: Bytes32,
+
+    /// New label. The current one is kept if not set.
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// New description. The current one is kept if not set.
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// New size in bytes. The current one is kept if not set.
+    #[arg(long)]
+    pub bytes: Option<u64>,
+
+    #[command(flatten)]
+    pub spend: SpendOptions,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct TransferArgs {
+    #[arg(value_parser = parse::bytes32)]
+    pub launcher_id: Bytes32,
+
+    /// Address or puzzle hash of the new owner.
+    #[arg(value_parser = parse::puzzle_hash)]
+    pub new_owner: Bytes32,
+
+    #[command(flatten)]
+    pub spend: SpendOptions,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct WriterArgs {
+    #[arg(value_parser = parse::bytes32)]
+    pub launcher_id: Bytes32,
+
+    /// Synthetic key of the writer.
+    #[arg(value_parser = parse::public_key)]
+    pub writer: PublicKey,
+
+    #[command(flatten)]
+    pub spend: SpendOptions,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct MeltArgs {
+    #[arg(value_parser = parse::bytes32)]
+    pub launcher_id: Bytes32,
+
+    #[command(flatten)]
+    pub spend: SpendOptions,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct AddServerCoinArgs {
+    #[arg(value_parser = parse::bytes32)]
+    pub launcher_id: Bytes32,
+
+    /// URLs the store's data is served from.
+    #[arg(required = true)]
+    pub urls: Vec<String>,
+
+    /// Amount of the server coin in mojos.
+    #[arg(long)]
+    pub amount: u64,
+
+    /// Hint of the server coin, e.g. a morphed launcher id. Defaults to the launcher id.
+    #[arg(long, value_parser = parse::bytes32)]
+    pub hint: Option<Bytes32>,
+
+    #[command(flatten)]
+    pub spend: SpendOptions,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ListServerCoinsArgs {
+    #[arg(value_parser = parse::bytes32)]
+    pub launcher_id: Bytes32,
+
+    /// Hint the server coins were created with. Defaults to the launcher id.
+    #[arg(long, value_parser = parse::bytes32)]
+    pub hint: Option<Bytes32>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct RemoveServerCoinsArgs {
+    /// Ids of the server coins to spend. Their amounts are sent back to the wallet.
+    #[arg(required = true, value_parser = parse::bytes32)]
+    pub coin_ids: Vec<Bytes32>,
+
+    #[command(flatten)]
+    pub spend: SpendOptions,
+}
+
+pub async fn mint(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    args: MintArgs,
+) -> Result<Value, CliError> {
+    let mut delegated_puzzles = Vec::new();
+    for admin in args.admins {
+        delegated_puzzles.push(DelegatedPuzzle::Admin(StandardArgs::curry_tree_hash(admin)));
+    }
+    for writer in args.writers {
+        delegated_puzzles.push(DelegatedPuzzle::Writer(StandardArgs::curry_tree_hash(
+            writer,
+        )));
+    }
+    for (puzzle_hash, fee) in args.oracles {
+        delegated_puzzles.push(DelegatedPuzzle::Oracle(puzzle_hash, fee));
+    }
+
+    let coins = wallet_coins(peer, network, key).await?;
+    let minted = mint_store(
+        key.synthetic_key,
+        select_coins(coins, args.spend.fee + 1).map_err(WalletError::from)?,
+        args.root_hash,
+        args.label,
+        args.description,
+        args.bytes,
+        args.owner.unwrap_or(key.puzzle_hash),
+        delegated_puzzles,
+        args.spend.fee,
+    )?;
+
+    let mut result = submit(peer, network, key, minted.coin_spends, &args.spend).await?;
+    result.insert("store".to_string(), output::store(&minted.new_datastore));
+    Ok(Value::Object(result))
+}
+
+pub async fn sync(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    launcher_id: Bytes32,
+) -> Result<Value, CliError> {
+    let response = sync_store_using_launcher_id(
+        peer,
+        launcher_id,
+        None,
+        network.genesis_challenge,
+        false,
+        &[],
+    )
+    .await?;
+
+    Ok(json!({
+        "height": response.latest_height,
+        "store": output::store(&response.latest_store),
+    }))
+}
+
+pub async fn history(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    launcher_id: Bytes32,
+) -> Result<Value, CliError> {
+    let response = sync_store_using_launcher_id(
+        peer,
+        launcher_id,
+        None,
+        network.genesis_challenge,
+        true,
+        &[],
+    )
+    .await?;
+    let history = response.history.unwrap_or_default();
+
+    Ok(json!({
+        "height": response.latest_height,
+        "history": history.iter().map(output::history_entry).collect::<Vec<_>>(),
+    }))
+}
+
+pub async fn update_root(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    args: UpdateRootArgs,
+) -> Result<Value, CliError> {
+    let store = latest_store(peer, network, args.launcher_id).await?;
+    let inner_spend = inner_spend(&store, key, true)?;
+    let metadata = store.info.metadata.clone();
+
+    let mut builder = TransactionBuilder::new(key.synthetic_key);
+    builder.set_fee(args.spend.fee);
+    let new_store = builder.update_store_metadata(
+        store,
+        args.root_hash,
+        args.label.or(metadata.label),
+        args.description.or(metadata.description),
+        args.bytes.or(metadata.bytes),
+        inner_spend,
+    )?;
+
+    submit_store_update(peer, network, key, builder, &new_store, &args.spend).await
+}
+
+pub async fn transfer(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    args: TransferArgs,
+) -> Result<Value, CliError> {
+    let store = latest_store(peer, network, args.launcher_id).await?;
+
+    // Admins can only change the delegated puzzles.
+    let DataStoreInnerSpend::Owner(owner_key) = inner_spend(&store, key, false)? else {
+        return Err(WalletError::Permission.into());
+    };
+
+    let delegated_puzzles = store.info.delegated_puzzles.clone();
+    let mut builder = TransactionBuilder::new(key.synthetic_key);
+    builder.set_fee(args.spend.fee);
+    let new_store = builder.update_store_ownership(
+        store,
+        args.new_owner,
+        delegated_puzzles,
+        DataStoreInnerSpend::Owner(owner_key),
+    )?;
+
+    submit_store_update(peer, network, key, builder, &new_store, &args.spend).await
+}
+
+pub async fn set_writer(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    args: WriterArgs,
+    is_writer: bool,
+) -> Result<Value, CliError> {
+    let store = latest_store(peer, network, args.launcher_id).await?;
+    let inner_spend = inner_spend(&store, key, false)?;
+
+    let writer = DelegatedPuzzle::Writer(StandardArgs::curry_tree_hash(args.writer));
+    let mut delegated_puzzles = store.info.delegated_puzzles.clone();
+
+    if delegated_puzzles.contains(&writer) == is_writer {
+        return Err(CliError::Invalid(if is_writer {
+            "the key is already a writer of the store".to_string()
+        } else {
+            "the key isn't a writer of the store".to_string()
+        }));
+    }
+
+    if is_writer {
+        delegated_puzzles.push(writer);
+    } else {
+        delegated_puzzles.retain(|delegated_puzzle| delegated_puzzle != &writer);
+    }
+
+    let owner_puzzle_hash = store.info.owner_puzzle_hash;
+    let mut builder = TransactionBuilder::new(key.synthetic_key);
+    builder.set_fee(args.spend.fee);
+    let new_store =
+        builder.update_store_ownership(store, owner_puzzle_hash, delegated_puzzles, inner_spend)?;
+
+    submit_store_update(peer, network, key, builder, &new_store, &args.spend).await
+}
+
+pub async fn melt(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    args: MeltArgs,
+) -> Result<Value, CliError> {
+    let store = latest_store(peer, network, args.launcher_id).await?;
+    if store.info.owner_puzzle_hash != key.puzzle_hash {
+        return Err(WalletError::Permission.into());
+    }
+
+    let mut builder = TransactionBuilder::new(key.synthetic_key);
+    builder.set_fee(args.spend.fee);
+    builder.melt_store(store, key.synthetic_key)?;
+
+    let coins = wallet_coins(peer, network, key).await?;
+    let transaction = builder.build(coins, network)?;
+
+    let mut result = submit(peer, network, key, transaction.coin_spends, &args.spend).await?;
+    result.insert("launcherId".to_string(), output::hex(args.launcher_id));
+    Ok(Value::Object(result))
+}
+
+pub async fn add_server_coin(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    args: AddServerCoinArgs,
+) -> Result<Value, CliError> {
+    let coins = wallet_coins(peer, network, key).await?;
+    let (coin_spends, server_coin) = create_server_coin(
+        key.synthetic_key,
+        select_coins(coins, args.amount + args.spend.fee).map_err(WalletError::from)?,
+        args.hint.unwrap_or(args.launcher_id),
+        args.urls,
+        args.amount,
+        args.spend.fee,
+    )?;
+
+    let mut result = submit(peer, network, key, coin_spends, &args.spend).await?;
+    result.insert("serverCoin".to_string(), output::server_coin(&server_coin));
+    Ok(Value::Object(result))
+}
+
+pub async fn list_server_coins(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    args: ListServerCoinsArgs,
+) -> Result<Value, CliError> {
+    let hint = args.hint.unwrap_or(args.launcher_id);
+    let coin_states =
+        get_unspent_coin_states(peer, hint, None, network.genesis_challenge, true).await?;
+    let mirror_puzzle_hash: Bytes32 = MirrorArgs::curry_tree_hash().into();
+
+    let mut server_coins = Vec::new();
+
+    for coin_state in coin_states.coin_states {
+        if coin_state.coin.puzzle_hash != mirror_puzzle_hash {
+            continue;
+        }
+
+        // Coins with the hint that weren't created like server coins are skipped.
+        match fetch_server_coin(peer, coin_state, MAX_SERVER_COIN_COST).await {
+            Ok(server_coin) => server_coins.push(output::server_coin(&server_coin)),
+            Err(WalletError::Parse) => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    Ok(json!({ "serverCoins": server_coins }))
+}
+
+pub async fn remove_server_coins(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    args: RemoveServerCoinsArgs,
+) -> Result<Value, CliError> {
+    let coin_states = peer
+        .request_coin_state(
+            args.coin_ids.clone(),
+            None,
+            network.genesis_challenge,
+            false,
+        )
+        .await?
+        .map_err(WalletError::RejectCoinState)?
+        .coin_states;
+
+    let mut server_coins = Vec::with_capacity(args.coin_ids.len());
+    for coin_id in &args.coin_ids {
+        let coin_state = coin_states
+            .iter()
+            .find(|coin_state| {
+                coin_state.coin.coin_id() == *coin_id && coin_state.spent_height.is_none()
+            })
+            .ok_or(WalletError::UnknownCoin(*coin_id))?;
+        server_coins.push(coin_state.coin);
+    }
+
+    // At least one wallet coin is spent to send the server coins' amounts back.
+    let coins = wallet_coins(peer, network, key).await?;
+    let mut selected_coins =
+        select_coins(coins, args.spend.fee.max(1)).map_err(WalletError::from)?;
+    selected_coins.extend(server_coins);
+
+    let coin_spends = spend_server_coins(
+        peer,
+        key.synthetic_key,
+        selected_coins,
+        args.spend.fee,
+        network,
+    )
+    .await?;
+
+    let mut result = submit(peer, network, key, coin_spends, &args.spend).await?;
+    result.insert(
+        "removedCoinIds".to_string(),
+        args.coin_ids.iter().map(output::hex).collect(),
+    );
+    Ok(Value::Object(result))
+}
+
+pub async fn broadcast(
+    peer: &impl WalletProtocol,
+    spend_bundle: SpendBundle,
+) -> Result<Value, CliError> {
+    let transaction_id = send_spend_bundle(peer, spend_bundle).await?;
+
+    Ok(json!({ "transactionId": output::hex(transaction_id) }))
+}
+
+async fn wallet_coins(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+) -> Result<Vec<Coin>, CliError> {
+    let coin_states = get_unspent_coin_states(
+        peer,
+        key.puzzle_hash,
+        None,
+        network.genesis_challenge,
+        false,
+    )
+    .await?;
+
+    Ok(coin_states
+        .coin_states
+        .into_iter()
+        .map(|coin_state| coin_state.coin)
+        .collect())
+}
+
+async fn latest_store(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    launcher_id: Bytes32,
+) -> Result<DataStore, CliError> {
+    Ok(sync_store_using_launcher_id(
+        peer,
+        launcher_id,
+        None,
+        network.genesis_challenge,
+        false,
+        &[],
+    )
+    .await?
+    .latest_store)
+}
+
+// The strongest role the key has on the store.
+fn inner_spend(
+    store: &DataStore,
+    key: &WalletKey,
+    allow_writer: bool,
+) -> Result<DataStoreInnerSpend, CliError> {
+    if store.info.owner_puzzle_hash == key.puzzle_hash {
+        return Ok(DataStoreInnerSpend::Owner(key.synthetic_key));
+    }
+
+    let puzzle_hash = key.puzzle_hash.into();
+    let delegated_puzzles = &store.info.delegated_puzzles;
+
+    if delegated_puzzles.contains(&DelegatedPuzzle::Admin(puzzle_hash)) {
+        return Ok(DataStoreInnerSpend::Admin(key.synthetic_key));
+    }
+
+    if allow_writer && delegated_puzzles.contains(&DelegatedPuzzle::Writer(puzzle_hash)) {
+        return Ok(DataStoreInnerSpend::Writer(key.synthetic_key));
+    }
+
+    Err(WalletError::Permission.into())
+}
+
+async fn submit_store_update(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    builder: TransactionBuilder,
+    new_store: &DataStore,
+    options: &SpendOptions,
+) -> Result<Value, CliError> {
+    let coins = wallet_coins(peer, network, key).await?;
+    let transaction = builder.build(coins, network)?;
+
+    let mut result = submit(peer, network, key, transaction.coin_spends, options).await?;
+    result.insert("store".to_string(), output::store(new_store));
+    Ok(Value::Object(result))
+}
+
+// Signs the coin spends and broadcasts them, or returns the spend bundle with `--no-broadcast`.
+async fn submit(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: &WalletKey,
+    coin_spends: Vec<chia::protocol::CoinSpend>,
+    options: &SpendOptions,
+) -> Result<Map<String, Value>, CliError> {
+    // Fails if a spend needs a signature the wallet key can't make, instead of broadcasting a
+    // spend bundle that's bound to be rejected.
+    let signer = KeySigner::new(vec![key.synthetic_secret_key.clone()]);
+    let signature = sign_with_signer(&signer, &coin_spends, network).await?;
+    let spend_bundle = SpendBundle::new(coin_spends, signature);
+
+    let mut result = Map::new();
+
+    if options.no_broadcast {
+        result.insert(
+            "spendBundle".to_string(),
+            output::hex(spend_bundle.to_bytes()?),
+        );
+    } else {
+        let transaction_id = send_spend_bundle(peer, spend_bundle).await?;
+        result.insert("transactionId".to_string(), output::hex(transaction_id));
+    }
+
+    Ok(result)
+}
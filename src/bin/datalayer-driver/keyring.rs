@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::Path;
+
+use chia::bls::{master_to_wallet_unhardened, PublicKey, SecretKey};
+use chia::protocol::Bytes32;
+use chia::puzzles::{standard::StandardArgs, DeriveSynthetic};
+use serde_json::Value;
+
+use crate::CliError;
+
+/// The wallet key a command spends and signs with.
+///
+/// Like `masterSecretKeyToWalletSyntheticSecretKey` in the JS bindings, this is the synthetic
+/// key of the first unhardened wallet key, i.e. the key behind the wallet's first address.
+#[derive(Debug, Clone)]
+pub struct WalletKey {
+    pub fingerprint: u32,
+    pub synthetic_secret_key: SecretKey,
+    pub synthetic_key: PublicKey,
+    pub puzzle_hash: Bytes32,
+}
+
+impl WalletKey {
+    pub fn from_master_secret_key(master_secret_key: &SecretKey) -> Self {
+        let synthetic_secret_key =
+            master_to_wallet_unhardened(master_secret_key, 0).derive_synthetic();
+        let synthetic_key = synthetic_secret_key.public_key();
+
+        Self {
+            fingerprint: master_secret_key.public_key().get_fingerprint(),
+            synthetic_secret_key,
+            synthetic_key,
+            puzzle_hash: StandardArgs::curry_tree_hash(synthetic_key).into(),
+        }
+    }
+}
+
+/// Reads a key from a keyring file, which holds named master secret keys:
+///
+/// ```json
+/// { "keys": [{ "name": "store-admin", "secretKey": "<32 bytes, hex>" }] }
+/// ```
+///
+/// `selector` is the name or fingerprint of the key. It can be left out if the keyring only
+/// holds one key.
+pub fn load_key(path: &Path, selector: Option<&str>) -> Result<WalletKey, CliError> {
+    let keyring: Value = serde_json::from_slice(&fs::read(path)?)?;
+    let entries = keyring["keys"]
+        .as_array()
+        .ok_or_else(|| CliError::Keyring("expected a \"keys\" array".to_string()))?;
+
+    let mut keys = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let name = entry["name"].as_str().unwrap_or_default();
+        let secret_key = entry["secretKey"]
+            .as_str()
+            .and_then(parse_secret_key)
+            .ok_or_else(|| CliError::Keyring(format!("key '{name}' has no valid secretKey")))?;
+
+        keys.push((name, WalletKey::from_master_secret_key(&secret_key)));
+    }
+
+    let mut matching = keys.into_iter().filter(|(name, key)| {
+        selector.is_none_or(|selector| selector == *name || selector == key.fingerprint.to_string())
+    });
+
+    match (matching.next(), matching.next()) {
+        (Some((_, key)), None) => Ok(key),
+        (None, _) => Err(CliError::Keyring(match selector {
+            Some(selector) => format!("no key named '{selector}'"),
+            None => "the keyring is empty".to_string(),
+        })),
+        (Some(_), Some(_)) => Err(CliError::Keyring(
+            "the keyring holds several keys, pick one with --key".to_string(),
+        )),
+    }
+}
+
+fn parse_secret_key(value: &str) -> Option<SecretKey> {
+    let bytes: [u8; 32] = hex::decode(value.trim_start_matches("0x"))
+        .ok()?
+        .try_into()
+        .ok()?;
+
+    SecretKey::from_bytes(&bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a file of its own, so tests running in parallel don't overwrite each other's keyrings
+    fn write_keyring(contents: &str) -> tempfile::NamedTempFile {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), contents).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_load_key() {
+        let first = SecretKey::from_seed(&[1; 32]);
+        let second = SecretKey::from_seed(&[2; 32]);
+        let keyring = write_keyring(&format!(
+            r#"{{ "keys": [{{ "name": "first", "secretKey": "{}" }}, {{ "name": "second", "secretKey": "0x{}" }}] }}"#,
+            hex::encode(first.to_bytes()),
+            hex::encode(second.to_bytes())
+        ));
+        let path = keyring.path();
+
+        let key = load_key(path, Some("second")).unwrap();
+        assert_eq!(key.fingerprint, second.public_key().get_fingerprint());
+        assert_eq!(
+            key.synthetic_key,
+            master_to_wallet_unhardened(&second.public_key(), 0).derive_synthetic()
+        );
+
+        let fingerprint = first.public_key().get_fingerprint().to_string();
+        assert_eq!(
+            load_key(path, Some(&fingerprint)).unwrap().synthetic_key,
+            WalletKey::from_master_secret_key(&first).synthetic_key
+        );
+
+        assert!(matches!(load_key(path, None), Err(CliError::Keyring(_))));
+        assert!(matches!(
+            load_key(path, Some("third")),
+            Err(CliError::Keyring(_))
+        ));
+
+        let keyring = write_keyring(r#"{ "keys": [{ "name": "short", "secretKey": "abcd" }] }"#);
+        assert!(matches!(
+            load_key(keyring.path(), None),
+            Err(CliError::Keyring(_))
+        ));
+    }
+}
@@ -0,0 +1,563 @@
+//! Command line tool for administering DataLayer stores and their server coins.
+//!
+//! Keys are read from a keyring file (see [`keyring::load_key`]) and the connection to the full
+//! node uses the wallet certificate of a local Chia install.
+
+mod commands;
+mod keyring;
+mod output;
+mod parse;
+
+use std::fs;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use chia::protocol::{Bytes32, SpendBundle};
+use chia::traits::Streamable;
+use chia_wallet_sdk::{
+    connect_peer, create_tls_connector, load_ssl_cert, ClientError, CoinSelectionError, Peer,
+    SignerError,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use datalayer_driver::discovery::Discovery;
+use datalayer_driver::{TargetNetwork, WalletError, WalletProtocol};
+use native_tls::TlsConnector;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::commands::{
+    AddServerCoinArgs, ListServerCoinsArgs, MeltArgs, MintArgs, RemoveServerCoinsArgs,
+    TransferArgs, UpdateRootArgs, WriterArgs,
+};
+use crate::keyring::WalletKey;
+
+/// How long to wait for a full node to accept the connection before trying the next one.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("{0}")]
+    Wallet(#[from] WalletError),
+
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid spend bundle: {0}")]
+    Streamable(#[from] chia::traits::Error),
+
+    #[error("Keyring error: {0}")]
+    Keyring(String),
+
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl From<ClientError> for CliError {
+    fn from(error: ClientError) -> Self {
+        WalletError::from(error).into()
+    }
+}
+
+impl From<CoinSelectionError> for CliError {
+    fn from(error: CoinSelectionError) -> Self {
+        WalletError::from(error).into()
+    }
+}
+
+impl From<SignerError> for CliError {
+    fn from(error: SignerError) -> Self {
+        WalletError::from(error).into()
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "datalayer-driver",
+    version,
+    about = "Administer DataLayer stores and their server coins"
+)]
+struct Cli {
+    #[arg(long, value_enum, global = true, default_value_t = NetworkArg::Mainnet)]
+    network: NetworkArg,
+
+    /// Full node to connect to. If not set, one is found through the network's introducers.
+    #[arg(long, global = true)]
+    node: Option<SocketAddr>,
+
+    /// Wallet certificate. Defaults to `config/ssl/wallet/wallet_node.crt` in `$CHIA_ROOT` or `~/.chia/mainnet`.
+    #[arg(long, global = true)]
+    ssl_cert: Option<PathBuf>,
+
+    /// Wallet certificate key. Defaults to `config/ssl/wallet/wallet_node.key` in `$CHIA_ROOT` or `~/.chia/mainnet`.
+    #[arg(long, global = true)]
+    ssl_key: Option<PathBuf>,
+
+    /// Keyring file holding the master secret keys.
+    #[arg(long, global = true, env = "DL_KEYRING")]
+    keyring: Option<PathBuf>,
+
+    /// Name or fingerprint of the keyring key to use.
+    #[arg(long, global = true)]
+    key: Option<String>,
+
+    /// Print results and errors as JSON.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum NetworkArg {
+    Mainnet,
+    Testnet11,
+}
+
+impl NetworkArg {
+    fn target_network(self) -> TargetNetwork {
+        match self {
+            Self::Mainnet => TargetNetwork::mainnet(),
+            Self::Testnet11 => TargetNetwork::testnet11(),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Mint a new store owned by the wallet, or by `--owner`.
+    Mint(MintArgs),
+
+    /// Print the latest state of a store.
+    Sync {
+        #[arg(value_parser = parse::bytes32)]
+        launcher_id: Bytes32,
+    },
+
+    /// Print every state of a store since it was minted.
+    History {
+        #[arg(value_parser = parse::bytes32)]
+        launcher_id: Bytes32,
+    },
+
+    /// Update the root hash and metadata of a store as its owner, an admin or a writer.
+    UpdateRoot(UpdateRootArgs),
+
+    /// Transfer a store to a new owner. Its admins, writers and oracles are kept.
+    Transfer(TransferArgs),
+
+    /// Add a writer to a store as its owner or an admin.
+    AddWriter(WriterArgs),
+
+    /// Remove a writer from a store as its owner or an admin.
+    RemoveWriter(WriterArgs),
+
+    /// Melt a store as its owner.
+    Melt(MeltArgs),
+
+    /// Manage the server coins that advertise a store's mirrors.
+    #[command(subcommand)]
+    ServerCoin(ServerCoinCommand),
+
+    /// Broadcast a spend bundle created with `--no-broadcast`.
+    Broadcast {
+        /// The spend bundle as hex, or a file holding the hex or the JSON output of a command.
+        spend_bundle: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ServerCoinCommand {
+    /// Create a server coin with the store's mirror URLs.
+    Add(AddServerCoinArgs),
+
+    /// List the server coins of a store.
+    List(ListServerCoinsArgs),
+
+    /// Spend server coins, sending their amounts back to the wallet.
+    Remove(RemoveServerCoinsArgs),
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let as_json = cli.json;
+
+    match run(cli).await {
+        Ok(value) => {
+            output::print(&value, as_json);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            if as_json {
+                eprintln!("{:#}", json!({ "error": error.to_string() }));
+            } else {
+                eprintln!("error: {error}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<Value, CliError> {
+    let network = cli.network.target_network();
+
+    // Parse the spend bundle and load the key before connecting, so mistakes are reported quickly.
+    let spend_bundle = match &cli.command {
+        Command::Broadcast { spend_bundle } => Some(read_spend_bundle(spend_bundle)?),
+        _ => None,
+    };
+
+    let key = if needs_key(&cli.command) {
+        let path = cli.keyring.as_deref().ok_or_else(|| {
+            CliError::Keyring("no keyring, set --keyring or DL_KEYRING".to_string())
+        })?;
+        Some(keyring::load_key(path, cli.key.as_deref())?)
+    } else {
+        None
+    };
+
+    let peer = connect(&cli, &network).await?;
+    execute(&peer, &network, key.as_ref(), cli.command, spend_bundle).await
+}
+
+fn needs_key(command: &Command) -> bool {
+    !matches!(
+        command,
+        Command::Sync { .. }
+            | Command::History { .. }
+            | Command::Broadcast { .. }
+            | Command::ServerCoin(ServerCoinCommand::List(_))
+    )
+}
+
+async fn execute(
+    peer: &impl WalletProtocol,
+    network: &TargetNetwork,
+    key: Option<&WalletKey>,
+    command: Command,
+    spend_bundle: Option<SpendBundle>,
+) -> Result<Value, CliError> {
+    match command {
+        Command::Mint(args) => commands::mint(peer, network, require_key(key)?, args).await,
+        Command::Sync { launcher_id } => commands::sync(peer, network, launcher_id).await,
+        Command::History { launcher_id } => commands::history(peer, network, launcher_id).await,
+        Command::UpdateRoot(args) => {
+            commands::update_root(peer, network, require_key(key)?, args).await
+        }
+        Command::Transfer(args) => commands::transfer(peer, network, require_key(key)?, args).await,
+        Command::AddWriter(args) => {
+            commands::set_writer(peer, network, require_key(key)?, args, true).await
+        }
+        Command::RemoveWriter(args) => {
+            commands::set_writer(peer, network, require_key(key)?, args, false).await
+        }
+        Command::Melt(args) => commands::melt(peer, network, require_key(key)?, args).await,
+        Command::ServerCoin(ServerCoinCommand::Add(args)) => {
+            commands::add_server_coin(peer, network, require_key(key)?, args).await
+        }
+        Command::ServerCoin(ServerCoinCommand::List(args)) => {
+            commands::list_server_coins(peer, network, args).await
+        }
+        Command::ServerCoin(ServerCoinCommand::Remove(args)) => {
+            commands::remove_server_coins(peer, network, require_key(key)?, args).await
+        }
+        Command::Broadcast { .. } => {
+            let spend_bundle = spend_bundle
+                .ok_or_else(|| CliError::Invalid("the spend bundle wasn't read".to_string()))?;
+            commands::broadcast(peer, spend_bundle).await
+        }
+    }
+}
+
+fn require_key(key: Option<&WalletKey>) -> Result<&WalletKey, CliError> {
+    key.ok_or_else(|| CliError::Keyring("no key loaded".to_string()))
+}
+
+// Accepts the hex of a spend bundle, or a file holding the hex or a command's JSON output.
+fn read_spend_bundle(value: &str) -> Result<SpendBundle, CliError> {
+    let text = match fs::read_to_string(value) {
+        Ok(contents) => contents,
+        Err(_) if hex::decode(value.trim_start_matches("0x")).is_ok() => value.to_string(),
+        Err(error) => return Err(error.into()),
+    };
+    let text = text.trim();
+
+    let hex_text = if text.starts_with('{') {
+        let output: Value = serde_json::from_str(text)?;
+        output["spendBundle"]
+            .as_str()
+            .ok_or_else(|| CliError::Invalid("the JSON has no \"spendBundle\" field".to_string()))?
+            .to_string()
+    } else {
+        text.to_string()
+    };
+
+    let bytes = hex::decode(hex_text.trim_start_matches("0x"))
+        .map_err(|error| CliError::Invalid(format!("invalid spend bundle hex: {error}")))?;
+
+    Ok(SpendBundle::from_bytes(&bytes)?)
+}
+
+async fn connect(cli: &Cli, network: &TargetNetwork) -> Result<Peer, CliError> {
+    let ssl_dir = chia_root().join("config/ssl/wallet");
+    let cert_path = cli
+        .ssl_cert
+        .clone()
+        .unwrap_or_else(|| ssl_dir.join("wallet_node.crt"));
+    let key_path = cli
+        .ssl_key
+        .clone()
+        .unwrap_or_else(|| ssl_dir.join("wallet_node.key"));
+
+    let cert = load_ssl_cert(&cert_path.to_string_lossy(), &key_path.to_string_lossy())?;
+    let tls_connector = create_tls_connector(&cert)?;
+
+    let socket_addrs = match cli.node {
+        Some(socket_addr) => vec![socket_addr],
+        None => {
+            Discovery::for_network(network, tls_connector.clone())
+                .discover()
+                .await?
+        }
+    };
+
+    let mut last_error = CliError::Wallet(WalletError::NoPeers);
+
+    for socket_addr in socket_addrs {
+        match connect_node(network, tls_connector.clone(), socket_addr).await {
+            Ok(peer) => return Ok(peer),
+            Err(error) => last_error = error,
+        }
+    }
+
+    Err(last_error)
+}
+
+async fn connect_node(
+    network: &TargetNetwork,
+    tls_connector: TlsConnector,
+    socket_addr: SocketAddr,
+) -> Result<Peer, CliError> {
+    let connection = connect_peer(network.network_id.clone(), tls_connector, socket_addr);
+    let (peer, mut receiver) = tokio::time::timeout(CONNECT_TIMEOUT, connection)
+        .await
+        .map_err(|_| CliError::Invalid(format!("timed out connecting to {socket_addr}")))??;
+
+    // Unsolicited messages aren't used, but the channel has to be drained.
+    tokio::spawn(async move { while receiver.recv().await.is_some() {} });
+
+    Ok(peer)
+}
+
+fn chia_root() -> PathBuf {
+    if let Some(root) = std::env::var_os("CHIA_ROOT") {
+        return root.into();
+    }
+
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .unwrap_or_default();
+    PathBuf::from(home).join(".chia/mainnet")
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use datalayer_driver::simulator::ChainSimulator;
+
+    use super::*;
+    use crate::commands::SpendOptions;
+
+    #[test]
+    fn test_parse_cli() {
+        let cli = Cli::try_parse_from([
+            "datalayer-driver",
+            "--network",
+            "testnet11",
+            "server-coin",
+            "add",
+            &"ab".repeat(32),
+            "https://example.com",
+            "--amount",
+            "1000",
+            "--json",
+        ])
+        .unwrap();
+
+        assert!(cli.json);
+        assert!(matches!(cli.network, NetworkArg::Testnet11));
+        assert!(matches!(
+            cli.command,
+            Command::ServerCoin(ServerCoinCommand::Add(AddServerCoinArgs {
+                amount: 1000,
+                ..
+            }))
+        ));
+
+        assert!(Cli::try_parse_from(["datalayer-driver", "sync", "abcd"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_store_administration() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let network = sim.network().clone();
+
+        let key = WalletKey::from_master_secret_key(&SecretKey::from_seed(&[3; 32]));
+        let writer = WalletKey::from_master_secret_key(&SecretKey::from_seed(&[4; 32]));
+        sim.new_coin(key.puzzle_hash, 1_000_000);
+        sim.new_coin(writer.puzzle_hash, 1_000);
+
+        let spend = SpendOptions {
+            fee: 10,
+            no_broadcast: false,
+        };
+        let root_hash = Bytes32::new([1; 32]);
+
+        let minted = commands::mint(
+            &sim,
+            &network,
+            &key,
+            MintArgs {
+                root_hash,
+                label: Some("label".to_string()),
+                description: None,
+                bytes: None,
+                owner: None,
+                admins: Vec::new(),
+                writers: Vec::new(),
+                oracles: Vec::new(),
+                spend: spend.clone(),
+            },
+        )
+        .await?;
+        let launcher_id = parse::bytes32(minted["store"]["launcherId"].as_str().unwrap()).unwrap();
+
+        commands::set_writer(
+            &sim,
+            &network,
+            &key,
+            WriterArgs {
+                launcher_id,
+                writer: writer.synthetic_key,
+                spend: spend.clone(),
+            },
+            true,
+        )
+        .await?;
+
+        // The writer can update the root, and the label is kept.
+        let updated = commands::update_root(
+            &sim,
+            &network,
+            &writer,
+            UpdateRootArgs {
+                launcher_id,
+                root_hash: Bytes32::new([2; 32]),
+                label: None,
+                description: None,
+                bytes: Some(100),
+                spend: SpendOptions::default(),
+            },
+        )
+        .await?;
+        assert_eq!(updated["store"]["label"], "label");
+        assert_eq!(updated["store"]["bytes"], 100);
+
+        // But it can't melt the store.
+        let melt = commands::melt(
+            &sim,
+            &network,
+            &writer,
+            MeltArgs {
+                launcher_id,
+                spend: SpendOptions::default(),
+            },
+        )
+        .await;
+        assert!(matches!(
+            melt,
+            Err(CliError::Wallet(WalletError::Permission))
+        ));
+
+        let synced = commands::sync(&sim, &network, launcher_id).await?;
+        assert_eq!(synced["store"], updated["store"]);
+
+        let history = commands::history(&sim, &network, launcher_id).await?;
+        let kinds: Vec<&str> = history["history"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, ["launch", "owner", "writer"]);
+
+        // Server coins are listed by the launcher id they're hinted with.
+        let urls = vec!["https://example.com".to_string()];
+        let added = commands::add_server_coin(
+            &sim,
+            &network,
+            &key,
+            AddServerCoinArgs {
+                launcher_id,
+                urls: urls.clone(),
+                amount: 1_000,
+                hint: None,
+                spend: spend.clone(),
+            },
+        )
+        .await?;
+
+        let listed = commands::list_server_coins(
+            &sim,
+            &network,
+            ListServerCoinsArgs {
+                launcher_id,
+                hint: None,
+            },
+        )
+        .await?;
+        assert_eq!(listed["serverCoins"], json!([added["serverCoin"]]));
+        assert_eq!(listed["serverCoins"][0]["urls"], json!(urls));
+
+        let coin_id = parse::bytes32(added["serverCoin"]["coinId"].as_str().unwrap()).unwrap();
+        commands::remove_server_coins(
+            &sim,
+            &network,
+            &key,
+            RemoveServerCoinsArgs {
+                coin_ids: vec![coin_id],
+                spend: spend.clone(),
+            },
+        )
+        .await?;
+        assert!(sim.coin_state(coin_id).unwrap().spent_height.is_some());
+
+        // With `--no-broadcast`, the spend bundle is printed and can be broadcast later.
+        let melted = commands::melt(
+            &sim,
+            &network,
+            &key,
+            MeltArgs {
+                launcher_id,
+                spend: SpendOptions {
+                    fee: 0,
+                    no_broadcast: true,
+                },
+            },
+        )
+        .await?;
+        let spend_bundle = read_spend_bundle(melted["spendBundle"].as_str().unwrap())?;
+        commands::broadcast(&sim, spend_bundle).await?;
+
+        let synced = commands::sync(&sim, &network, launcher_id).await;
+        assert!(synced.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use chia::protocol::Bytes32;
+use datalayer_driver::server_coin::ServerCoin;
+use datalayer_driver::{
+    DataStore, DataStoreInfo, DelegatedPuzzle, StoreHistoryEntry, StoreSpendKind,
+};
+use serde_json::{json, Map, Value};
+
+/// Prints a command's result, either as JSON or as indented `key: value` lines.
+pub fn print(value: &Value, as_json: bool) {
+    if as_json {
+        println!("{value:#}");
+    } else {
+        let mut text = String::new();
+        write_text(&mut text, value, 0);
+        print!("{text}");
+    }
+}
+
+fn write_text(text: &mut String, value: &Value, indent: usize) {
+    let padding = " ".repeat(indent);
+
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                match value {
+                    Value::Object(_) | Value::Array(_) => {
+                        text.push_str(&format!("{padding}{key}:\n"));
+                        write_text(text, value, indent + 2);
+                    }
+                    _ => text.push_str(&format!("{padding}{key}: {}\n", scalar(value))),
+                }
+            }
+        }
+        Value::Array(items) if items.is_empty() => text.push_str(&format!("{padding}(none)\n")),
+        Value::Array(items) => {
+            for item in items {
+                // Items are written one level deeper, with a dash in front of their first line.
+                let mut item_text = String::new();
+                write_text(&mut item_text, item, indent + 2);
+                if item_text.len() >= indent + 2 {
+                    item_text.replace_range(indent..indent + 2, "- ");
+                }
+                text.push_str(&item_text);
+            }
+        }
+        _ => text.push_str(&format!("{padding}{}\n", scalar(value))),
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Null => "-".to_string(),
+        value => value.to_string(),
+    }
+}
+
+pub fn hex(bytes: impl AsRef<[u8]>) -> Value {
+    hex::encode(bytes).into()
+}
+
+pub fn store(store: &DataStore) -> Value {
+    let mut fields = Map::new();
+    fields.insert("coinId".to_string(), hex(store.coin.coin_id()));
+    fields.extend(info(&store.info));
+    Value::Object(fields)
+}
+
+pub fn info(info: &DataStoreInfo) -> Map<String, Value> {
+    let metadata = &info.metadata;
+    let delegated_puzzles = info.delegated_puzzles.iter().map(delegated_puzzle);
+
+    let mut fields = Map::new();
+    fields.insert("launcherId".to_string(), hex(info.launcher_id));
+    fields.insert("rootHash".to_string(), hex(metadata.root_hash));
+    fields.insert("label".to_string(), metadata.label.clone().into());
+    fields.insert(
+        "description".to_string(),
+        metadata.description.clone().into(),
+    );
+    fields.insert("bytes".to_string(), metadata.bytes.into());
+    fields.insert("ownerPuzzleHash".to_string(), hex(info.owner_puzzle_hash));
+    fields.insert("delegatedPuzzles".to_string(), delegated_puzzles.collect());
+    fields
+}
+
+pub fn delegated_puzzle(delegated_puzzle: &DelegatedPuzzle) -> Value {
+    match delegated_puzzle {
+        DelegatedPuzzle::Admin(puzzle_hash) => json!({
+            "kind": "admin",
+            "puzzleHash": hex(Bytes32::from(*puzzle_hash)),
+        }),
+        DelegatedPuzzle::Writer(puzzle_hash) => json!({
+            "kind": "writer",
+            "puzzleHash": hex(Bytes32::from(*puzzle_hash)),
+        }),
+        DelegatedPuzzle::Oracle(puzzle_hash, fee) => json!({
+            "kind": "oracle",
+            "puzzleHash": hex(puzzle_hash),
+            "fee": fee,
+        }),
+    }
+}
+
+pub fn history_entry(entry: &StoreHistoryEntry) -> Value {
+    let kind = match entry.kind {
+        StoreSpendKind::Launch => "launch",
+        StoreSpendKind::Owner => "owner",
+        StoreSpendKind::Admin => "admin",
+        StoreSpendKind::Writer => "writer",
+        StoreSpendKind::Oracle => "oracle",
+    };
+
+    let mut fields = Map::new();
+    fields.insert("kind".to_string(), kind.into());
+    fields.insert("height".to_string(), entry.height.into());
+    fields.insert("headerHash".to_string(), hex(entry.header_hash));
+    fields.insert("timestamp".to_string(), entry.timestamp.into());
+    fields.insert("spentCoinId".to_string(), hex(entry.coin_id));
+    fields.extend(info(&entry.info));
+    Value::Object(fields)
+}
+
+pub fn server_coin(server_coin: &ServerCoin) -> Value {
+    json!({
+        "coinId": hex(server_coin.coin.coin_id()),
+        "amount": server_coin.coin.amount,
+        "p2PuzzleHash": hex(server_coin.p2_puzzle_hash),
+        "urls": server_coin.memo_urls,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_output() {
+        let mut text = String::new();
+        write_text(
+            &mut text,
+            &json!({
+                "launcherId": "ab",
+                "label": null,
+                "urls": ["a", "b"],
+                "delegatedPuzzles": [{ "kind": "writer", "puzzleHash": "cd" }],
+                "history": [],
+            }),
+            0,
+        );
+
+        // Without serde_json's `preserve_order` feature, keys are sorted.
+        assert_eq!(
+            text,
+            "delegatedPuzzles:\n  - kind: writer\n    puzzleHash: cd\nhistory:\n  (none)\nlabel: -\nlauncherId: ab\nurls:\n  - a\n  - b\n"
+        );
+    }
+}
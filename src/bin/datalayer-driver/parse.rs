@@ -0,0 +1,40 @@
+use chia::bls::PublicKey;
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::decode_address;
+
+pub fn bytes32(value: &str) -> Result<Bytes32, String> {
+    let bytes: [u8; 32] = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|error| error.to_string())?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 bytes, got {}", bytes.len()))?;
+
+    Ok(bytes.into())
+}
+
+/// Accepts an address or a hex puzzle hash.
+pub fn puzzle_hash(value: &str) -> Result<Bytes32, String> {
+    if let Ok((puzzle_hash, _)) = decode_address(value) {
+        return Ok(puzzle_hash.into());
+    }
+
+    bytes32(value).map_err(|error| format!("not an address or puzzle hash: {error}"))
+}
+
+pub fn public_key(value: &str) -> Result<PublicKey, String> {
+    let bytes: [u8; 48] = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|error| error.to_string())?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 48 bytes, got {}", bytes.len()))?;
+
+    PublicKey::from_bytes(&bytes).map_err(|error| error.to_string())
+}
+
+/// Parses `<puzzle hash>:<fee>`.
+pub fn oracle(value: &str) -> Result<(Bytes32, u64), String> {
+    let (puzzle_hash, fee) = value
+        .split_once(':')
+        .ok_or_else(|| "expected <puzzle hash>:<fee>".to_string())?;
+    let fee = fee.parse().map_err(|_| format!("invalid fee '{fee}'"))?;
+
+    Ok((self::puzzle_hash(puzzle_hash)?, fee))
+}
@@ -0,0 +1,795 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use chia::protocol::{Bytes32, CoinStateUpdate, Message, NewPeakWallet, ProtocolMessageTypes};
+use chia::traits::Streamable;
+use chia_wallet_sdk::connect_peer;
+use native_tls::TlsConnector;
+use tokio::sync::mpsc::{unbounded_channel, Receiver, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::{AbortHandle, JoinHandle};
+
+use crate::discovery::DISCOVERY_TIMEOUT;
+use crate::network::TargetNetwork;
+use crate::simulator::{BlockUpdate, ChainSimulator};
+use crate::spend_tracker::{SpendStatus, SpendTracker};
+use crate::wallet::{self, sync_store_using_launcher_id, StoreUpdate, WalletError};
+use crate::wallet_protocol::PeerBackend;
+
+/// Number of times a dropped connection is re-established before giving up.
+pub const RECONNECT_ATTEMPTS: u32 = 5;
+
+/// Delay before the first reconnection attempt. Doubles after every failed attempt.
+pub const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Delay before the first reconnection attempt to a simulator, which runs in-process.
+pub const SIMULATOR_RECONNECT_BASE_DELAY: Duration = Duration::from_millis(10);
+
+/// A connection to a full node (or a simulator) that is re-established when it drops.
+///
+/// The coins being waited on, tracked or watched are subscribed to again after a reconnect. If
+/// the connection can't be re-established within `RECONNECT_ATTEMPTS`, everything that is
+/// waiting fails with `Disconnected`, and so does everything that starts waiting afterwards.
+#[derive(Clone)]
+pub struct ReconnectingPeer {
+    inner: Arc<RwLock<PeerBackend>>,
+    peak: Arc<Mutex<Option<NewPeakWallet>>>,
+    coin_listeners: CoinListeners,
+    peak_listeners: PeakListeners,
+    disconnected: Arc<AtomicBool>,
+}
+
+impl ReconnectingPeer {
+    /// Connects to the full node at `socket_addr`.
+    pub async fn connect(
+        socket_addr: SocketAddr,
+        network: TargetNetwork,
+        tls_connector: TlsConnector,
+    ) -> Result<Self, WalletError> {
+        let connector = Connector::Node {
+            tls_connector,
+            socket_addr,
+        };
+        let (peer, updates) = connector.connect(&network).await?;
+
+        Ok(Self::spawn(network, connector, peer, updates, None))
+    }
+
+    /// Connects to an in-process chain simulator. Like with a full node, the peer reconnects when
+    /// the simulator disconnects it.
+    pub async fn from_simulator(simulator: Arc<ChainSimulator>) -> Result<Self, WalletError> {
+        // Full nodes send their peak right after the handshake.
+        let height = simulator.height();
+        let peak = simulator.header_hash(height).map(|header_hash| {
+            NewPeakWallet::new(header_hash, height, height.into(), height.saturating_sub(1))
+        });
+
+        let network = simulator.network().clone();
+        let connector = Connector::Simulator(simulator);
+        let (peer, updates) = connector.connect(&network).await?;
+
+        Ok(Self::spawn(network, connector, peer, updates, peak))
+    }
+
+    // Starts the task handling the connection's incoming updates.
+    fn spawn(
+        network: TargetNetwork,
+        connector: Connector,
+        backend: PeerBackend,
+        updates: Updates,
+        peak: Option<NewPeakWallet>,
+    ) -> Self {
+        let peer = Self {
+            inner: Arc::new(RwLock::new(backend)),
+            peak: Arc::new(Mutex::new(peak)),
+            coin_listeners: Arc::new(Mutex::new(HashMap::new())),
+            peak_listeners: Arc::new(Mutex::new(Vec::new())),
+            disconnected: Arc::new(AtomicBool::new(false)),
+        };
+
+        let connection = PeerConnection {
+            network,
+            connector,
+            inner: Arc::downgrade(&peer.inner),
+            peak: peer.peak.clone(),
+            coin_listeners: peer.coin_listeners.clone(),
+            peak_listeners: peer.peak_listeners.clone(),
+            disconnected: peer.disconnected.clone(),
+        };
+
+        tokio::spawn(connection.run(updates));
+
+        peer
+    }
+
+    /// The current connection, to send requests through.
+    pub async fn peer(&self) -> PeerBackend {
+        self.inner.read().await.clone()
+    }
+
+    /// The latest peak the node reported, if any.
+    pub async fn peak(&self) -> Option<NewPeakWallet> {
+        self.peak.lock().await.clone()
+    }
+
+    /// Whether the peer is still usable. False once the connection dropped and all reconnection
+    /// attempts failed.
+    pub fn is_connected(&self) -> bool {
+        !self.disconnected.load(Ordering::SeqCst)
+    }
+
+    /// Waits for a coin to be spent on chain, across reconnects.
+    pub async fn wait_for_coin_to_be_spent(
+        &self,
+        coin_id: Bytes32,
+        last_height: Option<u32>,
+        header_hash: Bytes32,
+    ) -> Result<(), WalletError> {
+        // The listener is registered first, so neither a spend nor a reconnect can slip in
+        // before it.
+        let (sender, mut receiver) = unbounded_channel();
+        {
+            let mut listeners = self.coin_listeners.lock().await;
+            if self.disconnected.load(Ordering::SeqCst) {
+                return Err(WalletError::Disconnected);
+            }
+            listeners.entry(coin_id).or_default().push(sender);
+        }
+
+        let peer = self.peer().await;
+        let spent_height = match wallet::subscribe_to_coin_states(
+            &peer,
+            coin_id,
+            last_height,
+            header_hash,
+        )
+        .await
+        {
+            Ok(spent_height) => spent_height,
+            Err(error) => {
+                drop(receiver);
+                let _ = release_coin(&peer, &self.coin_listeners, coin_id).await;
+                return Err(error);
+            }
+        };
+
+        if spent_height.is_none() {
+            receiver.recv().await.ok_or(WalletError::Disconnected)??;
+        }
+        drop(receiver);
+
+        release_coin(&self.peer().await, &self.coin_listeners, coin_id).await
+    }
+
+    /// Watches a store. Yields the store's current state first, then every new state as it is
+    /// confirmed on chain, until the store is melted.
+    pub fn watch_store(&self, launcher_id: Bytes32) -> UpdateStream<StoreUpdate> {
+        let (sender, receiver) = unbounded_channel();
+
+        let watch = StoreWatch {
+            inner: self.inner.clone(),
+            coin_listeners: self.coin_listeners.clone(),
+            disconnected: self.disconnected.clone(),
+            sender,
+        };
+
+        UpdateStream {
+            receiver,
+            task: tokio::spawn(watch.run(launcher_id)),
+        }
+    }
+
+    /// Tracks a broadcast spend bundle. Yields its status whenever it changes, until it's final.
+    pub fn track_spend(&self, tracker: SpendTracker) -> UpdateStream<SpendStatus> {
+        let (sender, receiver) = unbounded_channel();
+
+        let track = SpendTrack {
+            inner: self.inner.clone(),
+            peak: self.peak.clone(),
+            coin_listeners: self.coin_listeners.clone(),
+            peak_listeners: self.peak_listeners.clone(),
+            disconnected: self.disconnected.clone(),
+            sender,
+        };
+
+        UpdateStream {
+            receiver,
+            task: tokio::spawn(track.run(tracker)),
+        }
+    }
+}
+
+/// Updates produced by a task in the background, e.g. the states of a watched store. The task
+/// is stopped when the stream is dropped.
+pub struct UpdateStream<T> {
+    receiver: UnboundedReceiver<Result<T, WalletError>>,
+    task: JoinHandle<()>,
+}
+
+impl<T> UpdateStream<T> {
+    /// Waits for the next update. `None` once there are no more updates, or the task was stopped.
+    pub async fn next(&mut self) -> Option<Result<T, WalletError>> {
+        self.receiver.recv().await
+    }
+
+    /// A handle to stop the task while another one waits for the next update.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.task.abort_handle()
+    }
+}
+
+impl<T> Drop for UpdateStream<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// The task behind `ReconnectingPeer::track_spend`. It checks the bundle's coins when one of them
+// is spent, and its confirmations and expiry at every new peak.
+struct SpendTrack {
+    inner: Arc<RwLock<PeerBackend>>,
+    peak: Arc<Mutex<Option<NewPeakWallet>>>,
+    coin_listeners: CoinListeners,
+    peak_listeners: PeakListeners,
+    disconnected: Arc<AtomicBool>,
+    sender: UnboundedSender<Result<SpendStatus, WalletError>>,
+}
+
+impl SpendTrack {
+    async fn run(self, tracker: SpendTracker) {
+        if let Err(error) = self.track(tracker).await {
+            let _ = self.sender.send(Err(error));
+        }
+    }
+
+    async fn track(&self, mut tracker: SpendTracker) -> Result<(), WalletError> {
+        let (peak_sender, mut peak_receiver) = unbounded_channel();
+        let (coin_sender, coin_receiver) = unbounded_channel();
+        let mut coins = TrackedCoins {
+            inner: self.inner.clone(),
+            coin_listeners: self.coin_listeners.clone(),
+            coin_ids: tracker.coin_ids(),
+            receiver: coin_receiver,
+        };
+        {
+            // The listeners are registered first, so a spend can't slip in before them.
+            let mut listeners = self.coin_listeners.lock().await;
+            if self.disconnected.load(Ordering::SeqCst) {
+                return Err(WalletError::Disconnected);
+            }
+            for coin_id in &coins.coin_ids {
+                listeners
+                    .entry(*coin_id)
+                    .or_default()
+                    .push(coin_sender.clone());
+            }
+            self.peak_listeners.lock().await.push(peak_sender);
+        }
+
+        let genesis_challenge = wallet::get_genesis_challenge(&*self.inner.read().await).await?;
+        let mut peak = self.peak.lock().await.as_ref().map(|peak| peak.height);
+
+        loop {
+            let mut height = match peak {
+                Some(height) => height,
+                None => tokio::select! {
+                    height = peak_receiver.recv() => height.ok_or(WalletError::Disconnected)?,
+                    spent = coins.receiver.recv() => {
+                        spent.ok_or(WalletError::Disconnected)??;
+                        tracker.coin_spent();
+                        self.peak.lock().await.as_ref().map_or(0, |peak| peak.height)
+                    }
+                },
+            };
+            // Only the latest of the peaks that arrived in the meantime matters.
+            while let Ok(next_height) = peak_receiver.try_recv() {
+                height = next_height;
+            }
+
+            let peer = self.inner.read().await.clone();
+            if let Some(status) = tracker.update(&peer, genesis_challenge, height).await? {
+                if self.sender.send(Ok(status)).is_err() || status.is_final() {
+                    return Ok(());
+                }
+            }
+
+            peak = None;
+        }
+    }
+}
+
+// The coins a `SpendTrack` listens to. Their subscriptions are released when the task ends, be it
+// because the status is final, it failed, or the tracker was cancelled or dropped.
+struct TrackedCoins {
+    inner: Arc<RwLock<PeerBackend>>,
+    coin_listeners: CoinListeners,
+    coin_ids: Vec<Bytes32>,
+    receiver: UnboundedReceiver<Result<(), WalletError>>,
+}
+
+impl Drop for TrackedCoins {
+    fn drop(&mut self) {
+        // Closed first, so the tracker's listeners are no longer counted as waiting.
+        self.receiver.close();
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let inner = self.inner.clone();
+        let coin_listeners = self.coin_listeners.clone();
+        let coin_ids = std::mem::take(&mut self.coin_ids);
+
+        runtime.spawn(async move {
+            let peer = inner.read().await.clone();
+            for coin_id in coin_ids {
+                let _ = release_coin(&peer, &coin_listeners, coin_id).await;
+            }
+        });
+    }
+}
+
+// The task behind `ReconnectingPeer::watch_store`. It keeps the peer's connection open while it
+// runs.
+struct StoreWatch {
+    inner: Arc<RwLock<PeerBackend>>,
+    coin_listeners: CoinListeners,
+    disconnected: Arc<AtomicBool>,
+    sender: UnboundedSender<Result<StoreUpdate, WalletError>>,
+}
+
+impl StoreWatch {
+    async fn run(self, launcher_id: Bytes32) {
+        if let Err(error) = self.watch(launcher_id).await {
+            let _ = self.sender.send(Err(error));
+        }
+    }
+
+    async fn watch(&self, launcher_id: Bytes32) -> Result<(), WalletError> {
+        let peer = self.inner.read().await.clone();
+        let genesis_challenge = wallet::get_genesis_challenge(&peer).await?;
+
+        let synced =
+            sync_store_using_launcher_id(&peer, launcher_id, None, genesis_challenge, false, &[])
+                .await?;
+        let timestamp = wallet::HeaderCache::new()
+            .get_timestamp(&peer, synced.latest_height)
+            .await?;
+
+        let mut store = synced.latest_store.clone();
+        let current = StoreUpdate {
+            store: synced.latest_store,
+            height: synced.latest_height,
+            timestamp,
+        };
+        if self.sender.send(Ok(current)).is_err() {
+            return Ok(());
+        }
+
+        loop {
+            let coin_id = store.coin.coin_id();
+
+            // The listener is registered first, so a spend can't slip in before it.
+            let (sender, mut receiver) = unbounded_channel();
+            {
+                let mut listeners = self.coin_listeners.lock().await;
+                if self.disconnected.load(Ordering::SeqCst) {
+                    return Err(WalletError::Disconnected);
+                }
+                listeners.entry(coin_id).or_default().push(sender);
+            }
+
+            let peer = self.inner.read().await.clone();
+            let Some(spent_height) =
+                wallet::subscribe_to_coin_states(&peer, coin_id, None, genesis_challenge).await?
+            else {
+                receiver
+                    .recv()
+                    .await
+                    .unwrap_or(Err(WalletError::Disconnected))?;
+                continue;
+            };
+
+            drop(receiver);
+            release_coin(&peer, &self.coin_listeners, coin_id).await?;
+
+            let Some(update) = wallet::get_store_update(&peer, &store, spent_height).await? else {
+                return Ok(());
+            };
+
+            store = update.store.clone();
+            if self.sender.send(Ok(update)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+type CoinListener = UnboundedSender<Result<(), WalletError>>;
+
+type CoinListeners = Arc<Mutex<HashMap<Bytes32, Vec<CoinListener>>>>;
+
+// Resolves everything waiting for a coin to be spent.
+fn notify_coin_spent(listeners: &mut HashMap<Bytes32, Vec<CoinListener>>, coin_id: Bytes32) {
+    for listener in listeners.remove(&coin_id).unwrap_or_default() {
+        let _ = listener.send(Ok(()));
+    }
+}
+
+// Forgets the listeners that stopped waiting for a coin, and drops the peer's subscription to the
+// coin unless something is still waiting for it.
+async fn release_coin(
+    peer: &PeerBackend,
+    coin_listeners: &CoinListeners,
+    coin_id: Bytes32,
+) -> Result<(), WalletError> {
+    {
+        let mut listeners = coin_listeners.lock().await;
+        if let Some(waiting) = listeners.get_mut(&coin_id) {
+            waiting.retain(|listener| !listener.is_closed());
+            if !waiting.is_empty() {
+                return Ok(());
+            }
+            listeners.remove(&coin_id);
+        }
+    }
+
+    wallet::unsubscribe_from_coin_states(peer, coin_id).await
+}
+
+type PeakListeners = Arc<Mutex<Vec<UnboundedSender<u32>>>>;
+
+// Sends a new peak height to everything following the chain, and forgets those that stopped.
+fn notify_new_peak(listeners: &mut Vec<UnboundedSender<u32>>, height: u32) {
+    listeners.retain(|listener| listener.send(height).is_ok());
+}
+
+// Where a peer's connection comes from, so it can be re-established when it drops.
+enum Connector {
+    Node {
+        tls_connector: TlsConnector,
+        socket_addr: SocketAddr,
+    },
+    Simulator(Arc<ChainSimulator>),
+}
+
+// What the other end of a connection pushes to the peer.
+enum Updates {
+    Node(Receiver<Message>),
+    Simulator(UnboundedReceiver<BlockUpdate>),
+}
+
+impl Connector {
+    async fn connect(
+        &self,
+        network: &TargetNetwork,
+    ) -> Result<(PeerBackend, Updates), WalletError> {
+        match self {
+            Self::Node {
+                tls_connector,
+                socket_addr,
+            } => {
+                let (peer, receiver) = connect_peer(
+                    network.network_id.clone(),
+                    tls_connector.clone(),
+                    *socket_addr,
+                )
+                .await?;
+                Ok((PeerBackend::Node(peer), Updates::Node(receiver)))
+            }
+            Self::Simulator(simulator) => Ok((
+                PeerBackend::Simulator(simulator.clone()),
+                Updates::Simulator(simulator.connect()?),
+            )),
+        }
+    }
+
+    fn reconnect_base_delay(&self) -> Duration {
+        match self {
+            Self::Node { .. } => RECONNECT_BASE_DELAY,
+            Self::Simulator(_) => SIMULATOR_RECONNECT_BASE_DELAY,
+        }
+    }
+}
+
+// State shared between a `ReconnectingPeer` and the task handling its incoming messages.
+struct PeerConnection {
+    network: TargetNetwork,
+    connector: Connector,
+    // Weak, so the connection is closed once the `ReconnectingPeer` is dropped.
+    inner: Weak<RwLock<PeerBackend>>,
+    peak: Arc<Mutex<Option<NewPeakWallet>>>,
+    coin_listeners: CoinListeners,
+    peak_listeners: PeakListeners,
+    disconnected: Arc<AtomicBool>,
+}
+
+impl PeerConnection {
+    async fn run(self, mut updates: Updates) {
+        loop {
+            match &mut updates {
+                Updates::Node(receiver) => {
+                    while let Some(message) = receiver.recv().await {
+                        self.handle_message(message).await;
+                    }
+                }
+                Updates::Simulator(receiver) => {
+                    while let Some(update) = receiver.recv().await {
+                        if self.inner.strong_count() == 0 {
+                            return;
+                        }
+
+                        self.handle_block_update(update).await;
+                    }
+                }
+            }
+
+            if self.inner.strong_count() == 0 {
+                return;
+            }
+
+            let Some(new_updates) = self.reconnect().await else {
+                self.fail_listeners().await;
+                return;
+            };
+
+            updates = new_updates;
+            self.resubscribe().await;
+        }
+    }
+
+    async fn handle_message(&self, message: Message) {
+        if message.msg_type == ProtocolMessageTypes::NewPeakWallet {
+            if let Ok(new_peak) = NewPeakWallet::from_bytes(&message.data) {
+                let height = new_peak.height;
+                let mut peak_guard = self.peak.lock().await;
+                *peak_guard = Some(new_peak);
+                drop(peak_guard);
+
+                notify_new_peak(&mut *self.peak_listeners.lock().await, height);
+            }
+        }
+
+        if message.msg_type == ProtocolMessageTypes::CoinStateUpdate {
+            if let Ok(coin_state_update) = CoinStateUpdate::from_bytes(&message.data) {
+                let mut listeners = self.coin_listeners.lock().await;
+
+                for coin_state_update_item in coin_state_update.items {
+                    if coin_state_update_item.spent_height.is_none() {
+                        continue;
+                    }
+
+                    notify_coin_spent(&mut listeners, coin_state_update_item.coin.coin_id());
+                }
+            }
+        }
+    }
+
+    // Handles a block farmed by a simulator like a node's peak and coin state updates.
+    async fn handle_block_update(&self, update: BlockUpdate) {
+        *self.peak.lock().await = Some(NewPeakWallet::new(
+            update.header.header_hash,
+            update.header.height,
+            update.header.height.into(),
+            update.header.height.saturating_sub(1),
+        ));
+
+        notify_new_peak(&mut *self.peak_listeners.lock().await, update.header.height);
+
+        let mut listeners = self.coin_listeners.lock().await;
+
+        for coin_state in update.coin_states {
+            if coin_state.spent_height.is_none() {
+                continue;
+            }
+
+            notify_coin_spent(&mut listeners, coin_state.coin.coin_id());
+        }
+    }
+
+    async fn reconnect(&self) -> Option<Updates> {
+        let mut delay = self.connector.reconnect_base_delay();
+
+        for _ in 0..RECONNECT_ATTEMPTS {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+
+            // A handshake that hangs would otherwise hold up the remaining attempts.
+            let connection = self.connector.connect(&self.network);
+            if let Ok(Ok((peer, updates))) =
+                tokio::time::timeout(DISCOVERY_TIMEOUT, connection).await
+            {
+                let inner = self.inner.upgrade()?;
+                *inner.write().await = peer;
+                return Some(updates);
+            }
+        }
+
+        None
+    }
+
+    // Subscriptions are tied to the connection, so the coins that are still being waited on
+    // need to be registered again. Coins spent while disconnected resolve their listeners here,
+    // and coins that can't be registered again fail theirs instead of waiting forever.
+    async fn resubscribe(&self) {
+        let Some(inner) = self.inner.upgrade() else {
+            return;
+        };
+        let peer = inner.read().await.clone();
+        let coin_ids: Vec<Bytes32> = self.coin_listeners.lock().await.keys().copied().collect();
+
+        for coin_id in coin_ids {
+            match wallet::subscribe_to_coin_states(
+                &peer,
+                coin_id,
+                None,
+                self.network.genesis_challenge,
+            )
+            .await
+            {
+                Ok(Some(_)) => {
+                    notify_coin_spent(&mut *self.coin_listeners.lock().await, coin_id);
+                }
+                Ok(None) => {}
+                Err(_) => {
+                    let listeners = self.coin_listeners.lock().await.remove(&coin_id);
+
+                    for listener in listeners.unwrap_or_default() {
+                        let _ = listener.send(Err(WalletError::Disconnected));
+                    }
+                }
+            }
+        }
+    }
+
+    async fn fail_listeners(&self) {
+        let mut listeners = self.coin_listeners.lock().await;
+        self.disconnected.store(true, Ordering::SeqCst);
+
+        for listener in listeners.drain().flat_map(|(_, listeners)| listeners) {
+            let _ = listener.send(Err(WalletError::Disconnected));
+        }
+
+        self.peak_listeners.lock().await.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use chia::protocol::Coin;
+    use chia::puzzles::standard::StandardArgs;
+    use chia::puzzles::DeriveSynthetic;
+    use chia_wallet_sdk::{Conditions, SpendContext};
+
+    use super::*;
+    use crate::wallet::{mint_store, update_store_metadata, DataStoreInnerSpend};
+
+    // Waits until something is waiting for the coin to be spent.
+    async fn wait_for_listener(peer: &ReconnectingPeer, coin_id: Bytes32) {
+        while !peer.coin_listeners.lock().await.contains_key(&coin_id) {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    // Waits until the peer connected to the simulator again.
+    async fn wait_for_reconnect(sim: &ChainSimulator) {
+        while sim.connections() == 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    fn spend_coin(sim: &ChainSimulator, sk: &SecretKey, coin: Coin) -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        ctx.spend_p2_coin(coin, sk.public_key().derive_synthetic(), Conditions::new())?;
+        sim.sign_and_push(sk, ctx.take());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_coin_across_reconnects() -> anyhow::Result<()> {
+        let sim = Arc::new(ChainSimulator::new(TargetNetwork::mainnet()));
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let puzzle_hash: Bytes32 =
+            StandardArgs::curry_tree_hash(sk.public_key().derive_synthetic()).into();
+        let genesis_challenge = sim.network().genesis_challenge;
+        let peer = ReconnectingPeer::from_simulator(sim.clone()).await?;
+
+        let wait = |coin: Coin| {
+            let peer = peer.clone();
+            tokio::spawn(async move {
+                peer.wait_for_coin_to_be_spent(coin.coin_id(), None, genesis_challenge)
+                    .await
+            })
+        };
+
+        // Spent after the peer reconnected.
+        let coin = sim.new_coin(puzzle_hash, 1);
+        let waiter = wait(coin);
+        wait_for_listener(&peer, coin.coin_id()).await;
+        sim.disconnect();
+        wait_for_reconnect(&sim).await;
+        spend_coin(&sim, &sk, coin)?;
+        waiter.await??;
+
+        // Spent while the peer was disconnected, which the resubscription finds out.
+        let coin = sim.new_coin(puzzle_hash, 1);
+        let waiter = wait(coin);
+        wait_for_listener(&peer, coin.coin_id()).await;
+        sim.refuse_connections(1);
+        sim.disconnect();
+        spend_coin(&sim, &sk, coin)?;
+        waiter.await??;
+        assert!(peer.is_connected());
+
+        // Once the reconnection attempts run out, waits fail.
+        let coin = sim.new_coin(puzzle_hash, 1);
+        let waiter = wait(coin);
+        wait_for_listener(&peer, coin.coin_id()).await;
+        sim.refuse_connections(RECONNECT_ATTEMPTS);
+        sim.disconnect();
+        assert!(matches!(waiter.await?, Err(WalletError::Disconnected)));
+        assert!(!peer.is_connected());
+        assert!(matches!(wait(coin).await?, Err(WalletError::Disconnected)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watch_store_across_reconnects() -> anyhow::Result<()> {
+        let sim = Arc::new(ChainSimulator::new(TargetNetwork::mainnet()));
+        let sk = SecretKey::from_seed(&[2; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        let minted = mint_store(
+            synthetic_key,
+            vec![sim.new_coin(puzzle_hash, 1)],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            0,
+        )?;
+        sim.sign_and_push(&sk, minted.coin_spends);
+        let store = minted.new_datastore;
+
+        let peer = ReconnectingPeer::from_simulator(sim.clone()).await?;
+        let mut watcher = peer.watch_store(store.info.launcher_id);
+        let current = watcher.next().await.unwrap()?;
+        assert_eq!(current.store.coin, store.coin);
+
+        wait_for_listener(&peer, store.coin.coin_id()).await;
+        sim.disconnect();
+        wait_for_reconnect(&sim).await;
+
+        let updated = update_store_metadata(
+            store,
+            Bytes32::new([2; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Owner(synthetic_key),
+        )?;
+        sim.sign_and_push(&sk, updated.coin_spends);
+
+        let update = watcher.next().await.unwrap()?;
+        assert_eq!(update.store.coin, updated.new_datastore.coin);
+        assert_eq!(update.height, sim.height());
+
+        wait_for_listener(&peer, update.store.coin.coin_id()).await;
+        sim.refuse_connections(RECONNECT_ATTEMPTS);
+        sim.disconnect();
+        assert!(matches!(
+            watcher.next().await,
+            Some(Err(WalletError::Disconnected))
+        ));
+        assert!(watcher.next().await.is_none());
+
+        Ok(())
+    }
+}
@@ -6,12 +6,13 @@ use napi::bindgen_prelude::*;
 use napi::Result;
 use thiserror::Error;
 
-use crate::{js, rust};
+use crate::js::{self, ArgContext};
+use crate::{network::TargetNetwork, rust};
 
 #[derive(Error, Debug)]
 pub enum ConversionError {
-    #[error("Expected different byte length {0}")]
-    DifferentLength(u32),
+    #[error("Expected byte length {expected}, got {actual}")]
+    DifferentLength { expected: u32, actual: u32 },
 
     #[error("Invalid public key")]
     InvalidPublicKey,
@@ -30,6 +31,19 @@ pub enum ConversionError {
 
     #[error("Invalid URI: {0}")]
     InvalidUri(String),
+
+    #[error("Value exceeds {0}")]
+    OutOfRange(&'static str),
+
+    #[error("{0}")]
+    InvalidArgument(&'static str),
+}
+
+fn different_length(expected: u32, value: &[u8]) -> napi::Error {
+    js::err(ConversionError::DifferentLength {
+        expected,
+        actual: value.len() as u32,
+    })
 }
 
 pub trait FromJs<T> {
@@ -44,8 +58,7 @@ pub trait ToJs<T> {
 
 impl FromJs<Buffer> for Bytes32 {
     fn from_js(value: Buffer) -> Result<Self> {
-        Self::try_from(value.as_ref().to_vec())
-            .map_err(|_| js::err(ConversionError::DifferentLength(32)))
+        Self::try_from(value.as_ref().to_vec()).map_err(|_| different_length(32, &value))
     }
 }
 
@@ -82,8 +95,7 @@ impl ToJs<Buffer> for Bytes {
 impl FromJs<Buffer> for PublicKey {
     fn from_js(value: Buffer) -> Result<Self> {
         Self::from_bytes(
-            &<[u8; 48]>::try_from(value.to_vec())
-                .map_err(|_| js::err(ConversionError::DifferentLength(48)))?,
+            &<[u8; 48]>::try_from(value.to_vec()).map_err(|_| different_length(48, &value))?,
         )
         .map_err(|_| js::err(ConversionError::InvalidPublicKey))
     }
@@ -98,8 +110,7 @@ impl ToJs<Buffer> for PublicKey {
 impl FromJs<Buffer> for SecretKey {
     fn from_js(value: Buffer) -> Result<Self> {
         Self::from_bytes(
-            &<[u8; 32]>::try_from(value.to_vec())
-                .map_err(|_| js::err(ConversionError::DifferentLength(32)))?,
+            &<[u8; 32]>::try_from(value.to_vec()).map_err(|_| different_length(32, &value))?,
         )
         .map_err(|_| js::err(ConversionError::InvalidPrivateKey))
     }
@@ -114,8 +125,7 @@ impl ToJs<Buffer> for SecretKey {
 impl FromJs<Buffer> for Signature {
     fn from_js(value: Buffer) -> Result<Self> {
         Self::from_bytes(
-            &<[u8; 96]>::try_from(value.to_vec())
-                .map_err(|_| js::err(ConversionError::DifferentLength(96)))?,
+            &<[u8; 96]>::try_from(value.to_vec()).map_err(|_| different_length(96, &value))?,
         )
         .map_err(|_| js::err(ConversionError::InvalidSignature))
     }
@@ -142,9 +152,9 @@ impl ToJs<BigInt> for u64 {
 impl FromJs<js::Coin> for rust::Coin {
     fn from_js(value: js::Coin) -> Result<Self> {
         Ok(Self {
-            parent_coin_info: Bytes32::from_js(value.parent_coin_info)?,
-            puzzle_hash: Bytes32::from_js(value.puzzle_hash)?,
-            amount: u64::from_js(value.amount)?,
+            parent_coin_info: Bytes32::from_js(value.parent_coin_info).arg("parentCoinInfo")?,
+            puzzle_hash: Bytes32::from_js(value.puzzle_hash).arg("puzzleHash")?,
+            amount: u64::from_js(value.amount).arg("amount")?,
         })
     }
 }
@@ -162,12 +172,14 @@ impl ToJs<js::Coin> for rust::Coin {
 impl FromJs<js::CoinState> for rust::CoinState {
     fn from_js(value: js::CoinState) -> Result<Self> {
         Ok(Self {
-            coin: rust::Coin::from_js(value.coin)?,
+            coin: rust::Coin::from_js(value.coin).arg("coin")?,
             spent_height: value
                 .spent_height
                 .map(|height| {
                     u64::from_js(height).and_then(|height| {
-                        height.try_into().map_err(|_| js::err("height exceeds u32"))
+                        height
+                            .try_into()
+                            .map_err(|_| js::err(ConversionError::OutOfRange("u32")))
                     })
                 })
                 .transpose()?,
@@ -175,7 +187,9 @@ impl FromJs<js::CoinState> for rust::CoinState {
                 .created_height
                 .map(|height| {
                     u64::from_js(height).and_then(|height| {
-                        height.try_into().map_err(|_| js::err("height exceeds u32"))
+                        height
+                            .try_into()
+                            .map_err(|_| js::err(ConversionError::OutOfRange("u32")))
                     })
                 })
                 .transpose()?,
@@ -202,9 +216,9 @@ impl ToJs<js::CoinState> for rust::CoinState {
 impl FromJs<js::CoinSpend> for rust::CoinSpend {
     fn from_js(value: js::CoinSpend) -> Result<Self> {
         Ok(Self {
-            coin: rust::Coin::from_js(value.coin)?,
-            puzzle_reveal: Program::from_js(value.puzzle_reveal)?,
-            solution: Program::from_js(value.solution)?,
+            coin: rust::Coin::from_js(value.coin).arg("coin")?,
+            puzzle_reveal: Program::from_js(value.puzzle_reveal).arg("puzzleReveal")?,
+            solution: Program::from_js(value.solution).arg("solution")?,
         })
     }
 }
@@ -222,9 +236,11 @@ impl ToJs<js::CoinSpend> for rust::CoinSpend {
 impl FromJs<js::LineageProof> for rust::LineageProof {
     fn from_js(value: js::LineageProof) -> Result<Self> {
         Ok(Self {
-            parent_parent_coin_info: Bytes32::from_js(value.parent_parent_coin_info)?,
-            parent_inner_puzzle_hash: Bytes32::from_js(value.parent_inner_puzzle_hash)?,
-            parent_amount: u64::from_js(value.parent_amount)?,
+            parent_parent_coin_info: Bytes32::from_js(value.parent_parent_coin_info)
+                .arg("parentParentCoinInfo")?,
+            parent_inner_puzzle_hash: Bytes32::from_js(value.parent_inner_puzzle_hash)
+                .arg("parentInnerPuzzleHash")?,
+            parent_amount: u64::from_js(value.parent_amount).arg("parentAmount")?,
         })
     }
 }
@@ -242,8 +258,9 @@ impl ToJs<js::LineageProof> for rust::LineageProof {
 impl FromJs<js::EveProof> for rust::EveProof {
     fn from_js(value: js::EveProof) -> Result<Self> {
         Ok(rust::EveProof {
-            parent_parent_coin_info: Bytes32::from_js(value.parent_parent_coin_info)?,
-            parent_amount: u64::from_js(value.parent_amount)?,
+            parent_parent_coin_info: Bytes32::from_js(value.parent_parent_coin_info)
+                .arg("parentParentCoinInfo")?,
+            parent_amount: u64::from_js(value.parent_amount).arg("parentAmount")?,
         })
     }
 }
@@ -264,7 +281,9 @@ impl FromJs<js::Proof> for rust::Proof {
                 lineage_proof,
             )?))
         } else if let Some(eve_proof) = value.eve_proof {
-            Ok(rust::Proof::Eve(rust::EveProof::from_js(eve_proof)?))
+            Ok(rust::Proof::Eve(
+                rust::EveProof::from_js(eve_proof).arg("eveProof")?,
+            ))
         } else {
             Err(js::err(ConversionError::MissingProof))
         }
@@ -289,8 +308,8 @@ impl ToJs<js::Proof> for rust::Proof {
 impl FromJs<js::ServerCoin> for rust::ServerCoin {
     fn from_js(value: js::ServerCoin) -> Result<Self> {
         Ok(Self {
-            coin: rust::Coin::from_js(value.coin)?,
-            p2_puzzle_hash: Bytes32::from_js(value.p2_puzzle_hash)?,
+            coin: rust::Coin::from_js(value.coin).arg("coin")?,
+            p2_puzzle_hash: Bytes32::from_js(value.p2_puzzle_hash).arg("p2PuzzleHash")?,
             memo_urls: value.memo_urls,
         })
     }
@@ -305,3 +324,49 @@ impl ToJs<js::ServerCoin> for rust::ServerCoin {
         })
     }
 }
+
+impl FromJs<js::Network> for TargetNetwork {
+    fn from_js(value: js::Network) -> Result<Self> {
+        let genesis_challenge =
+            Bytes32::from_js(value.genesis_challenge).arg("genesisChallenge")?;
+
+        Ok(Self {
+            network_id: TargetNetwork::network_id_from_str(&value.network_id),
+            genesis_challenge,
+            agg_sig_me: value
+                .agg_sig_me
+                .map(Bytes32::from_js)
+                .transpose()
+                .arg("aggSigMe")?
+                .unwrap_or(genesis_challenge),
+            address_prefix: value.address_prefix,
+            default_port: value.default_port,
+            dns_introducers: value.dns_introducers.unwrap_or_default(),
+            introducers: value.introducers.unwrap_or_default(),
+        })
+    }
+}
+
+impl ToJs<js::Network> for TargetNetwork {
+    fn to_js(&self) -> Result<js::Network> {
+        Ok(js::Network {
+            network_id: self.network_id.to_string(),
+            genesis_challenge: self.genesis_challenge.to_js()?,
+            agg_sig_me: Some(self.agg_sig_me.to_js()?),
+            address_prefix: self.address_prefix.clone(),
+            default_port: self.default_port,
+            dns_introducers: Some(self.dns_introducers.clone()),
+            introducers: Some(self.introducers.clone()),
+        })
+    }
+}
+
+// APIs that predate network descriptors take `true` for testnet11 and `false` for mainnet.
+impl FromJs<Either<bool, js::Network>> for TargetNetwork {
+    fn from_js(value: Either<bool, js::Network>) -> Result<Self> {
+        match value {
+            Either::A(testnet) => Ok(TargetNetwork::from_testnet(testnet)),
+            Either::B(network) => TargetNetwork::from_js(network),
+        }
+    }
+}
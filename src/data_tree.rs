@@ -0,0 +1,982 @@
+use std::collections::{HashMap, HashSet};
+
+use chia::clvm_traits::{self, FromClvm, ToClvm};
+use chia::clvm_utils::{tree_hash_atom, tree_hash_pair};
+use chia::protocol::{Bytes, Bytes32};
+
+use crate::store_cache::{decode, encode};
+use crate::wallet::WalletError;
+
+/// Root hash of a store without any data.
+pub const EMPTY_ROOT_HASH: Bytes32 = Bytes32::new([0; 32]);
+
+/// Hash of a key/value pair, i.e. the tree hash of `(key . value)`.
+pub fn leaf_hash(key: &[u8], value: &[u8]) -> Bytes32 {
+    tree_hash_pair(tree_hash_atom(key), tree_hash_atom(value)).into()
+}
+
+/// Hash of an internal node, i.e. the tree hash of a pair with the given child hashes.
+pub fn internal_hash(left: Bytes32, right: Bytes32) -> Bytes32 {
+    tree_hash_pair(left.into(), right.into()).into()
+}
+
+// Which child of its parent a new leaf becomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    Leaf { key: Bytes, value: Bytes },
+    Internal { left: usize, right: usize },
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    hash: Bytes32,
+    parent: Option<usize>,
+    kind: NodeKind,
+}
+
+/// The key/value Merkle tree a DataLayer store's root hash commits to.
+///
+/// Leaves are placed like the Chia data layer's autoinsert: the leaf hash of a new pair is used
+/// as a seed whose bits, starting from the last byte's lowest bit, pick the way down to an
+/// existing leaf. The new leaf is paired with it, on the left if the seed's first bit is 0.
+/// Deleting a leaf replaces its parent with its sibling, and upserting an existing key replaces
+/// its leaf where it is.
+#[derive(Debug, Clone, Default)]
+pub struct DataTree {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    leaves: HashMap<Vec<u8>, usize>,
+}
+
+impl DataTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a tree by inserting the pairs in order.
+    pub fn from_pairs(pairs: impl IntoIterator<Item = (Bytes, Bytes)>) -> Self {
+        let mut tree = Self::new();
+        for (key, value) in pairs {
+            tree.insert(key, value);
+        }
+        tree
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// The root hash to publish in the store's metadata. `EMPTY_ROOT_HASH` for an empty tree.
+    pub fn root_hash(&self) -> Bytes32 {
+        self.root
+            .map_or(EMPTY_ROOT_HASH, |root| self.nodes[root].hash)
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&Bytes> {
+        let index = self.leaves.get(key)?;
+
+        match &self.nodes[*index].kind {
+            NodeKind::Leaf { value, .. } => Some(value),
+            NodeKind::Internal { .. } => None,
+        }
+    }
+
+    /// Key/value pairs, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Bytes, &Bytes)> {
+        self.leaves
+            .values()
+            .filter_map(|index| match &self.nodes[*index].kind {
+                NodeKind::Leaf { key, value } => Some((key, value)),
+                NodeKind::Internal { .. } => None,
+            })
+    }
+
+    /// Inserts a pair, replacing the value of an existing key. Returns the replaced value.
+    ///
+    /// An existing key is deleted and inserted again, so its leaf moves like it would with a
+    /// delete followed by an insert in the data layer. Use `upsert` to keep it in place.
+    pub fn insert(&mut self, key: Bytes, value: Bytes) -> Option<Bytes> {
+        if self.get(&key) == Some(&value) {
+            return Some(value);
+        }
+
+        let old_value = self.delete(&key);
+        let hash = leaf_hash(&key, &value);
+        let leaf = self.alloc(Node {
+            hash,
+            parent: None,
+            kind: NodeKind::Leaf {
+                key: key.clone(),
+                value,
+            },
+        });
+        self.leaves.insert(key.to_vec(), leaf);
+
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            return old_value;
+        };
+
+        let reference = self.leaf_for_seed(root, hash);
+        let parent = self.nodes[reference].parent;
+
+        let (left, right) = match side_for_seed(hash) {
+            Side::Left => (leaf, reference),
+            Side::Right => (reference, leaf),
+        };
+        let internal = self.alloc(Node {
+            hash: internal_hash(self.nodes[left].hash, self.nodes[right].hash),
+            parent,
+            kind: NodeKind::Internal { left, right },
+        });
+        self.nodes[leaf].parent = Some(internal);
+        self.nodes[reference].parent = Some(internal);
+        self.replace_child(parent, reference, internal);
+        self.rehash(parent);
+
+        old_value
+    }
+
+    /// Inserts a pair, or replaces the value of an existing key without moving its leaf, like
+    /// the data layer's upsert. Returns the replaced value.
+    pub fn upsert(&mut self, key: Bytes, value: Bytes) -> Option<Bytes> {
+        let Some(&leaf) = self.leaves.get(key.as_ref()) else {
+            return self.insert(key, value);
+        };
+
+        let hash = leaf_hash(&key, &value);
+        let NodeKind::Leaf {
+            value: old_value, ..
+        } = &mut self.nodes[leaf].kind
+        else {
+            unreachable!("keys map to leaves");
+        };
+        let old_value = std::mem::replace(old_value, value);
+        self.nodes[leaf].hash = hash;
+        self.rehash(self.nodes[leaf].parent);
+
+        Some(old_value)
+    }
+
+    /// Deletes a key. Returns its value.
+    pub fn delete(&mut self, key: &[u8]) -> Option<Bytes> {
+        let leaf = self.leaves.remove(key)?;
+        let parent = self.nodes[leaf].parent;
+        let value = match self.free(leaf).kind {
+            NodeKind::Leaf { value, .. } => value,
+            NodeKind::Internal { .. } => unreachable!("keys map to leaves"),
+        };
+
+        let Some(parent) = parent else {
+            self.root = None;
+            return Some(value);
+        };
+
+        let sibling = match self.nodes[parent].kind {
+            NodeKind::Internal { left, right } if left == leaf => right,
+            NodeKind::Internal { left, .. } => left,
+            NodeKind::Leaf { .. } => unreachable!("leaves have no children"),
+        };
+        let grandparent = self.free(parent).parent;
+        self.nodes[sibling].parent = grandparent;
+        self.replace_child(grandparent, parent, sibling);
+        self.rehash(grandparent);
+
+        Some(value)
+    }
+
+    /// Proves that a key is in the tree with its current value. `None` if the key isn't in the tree.
+    pub fn prove_inclusion(&self, key: &[u8]) -> Option<InclusionProof> {
+        let leaf = *self.leaves.get(key)?;
+        let NodeKind::Leaf { key, value } = &self.nodes[leaf].kind else {
+            return None;
+        };
+
+        let mut layers = Vec::new();
+        let mut index = leaf;
+
+        while let Some(parent) = self.nodes[index].parent {
+            if let NodeKind::Internal { left, right } = self.nodes[parent].kind {
+                layers.push(if left == index {
+                    ProofLayer {
+                        other_hash: self.nodes[right].hash,
+                        other_on_left: false,
+                    }
+                } else {
+                    ProofLayer {
+                        other_hash: self.nodes[left].hash,
+                        other_on_left: true,
+                    }
+                });
+            }
+            index = parent;
+        }
+
+        Some(InclusionProof {
+            key: key.clone(),
+            value: value.clone(),
+            layers,
+        })
+    }
+
+    /// Proves that a key isn't in the tree. `None` if the key is in the tree.
+    pub fn prove_exclusion(&self, key: &[u8]) -> Option<ExclusionProof> {
+        if self.leaves.contains_key(key) {
+            return None;
+        }
+
+        let mut leaves = Vec::new();
+        let mut stack: Vec<(usize, u32)> = self.root.map(|root| (root, 0)).into_iter().collect();
+
+        // Depth first, left to right, so the verifier can rebuild the tree from the depths.
+        while let Some((index, depth)) = stack.pop() {
+            match &self.nodes[index].kind {
+                NodeKind::Leaf { key, value } => leaves.push(ExclusionProofLeaf {
+                    depth,
+                    key: key.clone(),
+                    value_hash: tree_hash_atom(value).into(),
+                }),
+                NodeKind::Internal { left, right } => {
+                    stack.push((*right, depth + 1));
+                    stack.push((*left, depth + 1));
+                }
+            }
+        }
+
+        Some(ExclusionProof {
+            key: key.to_vec().into(),
+            leaves,
+        })
+    }
+
+    /// Lists the changes that turn this tree's pairs into the other tree's, ordered by key.
+    pub fn diff(&self, new: &DataTree) -> Vec<DataTreeChange> {
+        let mut changes = Vec::new();
+
+        for (key, value) in self.iter() {
+            match new.get(key) {
+                None => changes.push(DataTreeChange::Delete {
+                    key: key.clone(),
+                    value: value.clone(),
+                }),
+                Some(new_value) if new_value != value => changes.push(DataTreeChange::Update {
+                    key: key.clone(),
+                    previous_value: value.clone(),
+                    value: new_value.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        for (key, value) in new.iter() {
+            if self.get(key).is_none() {
+                changes.push(DataTreeChange::Insert {
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+
+        changes.sort_by(|a, b| a.key().cmp(b.key()));
+        changes
+    }
+
+    /// Describes the other tree in terms of this one. Subtrees both trees have are referenced by
+    /// hash, so the delta's size depends on how much changed rather than on the size of the tree.
+    pub fn delta(&self, new: &DataTree) -> TreeDelta {
+        let hashes: HashSet<Bytes32> = self
+            .nodes_below(self.root)
+            .map(|index| self.nodes[index].hash)
+            .collect();
+        let mut nodes = Vec::new();
+
+        // Post-order, so applying the delta only needs a stack.
+        let mut stack: Vec<(usize, bool)> =
+            new.root.map(|root| (root, false)).into_iter().collect();
+
+        while let Some((index, children_done)) = stack.pop() {
+            let node = &new.nodes[index];
+
+            if hashes.contains(&node.hash) {
+                nodes.push(DeltaNode::Subtree(node.hash));
+                continue;
+            }
+
+            match &node.kind {
+                NodeKind::Leaf { key, value } => nodes.push(DeltaNode::Leaf {
+                    key: key.clone(),
+                    value: value.clone(),
+                }),
+                NodeKind::Internal { .. } if children_done => nodes.push(DeltaNode::Internal),
+                NodeKind::Internal { left, right } => {
+                    stack.push((index, true));
+                    stack.push((*right, false));
+                    stack.push((*left, false));
+                }
+            }
+        }
+
+        TreeDelta {
+            from_root_hash: self.root_hash(),
+            to_root_hash: new.root_hash(),
+            nodes,
+        }
+    }
+
+    /// Builds the tree a delta describes on top of this one, checking that it has the delta's root hash.
+    pub fn apply_delta(&self, delta: &TreeDelta) -> Result<DataTree, WalletError> {
+        if self.root_hash() != delta.from_root_hash {
+            return Err(WalletError::InvalidDelta);
+        }
+
+        let subtrees: HashMap<Bytes32, usize> = self
+            .nodes_below(self.root)
+            .map(|index| (self.nodes[index].hash, index))
+            .collect();
+
+        let mut tree = DataTree::new();
+        let mut stack = Vec::new();
+
+        for node in &delta.nodes {
+            let index = match node {
+                DeltaNode::Subtree(hash) => {
+                    let index = *subtrees.get(hash).ok_or(WalletError::InvalidDelta)?;
+                    tree.copy_subtree(self, index)?
+                }
+                DeltaNode::Leaf { key, value } => tree.add_leaf(key.clone(), value.clone())?,
+                DeltaNode::Internal => {
+                    let right = stack.pop().ok_or(WalletError::InvalidDelta)?;
+                    let left = stack.pop().ok_or(WalletError::InvalidDelta)?;
+                    tree.add_internal(left, right)
+                }
+            };
+            stack.push(index);
+        }
+
+        tree.root = match stack.as_slice() {
+            [] => None,
+            [root] => Some(*root),
+            _ => return Err(WalletError::InvalidDelta),
+        };
+
+        if tree.root_hash() != delta.to_root_hash {
+            return Err(WalletError::InvalidDelta);
+        }
+
+        Ok(tree)
+    }
+
+    // Indices of a node and all of its descendants.
+    fn nodes_below(&self, index: Option<usize>) -> impl Iterator<Item = usize> + '_ {
+        let mut stack: Vec<usize> = index.into_iter().collect();
+
+        std::iter::from_fn(move || {
+            let index = stack.pop()?;
+            if let NodeKind::Internal { left, right } = self.nodes[index].kind {
+                stack.push(right);
+                stack.push(left);
+            }
+            Some(index)
+        })
+    }
+
+    // Copies a subtree of another tree into this one, without a parent.
+    fn copy_subtree(&mut self, other: &DataTree, index: usize) -> Result<usize, WalletError> {
+        match &other.nodes[index].kind {
+            NodeKind::Leaf { key, value } => self.add_leaf(key.clone(), value.clone()),
+            NodeKind::Internal { left, right } => {
+                let left = self.copy_subtree(other, *left)?;
+                let right = self.copy_subtree(other, *right)?;
+                Ok(self.add_internal(left, right))
+            }
+        }
+    }
+
+    fn add_leaf(&mut self, key: Bytes, value: Bytes) -> Result<usize, WalletError> {
+        if self.leaves.contains_key(key.as_ref()) {
+            return Err(WalletError::InvalidDelta);
+        }
+
+        let leaf = self.alloc(Node {
+            hash: leaf_hash(&key, &value),
+            parent: None,
+            kind: NodeKind::Leaf {
+                key: key.clone(),
+                value,
+            },
+        });
+        self.leaves.insert(key.to_vec(), leaf);
+
+        Ok(leaf)
+    }
+
+    fn add_internal(&mut self, left: usize, right: usize) -> usize {
+        let internal = self.alloc(Node {
+            hash: internal_hash(self.nodes[left].hash, self.nodes[right].hash),
+            parent: None,
+            kind: NodeKind::Internal { left, right },
+        });
+        self.nodes[left].parent = Some(internal);
+        self.nodes[right].parent = Some(internal);
+
+        internal
+    }
+
+    // Follows the seed's bits down to a leaf. Past the seed's 256 bits, the way is always left.
+    fn leaf_for_seed(&self, root: usize, seed: Bytes32) -> usize {
+        let mut index = root;
+        let mut bit = 0;
+
+        while let NodeKind::Internal { left, right } = self.nodes[index].kind {
+            let byte = if bit < 256 { seed[31 - bit / 8] } else { 0 };
+            index = if byte >> (bit % 8) & 1 == 0 {
+                left
+            } else {
+                right
+            };
+            bit += 1;
+        }
+
+        index
+    }
+
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: usize) {
+        let Some(parent) = parent else {
+            self.root = Some(new);
+            return;
+        };
+
+        if let NodeKind::Internal { left, right } = &mut self.nodes[parent].kind {
+            if *left == old {
+                *left = new;
+            } else if *right == old {
+                *right = new;
+            }
+        }
+    }
+
+    // Recomputes the hashes of a node and its ancestors.
+    fn rehash(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            if let NodeKind::Internal { left, right } = self.nodes[current].kind {
+                self.nodes[current].hash =
+                    internal_hash(self.nodes[left].hash, self.nodes[right].hash);
+            }
+            index = self.nodes[current].parent;
+        }
+    }
+
+    fn alloc(&mut self, node: Node) -> usize {
+        if let Some(index) = self.free.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn free(&mut self, index: usize) -> Node {
+        self.free.push(index);
+        std::mem::replace(
+            &mut self.nodes[index],
+            Node {
+                hash: Bytes32::default(),
+                parent: None,
+                kind: NodeKind::Leaf {
+                    key: Bytes::default(),
+                    value: Bytes::default(),
+                },
+            },
+        )
+    }
+}
+
+fn side_for_seed(seed: Bytes32) -> Side {
+    if seed[0] >> 7 == 0 {
+        Side::Left
+    } else {
+        Side::Right
+    }
+}
+
+/// A sibling hash on the way from a leaf to the root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct ProofLayer {
+    pub other_hash: Bytes32,
+    pub other_on_left: bool,
+}
+
+/// Proof that a key/value pair is in a tree: the sibling hashes from its leaf up to the root.
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct InclusionProof {
+    pub key: Bytes,
+    pub value: Bytes,
+    pub layers: Vec<ProofLayer>,
+}
+
+impl InclusionProof {
+    /// The root hash of the tree the proof was made from.
+    pub fn root_hash(&self) -> Bytes32 {
+        self.layers
+            .iter()
+            .fold(leaf_hash(&self.key, &self.value), |hash, layer| {
+                if layer.other_on_left {
+                    internal_hash(layer.other_hash, hash)
+                } else {
+                    internal_hash(hash, layer.other_hash)
+                }
+            })
+    }
+
+    pub fn verify(&self, root_hash: Bytes32) -> bool {
+        self.root_hash() == root_hash
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WalletError> {
+        encode(self.clone())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WalletError> {
+        decode(bytes)
+    }
+}
+
+/// A leaf of the tree an exclusion proof was made from, with its depth below the root.
+///
+/// The key is revealed rather than its hash: internal nodes are hashed like leaves, so with
+/// only hashes an internal node could be passed off as a leaf and hide the keys below it.
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct ExclusionProofLeaf {
+    pub depth: u32,
+    pub key: Bytes,
+    pub value_hash: Bytes32,
+}
+
+/// Proof that a key isn't in a tree.
+///
+/// Leaves are placed by the hash of their key and value rather than by key, so there's no
+/// neighbouring leaf to point to. The proof instead holds every key, with the hash of its
+/// value, left to right, which rebuild the whole tree; its size grows with the number of pairs.
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct ExclusionProof {
+    pub key: Bytes,
+    pub leaves: Vec<ExclusionProofLeaf>,
+}
+
+impl ExclusionProof {
+    /// The root hash of the tree the proof was made from, or `None` if the leaves don't form a tree.
+    pub fn root_hash(&self) -> Option<Bytes32> {
+        if self.leaves.is_empty() {
+            return Some(EMPTY_ROOT_HASH);
+        }
+
+        // Subtrees whose left sibling is still missing, with their depth.
+        let mut stack: Vec<(u32, Bytes32)> = Vec::new();
+
+        for leaf in &self.leaves {
+            let mut node = (
+                leaf.depth,
+                tree_hash_pair(tree_hash_atom(&leaf.key), leaf.value_hash.into()).into(),
+            );
+
+            while let Some(&(depth, left)) = stack.last() {
+                if depth != node.0 {
+                    break;
+                }
+                stack.pop();
+                node = (depth.checked_sub(1)?, internal_hash(left, node.1));
+            }
+
+            stack.push(node);
+        }
+
+        match stack.as_slice() {
+            [(0, root_hash)] => Some(*root_hash),
+            _ => None,
+        }
+    }
+
+    pub fn verify(&self, root_hash: Bytes32) -> bool {
+        self.root_hash() == Some(root_hash) && self.leaves.iter().all(|leaf| leaf.key != self.key)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WalletError> {
+        encode(self.clone())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WalletError> {
+        decode(bytes)
+    }
+}
+
+/// A difference between the pairs of two trees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataTreeChange {
+    Insert {
+        key: Bytes,
+        value: Bytes,
+    },
+    Delete {
+        key: Bytes,
+        value: Bytes,
+    },
+    Update {
+        key: Bytes,
+        previous_value: Bytes,
+        value: Bytes,
+    },
+}
+
+impl DataTreeChange {
+    pub fn key(&self) -> &Bytes {
+        match self {
+            Self::Insert { key, .. } | Self::Delete { key, .. } | Self::Update { key, .. } => key,
+        }
+    }
+}
+
+/// A node of the tree a delta describes, in post-order.
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub enum DeltaNode {
+    /// A subtree of the tree the delta applies to, by hash.
+    Subtree(Bytes32),
+    Leaf {
+        key: Bytes,
+        value: Bytes,
+    },
+    /// An internal node whose children are the two nodes before it.
+    Internal,
+}
+
+/// Turns a tree with one root hash into a tree with another, e.g. to send a store's update to
+/// mirrors without sending the whole tree.
+///
+/// A list of changed pairs wouldn't do: where a pair ends up depends on the order pairs were
+/// inserted in, so replaying the changes wouldn't always reproduce the root hash. The delta
+/// describes the new tree's shape instead, reusing unchanged subtrees of the old one.
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct TreeDelta {
+    pub from_root_hash: Bytes32,
+    pub to_root_hash: Bytes32,
+    pub nodes: Vec<DeltaNode>,
+}
+
+impl TreeDelta {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WalletError> {
+        encode(self.clone())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WalletError> {
+        decode(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clvmr::sha2::Sha256;
+    use hex_literal::hex;
+
+    use super::*;
+
+    fn pair(key: &str, value: &str) -> (Bytes, Bytes) {
+        (
+            key.as_bytes().to_vec().into(),
+            value.as_bytes().to_vec().into(),
+        )
+    }
+
+    #[test]
+    fn test_hashes() {
+        let sha256 = |prefix: u8, parts: &[&[u8]]| -> Bytes32 {
+            let mut hasher = Sha256::new();
+            hasher.update([prefix]);
+            for part in parts {
+                hasher.update(part);
+            }
+            hasher.finalize().into()
+        };
+
+        // The data layer hashes keys and values as atoms and nodes as pairs.
+        let key = sha256(1, &[b"key"]);
+        let value = sha256(1, &[b"value"]);
+        assert_eq!(
+            leaf_hash(b"key", b"value"),
+            sha256(2, &[key.as_ref(), value.as_ref()])
+        );
+        assert_eq!(
+            internal_hash(key, value),
+            sha256(2, &[key.as_ref(), value.as_ref()])
+        );
+    }
+
+    #[test]
+    fn test_insert_and_delete() {
+        let mut tree = DataTree::new();
+        assert_eq!(tree.root_hash(), EMPTY_ROOT_HASH);
+
+        let (a, one) = pair("a", "1");
+        tree.insert(a.clone(), one.clone());
+        assert_eq!(tree.root_hash(), leaf_hash(&a, &one));
+
+        let (b, two) = pair("b", "2");
+        tree.insert(b.clone(), two.clone());
+        let leaves = (leaf_hash(&a, &one), leaf_hash(&b, &two));
+        assert!(
+            tree.root_hash() == internal_hash(leaves.0, leaves.1)
+                || tree.root_hash() == internal_hash(leaves.1, leaves.0)
+        );
+        let two_leaves = tree.root_hash();
+
+        let (c, three) = pair("c", "3");
+        tree.insert(c.clone(), three);
+        assert_eq!(tree.len(), 3);
+
+        // Deleting the last pair restores the previous tree.
+        assert_eq!(tree.delete(&c), Some(pair("c", "3").1));
+        assert_eq!(tree.root_hash(), two_leaves);
+        assert_eq!(tree.delete(&c), None);
+
+        // Updating a value moves the leaf, so the value is checked through the tree.
+        assert_eq!(tree.insert(a.clone(), two.clone()), Some(one));
+        assert_eq!(tree.get(&a), Some(&two));
+        assert_ne!(tree.root_hash(), two_leaves);
+
+        // Upserting keeps the leaf in place, so only its hash changes.
+        let (one, three) = (pair("a", "1").1, pair("c", "3").1);
+        let leaves = (leaf_hash(&a, &one), leaf_hash(&b, &two));
+        let mut upserted =
+            DataTree::from_pairs([(a.clone(), one.clone()), (b.clone(), two.clone())]);
+        let a_on_left = upserted.root_hash() == internal_hash(leaves.0, leaves.1);
+        assert_eq!(upserted.upsert(a.clone(), two.clone()), Some(one));
+        let updated = leaf_hash(&a, &two);
+        assert_eq!(
+            upserted.root_hash(),
+            if a_on_left {
+                internal_hash(updated, leaves.1)
+            } else {
+                internal_hash(leaves.1, updated)
+            }
+        );
+        assert_eq!(upserted.upsert(c.clone(), three.clone()), None);
+        assert_eq!(upserted.get(&c), Some(&three));
+
+        tree.delete(&a);
+        tree.delete(&b);
+        assert!(tree.is_empty());
+        assert_eq!(tree.root_hash(), EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn test_data_layer_root_hashes() {
+        // The same sequence as `scripts/data_layer_vectors.py`, which runs it through the
+        // `DataStore` of chia-blockchain 2.4.3's data_layer. These hashes were computed with a
+        // port of its `autoinsert`, `upsert` and `delete`; if the script prints different ones,
+        // its output is the reference.
+        let keys: Vec<Bytes> = (0..10u8).map(|i| vec![i; i as usize + 1].into()).collect();
+        let mut tree = DataTree::from_pairs(
+            keys.iter()
+                .enumerate()
+                .map(|(i, key)| (key.clone(), format!("value {i}").into_bytes().into())),
+        );
+        assert_eq!(
+            tree.root_hash(),
+            Bytes32::new(hex!(
+                "2fde825b34b3b9ec7b1cc62a294287c7f2c5c270c796d6b8107bf94b478f3375"
+            ))
+        );
+
+        tree.upsert(keys[3].clone(), b"updated".to_vec().into());
+        assert_eq!(
+            tree.root_hash(),
+            Bytes32::new(hex!(
+                "0cad35567b6ba007f623dfbae34e07e04a4f4e691f78a64600089b880ab1c4fd"
+            ))
+        );
+
+        tree.delete(&keys[7]);
+        assert_eq!(
+            tree.root_hash(),
+            Bytes32::new(hex!(
+                "5c3203562a189ecb7ba1f75a80e91b26eaa945d0bfab0898cac42789a7154d14"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_many_pairs() {
+        let pairs: Vec<(Bytes, Bytes)> = (0..500u32)
+            .map(|i| {
+                (
+                    i.to_be_bytes().to_vec().into(),
+                    (i * 7).to_be_bytes().to_vec().into(),
+                )
+            })
+            .collect();
+        let mut tree = DataTree::from_pairs(pairs.clone());
+        assert_eq!(tree.len(), 500);
+        assert_eq!(
+            tree.root_hash(),
+            DataTree::from_pairs(pairs.clone()).root_hash()
+        );
+
+        for (key, value) in &pairs {
+            assert_eq!(tree.get(key), Some(value));
+        }
+
+        let without_last = DataTree::from_pairs(pairs[..499].to_vec()).root_hash();
+        tree.delete(&pairs[499].0);
+        assert_eq!(tree.root_hash(), without_last);
+
+        for (key, _) in &pairs[..499] {
+            tree.delete(key);
+        }
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn test_proofs() -> anyhow::Result<()> {
+        let mut tree = DataTree::new();
+        let (a, one) = pair("a", "1");
+
+        let proof = tree.prove_exclusion(&a).unwrap();
+        assert!(proof.verify(EMPTY_ROOT_HASH));
+
+        for i in 0..100u32 {
+            tree.insert(
+                i.to_be_bytes().to_vec().into(),
+                i.to_le_bytes().to_vec().into(),
+            );
+        }
+        tree.insert(a.clone(), one.clone());
+        let root_hash = tree.root_hash();
+
+        let proof = tree.prove_inclusion(&a).unwrap();
+        assert!(proof.verify(root_hash));
+        assert!(tree.prove_exclusion(&a).is_none());
+
+        let proof = InclusionProof::from_bytes(&proof.to_bytes()?)?;
+        assert!(proof.verify(root_hash));
+
+        let mut forged = proof.clone();
+        forged.value = pair("a", "2").1;
+        assert!(!forged.verify(root_hash));
+
+        let (b, _) = pair("b", "2");
+        assert!(tree.prove_inclusion(&b).is_none());
+
+        let proof = ExclusionProof::from_bytes(&tree.prove_exclusion(&b).unwrap().to_bytes()?)?;
+        assert_eq!(proof.leaves.len(), 101);
+        assert!(proof.verify(root_hash));
+
+        // The proof doesn't hold for a key that is in the tree.
+        let mut forged = proof.clone();
+        forged.key = a.clone();
+        assert!(!forged.verify(root_hash));
+
+        // Leaves with the wrong depths don't rebuild the tree.
+        let mut forged = proof;
+        forged.leaves[0].depth += 1;
+        assert!(!forged.verify(root_hash));
+
+        // An internal node can't be passed off as a leaf: here the root, built from its children.
+        let NodeKind::Internal { left, right } = tree.nodes[tree.root.unwrap()].kind else {
+            unreachable!("the tree has more than one leaf");
+        };
+        let forged = ExclusionProof {
+            key: a.clone(),
+            leaves: vec![ExclusionProofLeaf {
+                depth: 0,
+                key: tree.nodes[left].hash.to_vec().into(),
+                value_hash: tree.nodes[right].hash,
+            }],
+        };
+        assert!(!forged.verify(root_hash));
+
+        tree.delete(&a);
+        assert!(!tree.prove_exclusion(&a).unwrap().verify(root_hash));
+        assert!(tree.prove_exclusion(&a).unwrap().verify(tree.root_hash()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_and_delta() -> anyhow::Result<()> {
+        let pairs: Vec<(Bytes, Bytes)> = (0..200u32)
+            .map(|i| {
+                (
+                    i.to_be_bytes().to_vec().into(),
+                    i.to_le_bytes().to_vec().into(),
+                )
+            })
+            .collect();
+        let old = DataTree::from_pairs(pairs.clone());
+
+        let mut new = old.clone();
+        new.delete(&pairs[3].0);
+        new.insert(pairs[7].0.clone(), pair("", "updated").1);
+        let (a, one) = pair("a", "1");
+        new.insert(a.clone(), one.clone());
+
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                DataTreeChange::Delete {
+                    key: pairs[3].0.clone(),
+                    value: pairs[3].1.clone(),
+                },
+                DataTreeChange::Update {
+                    key: pairs[7].0.clone(),
+                    previous_value: pairs[7].1.clone(),
+                    value: pair("", "updated").1,
+                },
+                DataTreeChange::Insert { key: a, value: one },
+            ]
+        );
+        assert!(old.diff(&old).is_empty());
+
+        let delta = TreeDelta::from_bytes(&old.delta(&new).to_bytes()?)?;
+        assert!(delta.nodes.len() < 100);
+
+        let applied = old.apply_delta(&delta)?;
+        assert_eq!(applied.root_hash(), new.root_hash());
+        assert!(applied.diff(&new).is_empty());
+
+        // The delta only applies to the tree it was made from.
+        assert!(matches!(
+            new.apply_delta(&delta),
+            Err(WalletError::InvalidDelta)
+        ));
+
+        let mut forged = delta.clone();
+        forged.nodes.swap(0, 1);
+        assert!(old.apply_delta(&forged).is_err());
+
+        // Deltas from and to an empty tree.
+        let empty = DataTree::new();
+        assert_eq!(
+            empty.apply_delta(&empty.delta(&new))?.root_hash(),
+            new.root_hash()
+        );
+        assert!(new.apply_delta(&new.delta(&empty))?.is_empty());
+
+        Ok(())
+    }
+}
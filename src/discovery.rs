@@ -0,0 +1,409 @@
+use std::future::Future;
+use std::io::Cursor;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use chia::protocol::{
+    ChiaProtocolMessage, Handshake, NodeType, ProtocolMessageTypes, TimestampedPeerInfo,
+};
+use chia::traits::{Result as StreamableResult, Streamable};
+use chia_wallet_sdk::{ClientError, Network, NetworkId, Peer};
+use clvmr::sha2::Sha256;
+use native_tls::TlsConnector;
+
+use crate::network::TargetNetwork;
+use crate::wallet::WalletError;
+
+/// Maximum time spent on a single DNS lookup, introducer request or connection to a discovered
+/// node.
+pub const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Resolves host names to IP addresses. Abstracted so discovery can run against a stub resolver.
+pub trait Resolver: Send + Sync {
+    fn lookup_host(
+        &self,
+        host: &str,
+    ) -> impl Future<Output = Result<Vec<IpAddr>, WalletError>> + Send;
+}
+
+/// Asks an introducer node for the addresses of full nodes it knows about.
+pub trait Introducer: Send + Sync {
+    fn request_peers(
+        &self,
+        socket_addr: SocketAddr,
+    ) -> impl Future<Output = Result<Vec<TimestampedPeerInfo>, WalletError>> + Send;
+}
+
+/// Resolver backed by the system's DNS configuration.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DnsResolver;
+
+impl Resolver for DnsResolver {
+    async fn lookup_host(&self, host: &str) -> Result<Vec<IpAddr>, WalletError> {
+        Ok(tokio::net::lookup_host((host, 0))
+            .await
+            .map_err(WalletError::Io)?
+            .map(|addr| addr.ip())
+            .collect())
+    }
+}
+
+/// Introducer client speaking the Chia peer protocol over TLS.
+#[derive(Debug, Clone)]
+pub struct TlsIntroducer {
+    network_id: NetworkId,
+    tls_connector: TlsConnector,
+}
+
+impl TlsIntroducer {
+    pub fn new(network_id: NetworkId, tls_connector: TlsConnector) -> Self {
+        Self {
+            network_id,
+            tls_connector,
+        }
+    }
+}
+
+impl Introducer for TlsIntroducer {
+    async fn request_peers(
+        &self,
+        socket_addr: SocketAddr,
+    ) -> Result<Vec<TimestampedPeerInfo>, WalletError> {
+        let (peer, mut receiver) = Peer::connect(socket_addr, self.tls_connector.clone()).await?;
+
+        // `connect_peer` only accepts full nodes, so the handshake is done by hand here.
+        peer.send(Handshake {
+            network_id: self.network_id.to_string(),
+            protocol_version: "0.0.37".to_string(),
+            software_version: "0.0.0".to_string(),
+            server_port: 0,
+            node_type: NodeType::Wallet,
+            capabilities: vec![(1, "1".to_string())],
+        })
+        .await?;
+
+        let message = receiver.recv().await.ok_or(ClientError::MissingHandshake)?;
+        if message.msg_type != ProtocolMessageTypes::Handshake {
+            return Err(ClientError::InvalidResponse(
+                vec![ProtocolMessageTypes::Handshake],
+                message.msg_type,
+            )
+            .into());
+        }
+
+        let handshake = Handshake::from_bytes(&message.data).map_err(ClientError::from)?;
+        if handshake.node_type != NodeType::Introducer {
+            return Err(
+                ClientError::WrongNodeType(NodeType::Introducer, handshake.node_type).into(),
+            );
+        }
+        if handshake.network_id != self.network_id.to_string() {
+            return Err(ClientError::WrongNetwork(
+                self.network_id.to_string(),
+                handshake.network_id,
+            )
+            .into());
+        }
+
+        // Unsolicited messages aren't used, but the channel has to be drained.
+        tokio::spawn(async move { while receiver.recv().await.is_some() {} });
+
+        let response: RespondPeersIntroducer =
+            peer.request_infallible(RequestPeersIntroducer).await?;
+
+        Ok(response.peer_list)
+    }
+}
+
+/// Finds full node addresses for a network, first through its DNS introducers and then,
+/// if those don't return anything, by asking its introducer nodes.
+#[derive(Debug, Clone)]
+pub struct Discovery<R, I> {
+    network: Network,
+    introducers: Vec<(String, u16)>,
+    resolver: R,
+    introducer: I,
+}
+
+impl Discovery<DnsResolver, TlsIntroducer> {
+    /// Discovery using the system's DNS and the network's DNS introducers and introducers.
+    pub fn for_network(network: &TargetNetwork, tls_connector: TlsConnector) -> Self {
+        let introducers = network
+            .introducers
+            .iter()
+            .map(|host| (host.clone(), network.default_port))
+            .collect();
+
+        Self::new(
+            network.network(),
+            introducers,
+            DnsResolver,
+            TlsIntroducer::new(network.network_id.clone(), tls_connector),
+        )
+    }
+}
+
+impl<R, I> Discovery<R, I>
+where
+    R: Resolver,
+    I: Introducer,
+{
+    pub fn new(
+        network: Network,
+        introducers: Vec<(String, u16)>,
+        resolver: R,
+        introducer: I,
+    ) -> Self {
+        Self {
+            network,
+            introducers,
+            resolver,
+            introducer,
+        }
+    }
+
+    /// Returns the addresses of candidate full nodes, without duplicates.
+    /// Individual DNS introducers or introducers that fail are skipped.
+    pub async fn discover(&self) -> Result<Vec<SocketAddr>, WalletError> {
+        let mut peers = self.lookup_dns_introducers().await;
+
+        if peers.is_empty() {
+            peers = self.request_introducers().await;
+        }
+
+        if peers.is_empty() {
+            return Err(WalletError::NoPeers);
+        }
+
+        Ok(peers)
+    }
+
+    async fn lookup_dns_introducers(&self) -> Vec<SocketAddr> {
+        let mut peers = Vec::new();
+
+        for dns_introducer in &self.network.dns_introducers {
+            for ip in self.lookup_host(dns_introducer).await {
+                push_unique(&mut peers, SocketAddr::new(ip, self.network.default_port));
+            }
+        }
+
+        peers
+    }
+
+    async fn request_introducers(&self) -> Vec<SocketAddr> {
+        let mut peers = Vec::new();
+
+        for (host, port) in &self.introducers {
+            for ip in self.lookup_host(host).await {
+                let request = self.introducer.request_peers(SocketAddr::new(ip, *port));
+                let Ok(Ok(peer_list)) = tokio::time::timeout(DISCOVERY_TIMEOUT, request).await
+                else {
+                    continue;
+                };
+
+                for peer_info in peer_list {
+                    if let Ok(ip) = peer_info.host.parse::<IpAddr>() {
+                        push_unique(&mut peers, SocketAddr::new(ip, peer_info.port));
+                    }
+                }
+            }
+        }
+
+        peers
+    }
+
+    async fn lookup_host(&self, host: &str) -> Vec<IpAddr> {
+        // Host names that are already IP addresses don't need a lookup.
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return vec![ip];
+        }
+
+        match tokio::time::timeout(DISCOVERY_TIMEOUT, self.resolver.lookup_host(host)).await {
+            Ok(Ok(ips)) => ips,
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn push_unique(peers: &mut Vec<SocketAddr>, socket_addr: SocketAddr) {
+    if !peers.contains(&socket_addr) {
+        peers.push(socket_addr);
+    }
+}
+
+/// Introducer protocol request, which isn't defined in `chia-protocol`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestPeersIntroducer;
+
+impl Streamable for RequestPeersIntroducer {
+    fn update_digest(&self, _digest: &mut Sha256) {}
+
+    fn stream(&self, _out: &mut Vec<u8>) -> StreamableResult<()> {
+        Ok(())
+    }
+
+    fn parse<const TRUSTED: bool>(_input: &mut Cursor<&[u8]>) -> StreamableResult<Self> {
+        Ok(Self)
+    }
+}
+
+impl ChiaProtocolMessage for RequestPeersIntroducer {
+    fn msg_type() -> ProtocolMessageTypes {
+        ProtocolMessageTypes::RequestPeersIntroducer
+    }
+}
+
+/// Introducer protocol response, which isn't defined in `chia-protocol`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RespondPeersIntroducer {
+    pub peer_list: Vec<TimestampedPeerInfo>,
+}
+
+impl Streamable for RespondPeersIntroducer {
+    fn update_digest(&self, digest: &mut Sha256) {
+        self.peer_list.update_digest(digest);
+    }
+
+    fn stream(&self, out: &mut Vec<u8>) -> StreamableResult<()> {
+        self.peer_list.stream(out)
+    }
+
+    fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> StreamableResult<Self> {
+        Ok(Self {
+            peer_list: Vec::parse::<TRUSTED>(input)?,
+        })
+    }
+}
+
+impl ChiaProtocolMessage for RespondPeersIntroducer {
+    fn msg_type() -> ProtocolMessageTypes {
+        ProtocolMessageTypes::RespondPeersIntroducer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    struct StubResolver(HashMap<String, Vec<IpAddr>>);
+
+    impl Resolver for StubResolver {
+        async fn lookup_host(&self, host: &str) -> Result<Vec<IpAddr>, WalletError> {
+            self.0.get(host).cloned().ok_or(WalletError::NoPeers)
+        }
+    }
+
+    struct StubIntroducer(HashMap<SocketAddr, Vec<TimestampedPeerInfo>>);
+
+    impl Introducer for StubIntroducer {
+        async fn request_peers(
+            &self,
+            socket_addr: SocketAddr,
+        ) -> Result<Vec<TimestampedPeerInfo>, WalletError> {
+            self.0
+                .get(&socket_addr)
+                .cloned()
+                .ok_or(WalletError::NoPeers)
+        }
+    }
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, last))
+    }
+
+    fn network(dns_introducers: &[&str]) -> Network {
+        Network {
+            default_port: 8444,
+            genesis_challenge: Default::default(),
+            agg_sig_me: None,
+            dns_introducers: dns_introducers.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dns_introducers() -> anyhow::Result<()> {
+        let resolver = StubResolver(HashMap::from([
+            ("seeder-a".to_string(), vec![ip(1), ip(2)]),
+            ("seeder-b".to_string(), vec![ip(2), ip(3)]),
+        ]));
+        let discovery = Discovery::new(
+            network(&["seeder-a", "broken-seeder", "seeder-b"]),
+            Vec::new(),
+            resolver,
+            StubIntroducer(HashMap::new()),
+        );
+
+        let peers = discovery.discover().await?;
+        assert_eq!(
+            peers,
+            vec![
+                SocketAddr::new(ip(1), 8444),
+                SocketAddr::new(ip(2), 8444),
+                SocketAddr::new(ip(3), 8444),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_introducer_fallback() -> anyhow::Result<()> {
+        let resolver = StubResolver(HashMap::from([(
+            "introducer".to_string(),
+            vec![ip(10), ip(11)],
+        )]));
+        let introducer = StubIntroducer(HashMap::from([(
+            SocketAddr::new(ip(11), 8444),
+            vec![
+                TimestampedPeerInfo::new("10.0.0.4".to_string(), 8444, 0),
+                TimestampedPeerInfo::new("not an ip".to_string(), 8444, 0),
+                TimestampedPeerInfo::new("10.0.0.5".to_string(), 9000, 0),
+            ],
+        )]));
+        let discovery = Discovery::new(
+            network(&["broken-seeder"]),
+            vec![("introducer".to_string(), 8444)],
+            resolver,
+            introducer,
+        );
+
+        let peers = discovery.discover().await?;
+        assert_eq!(
+            peers,
+            vec![SocketAddr::new(ip(4), 8444), SocketAddr::new(ip(5), 9000)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_nothing_discovered() {
+        let discovery = Discovery::new(
+            network(&["broken-seeder"]),
+            vec![("10.0.0.10".to_string(), 8444)],
+            StubResolver(HashMap::new()),
+            StubIntroducer(HashMap::new()),
+        );
+
+        assert!(matches!(
+            discovery.discover().await,
+            Err(WalletError::NoPeers)
+        ));
+    }
+
+    #[test]
+    fn test_respond_peers_introducer_roundtrip() -> anyhow::Result<()> {
+        let response = RespondPeersIntroducer {
+            peer_list: vec![TimestampedPeerInfo::new("10.0.0.1".to_string(), 8444, 42)],
+        };
+
+        let bytes = response.to_bytes()?;
+        assert_eq!(RespondPeersIntroducer::from_bytes(&bytes)?, response);
+        assert!(RequestPeersIntroducer.to_bytes()?.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,644 @@
+use chia::bls::{PublicKey, Signature};
+use chia::clvm_traits::FromClvm;
+use chia::clvm_utils::tree_hash;
+use chia::protocol::{Bytes, Bytes32, Coin, CoinSpend, SpendBundle, TransactionAck};
+use chia::puzzles::standard::{StandardArgs, StandardSolution};
+use chia_wallet_sdk::{
+    Condition, Conditions, CreateCoin, DataStore, DelegatedPuzzle, SpendContext,
+};
+use clvmr::NodePtr;
+
+use crate::network::TargetNetwork;
+use crate::wallet::{
+    add_fee, broadcast_spend_bundle, get_cost, get_fee_and_cost, get_fee_estimate, mint_store,
+    select_coins, update_store_metadata, DataStoreInnerSpend, SuccessResponse, WalletError,
+    MEMPOOL_INCLUSION_FAILED, MEMPOOL_INCLUSION_PENDING,
+};
+use crate::wallet_protocol::WalletProtocol;
+
+/// Number of times a spend is rebuilt with a higher fee before giving up. The fee only has to
+/// grow when a higher fee makes the spend more expensive, e.g. by selecting another coin.
+pub const MAX_FEE_ATTEMPTS: usize = 5;
+
+/// The minimum fee increase full nodes require to replace a spend bundle in their mempool.
+pub const MIN_FEE_INCREASE: u64 = 10_000_000;
+
+/// The mempool error of a spend bundle that conflicts with a pending one it can't replace.
+pub const MEMPOOL_CONFLICT: &str = "MEMPOOL_CONFLICT";
+
+/// How the fee of a spend is determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeePolicy {
+    /// A fixed fee, regardless of the spend's cost.
+    Fixed(u64),
+    /// A fee of this many mojos per unit of the spend's CLVM cost.
+    MojosPerCost(u64),
+}
+
+impl FeePolicy {
+    /// A fee rate estimated by the node to confirm within `target_time_seconds`.
+    pub async fn from_target_time(
+        peer: &impl WalletProtocol,
+        target_time_seconds: u64,
+    ) -> Result<Self, WalletError> {
+        Ok(Self::MojosPerCost(
+            get_fee_estimate(peer, target_time_seconds).await?,
+        ))
+    }
+
+    /// The fee a spend with the given cost has to pay.
+    pub fn fee_for_cost(&self, cost: u64) -> u64 {
+        match self {
+            Self::Fixed(fee) => *fee,
+            Self::MojosPerCost(mojos_per_cost) => mojos_per_cost.saturating_mul(cost),
+        }
+    }
+
+    /// The fee to build a spend with first.
+    pub fn initial_fee(&self) -> u64 {
+        match self {
+            Self::Fixed(fee) => *fee,
+            Self::MojosPerCost(_) => 0,
+        }
+    }
+}
+
+/// A spend built with a fee that covers its own cost.
+#[derive(Debug, Clone)]
+pub struct PricedResponse {
+    pub response: SuccessResponse,
+    pub fee: u64,
+    pub cost: u64,
+}
+
+/// Mints a store like `mint_store`, selecting coins out of `coins` and raising the fee until it
+/// covers the cost of the mint.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_store_with_fee_policy(
+    minter_synthetic_key: PublicKey,
+    coins: Vec<Coin>,
+    root_hash: Bytes32,
+    label: Option<String>,
+    description: Option<String>,
+    bytes: Option<u64>,
+    owner_puzzle_hash: Bytes32,
+    delegated_puzzles: Vec<DelegatedPuzzle>,
+    fee_policy: FeePolicy,
+    network: &TargetNetwork,
+) -> Result<PricedResponse, WalletError> {
+    let mut fee = fee_policy.initial_fee();
+
+    for _ in 0..MAX_FEE_ATTEMPTS {
+        let response = mint_store(
+            minter_synthetic_key,
+            select_coins(coins.clone(), fee + 1)?,
+            root_hash,
+            label.clone(),
+            description.clone(),
+            bytes,
+            owner_puzzle_hash,
+            delegated_puzzles.clone(),
+            fee,
+        )?;
+
+        let cost = get_cost(response.coin_spends.clone(), network)?;
+        let required_fee = fee_policy.fee_for_cost(cost);
+
+        if fee >= required_fee {
+            return Ok(PricedResponse {
+                response,
+                fee,
+                cost,
+            });
+        }
+
+        fee = required_fee;
+    }
+
+    Err(WalletError::FeeNotConverged(MAX_FEE_ATTEMPTS))
+}
+
+/// Updates the metadata of a store like `update_store_metadata`, paying a fee with coins of the
+/// wallet with `fee_synthetic_key` selected out of `coins`, and raising the fee until it covers
+/// the cost of the update.
+#[allow(clippy::too_many_arguments)]
+pub fn update_store_metadata_with_fee_policy(
+    datastore: DataStore,
+    new_root_hash: Bytes32,
+    new_label: Option<String>,
+    new_description: Option<String>,
+    new_bytes: Option<u64>,
+    inner_spend_info: DataStoreInnerSpend,
+    fee_synthetic_key: PublicKey,
+    coins: Vec<Coin>,
+    fee_policy: FeePolicy,
+    network: &TargetNetwork,
+) -> Result<PricedResponse, WalletError> {
+    let update = update_store_metadata(
+        datastore,
+        new_root_hash,
+        new_label,
+        new_description,
+        new_bytes,
+        inner_spend_info,
+    )?;
+    let store_coin_ids: Vec<Bytes32> = update
+        .coin_spends
+        .iter()
+        .map(|cs| cs.coin.coin_id())
+        .collect();
+    let mut fee = fee_policy.initial_fee();
+
+    for _ in 0..MAX_FEE_ATTEMPTS {
+        let mut coin_spends = update.coin_spends.clone();
+        if fee > 0 {
+            coin_spends.extend(add_fee(
+                fee_synthetic_key,
+                select_coins(coins.clone(), fee)?,
+                store_coin_ids.clone(),
+                fee,
+            )?);
+        }
+
+        let cost = get_cost(coin_spends.clone(), network)?;
+        let required_fee = fee_policy.fee_for_cost(cost);
+
+        if fee >= required_fee {
+            return Ok(PricedResponse {
+                response: SuccessResponse {
+                    coin_spends,
+                    new_datastore: update.new_datastore,
+                },
+                fee,
+                cost,
+            });
+        }
+
+        fee = required_fee;
+    }
+
+    Err(WalletError::FeeNotConverged(MAX_FEE_ATTEMPTS))
+}
+
+/// A pending spend bundle rebuilt with a higher fee, to replace it in the mempool.
+#[derive(Debug, Clone)]
+pub struct FeeBump {
+    /// The new coin spends. They have to be signed again, since the wallet's spends changed.
+    pub coin_spends: Vec<CoinSpend>,
+    pub fee: u64,
+    pub cost: u64,
+    pub previous_fee: u64,
+    pub previous_cost: u64,
+}
+
+/// Rebuilds the coin spends of a pending spend bundle with a higher fee, following the mempool's
+/// replacement rules: every coin the bundle spends is spent again, the fee grows by at least
+/// `MIN_FEE_INCREASE`, and the fee per cost is higher than before.
+///
+/// The extra fee is taken out of the change the wallet with `synthetic_key` gets back in the
+/// bundle. If there isn't enough change, the rest is paid with coins selected out of `fee_coins`.
+pub fn bump_fee(
+    coin_spends: Vec<CoinSpend>,
+    synthetic_key: PublicKey,
+    fee_coins: Vec<Coin>,
+    fee_policy: FeePolicy,
+    network: &TargetNetwork,
+) -> Result<FeeBump, WalletError> {
+    let (previous_fee, previous_cost) = get_fee_and_cost(coin_spends.clone(), network)?;
+    let mut fee = (previous_fee + MIN_FEE_INCREASE).max(fee_policy.initial_fee());
+
+    for _ in 0..MAX_FEE_ATTEMPTS {
+        let bumped = add_fee_to_spends(
+            &coin_spends,
+            synthetic_key,
+            fee_coins.clone(),
+            fee - previous_fee,
+        )?;
+        let (new_fee, cost) = get_fee_and_cost(bumped.clone(), network)?;
+
+        let required_fee = fee_policy
+            .fee_for_cost(cost)
+            .max(previous_fee + MIN_FEE_INCREASE)
+            .max(higher_fee_per_cost(previous_fee, previous_cost, cost));
+
+        if new_fee >= required_fee {
+            return Ok(FeeBump {
+                coin_spends: bumped,
+                fee: new_fee,
+                cost,
+                previous_fee,
+                previous_cost,
+            });
+        }
+
+        fee = required_fee;
+    }
+
+    Err(WalletError::FeeNotConverged(MAX_FEE_ATTEMPTS))
+}
+
+/// Broadcasts the coin spends of a fee bump, signed with `signature`, to replace the pending
+/// spend bundle.
+///
+/// Fails with `TransactionRejected` if the mempool refused the bump, or kept it pending with a
+/// `MEMPOOL_CONFLICT` because it doesn't replace the pending bundle. A bump whose fee doesn't
+/// grow by `MIN_FEE_INCREASE` and to a higher fee per cost is rejected the same way without
+/// being broadcast, as the node would refuse to replace the pending bundle with it.
+pub async fn broadcast_fee_bump(
+    peer: &impl WalletProtocol,
+    bump: &FeeBump,
+    signature: Signature,
+) -> Result<TransactionAck, WalletError> {
+    if bump.fee < bump.previous_fee.saturating_add(MIN_FEE_INCREASE)
+        || bump.fee < higher_fee_per_cost(bump.previous_fee, bump.previous_cost, bump.cost)
+    {
+        return Err(WalletError::TransactionRejected {
+            status: MEMPOOL_INCLUSION_PENDING,
+            error: MEMPOOL_CONFLICT.to_string(),
+        });
+    }
+
+    let ack =
+        broadcast_spend_bundle(peer, SpendBundle::new(bump.coin_spends.clone(), signature)).await?;
+
+    if ack.status == MEMPOOL_INCLUSION_FAILED || ack.error.as_deref() == Some(MEMPOOL_CONFLICT) {
+        return Err(WalletError::TransactionRejected {
+            status: ack.status,
+            error: ack.error.unwrap_or_default(),
+        });
+    }
+
+    Ok(ack)
+}
+
+// The lowest fee for `cost` with a higher fee per cost than `previous_fee` for `previous_cost`.
+fn higher_fee_per_cost(previous_fee: u64, previous_cost: u64, cost: u64) -> u64 {
+    if previous_cost == 0 {
+        return previous_fee + 1;
+    }
+
+    let fee = u128::from(previous_fee) * u128::from(cost) / u128::from(previous_cost) + 1;
+    u64::try_from(fee).unwrap_or(u64::MAX)
+}
+
+// Adds `extra_fee` to the coin spends, out of the wallet's change first.
+fn add_fee_to_spends(
+    coin_spends: &[CoinSpend],
+    synthetic_key: PublicKey,
+    fee_coins: Vec<Coin>,
+    extra_fee: u64,
+) -> Result<Vec<CoinSpend>, WalletError> {
+    let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+    let mut ctx = SpendContext::new();
+    let mut remaining_fee = extra_fee;
+    let mut bumped = Vec::with_capacity(coin_spends.len());
+
+    for coin_spend in coin_spends {
+        if remaining_fee == 0 || coin_spend.coin.puzzle_hash != puzzle_hash {
+            bumped.push(coin_spend.clone());
+            continue;
+        }
+
+        let output =
+            coin_spend
+                .puzzle_reveal
+                .run(&mut ctx.allocator, 0, u64::MAX, &coin_spend.solution)?;
+        let conditions = Vec::<Condition>::from_clvm(&ctx.allocator, output.1)?;
+
+        // The standard puzzle adds a signature for its delegated puzzle, which is replaced, and
+        // the reserved fees are merged so that bumping again doesn't add to the cost.
+        let solution = ctx.alloc(&coin_spend.solution)?;
+        let solution = StandardSolution::<NodePtr, NodePtr>::from_clvm(&ctx.allocator, solution)?;
+        let delegated_puzzle_hash: Bytes = tree_hash(&ctx.allocator, solution.delegated_puzzle)
+            .to_vec()
+            .into();
+
+        let Some(change) = conditions.iter().find_map(|condition| match condition {
+            Condition::CreateCoin(create_coin) if create_coin.puzzle_hash == puzzle_hash => {
+                Some(create_coin.amount)
+            }
+            _ => None,
+        }) else {
+            bumped.push(coin_spend.clone());
+            continue;
+        };
+
+        let taken = change.min(remaining_fee);
+        remaining_fee -= taken;
+
+        let mut new_conditions = Conditions::new();
+        let mut change_reduced = false;
+        let mut reserved_fee = taken;
+
+        for condition in conditions {
+            match condition {
+                Condition::CreateCoin(create_coin)
+                    if create_coin.puzzle_hash == puzzle_hash && !change_reduced =>
+                {
+                    change_reduced = true;
+                    if create_coin.amount > taken {
+                        new_conditions = new_conditions.with(CreateCoin {
+                            amount: create_coin.amount - taken,
+                            ..create_coin
+                        });
+                    }
+                }
+                Condition::AggSigMe(agg_sig)
+                    if agg_sig.public_key == synthetic_key
+                        && agg_sig.message == delegated_puzzle_hash => {}
+                Condition::ReserveFee(reserve_fee) => reserved_fee += reserve_fee.amount,
+                condition => new_conditions = new_conditions.with(condition),
+            }
+        }
+
+        ctx.spend_p2_coin(
+            coin_spend.coin,
+            synthetic_key,
+            new_conditions.reserve_fee(reserved_fee),
+        )?;
+        bumped.extend(ctx.take());
+    }
+
+    if remaining_fee > 0 {
+        let spent: Vec<Bytes32> = coin_spends.iter().map(|cs| cs.coin.coin_id()).collect();
+        let fee_coins = fee_coins
+            .into_iter()
+            .filter(|coin| !spent.contains(&coin.coin_id()))
+            .collect();
+        let selected_coins = select_coins(fee_coins, remaining_fee)?;
+        let change = selected_coins.iter().map(|coin| coin.amount).sum::<u64>() - remaining_fee;
+
+        let mut conditions = Conditions::new().reserve_fee(remaining_fee);
+        if change > 0 {
+            conditions = conditions.create_coin(puzzle_hash, change, vec![puzzle_hash.into()]);
+        }
+        if let Some(coin_id) = spent.first() {
+            conditions = conditions.assert_concurrent_spend(*coin_id);
+        }
+
+        let lead_coin_id = selected_coins[0].coin_id();
+        ctx.spend_p2_coin(selected_coins[0], synthetic_key, conditions)?;
+
+        for coin in selected_coins.into_iter().skip(1) {
+            ctx.spend_p2_coin(
+                coin,
+                synthetic_key,
+                Conditions::new().assert_concurrent_spend(lead_coin_id),
+            )?;
+        }
+        bumped.extend(ctx.take());
+    }
+
+    Ok(bumped)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use chia::puzzles::DeriveSynthetic;
+    use chia_wallet_sdk::RequiredSignature;
+    use clvmr::Allocator;
+
+    use super::*;
+    use crate::simulator::ChainSimulator;
+    use crate::wallet::sign_coin_spends;
+
+    fn required_signatures(coin_spends: &[CoinSpend]) -> anyhow::Result<usize> {
+        let mut allocator = Allocator::new();
+        Ok(RequiredSignature::from_coin_spends(
+            &mut allocator,
+            coin_spends,
+            &TargetNetwork::mainnet().constants(),
+        )?
+        .len())
+    }
+
+    fn spent_coins(coin_spends: &[CoinSpend]) -> Vec<Coin> {
+        coin_spends.iter().map(|cs| cs.coin).collect()
+    }
+
+    #[test]
+    fn test_bump_fee() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[6; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        // The extra fee comes out of the change.
+        let pending = mint_store(
+            synthetic_key,
+            vec![sim.new_coin(puzzle_hash, 1_000_000_000)],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            100,
+        )?;
+
+        let bumped = bump_fee(
+            pending.coin_spends.clone(),
+            synthetic_key,
+            vec![],
+            FeePolicy::MojosPerCost(0),
+            sim.network(),
+        )?;
+        assert_eq!(bumped.previous_fee, 100);
+        assert_eq!(bumped.fee, 100 + MIN_FEE_INCREASE);
+        assert!(
+            u128::from(bumped.fee) * u128::from(bumped.previous_cost)
+                > u128::from(bumped.previous_fee) * u128::from(bumped.cost)
+        );
+        assert_eq!(
+            spent_coins(&bumped.coin_spends),
+            spent_coins(&pending.coin_spends)
+        );
+        assert_eq!(
+            required_signatures(&bumped.coin_spends)?,
+            required_signatures(&pending.coin_spends)?
+        );
+
+        // Bumping again doesn't pile up signatures or cost.
+        let bumped_again = bump_fee(
+            bumped.coin_spends.clone(),
+            synthetic_key,
+            vec![],
+            FeePolicy::MojosPerCost(0),
+            sim.network(),
+        )?;
+        assert_eq!(
+            required_signatures(&bumped_again.coin_spends)?,
+            required_signatures(&pending.coin_spends)?
+        );
+        assert_eq!(bumped_again.cost, bumped.cost);
+        sim.sign_and_push(&sk, bumped_again.coin_spends);
+
+        // Without change, the fee is paid with other coins.
+        let pending = mint_store(
+            synthetic_key,
+            vec![sim.new_coin(puzzle_hash, 1)],
+            Bytes32::new([2; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            0,
+        )?;
+
+        let fee_coin = sim.new_coin(puzzle_hash, 100_000_000);
+        assert!(matches!(
+            bump_fee(
+                pending.coin_spends.clone(),
+                synthetic_key,
+                vec![],
+                FeePolicy::Fixed(0),
+                sim.network(),
+            ),
+            Err(WalletError::CoinSelection(_))
+        ));
+
+        let bumped = bump_fee(
+            pending.coin_spends.clone(),
+            synthetic_key,
+            vec![fee_coin],
+            FeePolicy::Fixed(50_000_000),
+            sim.network(),
+        )?;
+        assert_eq!(bumped.fee, 50_000_000);
+        assert!(spent_coins(&bumped.coin_spends).contains(&fee_coin));
+        sim.sign_and_push(&sk, bumped.coin_spends);
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_broadcast_fee_bump() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[7; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        let pending = mint_store(
+            synthetic_key,
+            vec![sim.new_coin(puzzle_hash, 1_000_000_000)],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            100,
+        )?;
+        let bumped = bump_fee(
+            pending.coin_spends,
+            synthetic_key,
+            vec![],
+            FeePolicy::MojosPerCost(0),
+            sim.network(),
+        )?;
+        let signature =
+            sign_coin_spends(bumped.coin_spends.clone(), vec![sk.clone()], sim.network())?;
+
+        let height = sim.height();
+
+        // A bump the node wouldn't replace the pending bundle with isn't broadcast.
+        let too_low = FeeBump {
+            fee: bumped.previous_fee + MIN_FEE_INCREASE - 1,
+            ..bumped.clone()
+        };
+        assert!(matches!(
+            broadcast_fee_bump(&sim, &too_low, signature.clone()).await,
+            Err(WalletError::TransactionRejected { status: MEMPOOL_INCLUSION_PENDING, error })
+                if error == MEMPOOL_CONFLICT
+        ));
+        let lower_fee_per_cost = FeeBump {
+            cost: bumped.previous_cost * 1_000_000,
+            ..bumped.clone()
+        };
+        assert!(matches!(
+            broadcast_fee_bump(&sim, &lower_fee_per_cost, signature.clone()).await,
+            Err(WalletError::TransactionRejected {
+                status: MEMPOOL_INCLUSION_PENDING,
+                ..
+            })
+        ));
+        assert_eq!(sim.height(), height);
+
+        // The mempool's refusal is surfaced.
+        assert!(matches!(
+            broadcast_fee_bump(&sim, &bumped, Signature::default()).await,
+            Err(WalletError::TransactionRejected { status: MEMPOOL_INCLUSION_FAILED, error })
+                if error == "BadAggregateSignature"
+        ));
+
+        let ack = broadcast_fee_bump(&sim, &bumped, signature).await?;
+        assert_eq!(ack.status, 1);
+        assert_eq!(ack.error, None);
+        assert_eq!(sim.height(), height + 1);
+
+        Ok(())
+    }
+    #[test]
+    fn test_update_store_metadata_with_fee_policy() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[8; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        let minted = mint_store(
+            synthetic_key,
+            vec![sim.new_coin(puzzle_hash, 1)],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            0,
+        )?;
+        sim.sign_and_push(&sk, minted.coin_spends);
+        let fee_coins = vec![
+            sim.new_coin(puzzle_hash, 1_000_000),
+            sim.new_coin(puzzle_hash, 1_000_000_000),
+        ];
+
+        let update = |fee_policy| {
+            update_store_metadata_with_fee_policy(
+                minted.new_datastore.clone(),
+                Bytes32::new([2; 32]),
+                None,
+                None,
+                None,
+                DataStoreInnerSpend::Owner(synthetic_key),
+                synthetic_key,
+                fee_coins.clone(),
+                fee_policy,
+                sim.network(),
+            )
+        };
+
+        // Without a fee, no coins are spent.
+        let free = update(FeePolicy::Fixed(0))?;
+        assert_eq!(free.fee, 0);
+        assert_eq!(free.response.coin_spends.len(), 1);
+
+        let priced = update(FeePolicy::MojosPerCost(10))?;
+        assert!(priced.fee >= priced.cost * 10);
+        assert_eq!(
+            priced.cost,
+            get_cost(priced.response.coin_spends.clone(), sim.network())?
+        );
+        assert_eq!(
+            get_fee_and_cost(priced.response.coin_spends.clone(), sim.network())?,
+            (priced.fee, priced.cost)
+        );
+        assert_eq!(
+            priced.response.new_datastore.info.metadata.root_hash,
+            Bytes32::new([2; 32])
+        );
+        sim.sign_and_push(&sk, priced.response.coin_spends);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,612 @@
+use chia::bls::PublicKey;
+use chia::clvm_traits::ToClvm;
+use chia::protocol::{Bytes32, Coin, CoinSpend};
+use chia::puzzles::singleton::SINGLETON_LAUNCHER_PUZZLE_HASH;
+use chia_wallet_sdk::{
+    Condition, CreateCoin, DataStore, DataStoreInfo, DataStoreMetadata, DelegatedPuzzle,
+    DelegationLayer, DriverError, Layer, NftStateLayer, OracleLayer, Puzzle, SingletonLayer,
+    SpendContext, StandardLayer, WriterLayer, MAINNET_CONSTANTS,
+};
+use clvmr::cost::Cost;
+use clvmr::reduction::Reduction;
+use clvmr::{ChiaDialect, NodePtr};
+
+use crate::server_coin::MirrorArgs;
+use crate::wallet::{StoreSpendKind, WalletError};
+
+/// What a set of coin spends does, decoded by running their puzzles, so that it can be
+/// reviewed before signing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendIntent {
+    /// One entry per coin spend, in the same order.
+    pub spends: Vec<DecodedSpend>,
+    /// The amount of the spent coins minus the amount of the created coins, which is what the
+    /// farmer gets. Zero if the spends create more than they spend, which the network rejects.
+    pub fee: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSpend {
+    pub coin: Coin,
+    pub action: SpendAction,
+    /// The coins the spend creates.
+    pub additions: Vec<Coin>,
+    /// The fee the spend reserves with `RESERVE_FEE` conditions.
+    pub reserved_fee: u64,
+}
+
+/// What a single coin spend does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpendAction {
+    /// Launches a store from its singleton launcher.
+    LaunchStore(DataStoreInfo),
+    /// Spends a store into its next state.
+    UpdateStore(Box<StoreChange>),
+    /// Melts a store, destroying it.
+    MeltStore {
+        launcher_id: Bytes32,
+        owner_puzzle_hash: Bytes32,
+    },
+    /// Spends a store through its oracle delegated puzzle, which pays the oracle fee and
+    /// leaves the store unchanged.
+    PayOracle {
+        launcher_id: Bytes32,
+        oracle_puzzle_hash: Bytes32,
+        oracle_fee: u64,
+    },
+    /// Spends a standard coin.
+    Payment(Payment),
+    /// A puzzle that isn't recognized, or a store spend through a delegated puzzle that isn't
+    /// an admin, writer or oracle puzzle.
+    Unknown,
+}
+
+/// A store spend, with the store's state before and after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreChange {
+    pub launcher_id: Bytes32,
+    /// What authorized the spend. Never `Launch` or `Oracle`, which are decoded as
+    /// `SpendAction::LaunchStore` and `SpendAction::PayOracle`.
+    pub kind: StoreSpendKind,
+    /// The delegated puzzle that authorized the spend. `None` for owner spends.
+    pub delegated_puzzle: Option<DelegatedPuzzle>,
+    pub previous_metadata: DataStoreMetadata,
+    pub metadata: DataStoreMetadata,
+    pub previous_owner_puzzle_hash: Bytes32,
+    pub owner_puzzle_hash: Bytes32,
+    /// The delegated puzzles after the spend. A store's puzzle only commits to the merkle root
+    /// of its delegated puzzles, so they're only known when the spend replaces them; `None`
+    /// means they're unchanged.
+    pub delegated_puzzles: Option<Vec<DelegatedPuzzle>>,
+}
+
+impl StoreChange {
+    pub fn metadata_changed(&self) -> bool {
+        self.previous_metadata != self.metadata
+    }
+
+    pub fn ownership_changed(&self) -> bool {
+        self.previous_owner_puzzle_hash != self.owner_puzzle_hash
+            || self.delegated_puzzles.is_some()
+    }
+}
+
+/// A standard coin spend, with the coins it creates split into payments, server coins and
+/// change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payment {
+    pub synthetic_key: PublicKey,
+    /// Coins created for other puzzle hashes, except server coins.
+    pub outputs: Vec<CreateCoin>,
+    pub server_coins: Vec<ServerCoinOutput>,
+    /// The amount sent back to the spent coin's puzzle hash.
+    pub change: u64,
+}
+
+/// A server coin created by a standard coin spend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerCoinOutput {
+    /// The hint of the server coin, usually the original or morphed launcher id.
+    pub hint: Bytes32,
+    pub amount: u64,
+    pub memo_urls: Vec<String>,
+}
+
+/// Decodes what the coin spends do by running their puzzles.
+///
+/// Each spend is decoded on its own, without looking up the coins on chain, so only what the
+/// puzzles and solutions reveal is reported. Spends whose puzzle isn't recognized are decoded
+/// as `SpendAction::Unknown`, but still list the coins they create.
+///
+/// The puzzles can run for at most the cost of a block in total, like they would on chain.
+pub fn decode_spend_intent(coin_spends: &[CoinSpend]) -> Result<SpendIntent, WalletError> {
+    let mut ctx = SpendContext::new();
+    let mut spends = Vec::with_capacity(coin_spends.len());
+    // custom networks share the block cost limit of mainnet
+    let mut cost_left = MAINNET_CONSTANTS.max_block_cost_clvm;
+
+    for cs in coin_spends {
+        spends.push(decode_spend(&mut ctx, cs, &mut cost_left)?);
+    }
+
+    let removed: u128 = coin_spends
+        .iter()
+        .map(|cs| u128::from(cs.coin.amount))
+        .sum();
+    let added: u128 = spends
+        .iter()
+        .flat_map(|spend| &spend.additions)
+        .map(|coin| u128::from(coin.amount))
+        .sum();
+
+    Ok(SpendIntent {
+        spends,
+        fee: removed.saturating_sub(added).try_into().unwrap_or(u64::MAX),
+    })
+}
+
+/// Runs a puzzle with a cost limit, returning the cost it took and its output.
+fn run_puzzle(
+    ctx: &mut SpendContext,
+    puzzle: NodePtr,
+    solution: NodePtr,
+    max_cost: Cost,
+) -> Result<(Cost, NodePtr), WalletError> {
+    let Reduction(cost, output) = clvmr::run_program(
+        &mut ctx.allocator,
+        &ChiaDialect::new(0),
+        puzzle,
+        solution,
+        max_cost,
+    )?;
+    Ok((cost, output))
+}
+
+fn decode_spend(
+    ctx: &mut SpendContext,
+    cs: &CoinSpend,
+    cost_left: &mut Cost,
+) -> Result<DecodedSpend, WalletError> {
+    let puzzle = cs
+        .puzzle_reveal
+        .to_clvm(&mut ctx.allocator)
+        .map_err(DriverError::ToClvm)?;
+    let solution = cs
+        .solution
+        .to_clvm(&mut ctx.allocator)
+        .map_err(DriverError::ToClvm)?;
+
+    let (cost, output) = run_puzzle(ctx, puzzle, solution, *cost_left)?;
+    *cost_left -= cost;
+    let conditions: Vec<Condition> = ctx.extract(output)?;
+
+    let mut additions = Vec::new();
+    let mut reserved_fee = 0;
+
+    for condition in &conditions {
+        match condition {
+            Condition::CreateCoin(create_coin) => additions.push(Coin::new(
+                cs.coin.coin_id(),
+                create_coin.puzzle_hash,
+                create_coin.amount,
+            )),
+            Condition::ReserveFee(reserve_fee) => {
+                reserved_fee += reserve_fee.amount;
+            }
+            _ => {}
+        }
+    }
+
+    let puzzle = Puzzle::parse(&ctx.allocator, puzzle);
+
+    let action = if cs.coin.puzzle_hash == SINGLETON_LAUNCHER_PUZZLE_HASH.into() {
+        // launchers of other singletons, e.g. NFTs, don't parse as stores
+        match DataStore::<DataStoreMetadata>::from_spend(&mut ctx.allocator, cs, &[]) {
+            Ok(Some(store)) => SpendAction::LaunchStore(store.info),
+            _ => SpendAction::Unknown,
+        }
+    } else if let Some(action) = decode_store_spend(ctx, cs, puzzle, solution, cost)? {
+        action
+    } else if let Some(layer) = StandardLayer::parse_puzzle(&ctx.allocator, puzzle)? {
+        SpendAction::Payment(decode_payment(cs, layer.synthetic_key, &conditions))
+    } else {
+        SpendAction::Unknown
+    };
+
+    Ok(DecodedSpend {
+        coin: cs.coin,
+        action,
+        additions,
+        reserved_fee,
+    })
+}
+
+fn decode_store_spend(
+    ctx: &mut SpendContext,
+    cs: &CoinSpend,
+    puzzle: Puzzle,
+    solution: NodePtr,
+    max_cost: Cost,
+) -> Result<Option<SpendAction>, WalletError> {
+    let Some(singleton_layer) = SingletonLayer::<Puzzle>::parse_puzzle(&ctx.allocator, puzzle)?
+    else {
+        return Ok(None);
+    };
+    let Some(state_layer) = NftStateLayer::<DataStoreMetadata, Puzzle>::parse_puzzle(
+        &ctx.allocator,
+        singleton_layer.inner_puzzle,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    let launcher_id = singleton_layer.launcher_id;
+    let inner_puzzle = state_layer.inner_puzzle;
+    let inner_solution =
+        SingletonLayer::<NftStateLayer<DataStoreMetadata, Puzzle>>::parse_solution(
+            &ctx.allocator,
+            solution,
+        )?
+        .inner_solution
+        .inner_solution;
+
+    let delegation_layer = DelegationLayer::parse_puzzle(&ctx.allocator, inner_puzzle)?;
+    let previous_owner_puzzle_hash = delegation_layer
+        .as_ref()
+        .map_or(inner_puzzle.curried_puzzle_hash().into(), |layer| {
+            layer.owner_puzzle_hash
+        });
+
+    // the conditions of the state layer's inner puzzle, i.e. the owner or delegation layer
+    // it runs as part of the full puzzle, so it can't cost more
+    let (_, inner_output) = run_puzzle(ctx, inner_puzzle.ptr(), inner_solution, max_cost)?;
+    let inner_conditions: Vec<Condition> = ctx.extract(inner_output)?;
+
+    if inner_conditions
+        .iter()
+        .any(|condition| matches!(condition, Condition::MeltSingleton(_)))
+    {
+        return Ok(Some(SpendAction::MeltStore {
+            launcher_id,
+            owner_puzzle_hash: previous_owner_puzzle_hash,
+        }));
+    }
+
+    let (kind, delegated_puzzle) = match delegation_layer {
+        Some(_) => {
+            let solution = DelegationLayer::parse_solution(&ctx.allocator, inner_solution)?;
+
+            // the owner puzzle is run without a merkle proof
+            if solution.merkle_proof.is_none() {
+                (StoreSpendKind::Owner, None)
+            } else {
+                let delegated_puzzle = Puzzle::parse(&ctx.allocator, solution.puzzle_reveal);
+
+                // the oracle layer fails to parse uncurried puzzles that aren't a quoted list of
+                // conditions, which are just not oracles
+                if let Ok(Some(oracle)) =
+                    OracleLayer::parse_puzzle(&ctx.allocator, delegated_puzzle)
+                {
+                    return Ok(Some(SpendAction::PayOracle {
+                        launcher_id,
+                        oracle_puzzle_hash: oracle.oracle_puzzle_hash,
+                        oracle_fee: oracle.oracle_fee,
+                    }));
+                }
+
+                if let Some(writer) =
+                    WriterLayer::<Puzzle>::parse_puzzle(&ctx.allocator, delegated_puzzle)?
+                {
+                    (
+                        StoreSpendKind::Writer,
+                        Some(DelegatedPuzzle::Writer(
+                            writer.inner_puzzle.curried_puzzle_hash(),
+                        )),
+                    )
+                } else if StandardLayer::parse_puzzle(&ctx.allocator, delegated_puzzle)?.is_some() {
+                    // admins are added by their key, so their delegated puzzle is a standard one
+                    (
+                        StoreSpendKind::Admin,
+                        Some(DelegatedPuzzle::Admin(
+                            delegated_puzzle.curried_puzzle_hash(),
+                        )),
+                    )
+                } else {
+                    return Ok(Some(SpendAction::Unknown));
+                }
+            }
+        }
+        None => (StoreSpendKind::Owner, None),
+    };
+
+    let new_store = DataStore::<DataStoreMetadata>::from_spend(&mut ctx.allocator, cs, &[])?
+        .ok_or(WalletError::Parse)?;
+
+    // The store keeps its owner and delegated puzzles if its inner puzzle is recreated as is.
+    let inner_puzzle_recreated = inner_conditions.iter().any(|condition| {
+        matches!(
+            condition,
+            Condition::CreateCoin(create_coin)
+                if create_coin.amount % 2 == 1
+                    && create_coin.puzzle_hash == inner_puzzle.curried_puzzle_hash().into()
+        )
+    });
+
+    Ok(Some(SpendAction::UpdateStore(Box::new(StoreChange {
+        launcher_id,
+        kind,
+        delegated_puzzle,
+        previous_metadata: state_layer.metadata,
+        metadata: new_store.info.metadata,
+        previous_owner_puzzle_hash,
+        owner_puzzle_hash: if inner_puzzle_recreated {
+            previous_owner_puzzle_hash
+        } else {
+            new_store.info.owner_puzzle_hash
+        },
+        delegated_puzzles: if inner_puzzle_recreated {
+            None
+        } else {
+            Some(new_store.info.delegated_puzzles)
+        },
+    }))))
+}
+
+fn decode_payment(cs: &CoinSpend, synthetic_key: PublicKey, conditions: &[Condition]) -> Payment {
+    let mut payment = Payment {
+        synthetic_key,
+        outputs: Vec::new(),
+        server_coins: Vec::new(),
+        change: 0,
+    };
+
+    for condition in conditions {
+        let Condition::CreateCoin(create_coin) = condition else {
+            continue;
+        };
+
+        if create_coin.puzzle_hash == cs.coin.puzzle_hash {
+            payment.change += create_coin.amount;
+            continue;
+        }
+
+        if create_coin.puzzle_hash == MirrorArgs::curry_tree_hash().into() {
+            let hint = create_coin
+                .memos
+                .first()
+                .and_then(|memo| Bytes32::try_from(memo.as_ref()).ok());
+            let memo_urls = create_coin
+                .memos
+                .iter()
+                .skip(1)
+                .map(|memo| String::from_utf8(memo.as_ref().to_vec()).ok())
+                .collect::<Option<Vec<String>>>();
+
+            if let (Some(hint), Some(memo_urls)) = (hint, memo_urls) {
+                payment.server_coins.push(ServerCoinOutput {
+                    hint,
+                    amount: create_coin.amount,
+                    memo_urls,
+                });
+                continue;
+            }
+        }
+
+        payment.outputs.push(create_coin.clone());
+    }
+
+    payment
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use chia::puzzles::standard::StandardArgs;
+    use chia::puzzles::DeriveSynthetic;
+    use chia_wallet_sdk::Spend;
+
+    use super::*;
+    use crate::wallet::{
+        create_server_coin, melt_store, mint_store, oracle_spend, update_store_metadata,
+        update_store_ownership, DataStoreInnerSpend,
+    };
+
+    fn coin(puzzle_hash: Bytes32, amount: u64) -> Coin {
+        Coin::new(Bytes32::new([1; 32]), puzzle_hash, amount)
+    }
+
+    fn find_store_action(intent: &SpendIntent) -> &SpendAction {
+        intent
+            .spends
+            .iter()
+            .map(|spend| &spend.action)
+            .find(|action| !matches!(action, SpendAction::Payment(_)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decode_spend_intent() -> anyhow::Result<()> {
+        let owner_key = SecretKey::from_seed(&[10; 32])
+            .public_key()
+            .derive_synthetic();
+        let writer_key = SecretKey::from_seed(&[11; 32])
+            .public_key()
+            .derive_synthetic();
+        let admin_key = SecretKey::from_seed(&[12; 32])
+            .public_key()
+            .derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(owner_key).into();
+        let writer = DelegatedPuzzle::Writer(StandardArgs::curry_tree_hash(writer_key));
+        let oracle = DelegatedPuzzle::Oracle(Bytes32::new([7; 32]), 100);
+        let admin = DelegatedPuzzle::Admin(StandardArgs::curry_tree_hash(admin_key));
+
+        let minted = mint_store(
+            owner_key,
+            vec![coin(puzzle_hash, 1000)],
+            Bytes32::new([2; 32]),
+            Some("label".to_string()),
+            None,
+            None,
+            puzzle_hash,
+            vec![admin, writer, oracle],
+            10,
+        )?;
+        let intent = decode_spend_intent(&minted.coin_spends)?;
+        assert_eq!(intent.fee, 10);
+        assert_eq!(
+            find_store_action(&intent),
+            &SpendAction::LaunchStore(minted.new_datastore.info.clone())
+        );
+        let payment = intent
+            .spends
+            .iter()
+            .find_map(|spend| match &spend.action {
+                SpendAction::Payment(payment) => Some(payment),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(payment.synthetic_key, owner_key);
+        assert_eq!(payment.change, 989);
+        let store = minted.new_datastore;
+
+        // a writer can only update the metadata
+        let updated = update_store_metadata(
+            store.clone(),
+            Bytes32::new([3; 32]),
+            Some("new label".to_string()),
+            None,
+            None,
+            DataStoreInnerSpend::Writer(writer_key),
+        )?;
+        let SpendAction::UpdateStore(change) =
+            find_store_action(&decode_spend_intent(&updated.coin_spends)?).clone()
+        else {
+            panic!("expected a store update");
+        };
+        assert_eq!(change.kind, StoreSpendKind::Writer);
+        assert_eq!(change.delegated_puzzle, Some(writer));
+        assert_eq!(change.previous_metadata, store.info.metadata);
+        assert_eq!(change.metadata, updated.new_datastore.info.metadata);
+        assert!(change.metadata_changed());
+        assert!(!change.ownership_changed());
+
+        let updated = update_store_metadata(
+            store.clone(),
+            Bytes32::new([3; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Admin(admin_key),
+        )?;
+        let SpendAction::UpdateStore(change) =
+            find_store_action(&decode_spend_intent(&updated.coin_spends)?).clone()
+        else {
+            panic!("expected a store update");
+        };
+        assert_eq!(change.kind, StoreSpendKind::Admin);
+        assert_eq!(change.delegated_puzzle, Some(admin));
+
+        let new_owner_puzzle_hash = Bytes32::new([4; 32]);
+        let transferred = update_store_ownership(
+            store.clone(),
+            new_owner_puzzle_hash,
+            vec![oracle],
+            DataStoreInnerSpend::Owner(owner_key),
+        )?;
+        let SpendAction::UpdateStore(change) =
+            find_store_action(&decode_spend_intent(&transferred.coin_spends)?).clone()
+        else {
+            panic!("expected a store update");
+        };
+        assert_eq!(change.kind, StoreSpendKind::Owner);
+        assert_eq!(change.previous_owner_puzzle_hash, puzzle_hash);
+        assert_eq!(change.owner_puzzle_hash, new_owner_puzzle_hash);
+        assert_eq!(change.delegated_puzzles, Some(vec![oracle]));
+        assert!(!change.metadata_changed());
+
+        let paid = oracle_spend(owner_key, vec![coin(puzzle_hash, 1000)], store.clone(), 20)?;
+        let intent = decode_spend_intent(&paid.coin_spends)?;
+        assert_eq!(
+            find_store_action(&intent),
+            &SpendAction::PayOracle {
+                launcher_id: store.info.launcher_id,
+                oracle_puzzle_hash: Bytes32::new([7; 32]),
+                oracle_fee: 100,
+            }
+        );
+        assert_eq!(intent.fee, 20);
+
+        let melted = melt_store(store.clone(), owner_key)?;
+        assert_eq!(
+            find_store_action(&decode_spend_intent(&melted)?),
+            &SpendAction::MeltStore {
+                launcher_id: store.info.launcher_id,
+                owner_puzzle_hash: puzzle_hash,
+            }
+        );
+
+        let (coin_spends, _) = create_server_coin(
+            owner_key,
+            vec![coin(puzzle_hash, 1000)],
+            store.info.launcher_id,
+            vec!["https://example.com".to_string()],
+            300,
+            5,
+        )?;
+        let intent = decode_spend_intent(&coin_spends)?;
+        let SpendAction::Payment(payment) = &intent.spends[0].action else {
+            panic!("expected a payment");
+        };
+        assert_eq!(
+            payment.server_coins,
+            vec![ServerCoinOutput {
+                hint: store.info.launcher_id,
+                amount: 300,
+                memo_urls: vec!["https://example.com".to_string()],
+            }]
+        );
+        assert!(payment.outputs.is_empty());
+        assert_eq!(payment.change, 695);
+        assert_eq!(intent.fee, 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_unknown_delegated_puzzle() -> anyhow::Result<()> {
+        let owner_key = SecretKey::from_seed(&[10; 32])
+            .public_key()
+            .derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(owner_key).into();
+
+        // a delegated puzzle that isn't a standard, writer or oracle puzzle
+        let mut ctx = SpendContext::new();
+        let delegated_puzzle = ctx.allocator.one();
+        let delegated_puzzle_hash = ctx.tree_hash(delegated_puzzle);
+
+        let minted = mint_store(
+            owner_key,
+            vec![coin(puzzle_hash, 1000)],
+            Bytes32::new([2; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![
+                DelegatedPuzzle::Admin(delegated_puzzle_hash),
+                DelegatedPuzzle::Oracle(Bytes32::new([7; 32]), 100),
+            ],
+            0,
+        )?;
+        let coin_spend = minted
+            .new_datastore
+            .spend(&mut ctx, Spend::new(delegated_puzzle, NodePtr::NIL))?;
+
+        assert_eq!(
+            decode_spend_intent(&[coin_spend])?.spends[0].action,
+            SpendAction::Unknown
+        );
+
+        Ok(())
+    }
+}
@@ -1,4 +1,9 @@
+use chia_wallet_sdk::{AddressError, ClientError, CoinSelectionError, SignerError};
 use napi::bindgen_prelude::*;
+use serde_json::{Map, Value};
+
+use crate::conversions::ConversionError;
+use crate::wallet::WalletError;
 
 #[napi(object)]
 #[derive(Clone)]
@@ -86,9 +91,268 @@ pub struct ServerCoin {
     pub memo_urls: Vec<String>,
 }
 
+#[napi(object)]
+#[derive(Clone)]
+/// Describes a Chia network. Use `getMainnetNetwork` or `getTestnet11Network` for the public networks.
+///
+/// @property {string} networkId - Network id sent in the peer handshake (e.g., 'mainnet', 'testnet11' or 'simulator0').
+/// @property {Buffer} genesisChallenge - Genesis challenge of the network.
+/// @property {Option<Buffer>} aggSigMe - AGG_SIG_ME additional data. Defaults to the genesis challenge.
+/// @property {string} addressPrefix - Address prefix (e.g., 'xch').
+/// @property {number} defaultPort - Default full node port.
+/// @property {Option<Array<string>>} dnsIntroducers - Host names of the DNS introducers used for peer discovery.
+/// @property {Option<Array<string>>} introducers - Host names of the introducer nodes used for peer discovery.
+pub struct Network {
+    pub network_id: String,
+    pub genesis_challenge: Buffer,
+    pub agg_sig_me: Option<Buffer>,
+    pub address_prefix: String,
+    pub default_port: u16,
+    pub dns_introducers: Option<Vec<String>>,
+    pub introducers: Option<Vec<String>>,
+}
+
+/// An error that can be thrown to JS. It's sent as a JSON object holding a stable `code`, the
+/// `message` and the error's structured fields, which `index.js` turns into a typed error.
+pub trait ErrorInfo: ToString {
+    fn code(&self) -> &'static str;
+
+    fn fields(&self) -> Map<String, Value> {
+        Map::new()
+    }
+}
+
 pub fn err<T>(error: T) -> napi::Error
 where
-    T: ToString,
+    T: ErrorInfo,
 {
-    napi::Error::from_reason(error.to_string())
+    let mut info = error.fields();
+    info.insert("code".to_string(), error.code().into());
+    info.insert("message".to_string(), error.to_string().into());
+    napi::Error::from_reason(Value::Object(info).to_string())
+}
+
+/// Names the argument a conversion failed on, so that JS callers can tell which one was wrong.
+pub trait ArgContext<T> {
+    fn arg(self, name: &str) -> Result<T>;
+}
+
+impl<T> ArgContext<T> for Result<T> {
+    fn arg(self, name: &str) -> Result<T> {
+        self.map_err(|mut error| {
+            if let Ok(Value::Object(mut info)) = serde_json::from_str(&error.reason) {
+                // Arguments nested in objects are named by their path, e.g. `store.launcherId`.
+                let argument = match info.get("argument").and_then(Value::as_str) {
+                    Some(inner) => format!("{name}.{inner}"),
+                    None => name.to_string(),
+                };
+                info.insert("argument".to_string(), argument.into());
+                error.reason = Value::Object(info).to_string();
+            }
+            error
+        })
+    }
+}
+
+impl ErrorInfo for &str {
+    fn code(&self) -> &'static str {
+        "GENERIC_FAILURE"
+    }
+}
+
+impl ErrorInfo for String {
+    fn code(&self) -> &'static str {
+        "GENERIC_FAILURE"
+    }
+}
+
+impl ErrorInfo for ConversionError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::DifferentLength { .. } => "INVALID_LENGTH",
+            Self::InvalidPublicKey => "INVALID_PUBLIC_KEY",
+            Self::InvalidPrivateKey => "INVALID_PRIVATE_KEY",
+            Self::InvalidSignature => "INVALID_SIGNATURE",
+            Self::MissingProof => "MISSING_PROOF",
+            Self::MissingDelegatedPuzzleInfo => "MISSING_DELEGATED_PUZZLE_INFO",
+            Self::InvalidUri(_) => "INVALID_URI",
+            Self::OutOfRange(_) => "OUT_OF_RANGE",
+            Self::InvalidArgument(_) => "INVALID_ARGUMENT",
+        }
+    }
+
+    fn fields(&self) -> Map<String, Value> {
+        let mut fields = Map::new();
+
+        match self {
+            Self::DifferentLength { expected, actual } => {
+                fields.insert("expectedLength".to_string(), (*expected).into());
+                fields.insert("actualLength".to_string(), (*actual).into());
+            }
+            Self::InvalidUri(uri) => {
+                fields.insert("uri".to_string(), uri.as_str().into());
+            }
+            _ => {}
+        }
+
+        fields
+    }
+}
+
+impl ErrorInfo for ClientError {
+    fn code(&self) -> &'static str {
+        "CLIENT_ERROR"
+    }
+}
+
+impl ErrorInfo for AddressError {
+    fn code(&self) -> &'static str {
+        "INVALID_ADDRESS"
+    }
+}
+
+impl ErrorInfo for SignerError {
+    fn code(&self) -> &'static str {
+        "SIGNER_ERROR"
+    }
+}
+
+impl ErrorInfo for CoinSelectionError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::NoSpendableCoins => "NO_SPENDABLE_COINS",
+            Self::InsufficientBalance(_) => "INSUFFICIENT_BALANCE",
+            Self::ExceededMaxCoins => "EXCEEDED_MAX_COINS",
+        }
+    }
+
+    fn fields(&self) -> Map<String, Value> {
+        let mut fields = Map::new();
+
+        if let Self::InsufficientBalance(amount) = self {
+            // Amounts can exceed the integers JS numbers hold exactly.
+            fields.insert("amount".to_string(), amount.to_string().into());
+        }
+
+        fields
+    }
+}
+
+impl ErrorInfo for WalletError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Client(error) => error.code(),
+            Self::RejectPuzzleState(_) => "REJECT_PUZZLE_STATE",
+            Self::RejectCoinState(_) => "REJECT_COIN_STATE",
+            Self::RejectPuzzleSolution(_) => "REJECT_PUZZLE_SOLUTION",
+            Self::RejectHeaderRequest(_) => "REJECT_HEADER_REQUEST",
+            Self::Driver(_) => "DRIVER_ERROR",
+            Self::Parse => "PARSE_ERROR",
+            Self::FromClvm(_) => "FROM_CLVM_ERROR",
+            Self::ToClvm(_) => "TO_CLVM_ERROR",
+            Self::UnknownCoin(_) => "UNKNOWN_COIN",
+            Self::Clvm(_) => "CLVM_ERROR",
+            Self::Permission => "PERMISSION_DENIED",
+            Self::Io(_) => "IO_ERROR",
+            Self::Validation(_) => "VALIDATION_ERROR",
+            Self::FeeEstimateRejection(_) => "FEE_ESTIMATE_REJECTED",
+            Self::NoPeers => "NO_PEERS",
+            Self::Disconnected => "DISCONNECTED",
+            Self::MissingTimestamp(_) => "MISSING_TIMESTAMP",
+            Self::Cache(_) => "CACHE_ERROR",
+            Self::InvalidDelta => "INVALID_DELTA",
+            Self::CoinSelection(error) => error.code(),
+            Self::Signer(error) => error.code(),
+            Self::MeltedStore(_) => "MELTED_STORE",
+            Self::FeeNotConverged(_) => "FEE_NOT_CONVERGED",
+            Self::TransactionRejected { .. } => "TRANSACTION_REJECTED",
+            Self::MissingSignatures(_) => "MISSING_SIGNATURES",
+            Self::SignatureMismatch(_) => "SIGNATURE_MISMATCH",
+            Self::InvalidUnsignedTransaction(_) => "INVALID_UNSIGNED_TRANSACTION",
+            Self::SignerFailed(_) => "SIGNER_FAILED",
+            Self::InvalidSpendBundle(_) => "INVALID_SPEND_BUNDLE",
+            Self::CoinAlreadySpent { .. } => "COIN_ALREADY_SPENT",
+        }
+    }
+
+    fn fields(&self) -> Map<String, Value> {
+        let mut fields = Map::new();
+
+        match self {
+            Self::RejectPuzzleState(rejection) => {
+                fields.insert(
+                    "reason".to_string(),
+                    format!("{:?}", rejection.reason).into(),
+                );
+            }
+            Self::RejectCoinState(rejection) => {
+                fields.insert(
+                    "reason".to_string(),
+                    format!("{:?}", rejection.reason).into(),
+                );
+            }
+            Self::RejectPuzzleSolution(rejection) => {
+                fields.insert(
+                    "coinId".to_string(),
+                    hex::encode(rejection.coin_name).into(),
+                );
+                fields.insert("height".to_string(), rejection.height.into());
+            }
+            Self::RejectHeaderRequest(rejection) => {
+                fields.insert("height".to_string(), rejection.height.into());
+            }
+            Self::UnknownCoin(coin_id) => {
+                fields.insert("coinId".to_string(), hex::encode(coin_id).into());
+            }
+            Self::Validation(error) => {
+                fields.insert(
+                    "validationError".to_string(),
+                    format!("{:?}", error.1).into(),
+                );
+            }
+            Self::FeeEstimateRejection(reason) => {
+                fields.insert("reason".to_string(), reason.as_str().into());
+            }
+            Self::MissingTimestamp(height) => {
+                fields.insert("height".to_string(), (*height).into());
+            }
+            Self::CoinSelection(error) => return error.fields(),
+            Self::MeltedStore(launcher_id) => {
+                fields.insert("launcherId".to_string(), hex::encode(launcher_id).into());
+            }
+            Self::FeeNotConverged(attempts) => {
+                fields.insert("attempts".to_string(), (*attempts).into());
+            }
+            Self::TransactionRejected { status, error } => {
+                fields.insert("status".to_string(), (*status).into());
+                fields.insert("mempoolError".to_string(), error.as_str().into());
+            }
+            Self::MissingSignatures(public_keys) => {
+                let public_keys = public_keys
+                    .iter()
+                    .map(|public_key| hex::encode(public_key.to_bytes()).into())
+                    .collect();
+                fields.insert("publicKeys".to_string(), Value::Array(public_keys));
+            }
+            Self::SignatureMismatch(public_key) => {
+                fields.insert(
+                    "publicKey".to_string(),
+                    hex::encode(public_key.to_bytes()).into(),
+                );
+            }
+            Self::InvalidUnsignedTransaction(reason) | Self::SignerFailed(reason) => {
+                fields.insert("reason".to_string(), reason.as_str().into());
+            }
+            Self::InvalidSpendBundle(error) => {
+                fields.insert("validationError".to_string(), format!("{error:?}").into());
+            }
+            Self::CoinAlreadySpent { coin_id, height } => {
+                fields.insert("coinId".to_string(), hex::encode(coin_id).into());
+                fields.insert("height".to_string(), (*height).into());
+            }
+            _ => {}
+        }
+
+        fields
+    }
 }
@@ -0,0 +1,143 @@
+use chia::consensus::consensus_constants::ConsensusConstants;
+use chia::protocol::Bytes32;
+use chia_wallet_sdk::{Network, NetworkId, MAINNET_CONSTANTS, TESTNET11_CONSTANTS};
+use clvmr::sha2::Sha256;
+
+/// Describes the network a wallet talks to and signs for.
+///
+/// Consensus constants other than the genesis challenge and the `AGG_SIG_*` additional data
+/// are taken from testnet11 for testnet11 and from mainnet for every other network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetNetwork {
+    pub network_id: NetworkId,
+    pub genesis_challenge: Bytes32,
+    /// `AGG_SIG_ME` additional data. The other `AGG_SIG_*` values are derived from it.
+    pub agg_sig_me: Bytes32,
+    pub address_prefix: String,
+    pub default_port: u16,
+    pub dns_introducers: Vec<String>,
+    /// Host names of introducer nodes, reached on `default_port`.
+    pub introducers: Vec<String>,
+}
+
+impl TargetNetwork {
+    pub fn mainnet() -> Self {
+        let network = Network::default_mainnet();
+
+        Self {
+            network_id: NetworkId::Mainnet,
+            genesis_challenge: network.genesis_challenge,
+            agg_sig_me: network.agg_sig_me.unwrap_or(network.genesis_challenge),
+            address_prefix: "xch".to_string(),
+            default_port: network.default_port,
+            dns_introducers: network.dns_introducers,
+            introducers: vec!["introducer.chia.net".to_string()],
+        }
+    }
+
+    pub fn testnet11() -> Self {
+        let network = Network::default_testnet11();
+
+        Self {
+            network_id: NetworkId::Testnet11,
+            genesis_challenge: network.genesis_challenge,
+            agg_sig_me: network.agg_sig_me.unwrap_or(network.genesis_challenge),
+            address_prefix: "txch".to_string(),
+            default_port: network.default_port,
+            dns_introducers: network.dns_introducers,
+            introducers: vec!["introducer-testnet11.chia.net".to_string()],
+        }
+    }
+
+    /// Mainnet or testnet11, for the APIs that used to take a `testnet` flag.
+    pub fn from_testnet(testnet: bool) -> Self {
+        if testnet {
+            Self::testnet11()
+        } else {
+            Self::mainnet()
+        }
+    }
+
+    /// Parses a network id as used in the peer handshake.
+    pub fn network_id_from_str(network_id: &str) -> NetworkId {
+        match network_id {
+            "mainnet" => NetworkId::Mainnet,
+            "testnet11" => NetworkId::Testnet11,
+            "simulator0" => NetworkId::Simulator0,
+            name => NetworkId::Custom(name.to_string()),
+        }
+    }
+
+    pub fn constants(&self) -> ConsensusConstants {
+        let base = if self.network_id == NetworkId::Testnet11 {
+            &TESTNET11_CONSTANTS
+        } else {
+            &MAINNET_CONSTANTS
+        };
+
+        ConsensusConstants {
+            genesis_challenge: self.genesis_challenge,
+            agg_sig_me_additional_data: self.agg_sig_me,
+            agg_sig_parent_additional_data: agg_sig_data(self.agg_sig_me, 43),
+            agg_sig_puzzle_additional_data: agg_sig_data(self.agg_sig_me, 44),
+            agg_sig_amount_additional_data: agg_sig_data(self.agg_sig_me, 45),
+            agg_sig_puzzle_amount_additional_data: agg_sig_data(self.agg_sig_me, 46),
+            agg_sig_parent_amount_additional_data: agg_sig_data(self.agg_sig_me, 47),
+            agg_sig_parent_puzzle_additional_data: agg_sig_data(self.agg_sig_me, 48),
+            ..(*base).clone()
+        }
+    }
+
+    /// The network in the form used by the SDK's DNS lookups.
+    pub fn network(&self) -> Network {
+        Network {
+            default_port: self.default_port,
+            genesis_challenge: self.genesis_challenge,
+            agg_sig_me: Some(self.agg_sig_me),
+            dns_introducers: self.dns_introducers.clone(),
+        }
+    }
+}
+
+// By convention, the additional data of the other `AGG_SIG_*` conditions is the hash of
+// the `AGG_SIG_ME` additional data and the condition's opcode.
+fn agg_sig_data(agg_sig_me: Bytes32, opcode: u8) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update(agg_sig_me);
+    hasher.update([opcode]);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_network_constants() {
+        assert_eq!(TargetNetwork::mainnet().constants(), *MAINNET_CONSTANTS);
+        assert_eq!(TargetNetwork::testnet11().constants(), *TESTNET11_CONSTANTS);
+    }
+
+    #[test]
+    fn test_custom_network_constants() {
+        let network = TargetNetwork {
+            network_id: TargetNetwork::network_id_from_str("private"),
+            genesis_challenge: Bytes32::new([1; 32]),
+            agg_sig_me: Bytes32::new([2; 32]),
+            ..TargetNetwork::mainnet()
+        };
+        let constants = network.constants();
+
+        assert_eq!(network.network_id, NetworkId::Custom("private".to_string()));
+        assert_eq!(constants.genesis_challenge, Bytes32::new([1; 32]));
+        assert_eq!(constants.agg_sig_me_additional_data, Bytes32::new([2; 32]));
+        assert_eq!(
+            constants.agg_sig_parent_additional_data,
+            agg_sig_data(Bytes32::new([2; 32]), 43)
+        );
+        assert_eq!(
+            constants.max_block_cost_clvm,
+            MAINNET_CONSTANTS.max_block_cost_clvm
+        );
+    }
+}
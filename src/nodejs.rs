@@ -0,0 +1,4237 @@
+use chia::bls::{
+    master_to_wallet_unhardened, PublicKey as RustPublicKey, SecretKey as RustSecretKey,
+    Signature as RustSignature,
+};
+
+use crate::batch_sync::{sync_stores, StoreSyncTarget, DEFAULT_SYNC_CONCURRENCY};
+use crate::connection::{ReconnectingPeer, UpdateStream};
+use crate::conversions::{ConversionError, FromJs, ToJs};
+use crate::data_tree::{
+    DataTree as RustDataTree, DataTreeChange as RustDataTreeChange,
+    ExclusionProof as RustExclusionProof, InclusionProof as RustInclusionProof,
+    TreeDelta as RustTreeDelta,
+};
+use crate::discovery::{Discovery, DISCOVERY_TIMEOUT};
+use crate::fees::{
+    FeeBump as RustFeeBump, FeePolicy as RustFeePolicy, PricedResponse as RustPricedResponse,
+};
+use crate::intent::{DecodedSpend as RustDecodedSpend, SpendAction as RustSpendAction};
+use crate::js::{ArgContext, Coin, CoinSpend, CoinState, EveProof, Network, Proof, ServerCoin};
+use crate::network::TargetNetwork;
+use crate::peer_pool::PeerPool as RustPeerPool;
+use crate::signer::{sign_with_signer, Signer};
+use crate::simulator::ChainSimulator;
+use crate::spend_tracker::{
+    SpendStatus as RustSpendStatus, SpendTracker as RustSpendTracker, DEFAULT_CONFIRMATIONS,
+    DEFAULT_EXPIRY_BLOCKS,
+};
+use crate::store_cache::{sync_cached_store, StoreCache as RustStoreCache};
+use crate::transaction::{
+    Transaction as RustTransaction, TransactionBuilder as RustTransactionBuilder,
+};
+use crate::unsigned::UnsignedTransaction as RustUnsignedTransaction;
+use crate::wallet::{
+    get_header_hash, get_unspent_coin_states, is_coin_spent, sync_store,
+    sync_store_using_launcher_id, DataStoreInnerSpend,
+    PossibleLaunchersResponse as RustPossibleLaunchersResponse,
+    StoreHistoryEntry as RustStoreHistoryEntry, StoreReorg as RustStoreReorg,
+    StoreSpendKind as RustStoreSpendKind, StoreUpdate as RustStoreUpdate,
+    SuccessResponse as RustSuccessResponse, SyncStoreResponse as RustSyncStoreResponse,
+    WalletError,
+};
+use crate::wallet_protocol::PeerBackend;
+use crate::{fees, intent, js, rust, server_coin, wallet};
+use chia::consensus::gen::owned_conditions::OwnedSpendBundleConditions;
+use chia::protocol::{
+    Bytes as RustBytes, Bytes32 as RustBytes32, Coin as RustCoin, CoinSpend as RustCoinSpend,
+    SpendBundle as RustSpendBundle,
+};
+use chia::puzzles::{standard::StandardArgs, DeriveSynthetic, Proof as RustProof};
+use chia_wallet_sdk::{
+    create_tls_connector, decode_address, encode_address, load_ssl_cert, AddressError,
+    DataStore as RustDataStore, DataStoreInfo as RustDataStoreInfo,
+    DataStoreMetadata as RustDataStoreMetadata, DelegatedPuzzle as RustDelegatedPuzzle,
+    MAINNET_CONSTANTS, TESTNET11_CONSTANTS,
+};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction};
+use napi::{JsFunction, JsObject, Result};
+use native_tls::TlsConnector;
+use std::{net::SocketAddr, sync::Arc};
+use tokio::sync::Mutex;
+use tokio::task::AbortHandle;
+
+#[napi]
+/// Creates a new lineage proof.
+///
+/// @param {LineageProof} lineageProof - The lineage proof.
+/// @returns {Proof} The new proof.
+pub fn new_lineage_proof(lineage_proof: js::LineageProof) -> js::Proof {
+    js::Proof {
+        lineage_proof: Some(lineage_proof),
+        eve_proof: None,
+    }
+}
+
+#[napi]
+/// Creates a new eve proof.
+///
+/// @param {EveProof} eveProof - The eve proof.
+/// @returns {Proof} The new proof.
+pub fn new_eve_proof(eve_proof: EveProof) -> Proof {
+    Proof {
+        lineage_proof: None,
+        eve_proof: Some(eve_proof),
+    }
+}
+
+#[napi(object)]
+#[derive(Clone)]
+/// Represents metadata for a data store.
+///
+/// @property {Buffer} rootHash - Root hash.
+/// @property {Option<String>} label - Label (optional).
+/// @property {Option<String>} description - Description (optional).
+/// @property {Option<BigInt>} bytes - Size of the store in bytes (optional).
+pub struct DataStoreMetadata {
+    pub root_hash: Buffer,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub bytes: Option<BigInt>,
+}
+
+impl FromJs<DataStoreMetadata> for RustDataStoreMetadata {
+    fn from_js(value: DataStoreMetadata) -> Result<Self> {
+        Ok(RustDataStoreMetadata {
+            root_hash: RustBytes32::from_js(value.root_hash).arg("rootHash")?,
+            label: value.label,
+            description: value.description,
+            bytes: if let Some(bytes) = value.bytes {
+                Some(u64::from_js(bytes).arg("bytes")?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl ToJs<DataStoreMetadata> for RustDataStoreMetadata {
+    fn to_js(&self) -> Result<DataStoreMetadata> {
+        Ok(DataStoreMetadata {
+            root_hash: self.root_hash.to_js()?,
+            label: self.label.clone(),
+            description: self.description.clone(),
+            bytes: if let Some(bytes) = self.bytes {
+                Some(bytes.to_js()?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+#[napi(object)]
+#[derive(Clone)]
+/// Represents information about a delegated puzzle. Note that this struct can represent all three types of delegated puzzles, but only represents one at a time.
+///
+/// @property {Option<Buffer>} adminInnerPuzzleHash - Admin inner puzzle hash, if this is an admin delegated puzzle.
+/// @property {Option<Buffer>} writerInnerPuzzleHash - Writer inner puzzle hash, if this is a writer delegated puzzle.
+/// @property {Option<Buffer>} oraclePaymentPuzzleHash - Oracle payment puzzle hash, if this is an oracle delegated puzzle.
+/// @property {Option<BigInt>} oracleFee - Oracle fee, if this is an oracle delegated puzzle.
+pub struct DelegatedPuzzle {
+    pub admin_inner_puzzle_hash: Option<Buffer>,
+    pub writer_inner_puzzle_hash: Option<Buffer>,
+    pub oracle_payment_puzzle_hash: Option<Buffer>,
+    pub oracle_fee: Option<BigInt>,
+}
+
+impl FromJs<DelegatedPuzzle> for RustDelegatedPuzzle {
+    fn from_js(value: DelegatedPuzzle) -> Result<Self> {
+        Ok(
+            if let Some(admin_inner_puzzle_hash) = value.admin_inner_puzzle_hash {
+                RustDelegatedPuzzle::Admin(
+                    RustBytes32::from_js(admin_inner_puzzle_hash)
+                        .arg("adminInnerPuzzleHash")?
+                        .into(),
+                )
+            } else if let Some(writer_inner_puzzle_hash) = value.writer_inner_puzzle_hash {
+                RustDelegatedPuzzle::Writer(
+                    RustBytes32::from_js(writer_inner_puzzle_hash)
+                        .arg("writerInnerPuzzleHash")?
+                        .into(),
+                )
+            } else if let (Some(oracle_payment_puzzle_hash), Some(oracle_fee)) =
+                (value.oracle_payment_puzzle_hash, value.oracle_fee)
+            {
+                RustDelegatedPuzzle::Oracle(
+                    RustBytes32::from_js(oracle_payment_puzzle_hash)
+                        .arg("oraclePaymentPuzzleHash")?,
+                    u64::from_js(oracle_fee).arg("oracleFee")?,
+                )
+            } else {
+                return Err(js::err(ConversionError::MissingDelegatedPuzzleInfo));
+            },
+        )
+    }
+}
+
+impl ToJs<DelegatedPuzzle> for RustDelegatedPuzzle {
+    fn to_js(&self) -> Result<DelegatedPuzzle> {
+        match self {
+            RustDelegatedPuzzle::Admin(admin_inner_puzzle_hash) => {
+                let admin_inner_puzzle_hash: RustBytes32 = (*admin_inner_puzzle_hash).into();
+
+                Ok(DelegatedPuzzle {
+                    admin_inner_puzzle_hash: Some(admin_inner_puzzle_hash.to_js()?),
+                    writer_inner_puzzle_hash: None,
+                    oracle_payment_puzzle_hash: None,
+                    oracle_fee: None,
+                })
+            }
+            RustDelegatedPuzzle::Writer(writer_inner_puzzle_hash) => {
+                let writer_inner_puzzle_hash: RustBytes32 = (*writer_inner_puzzle_hash).into();
+
+                Ok(DelegatedPuzzle {
+                    admin_inner_puzzle_hash: None,
+                    writer_inner_puzzle_hash: Some(writer_inner_puzzle_hash.to_js()?),
+                    oracle_payment_puzzle_hash: None,
+                    oracle_fee: None,
+                })
+            }
+            RustDelegatedPuzzle::Oracle(oracle_payment_puzzle_hash, oracle_fee) => {
+                let oracle_payment_puzzle_hash: RustBytes32 = *oracle_payment_puzzle_hash;
+
+                Ok(DelegatedPuzzle {
+                    admin_inner_puzzle_hash: None,
+                    writer_inner_puzzle_hash: None,
+                    oracle_payment_puzzle_hash: Some(oracle_payment_puzzle_hash.to_js()?),
+                    oracle_fee: Some(oracle_fee.to_js()?),
+                })
+            }
+        }
+    }
+}
+
+#[napi(object)]
+#[derive(Clone)]
+/// Represents information about a data store. This information can be used to spend the store. It is recommended that this struct is stored in a database to avoid syncing it every time.
+///
+/// @property {Coin} coin - The coin associated with the data store.
+/// @property {Buffer} launcherId - The store's launcher/singleton ID.
+/// @property {Proof} proof - Proof that can be used to spend this store.
+/// @property {DataStoreMetadata} metadata - This store's metadata.
+/// @property {Buffer} ownerPuzzleHash - The puzzle hash of the owner puzzle.
+/// @property {Vec<DelegatedPuzzle>} delegatedPuzzles - This store's delegated puzzles. An empty list usually indicates a 'vanilla' store.
+pub struct DataStore {
+    pub coin: Coin,
+    // singleton layer
+    pub launcher_id: Buffer,
+    pub proof: Proof,
+    // NFT state layer
+    pub metadata: DataStoreMetadata,
+    // inner puzzle (either p2 or delegation_layer + p2)
+    pub owner_puzzle_hash: Buffer,
+    pub delegated_puzzles: Vec<DelegatedPuzzle>, // if empty, there is no delegation layer
+}
+
+impl FromJs<DataStore> for RustDataStore {
+    fn from_js(value: DataStore) -> Result<Self> {
+        Ok(RustDataStore {
+            coin: RustCoin::from_js(value.coin).arg("coin")?,
+            proof: RustProof::from_js(value.proof).arg("proof")?,
+
+            info: RustDataStoreInfo {
+                launcher_id: RustBytes32::from_js(value.launcher_id).arg("launcherId")?,
+                metadata: RustDataStoreMetadata::from_js(value.metadata).arg("metadata")?,
+                owner_puzzle_hash: RustBytes32::from_js(value.owner_puzzle_hash)
+                    .arg("ownerPuzzleHash")?,
+                delegated_puzzles: value
+                    .delegated_puzzles
+                    .into_iter()
+                    .map(RustDelegatedPuzzle::from_js)
+                    .collect::<Result<Vec<RustDelegatedPuzzle>>>()
+                    .arg("delegatedPuzzles")?,
+            },
+        })
+    }
+}
+
+impl ToJs<DataStore> for RustDataStore {
+    fn to_js(&self) -> Result<DataStore> {
+        Ok(DataStore {
+            coin: self.coin.to_js()?,
+            proof: self.proof.to_js()?,
+
+            launcher_id: self.info.launcher_id.to_js()?,
+            metadata: self.info.metadata.to_js()?,
+            owner_puzzle_hash: self.info.owner_puzzle_hash.to_js()?,
+            delegated_puzzles: self
+                .info
+                .delegated_puzzles
+                .iter()
+                .map(RustDelegatedPuzzle::to_js)
+                .collect::<Result<Vec<DelegatedPuzzle>>>()?,
+        })
+    }
+}
+
+#[napi(object)]
+// Represents a driver response indicating success.
+///
+/// @property {Vec<CoinSpend>} coinSpends - Coin spends that can be used to spend the provided store.
+/// @property {DataStore} newStore - New data store information after the spend is confirmed.
+pub struct SuccessResponse {
+    pub coin_spends: Vec<CoinSpend>,
+    pub new_store: DataStore,
+}
+
+impl FromJs<SuccessResponse> for RustSuccessResponse {
+    fn from_js(value: SuccessResponse) -> Result<Self> {
+        Ok(RustSuccessResponse {
+            coin_spends: value
+                .coin_spends
+                .into_iter()
+                .map(RustCoinSpend::from_js)
+                .collect::<Result<Vec<RustCoinSpend>>>()
+                .arg("coinSpends")?,
+            new_datastore: RustDataStore::from_js(value.new_store).arg("newStore")?,
+        })
+    }
+}
+
+impl ToJs<SuccessResponse> for RustSuccessResponse {
+    fn to_js(&self) -> Result<SuccessResponse> {
+        Ok(SuccessResponse {
+            coin_spends: self
+                .coin_spends
+                .iter()
+                .map(RustCoinSpend::to_js)
+                .collect::<Result<Vec<CoinSpend>>>()?,
+            new_store: self.new_datastore.to_js()?,
+        })
+    }
+}
+
+#[napi(object)]
+/// Represents a response from synchronizing a store.
+///
+/// @property {DataStore} latestStore - Latest data store information.
+/// @property {Option<Vec<Buffer>>} rootHashes - When synced with whistory, this list will contain all of the store's previous root hashes. Otherwise null.
+/// @property {Option<Vec<BigInt>>} rootHashesTimestamps - Timestamps of the root hashes (see `rootHashes`).
+/// @property {Option<Vec<StoreHistoryEntry>>} history - The spends that set the root hashes (see `rootHashes`), one entry per root hash.
+/// @property {u32} latestHeight - Latest sync height.
+/// @property {Option<StoreReorg>} reorg - Set if the block sync started from is no longer part of the chain. In that case, `rootHashes` and `history` start where `knownHistory` started, or at the store's launch.
+pub struct SyncStoreResponse {
+    pub latest_store: DataStore,
+    pub root_hashes: Option<Vec<Buffer>>,
+    pub root_hashes_timestamps: Option<Vec<BigInt>>,
+    pub history: Option<Vec<StoreHistoryEntry>>,
+    pub latest_height: u32,
+    pub reorg: Option<StoreReorg>,
+}
+
+#[napi(string_enum)]
+/// What authorized a store spend.
+pub enum StoreSpendKind {
+    Launch,
+    Owner,
+    Admin,
+    Writer,
+    Oracle,
+}
+
+impl FromJs<StoreSpendKind> for RustStoreSpendKind {
+    fn from_js(value: StoreSpendKind) -> Result<Self> {
+        Ok(match value {
+            StoreSpendKind::Launch => RustStoreSpendKind::Launch,
+            StoreSpendKind::Owner => RustStoreSpendKind::Owner,
+            StoreSpendKind::Admin => RustStoreSpendKind::Admin,
+            StoreSpendKind::Writer => RustStoreSpendKind::Writer,
+            StoreSpendKind::Oracle => RustStoreSpendKind::Oracle,
+        })
+    }
+}
+
+impl ToJs<StoreSpendKind> for RustStoreSpendKind {
+    fn to_js(&self) -> Result<StoreSpendKind> {
+        Ok(match self {
+            RustStoreSpendKind::Launch => StoreSpendKind::Launch,
+            RustStoreSpendKind::Owner => StoreSpendKind::Owner,
+            RustStoreSpendKind::Admin => StoreSpendKind::Admin,
+            RustStoreSpendKind::Writer => StoreSpendKind::Writer,
+            RustStoreSpendKind::Oracle => StoreSpendKind::Oracle,
+        })
+    }
+}
+
+#[napi(object)]
+/// Represents a confirmed spend of a store.
+///
+/// @property {StoreSpendKind} kind - What authorized the spend.
+/// @property {Option<DelegatedPuzzle>} delegatedPuzzle - The delegated puzzle that authorized the spend. Null for launches and owner spends.
+/// @property {Buffer} coinId - ID of the spent coin (the launcher or the store's previous coin).
+/// @property {u32} height - Height of the block the spend was confirmed in.
+/// @property {Buffer} headerHash - Header hash of that block.
+/// @property {BigInt} timestamp - Timestamp of that block.
+/// @property {Option<DataStoreMetadata>} previousMetadata - Metadata before the spend. Null for launches.
+/// @property {DataStoreMetadata} metadata - Metadata after the spend.
+/// @property {Option<Buffer>} previousOwnerPuzzleHash - Owner puzzle hash before the spend. Null for launches.
+/// @property {Buffer} ownerPuzzleHash - Owner puzzle hash after the spend.
+/// @property {Option<Vec<DelegatedPuzzle>>} previousDelegatedPuzzles - Delegated puzzles before the spend. Null for launches.
+/// @property {Vec<DelegatedPuzzle>} delegatedPuzzles - Delegated puzzles after the spend.
+pub struct StoreHistoryEntry {
+    pub kind: StoreSpendKind,
+    pub delegated_puzzle: Option<DelegatedPuzzle>,
+    pub coin_id: Buffer,
+    pub height: u32,
+    pub header_hash: Buffer,
+    pub timestamp: BigInt,
+    pub previous_metadata: Option<DataStoreMetadata>,
+    pub metadata: DataStoreMetadata,
+    pub previous_owner_puzzle_hash: Option<Buffer>,
+    pub owner_puzzle_hash: Buffer,
+    pub previous_delegated_puzzles: Option<Vec<DelegatedPuzzle>>,
+    pub delegated_puzzles: Vec<DelegatedPuzzle>,
+}
+
+// Entries don't repeat the store's launcher id, so it's passed separately.
+fn store_history_entry_from_js(
+    value: StoreHistoryEntry,
+    launcher_id: RustBytes32,
+) -> Result<RustStoreHistoryEntry> {
+    let delegated_puzzles = |delegated_puzzles: Vec<DelegatedPuzzle>| {
+        delegated_puzzles
+            .into_iter()
+            .map(RustDelegatedPuzzle::from_js)
+            .collect::<Result<Vec<RustDelegatedPuzzle>>>()
+    };
+
+    let previous_info = match (
+        value.previous_metadata,
+        value.previous_owner_puzzle_hash,
+        value.previous_delegated_puzzles,
+    ) {
+        (Some(metadata), Some(owner_puzzle_hash), Some(previous_delegated_puzzles)) => {
+            Some(RustDataStoreInfo {
+                launcher_id,
+                metadata: RustDataStoreMetadata::from_js(metadata).arg("previousMetadata")?,
+                owner_puzzle_hash: RustBytes32::from_js(owner_puzzle_hash)
+                    .arg("previousOwnerPuzzleHash")?,
+                delegated_puzzles: delegated_puzzles(previous_delegated_puzzles)
+                    .arg("previousDelegatedPuzzles")?,
+            })
+        }
+        _ => None,
+    };
+
+    Ok(RustStoreHistoryEntry {
+        kind: RustStoreSpendKind::from_js(value.kind).arg("kind")?,
+        delegated_puzzle: value
+            .delegated_puzzle
+            .map(RustDelegatedPuzzle::from_js)
+            .transpose()
+            .arg("delegatedPuzzle")?,
+        coin_id: RustBytes32::from_js(value.coin_id).arg("coinId")?,
+        height: value.height,
+        header_hash: RustBytes32::from_js(value.header_hash).arg("headerHash")?,
+        timestamp: u64::from_js(value.timestamp).arg("timestamp")?,
+        previous_info,
+        info: RustDataStoreInfo {
+            launcher_id,
+            metadata: RustDataStoreMetadata::from_js(value.metadata).arg("metadata")?,
+            owner_puzzle_hash: RustBytes32::from_js(value.owner_puzzle_hash)
+                .arg("ownerPuzzleHash")?,
+            delegated_puzzles: delegated_puzzles(value.delegated_puzzles)
+                .arg("delegatedPuzzles")?,
+        },
+    })
+}
+
+impl ToJs<StoreHistoryEntry> for RustStoreHistoryEntry {
+    fn to_js(&self) -> Result<StoreHistoryEntry> {
+        let delegated_puzzles = |delegated_puzzles: &[RustDelegatedPuzzle]| {
+            delegated_puzzles
+                .iter()
+                .map(RustDelegatedPuzzle::to_js)
+                .collect::<Result<Vec<DelegatedPuzzle>>>()
+        };
+
+        Ok(StoreHistoryEntry {
+            kind: self.kind.to_js()?,
+            delegated_puzzle: self
+                .delegated_puzzle
+                .as_ref()
+                .map(RustDelegatedPuzzle::to_js)
+                .transpose()?,
+            coin_id: self.coin_id.to_js()?,
+            height: self.height,
+            header_hash: self.header_hash.to_js()?,
+            timestamp: self.timestamp.to_js()?,
+            previous_metadata: self
+                .previous_info
+                .as_ref()
+                .map(|info| info.metadata.to_js())
+                .transpose()?,
+            metadata: self.info.metadata.to_js()?,
+            previous_owner_puzzle_hash: self
+                .previous_info
+                .as_ref()
+                .map(|info| info.owner_puzzle_hash.to_js())
+                .transpose()?,
+            owner_puzzle_hash: self.info.owner_puzzle_hash.to_js()?,
+            previous_delegated_puzzles: self
+                .previous_info
+                .as_ref()
+                .map(|info| delegated_puzzles(&info.delegated_puzzles))
+                .transpose()?,
+            delegated_puzzles: delegated_puzzles(&self.info.delegated_puzzles)?,
+        })
+    }
+}
+
+#[napi(object)]
+/// Represents a reorg detected while synchronizing a store. The store is synced again from the newest state of `knownHistory` that is still part of the chain.
+///
+/// @property {Option<u32>} forkHeight - Height of the newest known state that is still part of the chain. Null if there is none and the store was synced again from its launcher.
+/// @property {Vec<Buffer>} orphanedRootHashes - Root hashes of `knownHistory` that are no longer part of the chain, oldest first. Spends that were included again on the new chain aren't listed.
+pub struct StoreReorg {
+    pub fork_height: Option<u32>,
+    pub orphaned_root_hashes: Vec<Buffer>,
+}
+
+impl FromJs<SyncStoreResponse> for RustSyncStoreResponse {
+    fn from_js(value: SyncStoreResponse) -> Result<Self> {
+        let mut root_hash_history = None;
+
+        if let (Some(root_hashes), Some(root_hashes_timestamps)) =
+            (value.root_hashes, value.root_hashes_timestamps)
+        {
+            let mut v = vec![];
+
+            for (root_hash, timestamp) in root_hashes
+                .into_iter()
+                .zip(root_hashes_timestamps.into_iter())
+            {
+                v.push((
+                    RustBytes32::from_js(root_hash).arg("rootHash")?,
+                    u64::from_js(timestamp).arg("timestamp")?,
+                ));
+            }
+
+            root_hash_history = Some(v);
+        }
+
+        let reorg = value
+            .reorg
+            .map(|reorg| -> Result<RustStoreReorg> {
+                Ok(RustStoreReorg {
+                    fork_height: reorg.fork_height,
+                    orphaned_root_hashes: reorg
+                        .orphaned_root_hashes
+                        .into_iter()
+                        .map(RustBytes32::from_js)
+                        .collect::<Result<Vec<RustBytes32>>>()
+                        .arg("orphanedRootHashes")?,
+                })
+            })
+            .transpose()?;
+
+        let latest_store = RustDataStore::from_js(value.latest_store).arg("latestStore")?;
+
+        let history = value
+            .history
+            .map(|history| {
+                history
+                    .into_iter()
+                    .map(|entry| store_history_entry_from_js(entry, latest_store.info.launcher_id))
+                    .collect::<Result<Vec<RustStoreHistoryEntry>>>()
+            })
+            .transpose()?;
+
+        Ok(RustSyncStoreResponse {
+            latest_store,
+            latest_height: value.latest_height,
+            root_hash_history,
+            history,
+            reorg,
+        })
+    }
+}
+impl ToJs<SyncStoreResponse> for RustSyncStoreResponse {
+    fn to_js(&self) -> Result<SyncStoreResponse> {
+        let root_hashes = self
+            .root_hash_history
+            .as_ref()
+            .map(|v| {
+                v.iter()
+                    .map(|(rh, _)| rh.to_js())
+                    .collect::<Result<Vec<Buffer>>>()
+            })
+            .transpose()?;
+
+        let root_hashes_timestamps = self
+            .root_hash_history
+            .as_ref()
+            .map(|v| {
+                v.iter()
+                    .map(|(_, ts)| ts.to_js())
+                    .collect::<Result<Vec<BigInt>>>()
+            })
+            .transpose()?;
+
+        let reorg = self
+            .reorg
+            .as_ref()
+            .map(|reorg| -> Result<StoreReorg> {
+                Ok(StoreReorg {
+                    fork_height: reorg.fork_height,
+                    orphaned_root_hashes: reorg
+                        .orphaned_root_hashes
+                        .iter()
+                        .map(RustBytes32::to_js)
+                        .collect::<Result<Vec<Buffer>>>()?,
+                })
+            })
+            .transpose()?;
+
+        let history = self
+            .history
+            .as_ref()
+            .map(|history| {
+                history
+                    .iter()
+                    .map(RustStoreHistoryEntry::to_js)
+                    .collect::<Result<Vec<StoreHistoryEntry>>>()
+            })
+            .transpose()?;
+
+        Ok(SyncStoreResponse {
+            latest_store: self.latest_store.to_js()?,
+            latest_height: self.latest_height,
+            root_hashes,
+            root_hashes_timestamps,
+            history,
+            reorg,
+        })
+    }
+}
+
+fn known_history_from_js(
+    known_history: Option<Vec<StoreHistoryEntry>>,
+    launcher_id: RustBytes32,
+) -> Result<Vec<RustStoreHistoryEntry>> {
+    known_history
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| store_history_entry_from_js(entry, launcher_id))
+        .collect::<Result<Vec<RustStoreHistoryEntry>>>()
+        .arg("knownHistory")
+}
+
+#[napi(object)]
+/// Represents the result of synchronizing one store of a batch.
+///
+/// @property {Buffer} launcherId - The store's launcher/singleton ID.
+/// @property {Option<SyncStoreResponse>} response - The sync store response. Null if the store couldn't be synced.
+/// @property {Option<String>} error - Why the store couldn't be synced. Null if it was synced.
+pub struct BatchSyncResult {
+    pub launcher_id: Buffer,
+    pub response: Option<SyncStoreResponse>,
+    pub error: Option<String>,
+}
+
+// Pairs the results of a batch sync with the launcher ids of the stores they belong to.
+fn batch_sync_results_to_js(
+    launcher_ids: Vec<RustBytes32>,
+    results: Vec<std::result::Result<RustSyncStoreResponse, WalletError>>,
+) -> Result<Vec<BatchSyncResult>> {
+    launcher_ids
+        .into_iter()
+        .zip(results)
+        .map(|(launcher_id, result)| {
+            Ok(match result {
+                Ok(response) => BatchSyncResult {
+                    launcher_id: launcher_id.to_js()?,
+                    response: Some(response.to_js()?),
+                    error: None,
+                },
+                Err(error) => BatchSyncResult {
+                    launcher_id: launcher_id.to_js()?,
+                    response: None,
+                    error: Some(error.to_string()),
+                },
+            })
+        })
+        .collect()
+}
+
+#[napi(object)]
+/// Represents a new state of a watched store.
+///
+/// @property {DataStore} store - The store's new state.
+/// @property {u32} height - Height of the block the state was confirmed in.
+/// @property {BigInt} timestamp - Timestamp of that block.
+pub struct StoreUpdate {
+    pub store: DataStore,
+    pub height: u32,
+    pub timestamp: BigInt,
+}
+
+impl ToJs<StoreUpdate> for RustStoreUpdate {
+    fn to_js(&self) -> Result<StoreUpdate> {
+        Ok(StoreUpdate {
+            store: self.store.to_js()?,
+            height: self.height,
+            timestamp: self.timestamp.to_js()?,
+        })
+    }
+}
+
+#[napi(object)]
+/// Represents a response containing unspent coins.
+///
+/// @property {Vec<Coin>} coins - Unspent coins.
+/// @property {u32} lastHeight - Last height.
+/// @property {Buffer} lastHeaderHash - Last header hash.
+pub struct UnspentCoinsResponse {
+    pub coins: Vec<Coin>,
+    pub last_height: u32,
+    pub last_header_hash: Buffer,
+}
+
+impl FromJs<UnspentCoinsResponse> for rust::UnspentCoinsResponse {
+    fn from_js(value: UnspentCoinsResponse) -> Result<Self> {
+        Ok(rust::UnspentCoinsResponse {
+            coins: value
+                .coins
+                .into_iter()
+                .map(RustCoin::from_js)
+                .collect::<Result<Vec<RustCoin>>>()
+                .arg("coins")?,
+            last_height: value.last_height,
+            last_header_hash: RustBytes32::from_js(value.last_header_hash).arg("lastHeaderHash")?,
+        })
+    }
+}
+
+impl ToJs<UnspentCoinsResponse> for rust::UnspentCoinsResponse {
+    fn to_js(&self) -> Result<UnspentCoinsResponse> {
+        Ok(UnspentCoinsResponse {
+            coins: self
+                .coins
+                .iter()
+                .map(RustCoin::to_js)
+                .collect::<Result<Vec<Coin>>>()?,
+            last_height: self.last_height,
+            last_header_hash: self.last_header_hash.to_js()?,
+        })
+    }
+}
+
+#[napi(object)]
+/// Represents a response containing possible launcher ids for datastores.
+///
+/// @property {Vec<Buffer>} launcher_ids - Launcher ids of coins that might be datastores.
+/// @property {u32} lastHeight - Last height.
+/// @property {Buffer} lastHeaderHash - Last header hash.
+pub struct PossibleLaunchersResponse {
+    pub launcher_ids: Vec<Buffer>,
+    pub last_height: u32,
+    pub last_header_hash: Buffer,
+}
+
+impl FromJs<PossibleLaunchersResponse> for RustPossibleLaunchersResponse {
+    fn from_js(value: PossibleLaunchersResponse) -> Result<Self> {
+        Ok(RustPossibleLaunchersResponse {
+            last_header_hash: RustBytes32::from_js(value.last_header_hash).arg("lastHeaderHash")?,
+            last_height: value.last_height,
+            launcher_ids: value
+                .launcher_ids
+                .into_iter()
+                .map(RustBytes32::from_js)
+                .collect::<Result<Vec<RustBytes32>>>()
+                .arg("launcherIds")?,
+        })
+    }
+}
+
+impl ToJs<PossibleLaunchersResponse> for RustPossibleLaunchersResponse {
+    fn to_js(&self) -> Result<PossibleLaunchersResponse> {
+        Ok(PossibleLaunchersResponse {
+            last_header_hash: self.last_header_hash.to_js()?,
+            last_height: self.last_height,
+            launcher_ids: self
+                .launcher_ids
+                .iter()
+                .map(RustBytes32::to_js)
+                .collect::<Result<Vec<Buffer>>>()?,
+        })
+    }
+}
+
+#[napi]
+pub struct Tls(TlsConnector);
+
+#[napi]
+impl Tls {
+    #[napi(constructor)]
+    /// Creates a new TLS connector.
+    ///
+    /// @param {String} certPath - Path to the certificate file (usually '~/.chia/mainnet/config/ssl/wallet/wallet_node.crt').
+    /// @param {String} keyPath - Path to the key file (usually '~/.chia/mainnet/config/ssl/wallet/wallet_node.key').
+    pub fn new(cert_path: String, key_path: String) -> napi::Result<Self> {
+        let cert = load_ssl_cert(&cert_path, &key_path).map_err(js::err)?;
+        let tls = create_tls_connector(&cert).map_err(js::err)?;
+        Ok(Self(tls))
+    }
+}
+
+// Aggregates signatures passed in separately.
+fn aggregate_signature_from_js(sigs: Vec<Buffer>) -> Result<RustSignature> {
+    let mut agg_sig = RustSignature::default();
+    for sig in sigs.into_iter() {
+        agg_sig += &RustSignature::from_js(sig).arg("sigs")?;
+    }
+
+    Ok(agg_sig)
+}
+
+// Aggregates the signatures of a spend bundle passed in as separate coin spends and signatures.
+fn spend_bundle_from_js(coin_spends: Vec<CoinSpend>, sigs: Vec<Buffer>) -> Result<RustSpendBundle> {
+    Ok(RustSpendBundle::new(
+        coin_spends
+            .into_iter()
+            .map(RustCoinSpend::from_js)
+            .collect::<Result<Vec<RustCoinSpend>>>()
+            .arg("coinSpends")?,
+        aggregate_signature_from_js(sigs)?,
+    ))
+}
+
+#[napi]
+pub struct Peer(ReconnectingPeer);
+
+#[napi]
+impl Peer {
+    #[napi(factory)]
+    /// Creates a new Peer instance. If the connection drops, the peer reconnects automatically and resubscribes to the coins being waited on.
+    ///
+    /// @param {String} nodeUri - URI of the node (e.g., '127.0.0.1:58444').
+    /// @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+    /// @param {Tls} tls - TLS connector.
+    /// @returns {Promise<Peer>} A new Peer instance.
+    pub async fn new(
+        node_uri: String,
+        network: Either<bool, Network>,
+        tls: &Tls,
+    ) -> napi::Result<Self> {
+        ReconnectingPeer::connect(
+            parse_node_uri(node_uri)?,
+            TargetNetwork::from_js(network).arg("network")?,
+            tls.0.clone(),
+        )
+        .await
+        .map(Self)
+        .map_err(js::err)
+    }
+
+    #[napi(factory)]
+    /// Creates a new Peer instance connected to a full node found through the network's DNS introducers (or introducers, if DNS doesn't return any nodes). Nodes that don't accept the connection within 10 seconds are skipped.
+    ///
+    /// @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+    /// @param {Tls} tls - TLS connector.
+    /// @returns {Promise<Peer>} A new Peer instance.
+    pub async fn connect_random(network: Either<bool, Network>, tls: &Tls) -> napi::Result<Self> {
+        let network = TargetNetwork::from_js(network).arg("network")?;
+        let socket_addrs = Discovery::for_network(&network, tls.0.clone())
+            .discover()
+            .await
+            .map_err(js::err)?;
+
+        let mut last_error = WalletError::NoPeers;
+
+        for socket_addr in socket_addrs {
+            let connection = ReconnectingPeer::connect(socket_addr, network.clone(), tls.0.clone());
+            match tokio::time::timeout(DISCOVERY_TIMEOUT, connection).await {
+                Ok(Ok(peer)) => return Ok(Self(peer)),
+                Ok(Err(error)) => last_error = error,
+                Err(_) => last_error = std::io::Error::from(std::io::ErrorKind::TimedOut).into(),
+            }
+        }
+
+        Err(js::err(last_error))
+    }
+
+    #[napi(factory)]
+    /// Creates a new Peer instance backed by an in-process chain simulator instead of a full node. Meant for tests. Like with a full node, the peer reconnects when the simulator disconnects it.
+    ///
+    /// @param {Simulator} simulator - The simulator to use as the chain.
+    /// @returns {Promise<Peer>} A new Peer instance.
+    pub async fn from_simulator(simulator: &Simulator) -> napi::Result<Self> {
+        ReconnectingPeer::from_simulator(simulator.0.clone())
+            .await
+            .map(Self)
+            .map_err(js::err)
+    }
+
+    async fn peer(&self) -> PeerBackend {
+        self.0.peer().await
+    }
+
+    #[napi]
+    /// Watches a store. The watcher yields the store's current state first, then every new state as it is confirmed on chain, until the store is melted or the watcher is cancelled.
+    ///
+    /// @param {Buffer} launcherId - The store's launcher/singleton ID.
+    /// @returns {Promise<StoreWatcher>} The store watcher.
+    pub async fn watch_store(&self, launcher_id: Buffer) -> napi::Result<StoreWatcher> {
+        let launcher_id = RustBytes32::from_js(launcher_id).arg("launcherId")?;
+        let updates = self.0.watch_store(launcher_id);
+
+        Ok(StoreWatcher {
+            task: updates.abort_handle(),
+            updates: Arc::new(Mutex::new(updates)),
+        })
+    }
+
+    #[napi]
+    /// Checks whether the peer is still usable. Returns false once the connection dropped and all reconnection attempts failed.
+    ///
+    /// @returns {bool} Whether the peer is connected (or reconnecting).
+    pub fn is_connected(&self) -> bool {
+        self.0.is_connected()
+    }
+
+    #[napi]
+    /// Retrieves all coins that are unspent on the chain. Note that coins part of spend bundles that are pending in the mempool will also be included.
+    ///
+    /// @param {Buffer} puzzleHash - Puzzle hash of the wallet.
+    /// @param {Option<u32>} previousHeight - Previous height that was spent. If null, sync will be done from the genesis block.
+    /// @param {Buffer} previousHeaderHash - Header hash corresponding to the previous height. If previousHeight is null, this should be the genesis challenge of the current chain.
+    /// @returns {Promise<UnspentCoinsResponse>} The unspent coins response.
+    pub async fn get_all_unspent_coins(
+        &self,
+        puzzle_hash: Buffer,
+        previous_height: Option<u32>,
+        previous_header_hash: Buffer,
+    ) -> napi::Result<UnspentCoinsResponse> {
+        let resp: rust::UnspentCoinsResponse = get_unspent_coin_states(
+            &self.peer().await,
+            RustBytes32::from_js(puzzle_hash).arg("puzzleHash")?,
+            previous_height,
+            RustBytes32::from_js(previous_header_hash).arg("previousHeaderHash")?,
+            false,
+        )
+        .await
+        .map_err(js::err)?
+        .into();
+
+        resp.to_js()
+    }
+
+    #[napi]
+    /// Retrieves all hinted coin states that are unspent on the chain. Note that coins part of spend bundles that are pending in the mempool will also be included.
+    ///
+    /// @param {Buffer} puzzleHash - Puzzle hash to lookup hinted coins for.
+    /// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+    /// @returns {Promise<Vec<Coin>>} The unspent coins response.
+    pub async fn get_hinted_coin_states(
+        &self,
+        puzzle_hash: Buffer,
+        network: Either<bool, Network>,
+    ) -> napi::Result<Vec<CoinState>> {
+        let resp = get_unspent_coin_states(
+            &self.peer().await,
+            RustBytes32::from_js(puzzle_hash).arg("puzzleHash")?,
+            None,
+            TargetNetwork::from_js(network)
+                .arg("network")?
+                .genesis_challenge,
+            true,
+        )
+        .await
+        .map_err(js::err)?;
+
+        resp.coin_states
+            .into_iter()
+            .map(|c| c.to_js())
+            .collect::<Result<Vec<CoinState>>>()
+    }
+
+    #[napi]
+    /// Fetches the server coin from a given coin state.
+    ///
+    /// @param {CoinState} coinState - The coin state.
+    /// @param {BigInt} maxCost - The maximum cost to use when parsing the coin. For example, `11_000_000_000`.
+    /// @returns {Promise<ServerCoin>} The server coin.
+    pub async fn fetch_server_coin(
+        &self,
+        coin_state: CoinState,
+        max_cost: BigInt,
+    ) -> napi::Result<js::ServerCoin> {
+        let coin = wallet::fetch_server_coin(
+            &self.peer().await,
+            rust::CoinState::from_js(coin_state).arg("coinState")?,
+            u64::from_js(max_cost).arg("maxCost")?,
+        )
+        .await
+        .map_err(js::err)?;
+
+        coin.to_js()
+    }
+
+    #[napi]
+    /// Synchronizes a datastore. It doesn't use a `StoreCache`; see `syncCachedStore` to resume from one.
+    ///
+    /// @param {DataStore} store - Data store.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @param {bool} withHistory - Whether to return the root hash history of the store.
+    /// @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+    /// @returns {Promise<SyncStoreResponse>} The sync store response.
+    pub async fn sync_store(
+        &self,
+        store: DataStore,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+        with_history: bool,
+        known_history: Option<Vec<StoreHistoryEntry>>,
+    ) -> napi::Result<SyncStoreResponse> {
+        let store = RustDataStore::from_js(store).arg("store")?;
+        let known_history = known_history_from_js(known_history, store.info.launcher_id)?;
+
+        sync_store(
+            &self.peer().await,
+            &store,
+            last_height,
+            RustBytes32::from_js(last_header_hash).arg("lastHeaderHash")?,
+            with_history,
+            &known_history,
+        )
+        .await
+        .map_err(js::err)?
+        .to_js()
+    }
+
+    #[napi]
+    /// Synchronizes a store using its launcher ID.
+    ///
+    /// @param {Buffer} launcherId - The store's launcher/singleton ID.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @param {bool} withHistory - Whether to return the root hash history of the store.
+    /// @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+    /// @returns {Promise<SyncStoreResponse>} The sync store response.
+    pub async fn sync_store_from_launcher_id(
+        &self,
+        launcher_id: Buffer,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+        with_history: bool,
+        known_history: Option<Vec<StoreHistoryEntry>>,
+    ) -> napi::Result<SyncStoreResponse> {
+        let launcher_id = RustBytes32::from_js(launcher_id).arg("launcherId")?;
+        let known_history = known_history_from_js(known_history, launcher_id)?;
+
+        sync_store_using_launcher_id(
+            &self.peer().await,
+            launcher_id,
+            last_height,
+            RustBytes32::from_js(last_header_hash).arg("lastHeaderHash")?,
+            with_history,
+            &known_history,
+        )
+        .await
+        .map_err(js::err)?
+        .to_js()
+    }
+
+    #[napi]
+    /// Synchronizes a store, resuming from its state in `cache`, and saves the result to the cache. Stores that aren't cached yet are synced from their launcher.
+    ///
+    /// @param {StoreCache} cache - The cache to resume from and save to.
+    /// @param {Buffer} launcherId - The store's launcher/singleton ID.
+    /// @param {bool} withHistory - Whether to return the root hash history of the store, starting at its launch.
+    /// @returns {Promise<SyncStoreResponse>} The sync store response. If a reorg orphaned cached states, `reorg.orphanedRootHashes` lists their root hashes.
+    pub async fn sync_cached_store(
+        &self,
+        cache: &StoreCache,
+        launcher_id: Buffer,
+        with_history: bool,
+    ) -> napi::Result<SyncStoreResponse> {
+        sync_cached_store(
+            &self.peer().await,
+            &cache.0,
+            RustBytes32::from_js(launcher_id).arg("launcherId")?,
+            with_history,
+        )
+        .await
+        .map_err(js::err)?
+        .to_js()
+    }
+
+    #[napi]
+    /// Synchronizes many datastores at once. Coin states are requested in batches and puzzles are fetched concurrently; a store that can't be synced doesn't fail the others.
+    ///
+    /// @param {Vec<DataStore>} stores - Data stores.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @param {bool} withHistory - Whether to return the root hash history of the stores.
+    /// @param {Option<u32>} concurrency - Max. number of stores whose puzzles are fetched at the same time. Defaults to 10.
+    /// @returns {Promise<Vec<BatchSyncResult>>} The results, in the order of `stores`.
+    pub async fn sync_stores(
+        &self,
+        stores: Vec<DataStore>,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+        with_history: bool,
+        concurrency: Option<u32>,
+    ) -> napi::Result<Vec<BatchSyncResult>> {
+        let targets = stores
+            .into_iter()
+            .map(|store| {
+                Ok(StoreSyncTarget::Store(
+                    RustDataStore::from_js(store).arg("store")?,
+                ))
+            })
+            .collect::<Result<Vec<StoreSyncTarget>>>()?;
+
+        self.sync_targets(
+            targets,
+            last_height,
+            last_header_hash,
+            with_history,
+            concurrency,
+        )
+        .await
+    }
+
+    #[napi]
+    /// Synchronizes many stores at once using their launcher IDs. See `syncStores`.
+    ///
+    /// @param {Vec<Buffer>} launcherIds - The stores' launcher/singleton IDs.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @param {bool} withHistory - Whether to return the root hash history of the stores.
+    /// @param {Option<u32>} concurrency - Max. number of stores whose puzzles are fetched at the same time. Defaults to 10.
+    /// @returns {Promise<Vec<BatchSyncResult>>} The results, in the order of `launcherIds`.
+    pub async fn sync_stores_from_launcher_ids(
+        &self,
+        launcher_ids: Vec<Buffer>,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+        with_history: bool,
+        concurrency: Option<u32>,
+    ) -> napi::Result<Vec<BatchSyncResult>> {
+        let targets = launcher_ids
+            .into_iter()
+            .map(|launcher_id| {
+                Ok(StoreSyncTarget::LauncherId(RustBytes32::from_js(
+                    launcher_id,
+                )?))
+            })
+            .collect::<Result<Vec<StoreSyncTarget>>>()?;
+
+        self.sync_targets(
+            targets,
+            last_height,
+            last_header_hash,
+            with_history,
+            concurrency,
+        )
+        .await
+    }
+
+    async fn sync_targets(
+        &self,
+        targets: Vec<StoreSyncTarget>,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+        with_history: bool,
+        concurrency: Option<u32>,
+    ) -> napi::Result<Vec<BatchSyncResult>> {
+        let launcher_ids = targets.iter().map(StoreSyncTarget::launcher_id).collect();
+
+        let results = sync_stores(
+            &self.peer().await,
+            targets,
+            last_height,
+            RustBytes32::from_js(last_header_hash).arg("lastHeaderHash")?,
+            with_history,
+            concurrency.map_or(DEFAULT_SYNC_CONCURRENCY, |concurrency| concurrency as usize),
+        )
+        .await;
+
+        batch_sync_results_to_js(launcher_ids, results)
+    }
+
+    #[napi]
+    /// Fetch a store's creation height.
+    ///
+    /// @param {Buffer} launcherId - The store's launcher/singleton ID.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @returns {Promise<BigInt>} The store's creation height.
+    pub async fn get_store_creation_height(
+        &self,
+        launcher_id: Buffer,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+    ) -> napi::Result<BigInt> {
+        let res = wallet::get_store_creation_height(
+            &self.peer().await,
+            RustBytes32::from_js(launcher_id).arg("launcherId")?,
+            last_height,
+            RustBytes32::from_js(last_header_hash).arg("lastHeaderHash")?,
+        )
+        .await
+        .map_err(js::err)?;
+
+        (res as u64).to_js()
+    }
+
+    #[napi]
+    /// Broadcasts a spend bundle to the mempool.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+    /// @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+    /// @returns {Promise<String>} The broadcast error. If '', the broadcast was successful.
+    pub async fn broadcast_spend(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        sigs: Vec<Buffer>,
+    ) -> napi::Result<String> {
+        let spend_bundle = spend_bundle_from_js(coin_spends, sigs)?;
+
+        Ok(
+            wallet::broadcast_spend_bundle(&self.peer().await, spend_bundle)
+                .await
+                .map_err(js::err)?
+                .error
+                .unwrap_or(String::default()),
+        )
+    }
+
+    #[napi]
+    /// Broadcasts a spend bundle to the mempool, throwing a `TransactionRejectedError` with the mempool's error if it's refused.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+    /// @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+    /// @returns {Promise<Buffer>} The id of the spend bundle.
+    pub async fn send_spend(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        sigs: Vec<Buffer>,
+    ) -> napi::Result<Buffer> {
+        let spend_bundle = spend_bundle_from_js(coin_spends, sigs)?;
+
+        wallet::send_spend_bundle(&self.peer().await, spend_bundle)
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Broadcasts a transaction rebuilt with `bumpFee`, so that it replaces the pending one in the mempool. Throws a `TransactionRejectedError` if the mempool refuses it, or with the `MEMPOOL_CONFLICT` error if the pending transaction isn't replaced, e.g. because the new fee isn't high enough.
+    ///
+    /// @param {FeeBump} feeBump - The transaction with the higher fee, as returned by `bumpFee`.
+    /// @param {Vec<Buffer>} sigs - The signatures of its coin spends, to be aggregated and included in the bundle.
+    /// @returns {Promise<Buffer>} The id of the new spend bundle.
+    pub async fn broadcast_fee_bump(
+        &self,
+        fee_bump: FeeBump,
+        sigs: Vec<Buffer>,
+    ) -> napi::Result<Buffer> {
+        let fee_bump = RustFeeBump::from_js(fee_bump).arg("feeBump")?;
+        let signature = aggregate_signature_from_js(sigs)?;
+
+        fees::broadcast_fee_bump(&self.peer().await, &fee_bump, signature)
+            .await
+            .map_err(js::err)?
+            .txid
+            .to_js()
+    }
+
+    #[napi]
+    /// Validates a spend bundle with `validateSpendBundle` and checks that the coins it spends, except for the ones it creates itself, are unspent on chain. Throws a `ValidationError` with code `INVALID_SPEND_BUNDLE` or `COIN_ALREADY_SPENT`, or an `UnknownCoinError`.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+    /// @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+    /// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+    /// @returns {Promise<SpendBundleConditions>} The conditions of the bundle.
+    pub async fn validate_spend_bundle(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        sigs: Vec<Buffer>,
+        network: Either<bool, Network>,
+    ) -> napi::Result<SpendBundleConditions> {
+        let spend_bundle = spend_bundle_from_js(coin_spends, sigs)?;
+
+        wallet::validate_spend_bundle_on_chain(
+            &self.peer().await,
+            &spend_bundle,
+            &TargetNetwork::from_js(network).arg("network")?,
+        )
+        .await
+        .map_err(js::err)?
+        .to_js()
+    }
+
+    #[napi]
+    /// Checks if a coin is spent on-chain.
+    ///
+    /// @param {Buffer} coinId - The coin ID.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @returns {Promise<bool>} Whether the coin is spent on-chain.
+    pub async fn is_coin_spent(
+        &self,
+        coin_id: Buffer,
+        last_height: Option<u32>,
+        header_hash: Buffer,
+    ) -> napi::Result<bool> {
+        is_coin_spent(
+            &self.peer().await,
+            RustBytes32::from_js(coin_id).arg("coinId")?,
+            last_height,
+            RustBytes32::from_js(header_hash).arg("headerHash")?,
+        )
+        .await
+        .map_err(js::err)
+    }
+
+    #[napi]
+    /// Retrieves the current header hash corresponding to a given height.
+    ///
+    /// @param {u32} height - The height.
+    /// @returns {Promise<Buffer>} The header hash.
+    pub async fn get_header_hash(&self, height: u32) -> napi::Result<Buffer> {
+        get_header_hash(&self.peer().await, height)
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Retrieves the fee estimate for a given target time.
+    ///
+    /// @param {Peer} peer - The peer connection to the Chia node.
+    /// @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time for the fee estimate.
+    /// @returns {Promise<BigInt>} The estimated fee in mojos per CLVM cost.
+    pub async fn get_fee_estimate(&self, target_time_seconds: BigInt) -> napi::Result<BigInt> {
+        wallet::get_fee_estimate(
+            &self.peer().await,
+            u64::from_js(target_time_seconds).arg("targetTimeSeconds")?,
+        )
+        .await
+        .map_err(js::err)?
+        .to_js()
+    }
+
+    #[napi]
+    /// Gets a fee policy for confirming a transaction within a given time, using the node's fee estimate.
+    ///
+    /// @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time.
+    /// @returns {Promise<FeePolicy>} A policy with the estimated fee per CLVM cost.
+    pub async fn fee_policy_for_target_time(
+        &self,
+        target_time_seconds: BigInt,
+    ) -> napi::Result<FeePolicy> {
+        RustFeePolicy::from_target_time(
+            &self.peer().await,
+            u64::from_js(target_time_seconds).arg("targetTimeSeconds")?,
+        )
+        .await
+        .map_err(js::err)?
+        .to_js()
+    }
+
+    #[napi]
+    /// Retrieves the peer's peak.
+    ///
+    /// @returns {Option<u32>} A tuple consiting of the latest synced block's height, as reported by the peer. Null if the peer has not yet reported a peak.
+    pub async fn get_peak(&self) -> napi::Result<Option<u32>> {
+        Ok(self.0.peak().await.map(|p| p.height))
+    }
+
+    /// Spends the mirror coins to make them unusable in the future.
+    ///
+    /// @param {Buffer} syntheticKey - The synthetic key used by the wallet.
+    /// @param {Vec<Coin>} selectedCoins - Coins to be used for minting, as retured by `select_coins`. Note that the server coins will count towards the fee.
+    /// @param {BigInt} fee - The fee to use for the transaction.
+    /// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+    #[napi]
+    pub async fn lookup_and_spend_server_coins(
+        &self,
+        synthetic_key: Buffer,
+        selected_coins: Vec<Coin>,
+        fee: BigInt,
+        network: Either<bool, Network>,
+    ) -> napi::Result<Vec<CoinSpend>> {
+        let coin = wallet::spend_server_coins(
+            &self.peer().await,
+            RustPublicKey::from_js(synthetic_key).arg("syntheticKey")?,
+            selected_coins
+                .into_iter()
+                .map(RustCoin::from_js)
+                .collect::<Result<Vec<RustCoin>>>()
+                .arg("selectedCoins")?,
+            u64::from_js(fee).arg("fee")?,
+            &TargetNetwork::from_js(network).arg("network")?,
+        )
+        .await
+        .map_err(js::err)?;
+
+        coin.into_iter()
+            .map(|c| c.to_js())
+            .collect::<Result<Vec<CoinSpend>>>()
+    }
+
+    #[napi]
+    /// Looks up possible datastore launchers by searching for singleton launchers created with a DL-specific hint.
+    ///
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @returns {Promise<PossibleLaunchersResponse>} Possible launcher ids for datastores, as well as a height + header hash combo to use for the next call.
+    pub async fn look_up_possible_launchers(
+        &self,
+        last_height: Option<u32>,
+        header_hash: Buffer,
+    ) -> napi::Result<PossibleLaunchersResponse> {
+        wallet::look_up_possible_launchers(
+            &self.peer().await,
+            last_height,
+            RustBytes32::from_js(header_hash).arg("headerHash")?,
+        )
+        .await
+        .map_err(js::err)?
+        .to_js()
+    }
+
+    #[napi]
+    /// Waits for a coin to be spent on-chain.
+    ///
+    /// @param {Buffer} coin_id - Id of coin to track.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @returns {Promise<Buffer>} Promise that resolves when the coin is spent (returning the coin id).
+    pub async fn wait_for_coin_to_be_spent(
+        &self,
+        coin_id: Buffer,
+        last_height: Option<u32>,
+        header_hash: Buffer,
+    ) -> napi::Result<Buffer> {
+        let rust_coin_id = RustBytes32::from_js(coin_id).arg("coinId")?;
+        let header_hash = RustBytes32::from_js(header_hash).arg("headerHash")?;
+
+        self.0
+            .wait_for_coin_to_be_spent(rust_coin_id, last_height, header_hash)
+            .await
+            .map_err(js::err)?;
+
+        rust_coin_id.to_js()
+    }
+
+    #[napi]
+    /// Tracks a spend bundle that was broadcast with `broadcastSpend`, by subscribing to the coins it spends. The tracker yields the bundle's status whenever it changes: pending until it's confirmed, conflicted if one of its coins is spent by another spend (e.g., of the same store coin), or dropped if it isn't included in a block before it expires.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends of the broadcast bundle.
+    /// @param {Option<u32>} confirmations - Number of blocks the bundle's block needs to be buried under (including itself) before it's confirmed. Defaults to 1.
+    /// @param {Option<u32>} expiryBlocks - Number of blocks after which a bundle that isn't in a block is considered dropped. Defaults to 200 (about an hour on mainnet).
+    /// @returns {Promise<SpendTracker>} The spend tracker.
+    pub async fn track_spend(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        confirmations: Option<u32>,
+        expiry_blocks: Option<u32>,
+    ) -> napi::Result<SpendTracker> {
+        let tracker = RustSpendTracker::new(
+            coin_spends
+                .into_iter()
+                .map(RustCoinSpend::from_js)
+                .collect::<Result<Vec<RustCoinSpend>>>()
+                .arg("coinSpends")?,
+            confirmations.unwrap_or(DEFAULT_CONFIRMATIONS),
+            expiry_blocks.unwrap_or(DEFAULT_EXPIRY_BLOCKS),
+        );
+        let updates = self.0.track_spend(tracker);
+
+        Ok(SpendTracker {
+            task: updates.abort_handle(),
+            updates: Arc::new(Mutex::new(updates)),
+        })
+    }
+}
+
+#[napi(string_enum)]
+/// Where a tracked spend bundle stands.
+pub enum SpendStatusKind {
+    Pending,
+    Confirmed,
+    Conflicted,
+    Dropped,
+}
+
+#[napi(object)]
+/// A status of a tracked spend bundle.
+///
+/// @property {SpendStatusKind} kind - Whether the bundle is pending, confirmed, conflicted or dropped.
+/// @property {Option<u32>} height - Height of the block the bundle (or the conflicting spend) is in. Null if the bundle is pending or dropped.
+/// @property {Option<Buffer>} coinId - The coin spent by a conflicting spend. Null unless the bundle is conflicted.
+pub struct SpendStatus {
+    pub kind: SpendStatusKind,
+    pub height: Option<u32>,
+    pub coin_id: Option<Buffer>,
+}
+
+impl ToJs<SpendStatus> for RustSpendStatus {
+    fn to_js(&self) -> Result<SpendStatus> {
+        Ok(match self {
+            RustSpendStatus::Pending => SpendStatus {
+                kind: SpendStatusKind::Pending,
+                height: None,
+                coin_id: None,
+            },
+            RustSpendStatus::Confirmed { height } => SpendStatus {
+                kind: SpendStatusKind::Confirmed,
+                height: Some(*height),
+                coin_id: None,
+            },
+            RustSpendStatus::Conflicted { coin_id, height } => SpendStatus {
+                kind: SpendStatusKind::Conflicted,
+                height: Some(*height),
+                coin_id: Some(coin_id.to_js()?),
+            },
+            RustSpendStatus::Dropped => SpendStatus {
+                kind: SpendStatusKind::Dropped,
+                height: None,
+                coin_id: None,
+            },
+        })
+    }
+}
+
+#[napi]
+/// Streams the status of a broadcast spend bundle as it changes. Created with `Peer.trackSpend`.
+pub struct SpendTracker {
+    updates: Arc<Mutex<UpdateStream<RustSpendStatus>>>,
+    task: AbortHandle,
+}
+
+#[napi]
+impl SpendTracker {
+    #[napi]
+    /// Waits for the bundle's next status.
+    ///
+    /// @returns {Promise<Option<SpendStatus>>} The next status. Null once the bundle was confirmed, conflicted or dropped, or the tracker was cancelled.
+    pub async fn next(&self) -> napi::Result<Option<SpendStatus>> {
+        match self.updates.lock().await.next().await {
+            Some(status) => Ok(Some(status.map_err(js::err)?.to_js()?)),
+            None => Ok(None),
+        }
+    }
+
+    #[napi]
+    /// Stops tracking the bundle. Pending and future calls to `next` resolve to null.
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for SpendTracker {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[napi]
+/// Streams the states of a store as they are confirmed on chain. Created with `Peer.watchStore`.
+pub struct StoreWatcher {
+    updates: Arc<Mutex<UpdateStream<RustStoreUpdate>>>,
+    task: AbortHandle,
+}
+
+#[napi]
+impl StoreWatcher {
+    #[napi]
+    /// Waits for the store's next state.
+    ///
+    /// @returns {Promise<Option<StoreUpdate>>} The next state. Null once the store was melted or the watcher was cancelled.
+    pub async fn next(&self) -> napi::Result<Option<StoreUpdate>> {
+        match self.updates.lock().await.next().await {
+            Some(update) => Ok(Some(update.map_err(js::err)?.to_js()?)),
+            None => Ok(None),
+        }
+    }
+
+    #[napi]
+    /// Stops watching the store. Pending and future calls to `next` resolve to null.
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for StoreWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn parse_node_uri(node_uri: String) -> Result<SocketAddr> {
+    node_uri
+        .parse::<SocketAddr>()
+        .map_err(|_| js::err(ConversionError::InvalidUri(node_uri)))
+}
+
+#[napi]
+/// Finds full nodes through the network's DNS introducers, or by asking its introducers if DNS doesn't return any nodes.
+///
+/// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+/// @param {Tls} tls - TLS connector, used to talk to introducers.
+/// @returns {Promise<Vec<String>>} URIs of the discovered nodes (e.g., ['1.2.3.4:8444']).
+pub async fn discover_peers(
+    network: Either<bool, Network>,
+    tls: &Tls,
+) -> napi::Result<Vec<String>> {
+    Ok(Discovery::for_network(
+        &TargetNetwork::from_js(network).arg("network")?,
+        tls.0.clone(),
+    )
+    .discover()
+    .await
+    .map_err(js::err)?
+    .into_iter()
+    .map(|socket_addr| socket_addr.to_string())
+    .collect())
+}
+
+#[napi(object)]
+#[derive(Clone)]
+/// A key/value pair in a data tree.
+///
+/// @property {Buffer} key - Key.
+/// @property {Buffer} value - Value.
+pub struct KeyValue {
+    pub key: Buffer,
+    pub value: Buffer,
+}
+
+#[napi(string_enum)]
+/// How a key changed between two data trees.
+pub enum DataTreeChangeKind {
+    Insert,
+    Delete,
+    Update,
+}
+
+#[napi(object)]
+#[derive(Clone)]
+/// A key that changed between two data trees.
+///
+/// @property {DataTreeChangeKind} kind - Whether the key was inserted, deleted or given a new value.
+/// @property {Buffer} key - Key.
+/// @property {Option<Buffer>} previousValue - Value in the old tree, or null if the key was inserted.
+/// @property {Option<Buffer>} value - Value in the new tree, or null if the key was deleted.
+pub struct DataTreeChange {
+    pub kind: DataTreeChangeKind,
+    pub key: Buffer,
+    pub previous_value: Option<Buffer>,
+    pub value: Option<Buffer>,
+}
+
+impl ToJs<DataTreeChange> for RustDataTreeChange {
+    fn to_js(&self) -> Result<DataTreeChange> {
+        Ok(match self {
+            RustDataTreeChange::Insert { key, value } => DataTreeChange {
+                kind: DataTreeChangeKind::Insert,
+                key: key.to_js()?,
+                previous_value: None,
+                value: Some(value.to_js()?),
+            },
+            RustDataTreeChange::Delete { key, value } => DataTreeChange {
+                kind: DataTreeChangeKind::Delete,
+                key: key.to_js()?,
+                previous_value: Some(value.to_js()?),
+                value: None,
+            },
+            RustDataTreeChange::Update {
+                key,
+                previous_value,
+                value,
+            } => DataTreeChange {
+                kind: DataTreeChangeKind::Update,
+                key: key.to_js()?,
+                previous_value: Some(previous_value.to_js()?),
+                value: Some(value.to_js()?),
+            },
+        })
+    }
+}
+
+#[napi]
+/// The key/value Merkle tree a store's root hash commits to, hashed the same way as the Chia data layer. Use `rootHash` to get the root hash to pass to `mintStore` or `updateStoreMetadata`.
+pub struct DataTree(RustDataTree);
+
+#[napi]
+impl DataTree {
+    #[napi(constructor)]
+    /// Creates a data tree.
+    ///
+    /// @param {Option<Vec<KeyValue>>} pairs - Pairs to insert, in order. If null, the tree is empty.
+    pub fn new(pairs: Option<Vec<KeyValue>>) -> napi::Result<Self> {
+        let mut entries = Vec::new();
+
+        for pair in pairs.unwrap_or_default() {
+            entries.push((
+                RustBytes::from_js(pair.key)?,
+                RustBytes::from_js(pair.value)?,
+            ));
+        }
+
+        Ok(Self(RustDataTree::from_pairs(entries)))
+    }
+
+    #[napi]
+    /// Inserts a pair, replacing the value of an existing key. An existing key's leaf is moved, like with a delete followed by an insert; use `upsert` to keep it in place.
+    ///
+    /// @param {Buffer} key - Key.
+    /// @param {Buffer} value - Value.
+    /// @returns {Option<Buffer>} The replaced value, or null if the key was new.
+    pub fn insert(&mut self, key: Buffer, value: Buffer) -> napi::Result<Option<Buffer>> {
+        self.0
+            .insert(
+                RustBytes::from_js(key).arg("key")?,
+                RustBytes::from_js(value)?,
+            )
+            .map(|value| value.to_js())
+            .transpose()
+    }
+
+    #[napi]
+    /// Inserts a pair, or replaces the value of an existing key without moving its leaf, like the data layer's upsert.
+    ///
+    /// @param {Buffer} key - Key.
+    /// @param {Buffer} value - Value.
+    /// @returns {Option<Buffer>} The replaced value, or null if the key was new.
+    pub fn upsert(&mut self, key: Buffer, value: Buffer) -> napi::Result<Option<Buffer>> {
+        self.0
+            .upsert(
+                RustBytes::from_js(key).arg("key")?,
+                RustBytes::from_js(value)?,
+            )
+            .map(|value| value.to_js())
+            .transpose()
+    }
+
+    #[napi]
+    /// Deletes a key.
+    ///
+    /// @param {Buffer} key - Key.
+    /// @returns {Option<Buffer>} The deleted value, or null if the key wasn't in the tree.
+    pub fn delete(&mut self, key: Buffer) -> napi::Result<Option<Buffer>> {
+        self.0.delete(&key).map(|value| value.to_js()).transpose()
+    }
+
+    #[napi]
+    /// Gets the value of a key.
+    ///
+    /// @param {Buffer} key - Key.
+    /// @returns {Option<Buffer>} The value, or null if the key isn't in the tree.
+    pub fn get(&self, key: Buffer) -> napi::Result<Option<Buffer>> {
+        self.0.get(&key).map(|value| value.to_js()).transpose()
+    }
+
+    #[napi]
+    /// Lists the pairs in the tree.
+    ///
+    /// @returns {Vec<KeyValue>} The pairs, in no particular order.
+    pub fn entries(&self) -> napi::Result<Vec<KeyValue>> {
+        self.0
+            .iter()
+            .map(|(key, value)| {
+                Ok(KeyValue {
+                    key: key.to_js()?,
+                    value: value.to_js()?,
+                })
+            })
+            .collect()
+    }
+
+    #[napi]
+    /// Counts the pairs in the tree.
+    ///
+    /// @returns {u32} The number of pairs.
+    pub fn len(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    #[napi]
+    /// Checks whether the tree has no pairs.
+    ///
+    /// @returns {bool} True if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[napi]
+    /// Proves that a key is in the tree with its current value.
+    ///
+    /// @param {Buffer} key - Key.
+    /// @returns {Option<Buffer>} The serialized proof, or null if the key isn't in the tree.
+    pub fn prove_inclusion(&self, key: Buffer) -> napi::Result<Option<Buffer>> {
+        self.0
+            .prove_inclusion(&key)
+            .map(|proof| Ok(Buffer::from(proof.to_bytes().map_err(js::err)?)))
+            .transpose()
+    }
+
+    #[napi]
+    /// Proves that a key isn't in the tree. The proof holds every key and a hash of its value, so it grows with the tree.
+    ///
+    /// @param {Buffer} key - Key.
+    /// @returns {Option<Buffer>} The serialized proof, or null if the key is in the tree.
+    pub fn prove_exclusion(&self, key: Buffer) -> napi::Result<Option<Buffer>> {
+        self.0
+            .prove_exclusion(&key)
+            .map(|proof| Ok(Buffer::from(proof.to_bytes().map_err(js::err)?)))
+            .transpose()
+    }
+
+    #[napi]
+    /// Lists the keys that changed between this tree and another one.
+    ///
+    /// @param {DataTree} other - The newer tree.
+    /// @returns {Vec<DataTreeChange>} The changes, ordered by key.
+    pub fn diff(&self, other: &DataTree) -> napi::Result<Vec<DataTreeChange>> {
+        self.0.diff(&other.0).iter().map(ToJs::to_js).collect()
+    }
+
+    #[napi]
+    /// Creates a delta that turns this tree into another one. Only the parts of the other tree that changed are included.
+    ///
+    /// @param {DataTree} other - The newer tree.
+    /// @returns {Buffer} The serialized delta.
+    pub fn delta(&self, other: &DataTree) -> napi::Result<Buffer> {
+        Ok(Buffer::from(
+            self.0.delta(&other.0).to_bytes().map_err(js::err)?,
+        ))
+    }
+
+    #[napi]
+    /// Applies a delta from `DataTree.delta` to this tree. Fails if the delta wasn't made from this tree or doesn't produce the root hash it was made for.
+    ///
+    /// @param {Buffer} delta - The serialized delta.
+    /// @returns {DataTree} The new tree. This tree isn't changed.
+    pub fn apply_delta(&self, delta: Buffer) -> napi::Result<DataTree> {
+        let delta = RustTreeDelta::from_bytes(&delta).map_err(js::err)?;
+
+        Ok(DataTree(self.0.apply_delta(&delta).map_err(js::err)?))
+    }
+
+    #[napi]
+    /// Computes the tree's root hash.
+    ///
+    /// @returns {Buffer} The root hash, or 32 zero bytes if the tree is empty.
+    pub fn root_hash(&self) -> napi::Result<Buffer> {
+        self.0.root_hash().to_js()
+    }
+}
+
+#[napi]
+/// An on-disk cache of synced stores, kept in a SQLite database. Stores are saved with their full history and the block syncing resumes from, so they don't have to be synced from their launcher after a restart.
+pub struct StoreCache(Arc<RustStoreCache>);
+
+#[napi]
+impl StoreCache {
+    #[napi(constructor)]
+    /// Opens a store cache, creating the database if it doesn't exist.
+    ///
+    /// @param {Option<String>} path - Path of the database file. If null, the cache is only kept in memory.
+    pub fn new(path: Option<String>) -> napi::Result<Self> {
+        let cache = match path {
+            Some(path) => RustStoreCache::open(path),
+            None => RustStoreCache::open_in_memory(),
+        }
+        .map_err(js::err)?;
+
+        Ok(Self(Arc::new(cache)))
+    }
+
+    #[napi]
+    /// Lists the cached stores.
+    ///
+    /// @returns {Vec<Buffer>} The launcher IDs of the cached stores.
+    pub fn launcher_ids(&self) -> napi::Result<Vec<Buffer>> {
+        self.0
+            .launcher_ids()
+            .map_err(js::err)?
+            .iter()
+            .map(RustBytes32::to_js)
+            .collect()
+    }
+
+    #[napi]
+    /// Gets a cached store.
+    ///
+    /// @param {Buffer} launcherId - The store's launcher/singleton ID.
+    /// @returns {Option<SyncStoreResponse>} The store's cached state and full history, or null if it isn't cached.
+    pub fn get(&self, launcher_id: Buffer) -> napi::Result<Option<SyncStoreResponse>> {
+        self.0
+            .get(RustBytes32::from_js(launcher_id).arg("launcherId")?)
+            .map_err(js::err)?
+            .map(|cached| {
+                RustSyncStoreResponse::new(cached.store, cached.height, Some(cached.history))
+                    .to_js()
+            })
+            .transpose()
+    }
+
+    #[napi]
+    /// Removes a store from the cache.
+    ///
+    /// @param {Buffer} launcherId - The store's launcher/singleton ID.
+    pub fn remove(&self, launcher_id: Buffer) -> napi::Result<()> {
+        self.0
+            .remove(RustBytes32::from_js(launcher_id).arg("launcherId")?)
+            .map_err(js::err)
+    }
+}
+
+#[napi]
+/// An in-process blockchain for testing store lifecycles without a full node. Use `Peer.fromSimulator` to talk to it.
+pub struct Simulator(Arc<ChainSimulator>);
+
+#[napi]
+impl Simulator {
+    #[napi(constructor)]
+    /// Creates a new simulator with only the genesis block.
+    ///
+    /// @param {Option<bool | Network>} network - Network whose consensus rules are simulated. True for testnet11, false or null for mainnet, or a network descriptor.
+    pub fn new(network: Option<Either<bool, Network>>) -> napi::Result<Self> {
+        let network = match network {
+            Some(network) => TargetNetwork::from_js(network).arg("network")?,
+            None => TargetNetwork::mainnet(),
+        };
+
+        Ok(Self(Arc::new(ChainSimulator::new(network))))
+    }
+
+    #[napi]
+    /// Farms a block that creates a new coin, to fund test wallets.
+    ///
+    /// @param {Buffer} puzzleHash - Puzzle hash of the new coin.
+    /// @param {BigInt} amount - Amount of the new coin, in mojos.
+    /// @returns {Coin} The new coin.
+    pub fn new_coin(&self, puzzle_hash: Buffer, amount: BigInt) -> napi::Result<Coin> {
+        self.0
+            .new_coin(
+                RustBytes32::from_js(puzzle_hash).arg("puzzleHash")?,
+                u64::from_js(amount).arg("amount")?,
+            )
+            .to_js()
+    }
+
+    #[napi]
+    /// Farms empty blocks.
+    ///
+    /// @param {u32} count - Number of blocks to farm.
+    pub fn farm_blocks(&self, count: u32) {
+        self.0.farm_blocks(count);
+    }
+
+    #[napi]
+    /// Orphans every block after a given height, undoing their coin changes.
+    ///
+    /// @param {u32} forkHeight - Height of the last block to keep.
+    pub fn reorg(&self, fork_height: u32) {
+        self.0.reorg(fork_height);
+    }
+
+    #[napi]
+    /// Closes the connections of every peer using the simulator, like a node going away. The peers reconnect on their own.
+    pub fn disconnect(&self) {
+        self.0.disconnect();
+    }
+
+    #[napi]
+    /// Makes the next connection attempts fail, so peers that were disconnected can't reconnect right away.
+    ///
+    /// @param {u32} count - Number of connection attempts to refuse.
+    pub fn refuse_connections(&self, count: u32) {
+        self.0.refuse_connections(count);
+    }
+
+    #[napi]
+    /// Makes the next wallet protocol requests from peers fail, as if their connection dropped.
+    ///
+    /// @param {u32} count - Number of requests to fail.
+    pub fn fail_requests(&self, count: u32) {
+        self.0.fail_requests(count);
+    }
+
+    #[napi]
+    /// Retrieves the height of the peak block.
+    ///
+    /// @returns {u32} The peak height.
+    pub fn height(&self) -> u32 {
+        self.0.height()
+    }
+
+    #[napi]
+    /// Retrieves the header hash of the block at a given height.
+    ///
+    /// @param {u32} height - The height.
+    /// @returns {Option<Buffer>} The header hash. Null if no block was farmed at that height.
+    pub fn header_hash(&self, height: u32) -> napi::Result<Option<Buffer>> {
+        self.0
+            .header_hash(height)
+            .map(|header_hash| header_hash.to_js())
+            .transpose()
+    }
+
+    #[napi]
+    /// Retrieves the state of a coin.
+    ///
+    /// @param {Buffer} coinId - The coin ID.
+    /// @returns {Option<CoinState>} The coin state. Null if the coin was never created.
+    pub fn coin_state(&self, coin_id: Buffer) -> napi::Result<Option<CoinState>> {
+        self.0
+            .coin_state(RustBytes32::from_js(coin_id).arg("coinId")?)
+            .map(|coin_state| coin_state.to_js())
+            .transpose()
+    }
+
+    #[napi]
+    /// Retrieves the genesis challenge of the simulated network, to use as the header hash when syncing from the genesis block.
+    ///
+    /// @returns {Buffer} The genesis challenge.
+    pub fn genesis_challenge(&self) -> napi::Result<Buffer> {
+        self.0.network().genesis_challenge.to_js()
+    }
+}
+
+#[napi]
+pub struct PeerPool {
+    inner: Arc<RustPeerPool>,
+}
+
+#[napi]
+impl PeerPool {
+    #[napi(factory)]
+    /// Creates a new pool of peers. Requests are routed to a connected peer and retried on another one if the node rejects them or the connection drops.
+    ///
+    /// @param {Vec<String>} nodeUris - URIs of the nodes (e.g., ['127.0.0.1:58444']). Nodes that can't be reached right away are retried later.
+    /// @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+    /// @param {Tls} tls - TLS connector.
+    /// @returns {Promise<PeerPool>} A new PeerPool instance.
+    pub async fn new(
+        node_uris: Vec<String>,
+        network: Either<bool, Network>,
+        tls: &Tls,
+    ) -> napi::Result<Self> {
+        let socket_addrs = node_uris
+            .into_iter()
+            .map(parse_node_uri)
+            .collect::<Result<Vec<SocketAddr>>>()?;
+
+        let pool = RustPeerPool::connect(
+            TargetNetwork::from_js(network).arg("network")?.network_id,
+            tls.0.clone(),
+            socket_addrs,
+        )
+        .await
+        .map_err(js::err)?;
+
+        Ok(Self {
+            inner: Arc::new(pool),
+        })
+    }
+
+    #[napi(factory)]
+    /// Creates a new pool of peers connected to full nodes found through the network's DNS introducers (or introducers, if DNS doesn't return any nodes).
+    ///
+    /// @param {bool | Network} network - Network to connect to. True for testnet11, false for mainnet, or a network descriptor.
+    /// @param {Tls} tls - TLS connector.
+    /// @param {Option<u32>} maxPeers - Maximum number of nodes to connect to. Defaults to 3.
+    /// @returns {Promise<PeerPool>} A new PeerPool instance.
+    pub async fn discover(
+        network: Either<bool, Network>,
+        tls: &Tls,
+        max_peers: Option<u32>,
+    ) -> napi::Result<Self> {
+        let network = TargetNetwork::from_js(network).arg("network")?;
+        let socket_addrs = Discovery::for_network(&network, tls.0.clone())
+            .discover()
+            .await
+            .map_err(js::err)?;
+
+        let pool = RustPeerPool::new(network.network_id, Some(tls.0.clone()));
+
+        if pool
+            .add_discovered_peers(socket_addrs, max_peers.unwrap_or(3) as usize)
+            .await
+            == 0
+        {
+            return Err(js::err(WalletError::NoPeers));
+        }
+
+        Ok(Self {
+            inner: Arc::new(pool),
+        })
+    }
+
+    #[napi]
+    /// Connects to a new node and adds it to the pool.
+    ///
+    /// @param {String} nodeUri - URI of the node (e.g., '127.0.0.1:58444').
+    pub async fn add_peer(&self, node_uri: String) -> napi::Result<()> {
+        self.inner
+            .add_peer(parse_node_uri(node_uri)?)
+            .await
+            .map_err(js::err)
+    }
+
+    #[napi]
+    /// Removes a node from the pool and closes its connection.
+    ///
+    /// @param {String} nodeUri - URI of the node (e.g., '127.0.0.1:58444').
+    /// @returns {Promise<bool>} Whether the node was part of the pool.
+    pub async fn remove_peer(&self, node_uri: String) -> napi::Result<bool> {
+        Ok(self.inner.remove_peer(parse_node_uri(node_uri)?).await)
+    }
+
+    #[napi]
+    /// Lists the nodes the pool currently has a live connection to.
+    ///
+    /// @returns {Promise<Vec<String>>} URIs of the connected nodes.
+    pub async fn connected_peers(&self) -> napi::Result<Vec<String>> {
+        Ok(self
+            .inner
+            .connected_peers()
+            .await
+            .into_iter()
+            .map(|socket_addr| socket_addr.to_string())
+            .collect())
+    }
+
+    #[napi]
+    /// Lists all nodes in the pool, including the ones that are currently disconnected and waiting to be reconnected.
+    ///
+    /// @returns {Promise<Vec<String>>} URIs of the nodes.
+    pub async fn peers(&self) -> napi::Result<Vec<String>> {
+        Ok(self
+            .inner
+            .peers()
+            .await
+            .into_iter()
+            .map(|socket_addr| socket_addr.to_string())
+            .collect())
+    }
+
+    #[napi]
+    /// Retrieves all coins that are unspent on the chain. See `Peer.getAllUnspentCoins`.
+    ///
+    /// @param {Buffer} puzzleHash - Puzzle hash of the wallet.
+    /// @param {Option<u32>} previousHeight - Previous height that was spent. If null, sync will be done from the genesis block.
+    /// @param {Buffer} previousHeaderHash - Header hash corresponding to the previous height. If previousHeight is null, this should be the genesis challenge of the current chain.
+    /// @returns {Promise<UnspentCoinsResponse>} The unspent coins response.
+    pub async fn get_all_unspent_coins(
+        &self,
+        puzzle_hash: Buffer,
+        previous_height: Option<u32>,
+        previous_header_hash: Buffer,
+    ) -> napi::Result<UnspentCoinsResponse> {
+        let puzzle_hash = RustBytes32::from_js(puzzle_hash).arg("puzzleHash")?;
+        let previous_header_hash =
+            RustBytes32::from_js(previous_header_hash).arg("previousHeaderHash")?;
+
+        let resp: rust::UnspentCoinsResponse = self
+            .inner
+            .request(|peer| async move {
+                get_unspent_coin_states(
+                    &peer,
+                    puzzle_hash,
+                    previous_height,
+                    previous_header_hash,
+                    false,
+                )
+                .await
+            })
+            .await
+            .map_err(js::err)?
+            .into();
+
+        resp.to_js()
+    }
+
+    #[napi]
+    /// Synchronizes a datastore. See `Peer.syncStore`.
+    ///
+    /// @param {DataStore} store - Data store.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @param {bool} withHistory - Whether to return the root hash history of the store.
+    /// @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+    /// @returns {Promise<SyncStoreResponse>} The sync store response.
+    pub async fn sync_store(
+        &self,
+        store: DataStore,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+        with_history: bool,
+        known_history: Option<Vec<StoreHistoryEntry>>,
+    ) -> napi::Result<SyncStoreResponse> {
+        let store = RustDataStore::from_js(store).arg("store")?;
+        let last_header_hash = RustBytes32::from_js(last_header_hash).arg("lastHeaderHash")?;
+        let known_history = known_history_from_js(known_history, store.info.launcher_id)?;
+
+        self.inner
+            .request(|peer| {
+                let store = store.clone();
+                let known_history = known_history.clone();
+
+                async move {
+                    sync_store(
+                        &peer,
+                        &store,
+                        last_height,
+                        last_header_hash,
+                        with_history,
+                        &known_history,
+                    )
+                    .await
+                }
+            })
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Synchronizes a store using its launcher ID. See `Peer.syncStoreFromLauncherId`.
+    ///
+    /// @param {Buffer} launcherId - The store's launcher/singleton ID.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} lastHeaderHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @param {bool} withHistory - Whether to return the root hash history of the store.
+    /// @param {Option<Vec<StoreHistoryEntry>>} knownHistory - History synced before, oldest first, e.g. the `history` of previous responses. If a reorg happened, the sync resumes from its newest state that is still part of the chain and reports the others as orphaned.
+    /// @returns {Promise<SyncStoreResponse>} The sync store response.
+    pub async fn sync_store_from_launcher_id(
+        &self,
+        launcher_id: Buffer,
+        last_height: Option<u32>,
+        last_header_hash: Buffer,
+        with_history: bool,
+        known_history: Option<Vec<StoreHistoryEntry>>,
+    ) -> napi::Result<SyncStoreResponse> {
+        let launcher_id = RustBytes32::from_js(launcher_id).arg("launcherId")?;
+        let last_header_hash = RustBytes32::from_js(last_header_hash).arg("lastHeaderHash")?;
+        let known_history = known_history_from_js(known_history, launcher_id)?;
+
+        self.inner
+            .request(|peer| {
+                let known_history = known_history.clone();
+
+                async move {
+                    sync_store_using_launcher_id(
+                        &peer,
+                        launcher_id,
+                        last_height,
+                        last_header_hash,
+                        with_history,
+                        &known_history,
+                    )
+                    .await
+                }
+            })
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Broadcasts a spend bundle to the mempool. See `Peer.broadcastSpend`.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+    /// @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+    /// @returns {Promise<String>} The broadcast error. If '', the broadcast was successful.
+    pub async fn broadcast_spend(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        sigs: Vec<Buffer>,
+    ) -> napi::Result<String> {
+        let spend_bundle = spend_bundle_from_js(coin_spends, sigs)?;
+
+        Ok(self
+            .inner
+            .request(|peer| {
+                let spend_bundle = spend_bundle.clone();
+
+                async move { wallet::broadcast_spend_bundle(&peer, spend_bundle).await }
+            })
+            .await
+            .map_err(js::err)?
+            .error
+            .unwrap_or(String::default()))
+    }
+
+    #[napi]
+    /// Broadcasts a spend bundle to the mempool, throwing if it's refused. See `Peer.sendSpend`.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+    /// @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+    /// @returns {Promise<Buffer>} The id of the spend bundle.
+    pub async fn send_spend(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        sigs: Vec<Buffer>,
+    ) -> napi::Result<Buffer> {
+        let spend_bundle = spend_bundle_from_js(coin_spends, sigs)?;
+
+        self.inner
+            .request(|peer| {
+                let spend_bundle = spend_bundle.clone();
+
+                async move { wallet::send_spend_bundle(&peer, spend_bundle).await }
+            })
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Broadcasts a transaction rebuilt with `bumpFee`, throwing if it doesn't replace the pending one. See `Peer.broadcastFeeBump`.
+    ///
+    /// @param {FeeBump} feeBump - The transaction with the higher fee, as returned by `bumpFee`.
+    /// @param {Vec<Buffer>} sigs - The signatures of its coin spends, to be aggregated and included in the bundle.
+    /// @returns {Promise<Buffer>} The id of the new spend bundle.
+    pub async fn broadcast_fee_bump(
+        &self,
+        fee_bump: FeeBump,
+        sigs: Vec<Buffer>,
+    ) -> napi::Result<Buffer> {
+        let fee_bump = RustFeeBump::from_js(fee_bump).arg("feeBump")?;
+        let signature = aggregate_signature_from_js(sigs)?;
+
+        self.inner
+            .request(|peer| {
+                let fee_bump = fee_bump.clone();
+                let signature = signature.clone();
+
+                async move { fees::broadcast_fee_bump(&peer, &fee_bump, signature).await }
+            })
+            .await
+            .map_err(js::err)?
+            .txid
+            .to_js()
+    }
+
+    #[napi]
+    /// Validates a spend bundle and checks that its coins are unspent on chain. See `Peer.validateSpendBundle`.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+    /// @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+    /// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor.
+    /// @returns {Promise<SpendBundleConditions>} The conditions of the bundle.
+    pub async fn validate_spend_bundle(
+        &self,
+        coin_spends: Vec<CoinSpend>,
+        sigs: Vec<Buffer>,
+        network: Either<bool, Network>,
+    ) -> napi::Result<SpendBundleConditions> {
+        let spend_bundle = spend_bundle_from_js(coin_spends, sigs)?;
+        let network = TargetNetwork::from_js(network).arg("network")?;
+
+        self.inner
+            .request(|peer| {
+                let spend_bundle = spend_bundle.clone();
+                let network = network.clone();
+
+                async move {
+                    wallet::validate_spend_bundle_on_chain(&peer, &spend_bundle, &network).await
+                }
+            })
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Checks if a coin is spent on-chain. See `Peer.isCoinSpent`.
+    ///
+    /// @param {Buffer} coinId - The coin ID.
+    /// @param {Option<u32>} lastHeight - Min. height to search records from. If null, sync will be done from the genesis block.
+    /// @param {Buffer} headerHash - Header hash corresponding to `lastHeight`. If null, this should be the genesis challenge of the current chain.
+    /// @returns {Promise<bool>} Whether the coin is spent on-chain.
+    pub async fn is_coin_spent(
+        &self,
+        coin_id: Buffer,
+        last_height: Option<u32>,
+        header_hash: Buffer,
+    ) -> napi::Result<bool> {
+        let coin_id = RustBytes32::from_js(coin_id).arg("coinId")?;
+        let header_hash = RustBytes32::from_js(header_hash).arg("headerHash")?;
+
+        self.inner
+            .request(
+                |peer| async move { is_coin_spent(&peer, coin_id, last_height, header_hash).await },
+            )
+            .await
+            .map_err(js::err)
+    }
+
+    #[napi]
+    /// Retrieves the current header hash corresponding to a given height. See `Peer.getHeaderHash`.
+    ///
+    /// @param {u32} height - The height.
+    /// @returns {Promise<Buffer>} The header hash.
+    pub async fn get_header_hash(&self, height: u32) -> napi::Result<Buffer> {
+        self.inner
+            .request(|peer| async move { get_header_hash(&peer, height).await })
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Retrieves the fee estimate for a given target time. See `Peer.getFeeEstimate`.
+    ///
+    /// @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time for the fee estimate.
+    /// @returns {Promise<BigInt>} The estimated fee in mojos per CLVM cost.
+    pub async fn get_fee_estimate(&self, target_time_seconds: BigInt) -> napi::Result<BigInt> {
+        let target_time_seconds = u64::from_js(target_time_seconds).arg("targetTimeSeconds")?;
+
+        self.inner
+            .request(
+                |peer| async move { wallet::get_fee_estimate(&peer, target_time_seconds).await },
+            )
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Gets a fee policy for confirming a transaction within a given time. See `Peer.feePolicyForTargetTime`.
+    ///
+    /// @param {BigInt} targetTimeSeconds - Time delta: The target time in seconds from the current time.
+    /// @returns {Promise<FeePolicy>} A policy with the estimated fee per CLVM cost.
+    pub async fn fee_policy_for_target_time(
+        &self,
+        target_time_seconds: BigInt,
+    ) -> napi::Result<FeePolicy> {
+        let target_time_seconds = u64::from_js(target_time_seconds).arg("targetTimeSeconds")?;
+
+        self.inner
+            .request(|peer| async move {
+                RustFeePolicy::from_target_time(&peer, target_time_seconds).await
+            })
+            .await
+            .map_err(js::err)?
+            .to_js()
+    }
+}
+
+#[napi(object)]
+/// How the fee of a transaction is determined. Exactly one of the fields must be set. Use `Peer.feePolicyForTargetTime` to get a policy for a confirmation time.
+///
+/// @property {Option<BigInt>} fixed - A fixed fee, regardless of the transaction's cost.
+/// @property {Option<BigInt>} mojosPerCost - A fee of this many mojos per unit of the transaction's CLVM cost.
+pub struct FeePolicy {
+    pub fixed: Option<BigInt>,
+    pub mojos_per_cost: Option<BigInt>,
+}
+
+impl FromJs<FeePolicy> for RustFeePolicy {
+    fn from_js(value: FeePolicy) -> Result<Self> {
+        match (value.fixed, value.mojos_per_cost) {
+            (Some(fixed), None) => Ok(RustFeePolicy::Fixed(u64::from_js(fixed).arg("fixed")?)),
+            (None, Some(mojos_per_cost)) => Ok(RustFeePolicy::MojosPerCost(
+                u64::from_js(mojos_per_cost).arg("mojosPerCost")?,
+            )),
+            _ => Err(js::err(ConversionError::InvalidArgument(
+                "Exactly one of fixed, mojos_per_cost must be provided",
+            ))),
+        }
+    }
+}
+
+impl ToJs<FeePolicy> for RustFeePolicy {
+    fn to_js(&self) -> Result<FeePolicy> {
+        Ok(match self {
+            RustFeePolicy::Fixed(fixed) => FeePolicy {
+                fixed: Some(fixed.to_js()?),
+                mojos_per_cost: None,
+            },
+            RustFeePolicy::MojosPerCost(mojos_per_cost) => FeePolicy {
+                fixed: None,
+                mojos_per_cost: Some(mojos_per_cost.to_js()?),
+            },
+        })
+    }
+}
+
+#[napi(object)]
+/// A driver response for a spend whose fee covers its own cost.
+///
+/// @property {Vec<CoinSpend>} coinSpends - Coin spends that can be used to spend the provided store.
+/// @property {DataStore} newStore - New data store information after the spend is confirmed.
+/// @property {BigInt} fee - Fee paid.
+/// @property {BigInt} cost - CLVM cost of the coin spends.
+pub struct PricedSuccessResponse {
+    pub coin_spends: Vec<CoinSpend>,
+    pub new_store: DataStore,
+    pub fee: BigInt,
+    pub cost: BigInt,
+}
+
+impl ToJs<PricedSuccessResponse> for RustPricedResponse {
+    fn to_js(&self) -> Result<PricedSuccessResponse> {
+        let response = self.response.to_js()?;
+
+        Ok(PricedSuccessResponse {
+            coin_spends: response.coin_spends,
+            new_store: response.new_store,
+            fee: self.fee.to_js()?,
+            cost: self.cost.to_js()?,
+        })
+    }
+}
+
+#[napi(object)]
+/// A pending transaction rebuilt with a higher fee.
+///
+/// @property {Vec<CoinSpend>} coinSpends - The new coin spends. They have to be signed again before they're broadcast.
+/// @property {BigInt} fee - New fee.
+/// @property {BigInt} cost - New CLVM cost.
+/// @property {BigInt} previousFee - Fee of the pending transaction.
+/// @property {BigInt} previousCost - CLVM cost of the pending transaction.
+pub struct FeeBump {
+    pub coin_spends: Vec<CoinSpend>,
+    pub fee: BigInt,
+    pub cost: BigInt,
+    pub previous_fee: BigInt,
+    pub previous_cost: BigInt,
+}
+
+impl ToJs<FeeBump> for RustFeeBump {
+    fn to_js(&self) -> Result<FeeBump> {
+        Ok(FeeBump {
+            coin_spends: self
+                .coin_spends
+                .iter()
+                .map(RustCoinSpend::to_js)
+                .collect::<Result<Vec<CoinSpend>>>()?,
+            fee: self.fee.to_js()?,
+            cost: self.cost.to_js()?,
+            previous_fee: self.previous_fee.to_js()?,
+            previous_cost: self.previous_cost.to_js()?,
+        })
+    }
+}
+
+impl FromJs<FeeBump> for RustFeeBump {
+    fn from_js(value: FeeBump) -> Result<Self> {
+        Ok(RustFeeBump {
+            coin_spends: value
+                .coin_spends
+                .into_iter()
+                .map(RustCoinSpend::from_js)
+                .collect::<Result<Vec<RustCoinSpend>>>()
+                .arg("coinSpends")?,
+            fee: u64::from_js(value.fee).arg("fee")?,
+            cost: u64::from_js(value.cost).arg("cost")?,
+            previous_fee: u64::from_js(value.previous_fee).arg("previousFee")?,
+            previous_cost: u64::from_js(value.previous_cost).arg("previousCost")?,
+        })
+    }
+}
+
+#[napi(object)]
+/// A signature a transaction needs.
+///
+/// @property {Buffer} publicKey - Public key that has to sign.
+/// @property {Buffer} message - Final message to sign, including any additional data.
+pub struct RequiredSignature {
+    pub public_key: Buffer,
+    pub message: Buffer,
+}
+
+#[napi(object)]
+/// A transaction built by a `TransactionBuilder`.
+///
+/// @property {Vec<CoinSpend>} coinSpends - Coin spends of the whole transaction, to be signed and broadcast together.
+/// @property {Vec<DataStore>} newStores - States of the stores after the transaction, in the order they were first used. Melted stores are left out.
+/// @property {Vec<Coin>} selectedCoins - Wallet coins spent by the transaction.
+/// @property {BigInt} change - Change sent back to the wallet.
+/// @property {BigInt} fee - Fee paid, including the mojos of melted stores.
+/// @property {BigInt} cost - CLVM cost of the transaction.
+/// @property {Vec<RequiredSignature>} requiredSignatures - Signatures the transaction needs.
+pub struct Transaction {
+    pub coin_spends: Vec<CoinSpend>,
+    pub new_stores: Vec<DataStore>,
+    pub selected_coins: Vec<Coin>,
+    pub change: BigInt,
+    pub fee: BigInt,
+    pub cost: BigInt,
+    pub required_signatures: Vec<RequiredSignature>,
+}
+
+impl ToJs<Transaction> for RustTransaction {
+    fn to_js(&self) -> Result<Transaction> {
+        Ok(Transaction {
+            coin_spends: self
+                .coin_spends
+                .iter()
+                .map(RustCoinSpend::to_js)
+                .collect::<Result<Vec<CoinSpend>>>()?,
+            new_stores: self
+                .new_stores
+                .iter()
+                .map(RustDataStore::to_js)
+                .collect::<Result<Vec<DataStore>>>()?,
+            selected_coins: self
+                .selected_coins
+                .iter()
+                .map(RustCoin::to_js)
+                .collect::<Result<Vec<Coin>>>()?,
+            change: self.change.to_js()?,
+            fee: self.fee.to_js()?,
+            cost: self.cost.to_js()?,
+            required_signatures: self
+                .required_signatures
+                .iter()
+                .map(|required| {
+                    Ok(RequiredSignature {
+                        public_key: required.public_key().to_js()?,
+                        message: Buffer::from(required.final_message()),
+                    })
+                })
+                .collect::<Result<Vec<RequiredSignature>>>()?,
+        })
+    }
+}
+
+#[napi]
+/// Combines store operations and XCH payments into a single transaction. Coins are selected once when the transaction is built, to pay for the outputs, oracle fees and the fee, with the change going back to the wallet.
+pub struct TransactionBuilder(RustTransactionBuilder);
+
+#[napi]
+impl TransactionBuilder {
+    #[napi(constructor)]
+    /// Creates an empty transaction builder.
+    ///
+    /// @param {Buffer} syntheticKey - Synthetic key of the wallet that pays for the transaction.
+    pub fn new(synthetic_key: Buffer) -> napi::Result<Self> {
+        Ok(Self(RustTransactionBuilder::new(RustPublicKey::from_js(
+            synthetic_key,
+        )?)))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[napi]
+    /// Adds a metadata update. Either the owner, admin, or writer public key must be provided. If the store was already spent in this transaction, the update spends the latest state.
+    ///
+    /// @param {DataStore} store - Store information.
+    /// @param {Buffer} newRootHash - New root hash.
+    /// @param {Option<String>} newLabel - New label (optional).
+    /// @param {Option<String>} newDescription - New description (optional).
+    /// @param {Option<BigInt>} newBytes - New size in bytes (optional).
+    /// @param {Option<Buffer>} ownerPublicKey - Owner public key.
+    /// @param {Option<Buffer>} adminPublicKey - Admin public key.
+    /// @param {Option<Buffer>} writerPublicKey - Writer public key.
+    /// @returns {DataStore} The store after the update.
+    pub fn update_store_metadata(
+        &mut self,
+        store: DataStore,
+        new_root_hash: Buffer,
+        new_label: Option<String>,
+        new_description: Option<String>,
+        new_bytes: Option<BigInt>,
+        owner_public_key: Option<Buffer>,
+        admin_public_key: Option<Buffer>,
+        writer_public_key: Option<Buffer>,
+    ) -> napi::Result<DataStore> {
+        self.0
+            .update_store_metadata(
+                RustDataStore::from_js(store).arg("store")?,
+                RustBytes32::from_js(new_root_hash).arg("newRootHash")?,
+                new_label,
+                new_description,
+                new_bytes.map(u64::from_js).transpose().arg("newBytes")?,
+                metadata_inner_spend(owner_public_key, admin_public_key, writer_public_key)?,
+            )
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Adds an ownership update. Either the admin or owner public key must be provided.
+    ///
+    /// @param {DataStore} store - Store information.
+    /// @param {Option<Buffer>} newOwnerPuzzleHash - New owner puzzle hash. If null, the owner doesn't change.
+    /// @param {Vec<DelegatedPuzzle>} newDelegatedPuzzles - New delegated puzzles.
+    /// @param {Option<Buffer>} ownerPublicKey - Owner public key.
+    /// @param {Option<Buffer>} adminPublicKey - Admin public key.
+    /// @returns {DataStore} The store after the update.
+    pub fn update_store_ownership(
+        &mut self,
+        store: DataStore,
+        new_owner_puzzle_hash: Option<Buffer>,
+        new_delegated_puzzles: Vec<DelegatedPuzzle>,
+        owner_public_key: Option<Buffer>,
+        admin_public_key: Option<Buffer>,
+    ) -> napi::Result<DataStore> {
+        let store = RustDataStore::from_js(store).arg("store")?;
+        let new_owner_puzzle_hash = new_owner_puzzle_hash
+            .map(RustBytes32::from_js)
+            .unwrap_or_else(|| Ok(store.info.owner_puzzle_hash))?;
+
+        self.0
+            .update_store_ownership(
+                store,
+                new_owner_puzzle_hash,
+                new_delegated_puzzles
+                    .into_iter()
+                    .map(RustDelegatedPuzzle::from_js)
+                    .collect::<Result<Vec<RustDelegatedPuzzle>>>()
+                    .arg("newDelegatedPuzzles")?,
+                ownership_inner_spend(owner_public_key, admin_public_key)?,
+            )
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Adds a spend of the store in oracle mode. The oracle fee is paid from the selected coins.
+    ///
+    /// @param {DataStore} store - Store information.
+    /// @returns {DataStore} The store after the spend.
+    pub fn oracle_spend(&mut self, store: DataStore) -> napi::Result<DataStore> {
+        self.0
+            .oracle_spend(RustDataStore::from_js(store).arg("store")?)
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Adds a melt of the store. The store's mojo is added to the fee.
+    ///
+    /// @param {DataStore} store - Store information.
+    /// @param {Buffer} ownerPublicKey - Owner's public key.
+    pub fn melt_store(&mut self, store: DataStore, owner_public_key: Buffer) -> napi::Result<()> {
+        self.0
+            .melt_store(
+                RustDataStore::from_js(store).arg("store")?,
+                RustPublicKey::from_js(owner_public_key).arg("ownerPublicKey")?,
+            )
+            .map_err(js::err)
+    }
+
+    #[napi]
+    /// Adds an XCH payment.
+    ///
+    /// @param {Output} output - Puzzle hash, amount and memos of the coin to create.
+    pub fn send_xch(&mut self, output: Output) -> napi::Result<()> {
+        self.0.send_xch(
+            RustBytes32::from_js(output.puzzle_hash)?,
+            u64::from_js(output.amount)?,
+            output
+                .memos
+                .into_iter()
+                .map(RustBytes::from_js)
+                .collect::<Result<Vec<RustBytes>>>()
+                .arg("memos")?,
+        );
+        Ok(())
+    }
+
+    #[napi]
+    /// Sets the transaction fee.
+    ///
+    /// @param {BigInt} fee - Fee, in mojos.
+    pub fn set_fee(&mut self, fee: BigInt) -> napi::Result<()> {
+        self.0.set_fee(u64::from_js(fee).arg("fee")?);
+        Ok(())
+    }
+
+    #[napi]
+    /// Computes the amount the selected coins have to cover.
+    ///
+    /// @returns {BigInt} The total of the outputs, oracle fees and the fee.
+    pub fn required_amount(&self) -> napi::Result<BigInt> {
+        self.0.required_amount().to_js()
+    }
+
+    #[napi]
+    /// Selects coins and builds the transaction like `build`, raising the fee until it covers the transaction's cost. Replaces the fee set with `setFee`.
+    ///
+    /// @param {Vec<Coin>} coins - Wallet coins to select from.
+    /// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the cost and required signatures.
+    /// @param {FeePolicy} feePolicy - How the fee is determined.
+    /// @returns {Transaction} The transaction, with its fee and cost.
+    pub fn build_with_fee_policy(
+        &mut self,
+        coins: Vec<Coin>,
+        network: Either<bool, Network>,
+        fee_policy: FeePolicy,
+    ) -> napi::Result<Transaction> {
+        self.0
+            .build_with_fee_policy(
+                coins
+                    .into_iter()
+                    .map(RustCoin::from_js)
+                    .collect::<Result<Vec<RustCoin>>>()
+                    .arg("coins")?,
+                &TargetNetwork::from_js(network).arg("network")?,
+                RustFeePolicy::from_js(fee_policy).arg("feePolicy")?,
+            )
+            .map_err(js::err)?
+            .to_js()
+    }
+
+    #[napi]
+    /// Selects coins and builds the transaction. The builder can be built again, e.g. with other coins.
+    ///
+    /// @param {Vec<Coin>} coins - Wallet coins to select from.
+    /// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the required signatures.
+    /// @returns {Transaction} The transaction.
+    pub fn build(
+        &self,
+        coins: Vec<Coin>,
+        network: Either<bool, Network>,
+    ) -> napi::Result<Transaction> {
+        self.0
+            .build(
+                coins
+                    .into_iter()
+                    .map(RustCoin::from_js)
+                    .collect::<Result<Vec<RustCoin>>>()
+                    .arg("coins")?,
+                &TargetNetwork::from_js(network).arg("network")?,
+            )
+            .map_err(js::err)?
+            .to_js()
+    }
+}
+
+#[napi(object)]
+/// A signature an unsigned transaction needs.
+///
+/// @property {Buffer} publicKey - Public key that has to sign.
+/// @property {Buffer} message - Final message to sign, including any additional data.
+/// @property {Option<Buffer>} signature - The signature, once it's been added.
+pub struct SignatureRequest {
+    pub public_key: Buffer,
+    pub message: Buffer,
+    pub signature: Option<Buffer>,
+}
+
+#[napi]
+/// Coin spends waiting to be signed, e.g. on an air-gapped machine. Export them with `toBytes`, sign them elsewhere with `UnsignedTransaction.fromBytes(bytes).sign(privateKeys)`, bring the signed bytes back with `importSignatures` and broadcast `coinSpends()` with `aggregatedSignature()`.
+pub struct UnsignedTransaction(RustUnsignedTransaction);
+
+#[napi]
+impl UnsignedTransaction {
+    #[napi(constructor)]
+    /// Computes the signatures the coin spends need.
+    ///
+    /// @param {Vec<CoinSpend>} coinSpends - The coin spends to sign.
+    /// @param {bool | Network} network - Network to sign for. True for testnet11, false for mainnet, or a network descriptor.
+    /// @param {String} intent - Human-readable description of what the transaction does, shown to the signers. It isn't checked against the coin spends.
+    pub fn new(
+        coin_spends: Vec<CoinSpend>,
+        network: Either<bool, Network>,
+        intent: String,
+    ) -> napi::Result<Self> {
+        Ok(Self(
+            RustUnsignedTransaction::new(
+                coin_spends
+                    .into_iter()
+                    .map(RustCoinSpend::from_js)
+                    .collect::<Result<Vec<RustCoinSpend>>>()
+                    .arg("coinSpends")?,
+                &TargetNetwork::from_js(network).arg("network")?,
+                intent,
+            )
+            .map_err(js::err)?,
+        ))
+    }
+
+    #[napi(factory)]
+    /// Parses an exported transaction. The required signatures are computed again from the coin spends, and the signatures it holds have to verify.
+    ///
+    /// @param {Buffer} bytes - The output of `toBytes`.
+    pub fn from_bytes(bytes: Buffer) -> napi::Result<Self> {
+        Ok(Self(
+            RustUnsignedTransaction::from_bytes(&bytes).map_err(js::err)?,
+        ))
+    }
+
+    #[napi]
+    /// Serializes the transaction, with the signatures added so far.
+    ///
+    /// @returns {Buffer} The serialized transaction.
+    pub fn to_bytes(&self) -> napi::Result<Buffer> {
+        Ok(Buffer::from(self.0.to_bytes().map_err(js::err)?))
+    }
+
+    #[napi]
+    /// @returns {String} The human-readable description of the transaction.
+    pub fn intent(&self) -> String {
+        self.0.intent().to_string()
+    }
+
+    #[napi]
+    /// @returns {Network} The network the transaction is signed for.
+    pub fn network(&self) -> napi::Result<Network> {
+        self.0.network().to_js()
+    }
+
+    #[napi]
+    /// @returns {Vec<CoinSpend>} The coin spends.
+    pub fn coin_spends(&self) -> napi::Result<Vec<CoinSpend>> {
+        self.0
+            .coin_spends()
+            .iter()
+            .map(RustCoinSpend::to_js)
+            .collect()
+    }
+
+    #[napi]
+    /// @returns {Vec<SignatureRequest>} The signatures the transaction needs, with the ones added so far.
+    pub fn required_signatures(&self) -> napi::Result<Vec<SignatureRequest>> {
+        self.0
+            .required_signatures()
+            .iter()
+            .map(|request| {
+                Ok(SignatureRequest {
+                    public_key: request.public_key.to_js()?,
+                    message: request.message.to_js()?,
+                    signature: request
+                        .signature
+                        .as_ref()
+                        .map(RustSignature::to_js)
+                        .transpose()?,
+                })
+            })
+            .collect()
+    }
+
+    #[napi]
+    /// @returns {Vec<Buffer>} Public keys of the signatures that haven't been added yet.
+    pub fn missing_signatures(&self) -> napi::Result<Vec<Buffer>> {
+        self.0
+            .missing_signatures()
+            .iter()
+            .map(RustPublicKey::to_js)
+            .collect()
+    }
+
+    #[napi]
+    /// Signs the missing signatures whose public key is one of the keys or its synthetic key.
+    ///
+    /// @param {Vec<Buffer>} privateKeys - The private/secret keys to sign with.
+    /// @returns {number} The number of signatures added.
+    pub fn sign(&mut self, private_keys: Vec<Buffer>) -> napi::Result<u32> {
+        let private_keys = private_keys
+            .into_iter()
+            .map(RustSecretKey::from_js)
+            .collect::<Result<Vec<RustSecretKey>>>()
+            .arg("privateKeys")?;
+
+        Ok(self.0.sign(&private_keys) as u32)
+    }
+
+    #[napi]
+    /// Adds a signature made elsewhere, e.g. by a hardware wallet. Throws a `SigningError` if it isn't required or doesn't verify.
+    ///
+    /// @param {Buffer} publicKey - Public key of the signature request.
+    /// @param {Buffer} message - Message of the signature request.
+    /// @param {Buffer} signature - The signature.
+    pub fn add_signature(
+        &mut self,
+        public_key: Buffer,
+        message: Buffer,
+        signature: Buffer,
+    ) -> napi::Result<()> {
+        self.0
+            .add_signature(
+                RustPublicKey::from_js(public_key).arg("publicKey")?,
+                &message,
+                RustSignature::from_js(signature).arg("signature")?,
+            )
+            .map_err(js::err)
+    }
+
+    #[napi]
+    /// Copies the signatures of a copy of the transaction that was signed elsewhere.
+    ///
+    /// @param {Buffer} signed - The signed transaction, as returned by its `toBytes`.
+    /// @returns {number} The number of signatures added.
+    pub fn import_signatures(&mut self, signed: Buffer) -> napi::Result<u32> {
+        let signed = RustUnsignedTransaction::from_bytes(&signed)
+            .map_err(js::err)
+            .arg("signed")?;
+
+        Ok(self.0.import_signatures(&signed).map_err(js::err)? as u32)
+    }
+
+    #[napi]
+    /// Aggregates the signatures, to broadcast with `coinSpends()`. Throws a `SigningError` listing the public keys if any signature is missing.
+    ///
+    /// @returns {Buffer} The aggregated signature.
+    pub fn aggregated_signature(&self) -> napi::Result<Buffer> {
+        self.0
+            .spend_bundle()
+            .map_err(js::err)?
+            .aggregated_signature
+            .to_js()
+    }
+}
+
+#[napi(string_enum)]
+/// What a decoded coin spend does.
+pub enum SpendActionKind {
+    LaunchStore,
+    UpdateStore,
+    MeltStore,
+    PayOracle,
+    Payment,
+    Unknown,
+}
+
+#[napi(object)]
+/// A coin created by a standard coin spend.
+///
+/// @property {Buffer} puzzleHash - Puzzle hash of the coin.
+/// @property {BigInt} amount - Amount of the coin.
+/// @property {Vec<Buffer>} memos - Memos of the coin, e.g. its hint.
+pub struct PaymentOutput {
+    pub puzzle_hash: Buffer,
+    pub amount: BigInt,
+    pub memos: Vec<Buffer>,
+}
+
+#[napi(object)]
+/// A server coin created by a standard coin spend.
+///
+/// @property {Buffer} hint - The hint of the server coin, usually the original or morphed launcher id.
+/// @property {BigInt} amount - Amount of the server coin.
+/// @property {Vec<String>} memoUrls - The URLs the server coin advertises.
+pub struct ServerCoinOutput {
+    pub hint: Buffer,
+    pub amount: BigInt,
+    pub memo_urls: Vec<String>,
+}
+
+#[napi(object)]
+/// A decoded standard coin spend.
+///
+/// @property {Buffer} syntheticKey - Synthetic key of the spent coin.
+/// @property {Vec<PaymentOutput>} outputs - Coins created for other puzzle hashes, except server coins.
+/// @property {Vec<ServerCoinOutput>} serverCoins - Server coins created.
+/// @property {BigInt} change - Amount sent back to the spent coin's puzzle hash.
+pub struct Payment {
+    pub synthetic_key: Buffer,
+    pub outputs: Vec<PaymentOutput>,
+    pub server_coins: Vec<ServerCoinOutput>,
+    pub change: BigInt,
+}
+
+#[napi(object)]
+/// What a coin spend does, decoded by running its puzzle.
+///
+/// @property {Coin} coin - The spent coin.
+/// @property {SpendActionKind} kind - What the spend does.
+/// @property {Vec<Coin>} additions - Coins the spend creates.
+/// @property {BigInt} reservedFee - Fee the spend reserves with RESERVE_FEE conditions.
+/// @property {Option<Buffer>} launcherId - The store's launcher id. Null unless the spend launches, updates, melts or pays the oracle of a store.
+/// @property {Option<StoreSpendKind>} storeSpendKind - What authorized a store update. Null for other spends.
+/// @property {Option<DelegatedPuzzle>} delegatedPuzzle - The delegated puzzle that authorized a store update. Null for other spends and owner spends.
+/// @property {Option<DataStoreMetadata>} previousMetadata - Metadata before a store update. Null for other spends.
+/// @property {Option<DataStoreMetadata>} metadata - Metadata of a launched store, or after a store update. Null for other spends.
+/// @property {Option<Buffer>} previousOwnerPuzzleHash - Owner puzzle hash before a store update. Null for other spends.
+/// @property {Option<Buffer>} ownerPuzzleHash - Owner puzzle hash of a launched or melted store, or after a store update. Null for other spends.
+/// @property {Option<Vec<DelegatedPuzzle>>} delegatedPuzzles - Delegated puzzles of a launched store, or after a store update that replaces them. Null for other spends and updates that keep them.
+/// @property {Option<Buffer>} oraclePuzzleHash - Puzzle hash the oracle fee is paid to. Null unless the spend pays a store's oracle.
+/// @property {Option<BigInt>} oracleFee - The oracle fee. Null unless the spend pays a store's oracle.
+/// @property {Option<Payment>} payment - The decoded standard coin spend. Null for other spends.
+pub struct DecodedSpend {
+    pub coin: Coin,
+    pub kind: SpendActionKind,
+    pub additions: Vec<Coin>,
+    pub reserved_fee: BigInt,
+    pub launcher_id: Option<Buffer>,
+    pub store_spend_kind: Option<StoreSpendKind>,
+    pub delegated_puzzle: Option<DelegatedPuzzle>,
+    pub previous_metadata: Option<DataStoreMetadata>,
+    pub metadata: Option<DataStoreMetadata>,
+    pub previous_owner_puzzle_hash: Option<Buffer>,
+    pub owner_puzzle_hash: Option<Buffer>,
+    pub delegated_puzzles: Option<Vec<DelegatedPuzzle>>,
+    pub oracle_puzzle_hash: Option<Buffer>,
+    pub oracle_fee: Option<BigInt>,
+    pub payment: Option<Payment>,
+}
+
+impl ToJs<DecodedSpend> for RustDecodedSpend {
+    fn to_js(&self) -> Result<DecodedSpend> {
+        let delegated_puzzles = |delegated_puzzles: &[RustDelegatedPuzzle]| {
+            delegated_puzzles
+                .iter()
+                .map(RustDelegatedPuzzle::to_js)
+                .collect::<Result<Vec<DelegatedPuzzle>>>()
+        };
+
+        let mut spend = DecodedSpend {
+            coin: self.coin.to_js()?,
+            kind: SpendActionKind::Unknown,
+            additions: self
+                .additions
+                .iter()
+                .map(RustCoin::to_js)
+                .collect::<Result<Vec<Coin>>>()?,
+            reserved_fee: self.reserved_fee.to_js()?,
+            launcher_id: None,
+            store_spend_kind: None,
+            delegated_puzzle: None,
+            previous_metadata: None,
+            metadata: None,
+            previous_owner_puzzle_hash: None,
+            owner_puzzle_hash: None,
+            delegated_puzzles: None,
+            oracle_puzzle_hash: None,
+            oracle_fee: None,
+            payment: None,
+        };
+
+        match &self.action {
+            RustSpendAction::LaunchStore(info) => {
+                spend.kind = SpendActionKind::LaunchStore;
+                spend.launcher_id = Some(info.launcher_id.to_js()?);
+                spend.metadata = Some(info.metadata.to_js()?);
+                spend.owner_puzzle_hash = Some(info.owner_puzzle_hash.to_js()?);
+                spend.delegated_puzzles = Some(delegated_puzzles(&info.delegated_puzzles)?);
+            }
+            RustSpendAction::UpdateStore(change) => {
+                spend.kind = SpendActionKind::UpdateStore;
+                spend.launcher_id = Some(change.launcher_id.to_js()?);
+                spend.store_spend_kind = Some(change.kind.to_js()?);
+                spend.delegated_puzzle = change
+                    .delegated_puzzle
+                    .as_ref()
+                    .map(RustDelegatedPuzzle::to_js)
+                    .transpose()?;
+                spend.previous_metadata = Some(change.previous_metadata.to_js()?);
+                spend.metadata = Some(change.metadata.to_js()?);
+                spend.previous_owner_puzzle_hash = Some(change.previous_owner_puzzle_hash.to_js()?);
+                spend.owner_puzzle_hash = Some(change.owner_puzzle_hash.to_js()?);
+                spend.delegated_puzzles = change
+                    .delegated_puzzles
+                    .as_deref()
+                    .map(delegated_puzzles)
+                    .transpose()?;
+            }
+            RustSpendAction::MeltStore {
+                launcher_id,
+                owner_puzzle_hash,
+            } => {
+                spend.kind = SpendActionKind::MeltStore;
+                spend.launcher_id = Some(launcher_id.to_js()?);
+                spend.owner_puzzle_hash = Some(owner_puzzle_hash.to_js()?);
+            }
+            RustSpendAction::PayOracle {
+                launcher_id,
+                oracle_puzzle_hash,
+                oracle_fee,
+            } => {
+                spend.kind = SpendActionKind::PayOracle;
+                spend.launcher_id = Some(launcher_id.to_js()?);
+                spend.oracle_puzzle_hash = Some(oracle_puzzle_hash.to_js()?);
+                spend.oracle_fee = Some(oracle_fee.to_js()?);
+            }
+            RustSpendAction::Payment(payment) => {
+                spend.kind = SpendActionKind::Payment;
+                spend.payment = Some(Payment {
+                    synthetic_key: payment.synthetic_key.to_js()?,
+                    outputs: payment
+                        .outputs
+                        .iter()
+                        .map(|output| {
+                            Ok(PaymentOutput {
+                                puzzle_hash: output.puzzle_hash.to_js()?,
+                                amount: output.amount.to_js()?,
+                                memos: output
+                                    .memos
+                                    .iter()
+                                    .map(RustBytes::to_js)
+                                    .collect::<Result<Vec<Buffer>>>()?,
+                            })
+                        })
+                        .collect::<Result<Vec<PaymentOutput>>>()?,
+                    server_coins: payment
+                        .server_coins
+                        .iter()
+                        .map(|server_coin| {
+                            Ok(ServerCoinOutput {
+                                hint: server_coin.hint.to_js()?,
+                                amount: server_coin.amount.to_js()?,
+                                memo_urls: server_coin.memo_urls.clone(),
+                            })
+                        })
+                        .collect::<Result<Vec<ServerCoinOutput>>>()?,
+                    change: payment.change.to_js()?,
+                });
+            }
+            RustSpendAction::Unknown => {}
+        }
+
+        Ok(spend)
+    }
+}
+
+#[napi(object)]
+/// What a set of coin spends does, decoded by running their puzzles.
+///
+/// @property {Vec<DecodedSpend>} spends - One entry per coin spend, in the same order.
+/// @property {BigInt} fee - Amount of the spent coins minus the amount of the created coins, i.e. what the farmer gets.
+pub struct SpendIntent {
+    pub spends: Vec<DecodedSpend>,
+    pub fee: BigInt,
+}
+
+#[napi]
+/// Decodes what coin spends do, e.g. to review a transaction before signing it. Each spend is decoded on its own from its puzzle and solution, without looking up any coins.
+///
+/// @param {Vec<CoinSpend>} coinSpends - The coin spends to decode.
+/// @returns {SpendIntent} What each coin spend does and the fee.
+pub fn decode_spend_intent(coin_spends: Vec<CoinSpend>) -> napi::Result<SpendIntent> {
+    let coin_spends = coin_spends
+        .into_iter()
+        .map(RustCoinSpend::from_js)
+        .collect::<Result<Vec<RustCoinSpend>>>()
+        .arg("coinSpends")?;
+    let intent = intent::decode_spend_intent(&coin_spends).map_err(js::err)?;
+
+    Ok(SpendIntent {
+        spends: intent
+            .spends
+            .iter()
+            .map(RustDecodedSpend::to_js)
+            .collect::<Result<Vec<DecodedSpend>>>()?,
+        fee: intent.fee.to_js()?,
+    })
+}
+
+#[napi(object)]
+/// The conditions of a coin spend, as checked by the mempool.
+///
+/// @property {Coin} coin - The spent coin.
+/// @property {Vec<Coin>} additions - Coins the spend creates.
+/// @property {Option<u32>} heightRelative - Blocks that must pass after the coin was created (ASSERT_HEIGHT_RELATIVE).
+/// @property {Option<BigInt>} secondsRelative - Seconds that must pass after the coin was created (ASSERT_SECONDS_RELATIVE).
+/// @property {Option<u32>} beforeHeightRelative - Blocks after the coin was created before which the spend must be included (ASSERT_BEFORE_HEIGHT_RELATIVE).
+/// @property {Option<BigInt>} beforeSecondsRelative - Seconds after the coin was created before which the spend must be included (ASSERT_BEFORE_SECONDS_RELATIVE).
+/// @property {Option<u32>} birthHeight - Height the coin must have been created at (ASSERT_MY_BIRTH_HEIGHT).
+/// @property {Option<BigInt>} birthSeconds - Timestamp the coin must have been created at (ASSERT_MY_BIRTH_SECONDS).
+pub struct SpendConditions {
+    pub coin: Coin,
+    pub additions: Vec<Coin>,
+    pub height_relative: Option<u32>,
+    pub seconds_relative: Option<BigInt>,
+    pub before_height_relative: Option<u32>,
+    pub before_seconds_relative: Option<BigInt>,
+    pub birth_height: Option<u32>,
+    pub birth_seconds: Option<BigInt>,
+}
+
+#[napi(object)]
+/// The conditions of a validated spend bundle.
+///
+/// @property {Vec<SpendConditions>} spends - One entry per coin spend.
+/// @property {Vec<Coin>} additions - Coins the bundle creates.
+/// @property {Vec<Coin>} removals - Coins the bundle spends.
+/// @property {BigInt} fee - Amount of the removals minus the amount of the additions.
+/// @property {BigInt} reserveFee - Minimum fee the spends reserve with RESERVE_FEE conditions.
+/// @property {u32} heightAbsolute - Height after which the bundle can be included (ASSERT_HEIGHT_ABSOLUTE).
+/// @property {BigInt} secondsAbsolute - Timestamp after which the bundle can be included (ASSERT_SECONDS_ABSOLUTE).
+/// @property {Option<u32>} beforeHeightAbsolute - Height before which the bundle must be included (ASSERT_BEFORE_HEIGHT_ABSOLUTE).
+/// @property {Option<BigInt>} beforeSecondsAbsolute - Timestamp before which the bundle must be included (ASSERT_BEFORE_SECONDS_ABSOLUTE).
+/// @property {BigInt} cost - Cost of the bundle.
+pub struct SpendBundleConditions {
+    pub spends: Vec<SpendConditions>,
+    pub additions: Vec<Coin>,
+    pub removals: Vec<Coin>,
+    pub fee: BigInt,
+    pub reserve_fee: BigInt,
+    pub height_absolute: u32,
+    pub seconds_absolute: BigInt,
+    pub before_height_absolute: Option<u32>,
+    pub before_seconds_absolute: Option<BigInt>,
+    pub cost: BigInt,
+}
+
+impl ToJs<SpendBundleConditions> for OwnedSpendBundleConditions {
+    fn to_js(&self) -> Result<SpendBundleConditions> {
+        let spends = self
+            .spends
+            .iter()
+            .map(|spend| {
+                let coin = RustCoin::new(spend.parent_id, spend.puzzle_hash, spend.coin_amount);
+                let additions = spend
+                    .create_coin
+                    .iter()
+                    .map(|(puzzle_hash, amount, _)| {
+                        RustCoin::new(spend.coin_id, *puzzle_hash, *amount).to_js()
+                    })
+                    .collect::<Result<Vec<Coin>>>()?;
+
+                Ok(SpendConditions {
+                    coin: coin.to_js()?,
+                    additions,
+                    height_relative: spend.height_relative,
+                    seconds_relative: spend.seconds_relative.map(BigInt::from),
+                    before_height_relative: spend.before_height_relative,
+                    before_seconds_relative: spend.before_seconds_relative.map(BigInt::from),
+                    birth_height: spend.birth_height,
+                    birth_seconds: spend.birth_seconds.map(BigInt::from),
+                })
+            })
+            .collect::<Result<Vec<SpendConditions>>>()?;
+
+        Ok(SpendBundleConditions {
+            additions: spends
+                .iter()
+                .flat_map(|spend| spend.additions.clone())
+                .collect(),
+            removals: spends.iter().map(|spend| spend.coin.clone()).collect(),
+            spends,
+            fee: BigInt::from(self.removal_amount - self.addition_amount),
+            reserve_fee: self.reserve_fee.to_js()?,
+            height_absolute: self.height_absolute,
+            seconds_absolute: self.seconds_absolute.to_js()?,
+            before_height_absolute: self.before_height_absolute,
+            before_seconds_absolute: self.before_seconds_absolute.map(BigInt::from),
+            cost: self.cost.to_js()?,
+        })
+    }
+}
+
+#[napi]
+/// Validates a spend bundle like the mempool would, without looking up its coins: runs the puzzles, checks their conditions, including announcements and concurrent spend assertions, and verifies the aggregated signature. Throws a `ValidationError` with code `INVALID_SPEND_BUNDLE` if the bundle is invalid. Use `Peer.validateSpendBundle` to also check that the coins are unspent.
+///
+/// @param {Vec<CoinSpend>} coinSpends - The coin spends to be included in the bundle.
+/// @param {Vec<Buffer>} sigs - The signatures to be aggregated and included in the bundle.
+/// @param {Option<bool | Network>} network - Network whose consensus constants are used. True for testnet11, false for mainnet, or a network descriptor. Defaults to mainnet.
+/// @returns {SpendBundleConditions} The conditions of the bundle.
+pub fn validate_spend_bundle(
+    coin_spends: Vec<CoinSpend>,
+    sigs: Vec<Buffer>,
+    network: Option<Either<bool, Network>>,
+) -> napi::Result<SpendBundleConditions> {
+    let spend_bundle = spend_bundle_from_js(coin_spends, sigs)?;
+    let network = network
+        .map(TargetNetwork::from_js)
+        .transpose()
+        .arg("network")?
+        .unwrap_or_else(TargetNetwork::mainnet);
+
+    wallet::validate_spend_bundle(&spend_bundle, &network)
+        .map_err(js::err)?
+        .to_js()
+}
+
+/// Selects coins using the knapsack algorithm.
+///
+/// @param {Vec<Coin>} allCoins - Array of available coins (coins to select from).
+/// @param {BigInt} totalAmount - Amount needed for the transaction, including fee.
+/// @returns {Vec<Coin>} Array of selected coins.
+#[napi]
+pub fn select_coins(all_coins: Vec<Coin>, total_amount: BigInt) -> napi::Result<Vec<Coin>> {
+    let coins: Vec<RustCoin> = all_coins
+        .into_iter()
+        .map(RustCoin::from_js)
+        .collect::<Result<Vec<RustCoin>>>()
+        .arg("allCoins")?;
+    let selected_coins =
+        wallet::select_coins(coins, u64::from_js(total_amount).arg("totalAmount")?)
+            .map_err(js::err)?;
+
+    selected_coins
+        .into_iter()
+        .map(|c| c.to_js())
+        .collect::<Result<Vec<Coin>>>()
+}
+
+/// An output puzzle hash and amount.
+#[napi(object)]
+pub struct Output {
+    pub puzzle_hash: Buffer,
+    pub amount: BigInt,
+    pub memos: Vec<Buffer>,
+}
+
+/// Sends XCH to a given set of puzzle hashes.
+///
+/// @param {Buffer} syntheticKey - The synthetic key used by the wallet.
+/// @param {Vec<Coin>} selectedCoins - Coins to be spent, as retured by `select_coins`.
+/// @param {Vec<Output>} outputs - The output amounts to create.
+/// @param {BigInt} fee - The fee to use for the transaction.
+#[napi]
+pub fn send_xch(
+    synthetic_key: Buffer,
+    selected_coins: Vec<Coin>,
+    outputs: Vec<Output>,
+    fee: BigInt,
+) -> napi::Result<Vec<CoinSpend>> {
+    let mut items = Vec::new();
+
+    for output in outputs {
+        items.push((
+            RustBytes32::from_js(output.puzzle_hash)?,
+            u64::from_js(output.amount)?,
+            output
+                .memos
+                .into_iter()
+                .map(RustBytes::from_js)
+                .collect::<Result<Vec<RustBytes>>>()
+                .arg("memos")?,
+        ));
+    }
+
+    let coin_spends = wallet::send_xch(
+        RustPublicKey::from_js(synthetic_key).arg("syntheticKey")?,
+        &selected_coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("selectedCoins")?,
+        &items,
+        u64::from_js(fee).arg("fee")?,
+    )
+    .map_err(js::err)?;
+
+    coin_spends
+        .into_iter()
+        .map(|c| c.to_js())
+        .collect::<Result<Vec<CoinSpend>>>()
+}
+
+/// Adds an offset to a launcher id to make it deterministically unique from the original.
+///
+/// @param {Buffer} launcherId - The original launcher id.
+/// @param {BigInt} offset - The offset to add.
+#[napi]
+pub fn morph_launcher_id(launcher_id: Buffer, offset: BigInt) -> napi::Result<Buffer> {
+    server_coin::morph_launcher_id(
+        RustBytes32::from_js(launcher_id).arg("launcherId")?,
+        &u64::from_js(offset).arg("offset")?.into(),
+    )
+    .to_js()
+}
+
+/// The new server coin and coin spends to create it.
+#[napi(object)]
+pub struct NewServerCoin {
+    pub server_coin: ServerCoin,
+    pub coin_spends: Vec<CoinSpend>,
+}
+
+/// Creates a new mirror coin with the given URLs.
+///
+/// @param {Buffer} syntheticKey - The synthetic key used by the wallet.
+/// @param {Vec<Coin>} selectedCoins - Coins to be used for minting, as retured by `select_coins`. Note that, besides the fee, 1 mojo will be used to create the mirror coin.
+/// @param {Buffer} hint - The hint for the mirror coin, usually the original or morphed launcher id.
+/// @param {Vec<String>} uris - The URIs of the mirrors.
+/// @param {BigInt} amount - The amount to use for the created coin.
+/// @param {BigInt} fee - The fee to use for the transaction.
+#[napi]
+pub fn create_server_coin(
+    synthetic_key: Buffer,
+    selected_coins: Vec<Coin>,
+    hint: Buffer,
+    uris: Vec<String>,
+    amount: BigInt,
+    fee: BigInt,
+) -> napi::Result<NewServerCoin> {
+    let (coin_spends, server_coin) = wallet::create_server_coin(
+        RustPublicKey::from_js(synthetic_key).arg("syntheticKey")?,
+        selected_coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("selectedCoins")?,
+        RustBytes32::from_js(hint).arg("hint")?,
+        uris,
+        u64::from_js(amount).arg("amount")?,
+        u64::from_js(fee).arg("fee")?,
+    )
+    .map_err(js::err)?;
+
+    Ok(NewServerCoin {
+        coin_spends: coin_spends
+            .into_iter()
+            .map(|c| c.to_js())
+            .collect::<Result<Vec<CoinSpend>>>()?,
+        server_coin: server_coin.to_js()?,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[napi]
+/// Mints a new datastore.
+///
+/// @param {Buffer} minterSyntheticKey - Minter synthetic key.
+/// @param {Vec<Coin>} selectedCoins - Coins to be used for minting, as retured by `select_coins`. Note that, besides the fee, 1 mojo will be used to create the new store.
+/// @param {Buffer} rootHash - Root hash of the store.
+/// @param {Option<String>} label - Store label (optional).
+/// @param {Option<String>} description - Store description (optional).
+/// @param {Option<BigInt>} bytes - Store size in bytes (optional).
+/// @param {Buffer} ownerPuzzleHash - Owner puzzle hash.
+/// @param {Vec<DelegatedPuzzle>} delegatedPuzzles - Delegated puzzles.
+/// @param {BigInt} fee - Fee to use for the transaction. Total amount - 1 - fee will be sent back to the minter.
+/// @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+pub fn mint_store(
+    minter_synthetic_key: Buffer,
+    selected_coins: Vec<Coin>,
+    root_hash: Buffer,
+    label: Option<String>,
+    description: Option<String>,
+    bytes: Option<BigInt>,
+    owner_puzzle_hash: Buffer,
+    delegated_puzzles: Vec<DelegatedPuzzle>,
+    fee: BigInt,
+) -> napi::Result<SuccessResponse> {
+    let response = wallet::mint_store(
+        RustPublicKey::from_js(minter_synthetic_key).arg("minterSyntheticKey")?,
+        selected_coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("selectedCoins")?,
+        RustBytes32::from_js(root_hash).arg("rootHash")?,
+        label,
+        description,
+        if let Some(bytes) = bytes {
+            Some(u64::from_js(bytes).arg("bytes")?)
+        } else {
+            None
+        },
+        RustBytes32::from_js(owner_puzzle_hash).arg("ownerPuzzleHash")?,
+        delegated_puzzles
+            .into_iter()
+            .map(RustDelegatedPuzzle::from_js)
+            .collect::<Result<Vec<RustDelegatedPuzzle>>>()
+            .arg("delegatedPuzzles")?,
+        u64::from_js(fee).arg("fee")?,
+    )
+    .map_err(js::err)?;
+
+    response.to_js()
+}
+
+#[allow(clippy::too_many_arguments)]
+#[napi]
+/// Mints a new datastore like `mintStore`, selecting coins and raising the fee until it covers the cost of the mint.
+///
+/// @param {Buffer} minterSyntheticKey - Minter synthetic key.
+/// @param {Vec<Coin>} coins - Wallet coins to select from.
+/// @param {Buffer} rootHash - Root hash of the store.
+/// @param {Option<String>} label - Store label (optional).
+/// @param {Option<String>} description - Store description (optional).
+/// @param {Option<BigInt>} bytes - Store size in bytes (optional).
+/// @param {Buffer} ownerPuzzleHash - Owner puzzle hash.
+/// @param {Vec<DelegatedPuzzle>} delegatedPuzzles - Delegated puzzles.
+/// @param {FeePolicy} feePolicy - How the fee is determined.
+/// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the cost.
+/// @returns {PricedSuccessResponse} The coin spends, information about the new datastore, and the fee and cost of the mint.
+pub fn mint_store_with_fee_policy(
+    minter_synthetic_key: Buffer,
+    coins: Vec<Coin>,
+    root_hash: Buffer,
+    label: Option<String>,
+    description: Option<String>,
+    bytes: Option<BigInt>,
+    owner_puzzle_hash: Buffer,
+    delegated_puzzles: Vec<DelegatedPuzzle>,
+    fee_policy: FeePolicy,
+    network: Either<bool, Network>,
+) -> napi::Result<PricedSuccessResponse> {
+    fees::mint_store_with_fee_policy(
+        RustPublicKey::from_js(minter_synthetic_key).arg("minterSyntheticKey")?,
+        coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("coins")?,
+        RustBytes32::from_js(root_hash).arg("rootHash")?,
+        label,
+        description,
+        bytes.map(u64::from_js).transpose().arg("bytes")?,
+        RustBytes32::from_js(owner_puzzle_hash).arg("ownerPuzzleHash")?,
+        delegated_puzzles
+            .into_iter()
+            .map(RustDelegatedPuzzle::from_js)
+            .collect::<Result<Vec<RustDelegatedPuzzle>>>()
+            .arg("delegatedPuzzles")?,
+        RustFeePolicy::from_js(fee_policy).arg("feePolicy")?,
+        &TargetNetwork::from_js(network).arg("network")?,
+    )
+    .map_err(js::err)?
+    .to_js()
+}
+
+#[napi]
+/// Spends a store in oracle mode.
+///
+/// @param {Buffer} spenderSyntheticKey - Spender synthetic key.
+/// @param {Vec<Coin>} selectedCoins - Selected coins, as returned by `select_coins`.
+/// @param {DataStore} store - Up-to-daye store information.
+/// @param {BigInt} fee - Transaction fee to use.
+/// @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+pub fn oracle_spend(
+    spender_synthetic_key: Buffer,
+    selected_coins: Vec<Coin>,
+    store: DataStore,
+    fee: BigInt,
+) -> napi::Result<SuccessResponse> {
+    let response = wallet::oracle_spend(
+        RustPublicKey::from_js(spender_synthetic_key).arg("spenderSyntheticKey")?,
+        selected_coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("selectedCoins")?,
+        RustDataStore::from_js(store).arg("store")?,
+        u64::from_js(fee).arg("fee")?,
+    )
+    .map_err(js::err)?;
+
+    response.to_js()
+}
+
+#[napi]
+/// Rebuilds a pending transaction with a higher fee, so that it replaces the pending one in the mempool. Every coin of the pending transaction is spent again, and the fee grows by at least 0.00001 XCH and to a higher fee per cost, as full nodes require. The extra fee is taken out of the wallet's change; if there isn't enough, fee coins are spent too.
+///
+/// @param {Vec<CoinSpend>} coinSpends - Coin spends of the pending transaction.
+/// @param {Buffer} syntheticKey - Synthetic key of the wallet that pays the fee.
+/// @param {Vec<Coin>} feeCoins - Wallet coins that can be spent if the change doesn't cover the fee.
+/// @param {FeePolicy} feePolicy - How the new fee is determined. The replacement rules apply on top of it.
+/// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute fees and costs.
+/// @returns {FeeBump} The new coin spends, to be signed and broadcast with `Peer.broadcastFeeBump`, with the old and new fee and cost.
+pub fn bump_fee(
+    coin_spends: Vec<CoinSpend>,
+    synthetic_key: Buffer,
+    fee_coins: Vec<Coin>,
+    fee_policy: FeePolicy,
+    network: Either<bool, Network>,
+) -> napi::Result<FeeBump> {
+    fees::bump_fee(
+        coin_spends
+            .into_iter()
+            .map(RustCoinSpend::from_js)
+            .collect::<Result<Vec<RustCoinSpend>>>()
+            .arg("coinSpends")?,
+        RustPublicKey::from_js(synthetic_key).arg("syntheticKey")?,
+        fee_coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("feeCoins")?,
+        RustFeePolicy::from_js(fee_policy).arg("feePolicy")?,
+        &TargetNetwork::from_js(network).arg("network")?,
+    )
+    .map_err(js::err)?
+    .to_js()
+}
+
+#[napi]
+/// Adds a fee to any transaction. Change will be sent to spender.
+///
+/// @param {Buffer} spenderSyntheticKey - Synthetic key of spender.
+/// @param {Vec<Coin>} selectedCoins - Selected coins, as returned by `select_coins`.
+/// @param {Vec<Buffer>} assertCoinIds - IDs of coins that need to be spent for the fee to be paid. Usually all coin ids in the original transaction.
+/// @param {BigInt} fee - Fee to add.
+/// @returns {Vec<CoinSpend>} The coin spends to be added to the original transaction.
+pub fn add_fee(
+    spender_synthetic_key: Buffer,
+    selected_coins: Vec<Coin>,
+    assert_coin_ids: Vec<Buffer>,
+    fee: BigInt,
+) -> napi::Result<Vec<CoinSpend>> {
+    let response = wallet::add_fee(
+        RustPublicKey::from_js(spender_synthetic_key).arg("spenderSyntheticKey")?,
+        selected_coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("selectedCoins")?,
+        assert_coin_ids
+            .into_iter()
+            .map(RustBytes32::from_js)
+            .collect::<Result<Vec<RustBytes32>>>()
+            .arg("assertCoinIds")?,
+        u64::from_js(fee).arg("fee")?,
+    )
+    .map_err(js::err)?;
+
+    response
+        .into_iter()
+        .map(|cs| cs.to_js())
+        .collect::<Result<Vec<CoinSpend>>>()
+}
+
+#[napi]
+/// Converts a master public key to a wallet synthetic key.
+///
+/// @param {Buffer} publicKey - Master public key.
+/// @returns {Buffer} The (first) wallet synthetic key.
+pub fn master_public_key_to_wallet_synthetic_key(public_key: Buffer) -> napi::Result<Buffer> {
+    let public_key = RustPublicKey::from_js(public_key).arg("publicKey")?;
+    let wallet_pk = master_to_wallet_unhardened(&public_key, 0).derive_synthetic();
+    wallet_pk.to_js()
+}
+
+#[napi]
+/// Converts a master public key to the first puzzle hash.
+///
+/// @param {Buffer} publicKey - Master public key.
+/// @returns {Buffer} The first wallet puzzle hash.
+pub fn master_public_key_to_first_puzzle_hash(public_key: Buffer) -> napi::Result<Buffer> {
+    let public_key = RustPublicKey::from_js(public_key).arg("publicKey")?;
+    let wallet_pk = master_to_wallet_unhardened(&public_key, 0).derive_synthetic();
+
+    let puzzle_hash: RustBytes32 = StandardArgs::curry_tree_hash(wallet_pk).into();
+
+    puzzle_hash.to_js()
+}
+
+#[napi]
+/// Converts a master secret key to a wallet synthetic secret key.
+///
+/// @param {Buffer} secretKey - Master secret key.
+/// @returns {Buffer} The (first) wallet synthetic secret key.
+pub fn master_secret_key_to_wallet_synthetic_secret_key(
+    secret_key: Buffer,
+) -> napi::Result<Buffer> {
+    let secret_key = RustSecretKey::from_js(secret_key).arg("secretKey")?;
+    let wallet_sk = master_to_wallet_unhardened(&secret_key, 0).derive_synthetic();
+    wallet_sk.to_js()
+}
+
+#[napi]
+/// Converts a secret key to its corresponding public key.
+///
+/// @param {Buffer} secretKey - The secret key.
+/// @returns {Buffer} The public key.
+pub fn secret_key_to_public_key(secret_key: Buffer) -> napi::Result<Buffer> {
+    let secret_key = RustSecretKey::from_js(secret_key).arg("secretKey")?;
+    secret_key.public_key().to_js()
+}
+
+#[napi]
+/// Converts a puzzle hash to an address by encoding it using bech32m.
+///
+/// @param {Buffer} puzzleHash - The puzzle hash.
+/// @param {String} prefix - Address prefix (e.g., 'txch').
+/// @returns {Promise<String>} The converted address.
+pub fn puzzle_hash_to_address(puzzle_hash: Buffer, prefix: String) -> napi::Result<String> {
+    let puzzle_hash = RustBytes32::from_js(puzzle_hash).arg("puzzleHash")?;
+
+    encode_address(puzzle_hash.into(), &prefix).map_err(|error| js::err(AddressError::from(error)))
+}
+
+#[napi]
+/// Converts an address to a puzzle hash using bech32m.
+///
+/// @param {String} address - The address.
+/// @returns {Promise<Buffer>} The puzzle hash.
+pub fn address_to_puzzle_hash(address: String) -> napi::Result<Buffer> {
+    let (puzzle_hash, _) = decode_address(&address).map_err(js::err)?;
+    let puzzle_hash: RustBytes32 = RustBytes32::new(puzzle_hash);
+
+    puzzle_hash.to_js()
+}
+
+#[napi]
+/// Creates an admin delegated puzzle for a given key.
+///
+/// @param {Buffer} syntheticKey - Synthetic key.
+/// @returns {Promise<DelegatedPuzzle>} The delegated puzzle.
+pub fn admin_delegated_puzzle_from_key(synthetic_key: Buffer) -> napi::Result<DelegatedPuzzle> {
+    let synthetic_key = RustPublicKey::from_js(synthetic_key).arg("syntheticKey")?;
+
+    RustDelegatedPuzzle::Admin(StandardArgs::curry_tree_hash(synthetic_key)).to_js()
+}
+
+#[napi]
+/// Creates a writer delegated puzzle from a given key.
+///
+/// @param {Buffer} syntheticKey - Synthetic key.
+/// /// @returns {Promise<DelegatedPuzzle>} The delegated puzzle.
+pub fn writer_delegated_puzzle_from_key(synthetic_key: Buffer) -> napi::Result<DelegatedPuzzle> {
+    let synthetic_key = RustPublicKey::from_js(synthetic_key).arg("syntheticKey")?;
+
+    RustDelegatedPuzzle::Writer(StandardArgs::curry_tree_hash(synthetic_key)).to_js()
+}
+
+#[napi]
+// Creates an oracle delegated puzzle.
+///
+/// @param {Buffer} oraclePuzzleHash - The oracle puzzle hash (corresponding to the wallet where fees should be paid).
+/// @param {BigInt} oracleFee - The oracle fee (i.e., XCH amount to be paid for every oracle spend). This amount MUST be even.
+/// @returns {Promise<DelegatedPuzzle>} The delegated puzzle.
+pub fn oracle_delegated_puzzle(
+    oracle_puzzle_hash: Buffer,
+    oracle_fee: BigInt,
+) -> napi::Result<DelegatedPuzzle> {
+    let oracle_puzzle_hash = RustBytes32::from_js(oracle_puzzle_hash).arg("oraclePuzzleHash")?;
+    let oracle_fee = u64::from_js(oracle_fee).arg("oracleFee")?;
+
+    RustDelegatedPuzzle::Oracle(oracle_puzzle_hash, oracle_fee).to_js()
+}
+
+#[napi]
+/// Partially or fully signs coin spends using a list of keys. Signatures by other keys are skipped; `signCoinSpendsWithSigner` throws if any are missing.
+///
+/// @param {Vec<CoinSpend>} coinSpends - The coin spends to sign.
+/// @param {Vec<Buffer>} privateKeys - The private/secret keys to be used for signing.
+/// @param {bool | Network} network - Network to sign for. True for testnet11, false for mainnet, or a network descriptor.
+/// @returns {Promise<Buffer>} The signature.
+pub fn sign_coin_spends(
+    coin_spends: Vec<CoinSpend>,
+    private_keys: Vec<Buffer>,
+    network: Either<bool, Network>,
+) -> napi::Result<Buffer> {
+    let coin_spends = coin_spends
+        .iter()
+        .map(|cs| RustCoinSpend::from_js(cs.clone()))
+        .collect::<Result<Vec<RustCoinSpend>>>()?;
+    let private_keys = private_keys
+        .iter()
+        .map(|sk| RustSecretKey::from_js(sk.clone()))
+        .collect::<Result<Vec<RustSecretKey>>>()?;
+
+    let sig = wallet::sign_coin_spends(
+        coin_spends,
+        private_keys,
+        &TargetNetwork::from_js(network).arg("network")?,
+    )
+    .map_err(js::err)?;
+
+    sig.to_js()
+}
+
+/// A JS function that signs messages, called from the signing task through a thread-safe function.
+struct JsSigner(ThreadsafeFunction<(RustPublicKey, RustBytes)>);
+
+impl Signer for JsSigner {
+    async fn sign(
+        &self,
+        public_key: RustPublicKey,
+        message: RustBytes,
+    ) -> std::result::Result<Option<RustSignature>, WalletError> {
+        let signer_failed = |error: napi::Error| WalletError::SignerFailed(error.reason);
+
+        let signature = self
+            .0
+            .call_async::<Promise<Option<Buffer>>>(Ok((public_key, message)))
+            .await
+            .map_err(signer_failed)?
+            .await
+            .map_err(signer_failed)?;
+
+        let Some(signature) = signature else {
+            return Ok(None);
+        };
+
+        match RustSignature::from_js(signature) {
+            Ok(signature) => Ok(Some(signature)),
+            Err(_) => Err(WalletError::SignerFailed(
+                "signer returned an invalid signature".to_string(),
+            )),
+        }
+    }
+}
+
+#[napi(ts_return_type = "Promise<Buffer>")]
+/// Signs coin spends by asking a signer for each signature, e.g. a hardware security module or a wallet over RPC.
+///
+/// Unlike `signCoinSpends`, this fails with a `SigningError` listing the public keys the signer didn't sign for.
+///
+/// @param {Vec<CoinSpend>} coinSpends - The coin spends to sign.
+/// @param {Function} signer - Called with the public key and the final message of each signature. Returns or resolves to the signature, or null if it doesn't hold the key.
+/// @param {bool | Network} network - Network to sign for. True for testnet11, false for mainnet, or a network descriptor.
+/// @returns {Promise<Buffer>} The aggregated signature.
+pub fn sign_coin_spends_with_signer(
+    env: Env,
+    coin_spends: Vec<CoinSpend>,
+    #[napi(
+        ts_arg_type = "(publicKey: Buffer, message: Buffer) => Buffer | null | Promise<Buffer | null>"
+    )]
+    signer: JsFunction,
+    network: Either<bool, Network>,
+) -> napi::Result<JsObject> {
+    let coin_spends = coin_spends
+        .into_iter()
+        .map(RustCoinSpend::from_js)
+        .collect::<Result<Vec<RustCoinSpend>>>()
+        .arg("coinSpends")?;
+    let network = TargetNetwork::from_js(network).arg("network")?;
+    let signer = JsSigner(signer.create_threadsafe_function(
+        0,
+        |ctx: ThreadSafeCallContext<(RustPublicKey, RustBytes)>| {
+            let (public_key, message) = ctx.value;
+            Ok(vec![public_key.to_js()?, Buffer::from(message.to_vec())])
+        },
+    )?);
+
+    env.spawn_future(async move {
+        let signature = sign_with_signer(&signer, &coin_spends, &network)
+            .await
+            .map_err(js::err)?;
+
+        signature.to_js()
+    })
+}
+
+#[napi]
+/// Computes the ID (name) of a coin.
+///
+/// @param {Coin} coin - The coin.
+/// @returns {Buffer} The coin ID.
+pub fn get_coin_id(coin: Coin) -> napi::Result<Buffer> {
+    RustCoin::from_js(coin).arg("coin")?.coin_id().to_js()
+}
+
+fn metadata_inner_spend(
+    owner_public_key: Option<Buffer>,
+    admin_public_key: Option<Buffer>,
+    writer_public_key: Option<Buffer>,
+) -> napi::Result<DataStoreInnerSpend> {
+    Ok(
+        match (owner_public_key, admin_public_key, writer_public_key) {
+            (Some(owner_public_key), None, None) => {
+                DataStoreInnerSpend::Owner(RustPublicKey::from_js(owner_public_key).arg("ownerPublicKey")?)
+            }
+            (None, Some(admin_public_key), None) => {
+                DataStoreInnerSpend::Admin(RustPublicKey::from_js(admin_public_key).arg("adminPublicKey")?)
+            }
+            (None, None, Some(writer_public_key)) => {
+                DataStoreInnerSpend::Writer(RustPublicKey::from_js(writer_public_key).arg("writerPublicKey")?)
+            }
+            _ => return Err(js::err(ConversionError::InvalidArgument(
+                "Exactly one of owner_public_key, admin_public_key, writer_public_key must be provided",
+            ))),
+        },
+    )
+}
+
+fn ownership_inner_spend(
+    owner_public_key: Option<Buffer>,
+    admin_public_key: Option<Buffer>,
+) -> napi::Result<DataStoreInnerSpend> {
+    Ok(match (owner_public_key, admin_public_key) {
+        (Some(owner_public_key), None) => DataStoreInnerSpend::Owner(
+            RustPublicKey::from_js(owner_public_key).arg("ownerPublicKey")?,
+        ),
+        (None, Some(admin_public_key)) => DataStoreInnerSpend::Admin(
+            RustPublicKey::from_js(admin_public_key).arg("adminPublicKey")?,
+        ),
+        _ => {
+            return Err(js::err(ConversionError::InvalidArgument(
+                "Exactly one of owner_public_key, admin_public_key must be provided",
+            )))
+        }
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[napi]
+/// Updates the metadata of a store. Either the owner, admin, or writer public key must be provided.
+///
+/// @param {DataStore} store - Current store information.
+/// @param {Buffer} newRootHash - New root hash.
+/// @param {Option<String>} newLabel - New label (optional).
+/// @param {Option<String>} newDescription - New description (optional).
+/// @param {Option<BigInt>} newBytes - New size in bytes (optional).
+/// @param {Option<Buffer>} ownerPublicKey - Owner public key.
+/// @param {Option<Buffer>} adminPublicKey - Admin public key.
+/// @param {Option<Buffer>} writerPublicKey - Writer public key.
+/// @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+pub fn update_store_metadata(
+    store: DataStore,
+    new_root_hash: Buffer,
+    new_label: Option<String>,
+    new_description: Option<String>,
+    new_bytes: Option<BigInt>,
+    owner_public_key: Option<Buffer>,
+    admin_public_key: Option<Buffer>,
+    writer_public_key: Option<Buffer>,
+) -> napi::Result<SuccessResponse> {
+    let inner_spend_info =
+        metadata_inner_spend(owner_public_key, admin_public_key, writer_public_key)?;
+
+    let res = wallet::update_store_metadata(
+        RustDataStore::from_js(store).arg("store")?,
+        RustBytes32::from_js(new_root_hash).arg("newRootHash")?,
+        new_label,
+        new_description,
+        if let Some(bytes) = new_bytes {
+            Some(u64::from_js(bytes).arg("bytes")?)
+        } else {
+            None
+        },
+        inner_spend_info,
+    )
+    .map_err(js::err)?;
+
+    res.to_js()
+}
+
+#[allow(clippy::too_many_arguments)]
+#[napi]
+/// Updates the metadata of a store like `updateStoreMetadata`, paying a fee with coins selected out of the fee payer's wallet and raising it until it covers the cost of the update.
+///
+/// @param {DataStore} store - Current store information.
+/// @param {Buffer} newRootHash - New root hash.
+/// @param {Option<String>} newLabel - New label (optional).
+/// @param {Option<String>} newDescription - New description (optional).
+/// @param {Option<BigInt>} newBytes - New size in bytes (optional).
+/// @param {Option<Buffer>} ownerPublicKey - Owner public key.
+/// @param {Option<Buffer>} adminPublicKey - Admin public key.
+/// @param {Option<Buffer>} writerPublicKey - Writer public key.
+/// @param {Buffer} feeSyntheticKey - Synthetic key of the wallet that pays the fee.
+/// @param {Vec<Coin>} coins - Wallet coins to select the fee from.
+/// @param {FeePolicy} feePolicy - How the fee is determined.
+/// @param {bool | Network} network - True for testnet11, false for mainnet, or a network descriptor. Used to compute the cost.
+/// @returns {PricedSuccessResponse} The coin spends, information about the new datastore, and the fee and cost of the update.
+pub fn update_store_metadata_with_fee_policy(
+    store: DataStore,
+    new_root_hash: Buffer,
+    new_label: Option<String>,
+    new_description: Option<String>,
+    new_bytes: Option<BigInt>,
+    owner_public_key: Option<Buffer>,
+    admin_public_key: Option<Buffer>,
+    writer_public_key: Option<Buffer>,
+    fee_synthetic_key: Buffer,
+    coins: Vec<Coin>,
+    fee_policy: FeePolicy,
+    network: Either<bool, Network>,
+) -> napi::Result<PricedSuccessResponse> {
+    fees::update_store_metadata_with_fee_policy(
+        RustDataStore::from_js(store).arg("store")?,
+        RustBytes32::from_js(new_root_hash).arg("newRootHash")?,
+        new_label,
+        new_description,
+        new_bytes.map(u64::from_js).transpose().arg("newBytes")?,
+        metadata_inner_spend(owner_public_key, admin_public_key, writer_public_key)?,
+        RustPublicKey::from_js(fee_synthetic_key).arg("feeSyntheticKey")?,
+        coins
+            .into_iter()
+            .map(RustCoin::from_js)
+            .collect::<Result<Vec<RustCoin>>>()
+            .arg("coins")?,
+        RustFeePolicy::from_js(fee_policy).arg("feePolicy")?,
+        &TargetNetwork::from_js(network).arg("network")?,
+    )
+    .map_err(js::err)?
+    .to_js()
+}
+
+#[napi]
+/// Updates the ownership of a store. Either the admin or owner public key must be provided.
+///
+/// @param {DataStore} store - Store information.
+/// @param {Option<Buffer>} newOwnerPuzzleHash - New owner puzzle hash.
+/// @param {Vec<DelegatedPuzzle>} newDelegatedPuzzles - New delegated puzzles.
+/// @param {Option<Buffer>} ownerPublicKey - Owner public key.
+/// @param {Option<Buffer>} adminPublicKey - Admin public key.
+/// @returns {SuccessResponse} The success response, which includes coin spends and information about the new datastore.
+pub fn update_store_ownership(
+    store: DataStore,
+    new_owner_puzzle_hash: Option<Buffer>,
+    new_delegated_puzzles: Vec<DelegatedPuzzle>,
+    owner_public_key: Option<Buffer>,
+    admin_public_key: Option<Buffer>,
+) -> napi::Result<SuccessResponse> {
+    let store = RustDataStore::from_js(store).arg("store")?;
+    let new_owner_puzzle_hash = new_owner_puzzle_hash
+        .map(RustBytes32::from_js)
+        .unwrap_or_else(|| Ok(store.info.owner_puzzle_hash))?;
+
+    let inner_spend_info = ownership_inner_spend(owner_public_key, admin_public_key)?;
+
+    let res = wallet::update_store_ownership(
+        store,
+        new_owner_puzzle_hash,
+        new_delegated_puzzles
+            .into_iter()
+            .map(RustDelegatedPuzzle::from_js)
+            .collect::<Result<Vec<RustDelegatedPuzzle>>>()
+            .arg("newDelegatedPuzzles")?,
+        inner_spend_info,
+    )
+    .map_err(js::err)?;
+
+    res.to_js()
+}
+
+#[napi]
+/// Melts a store. The 1 mojo change will be used as a fee.
+///
+/// @param {DataStore} store - Store information.
+/// @param {Buffer} ownerPublicKey - Owner's public key.
+/// @returns {Vec<CoinSpend>} The coin spends that the owner can sign to melt the store.
+pub fn melt_store(store: DataStore, owner_public_key: Buffer) -> napi::Result<Vec<CoinSpend>> {
+    let res = wallet::melt_store(
+        RustDataStore::from_js(store).arg("store")?,
+        RustPublicKey::from_js(owner_public_key).arg("ownerPublicKey")?,
+    )
+    .map_err(js::err)?;
+
+    res.into_iter()
+        .map(|cs| cs.to_js())
+        .collect::<Result<Vec<CoinSpend>>>()
+}
+
+#[napi]
+/// Signs a message using the provided private key.
+///
+/// @param {Buffer} message - Message to sign, as bytes. "Chia Signed Message" will be prepended automatically, as per CHIP-2 - no need to add it before calling this function.
+/// @param {Buffer} private_key - Private key to sign the message with. No derivation is done.
+/// @returns {Buffer} The signature.
+pub fn sign_message(message: Buffer, private_key: Buffer) -> napi::Result<Buffer> {
+    wallet::sign_message(
+        RustBytes::from_js(message).arg("message")?,
+        RustSecretKey::from_js(private_key).arg("privateKey")?,
+    )
+    .map_err(js::err)?
+    .to_js()
+}
+
+#[napi]
+/// Verifies a signed message using the provided public key.
+///
+/// @param {Buffer} signature - Th signature to be verified.
+/// @param {Buffer} public_key - Public key corresponding to the private key that was used to sign the message.
+/// @param {Buffer} message - Message that was signed, as bytes. "Chia Signed Message" will be prepended automatically, as per CHIP-2 - no need to add it before calling this function.
+/// @returns {Buffer} Boolean - true indicates that the signature is valid, while false indicates that it is not.
+pub fn verify_signed_message(
+    signature: Buffer,
+    public_key: Buffer,
+    message: Buffer,
+) -> napi::Result<bool> {
+    wallet::verify_signature(
+        RustBytes::from_js(message).arg("message")?,
+        RustPublicKey::from_js(public_key).arg("publicKey")?,
+        RustSignature::from_js(signature).arg("signature")?,
+    )
+    .map_err(js::err)
+}
+
+#[napi]
+/// Converts a synthetic key to its corresponding standard puzzle hash.
+///
+/// @param {Buffer} syntheticKey - Synthetic key.
+/// @returns {Buffer} The standard puzzle (puzzle) hash.
+pub fn synthetic_key_to_puzzle_hash(synthetic_key: Buffer) -> napi::Result<Buffer> {
+    let puzzle_hash: RustBytes32 =
+        StandardArgs::curry_tree_hash(RustPublicKey::from_js(synthetic_key).arg("syntheticKey")?)
+            .into();
+
+    puzzle_hash.to_js()
+}
+
+#[napi]
+/// Calculates the total cost of a given array of coin spends/
+///
+/// @param {Vec<CoinSpend>} CoinSpend - Coin spends.
+/// @param {Option<bool | Network>} network - Network whose consensus constants are used. True for testnet11, false for mainnet, or a network descriptor. Defaults to mainnet.
+/// @returns {BigInt} The cost of the coin spends.
+pub fn get_cost(
+    coin_spends: Vec<CoinSpend>,
+    network: Option<Either<bool, Network>>,
+) -> napi::Result<BigInt> {
+    wallet::get_cost(
+        coin_spends
+            .into_iter()
+            .map(RustCoinSpend::from_js)
+            .collect::<Result<Vec<RustCoinSpend>>>()
+            .arg("coinSpends")?,
+        &network
+            .map(TargetNetwork::from_js)
+            .transpose()
+            .arg("network")?
+            .unwrap_or_else(TargetNetwork::mainnet),
+    )
+    .map_err(js::err)?
+    .to_js()
+}
+
+#[napi]
+/// Returns the mainnet genesis challenge.
+///
+/// @returns {Buffer} The mainnet genesis challenge.
+pub fn get_mainnet_genesis_challenge() -> napi::Result<Buffer> {
+    MAINNET_CONSTANTS.genesis_challenge.to_js()
+}
+
+#[napi]
+/// Returns the testnet11 genesis challenge.
+///
+/// @returns {Buffer} The testnet11 genesis challenge.
+pub fn get_testnet11_genesis_challenge() -> napi::Result<Buffer> {
+    TESTNET11_CONSTANTS.genesis_challenge.to_js()
+}
+
+#[napi]
+/// Returns the mainnet network descriptor. Can be used as a starting point for custom networks.
+///
+/// @returns {Network} The mainnet network descriptor.
+pub fn get_mainnet_network() -> napi::Result<Network> {
+    TargetNetwork::mainnet().to_js()
+}
+
+#[napi]
+/// Returns the testnet11 network descriptor.
+///
+/// @returns {Network} The testnet11 network descriptor.
+pub fn get_testnet11_network() -> napi::Result<Network> {
+    TargetNetwork::testnet11().to_js()
+}
+
+#[napi]
+/// Verifies that a key/value pair is in the tree with the given root hash, e.g. data downloaded from a mirror against the store's on-chain root hash.
+///
+/// @param {Buffer} proof - Serialized proof, from `DataTree.proveInclusion`.
+/// @param {Buffer} rootHash - Root hash of the tree, e.g. `DataStoreMetadata.rootHash`.
+/// @param {Buffer} key - Key.
+/// @param {Buffer} value - Value.
+/// @returns {bool} True if the proof shows that the pair is in the tree.
+pub fn verify_inclusion_proof(
+    proof: Buffer,
+    root_hash: Buffer,
+    key: Buffer,
+    value: Buffer,
+) -> napi::Result<bool> {
+    let proof = RustInclusionProof::from_bytes(&proof).map_err(js::err)?;
+
+    Ok(proof.key.as_ref() == key.as_ref()
+        && proof.value.as_ref() == value.as_ref()
+        && proof.verify(RustBytes32::from_js(root_hash).arg("rootHash")?))
+}
+
+#[napi]
+/// Verifies that a key isn't in the tree with the given root hash.
+///
+/// @param {Buffer} proof - Serialized proof, from `DataTree.proveExclusion`.
+/// @param {Buffer} rootHash - Root hash of the tree, e.g. `DataStoreMetadata.rootHash`.
+/// @param {Buffer} key - Key.
+/// @returns {bool} True if the proof shows that the key isn't in the tree.
+pub fn verify_exclusion_proof(proof: Buffer, root_hash: Buffer, key: Buffer) -> napi::Result<bool> {
+    let proof = RustExclusionProof::from_bytes(&proof).map_err(js::err)?;
+
+    Ok(proof.key.as_ref() == key.as_ref()
+        && proof.verify(RustBytes32::from_js(root_hash).arg("rootHash")?))
+}
@@ -0,0 +1,498 @@
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use chia::protocol::Message;
+use chia_wallet_sdk::{connect_peer, NetworkId, Peer};
+use native_tls::TlsConnector;
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::discovery::DISCOVERY_TIMEOUT;
+use crate::wallet::WalletError;
+
+/// Number of consecutive connection failures after which a peer is removed from the pool.
+pub const MAX_PEER_FAILURES: u32 = 3;
+
+#[derive(Debug)]
+struct PoolEntry {
+    socket_addr: SocketAddr,
+    peer: Option<Peer>,
+    failures: u32,
+    // Identifies the current connection, so failures of a replaced one aren't counted against it.
+    connection: u64,
+    watcher: Option<JoinHandle<()>>,
+}
+
+impl PoolEntry {
+    fn new(socket_addr: SocketAddr) -> Self {
+        Self {
+            socket_addr,
+            peer: None,
+            failures: 0,
+            connection: 0,
+            watcher: None,
+        }
+    }
+
+    // Drops the connection and stops watching it.
+    fn disconnect(&mut self) {
+        self.peer = None;
+        if let Some(watcher) = self.watcher.take() {
+            watcher.abort();
+        }
+    }
+}
+
+/// A set of full node connections that requests are routed through.
+///
+/// Each request is sent to a connected peer (round robin). If the peer rejects the request
+/// or the connection fails, the request is retried on the next peer. Peers whose connection
+/// drops are reconnected lazily and removed after `MAX_PEER_FAILURES` consecutive failures.
+pub struct PeerPool {
+    network_id: NetworkId,
+    tls_connector: Option<TlsConnector>,
+    entries: Arc<Mutex<Vec<PoolEntry>>>,
+    cursor: AtomicUsize,
+    next_connection: AtomicU64,
+}
+
+impl PeerPool {
+    pub fn new(network_id: NetworkId, tls_connector: Option<TlsConnector>) -> Self {
+        Self {
+            network_id,
+            tls_connector,
+            entries: Arc::new(Mutex::new(Vec::new())),
+            cursor: AtomicUsize::new(0),
+            next_connection: AtomicU64::new(1),
+        }
+    }
+
+    /// Creates a pool and connects to all given addresses. Fails only if no peer could be reached.
+    pub async fn connect(
+        network_id: NetworkId,
+        tls_connector: TlsConnector,
+        socket_addrs: Vec<SocketAddr>,
+    ) -> Result<Self, WalletError> {
+        let pool = Self::new(network_id, Some(tls_connector));
+        let mut last_error = None;
+
+        for socket_addr in socket_addrs {
+            if let Err(error) = pool.add_peer(socket_addr).await {
+                last_error = Some(error);
+            }
+        }
+
+        if pool.connected_peers().await.is_empty() {
+            return Err(last_error.unwrap_or(WalletError::NoPeers));
+        }
+
+        Ok(pool)
+    }
+
+    /// Connects to a new peer and adds it to the pool. The address is kept in the pool
+    /// (and retried later) even if the first connection attempt fails.
+    pub async fn add_peer(&self, socket_addr: SocketAddr) -> Result<(), WalletError> {
+        {
+            let mut entries = self.entries.lock().await;
+            if entries.iter().any(|e| e.socket_addr == socket_addr) {
+                return Ok(());
+            }
+
+            entries.push(PoolEntry::new(socket_addr));
+        }
+
+        self.reconnect(socket_addr).await
+    }
+
+    /// Connects to discovered addresses until `max_peers` peers are connected. Unlike
+    /// `add_peer`, addresses that can't be reached are not kept in the pool. Returns the number
+    /// of newly connected peers.
+    pub async fn add_discovered_peers(
+        &self,
+        socket_addrs: Vec<SocketAddr>,
+        max_peers: usize,
+    ) -> usize {
+        if self.tls_connector.is_none() {
+            return 0;
+        }
+
+        let mut added = 0;
+
+        for socket_addr in socket_addrs {
+            if self.connected_peers().await.len() >= max_peers {
+                break;
+            }
+
+            if self.peers().await.contains(&socket_addr) {
+                continue;
+            }
+
+            if let Ok((peer, receiver)) = self.connect_peer(socket_addr).await {
+                self.insert_peer(peer, receiver).await;
+                added += 1;
+            }
+        }
+
+        added
+    }
+
+    /// Adds an already established connection to the pool. If the pool already has a
+    /// connection to the same address, it's replaced, but its failures still count until a
+    /// request succeeds.
+    pub async fn insert_peer(&self, peer: Peer, receiver: Receiver<Message>) {
+        let socket_addr = peer.socket_addr();
+        let connection = self.next_connection.fetch_add(1, Ordering::Relaxed);
+
+        let mut entries = self.entries.lock().await;
+        let index = match entries.iter().position(|e| e.socket_addr == socket_addr) {
+            Some(index) => index,
+            None => {
+                entries.push(PoolEntry::new(socket_addr));
+                entries.len() - 1
+            }
+        };
+
+        let entry = &mut entries[index];
+        entry.disconnect();
+        entry.peer = Some(peer);
+        entry.connection = connection;
+        entry.watcher = Some(self.watch_connection(socket_addr, connection, receiver));
+    }
+
+    /// Removes a peer from the pool and closes its connection. Requests that are still using
+    /// the peer keep it open until they finish.
+    pub async fn remove_peer(&self, socket_addr: SocketAddr) -> bool {
+        let mut entries = self.entries.lock().await;
+        let Some(index) = entries.iter().position(|e| e.socket_addr == socket_addr) else {
+            return false;
+        };
+
+        entries.remove(index).disconnect();
+        true
+    }
+
+    /// Addresses of all peers that currently have a live connection.
+    pub async fn connected_peers(&self) -> Vec<SocketAddr> {
+        self.entries
+            .lock()
+            .await
+            .iter()
+            .filter(|e| e.peer.is_some())
+            .map(|e| e.socket_addr)
+            .collect()
+    }
+
+    /// Addresses of all peers known to the pool, including disconnected ones.
+    pub async fn peers(&self) -> Vec<SocketAddr> {
+        self.entries
+            .lock()
+            .await
+            .iter()
+            .map(|e| e.socket_addr)
+            .collect()
+    }
+
+    /// Runs a request against the pool, failing over to other peers on rejections and
+    /// connection errors. Other errors (e.g. parse errors) are returned immediately.
+    pub async fn request<T, F, Fut>(&self, f: F) -> Result<T, WalletError>
+    where
+        F: Fn(Peer) -> Fut,
+        Fut: Future<Output = Result<T, WalletError>>,
+    {
+        let mut last_error = None;
+
+        for round in 0..2 {
+            if round == 1 {
+                self.reconnect_all().await;
+            }
+
+            for (connection, peer) in self.ordered_peers().await {
+                let socket_addr = peer.socket_addr();
+
+                match f(peer).await {
+                    Ok(value) => {
+                        self.mark_success(socket_addr, connection).await;
+                        return Ok(value);
+                    }
+                    Err(error @ WalletError::Client(_)) => {
+                        mark_failure(&self.entries, socket_addr, Some(connection)).await;
+                        last_error = Some(error);
+                    }
+                    Err(error) if error.is_peer_rejection() => {
+                        last_error = Some(error);
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(WalletError::NoPeers))
+    }
+
+    // The connected peers, starting at the next one in turn, with their connection ids.
+    async fn ordered_peers(&self) -> Vec<(u64, Peer)> {
+        let peers: Vec<(u64, Peer)> = self
+            .entries
+            .lock()
+            .await
+            .iter()
+            .filter_map(|e| Some((e.connection, e.peer.clone()?)))
+            .collect();
+
+        if peers.is_empty() {
+            return peers;
+        }
+
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed) % peers.len();
+        peers[start..]
+            .iter()
+            .chain(&peers[..start])
+            .cloned()
+            .collect()
+    }
+
+    async fn mark_success(&self, socket_addr: SocketAddr, connection: u64) {
+        let mut entries = self.entries.lock().await;
+        if let Some(entry) = entries
+            .iter_mut()
+            .find(|e| e.socket_addr == socket_addr && e.connection == connection)
+        {
+            entry.failures = 0;
+        }
+    }
+
+    async fn reconnect_all(&self) {
+        let disconnected: Vec<SocketAddr> = self
+            .entries
+            .lock()
+            .await
+            .iter()
+            .filter(|e| e.peer.is_none())
+            .map(|e| e.socket_addr)
+            .collect();
+
+        for socket_addr in disconnected {
+            let _ = self.reconnect(socket_addr).await;
+        }
+    }
+
+    async fn reconnect(&self, socket_addr: SocketAddr) -> Result<(), WalletError> {
+        match self.connect_peer(socket_addr).await {
+            Ok((peer, receiver)) => {
+                self.insert_peer(peer, receiver).await;
+                Ok(())
+            }
+            Err(error) => {
+                mark_failure(&self.entries, socket_addr, None).await;
+                Err(error)
+            }
+        }
+    }
+
+    // A node that doesn't answer within `DISCOVERY_TIMEOUT` shouldn't hold up the other peers.
+    async fn connect_peer(
+        &self,
+        socket_addr: SocketAddr,
+    ) -> Result<(Peer, Receiver<Message>), WalletError> {
+        let Some(tls_connector) = self.tls_connector.clone() else {
+            return Err(WalletError::NoPeers);
+        };
+
+        let connection = connect_peer(self.network_id.clone(), tls_connector, socket_addr);
+        match tokio::time::timeout(DISCOVERY_TIMEOUT, connection).await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(io::Error::from(io::ErrorKind::TimedOut).into()),
+        }
+    }
+
+    // Unsolicited messages are not used by the pool, but the receiver has to be drained so the
+    // connection doesn't stall. The peer is marked as disconnected once the channel closes.
+    fn watch_connection(
+        &self,
+        socket_addr: SocketAddr,
+        connection: u64,
+        mut receiver: Receiver<Message>,
+    ) -> JoinHandle<()> {
+        let entries = self.entries.clone();
+
+        tokio::spawn(async move {
+            while receiver.recv().await.is_some() {}
+            mark_failure(&entries, socket_addr, Some(connection)).await;
+        })
+    }
+}
+
+// Counts a failure against a peer. Failures of a specific connection are ignored once it was
+// replaced; `None` counts against whatever the entry holds, e.g. for a failed reconnect.
+async fn mark_failure(
+    entries: &Mutex<Vec<PoolEntry>>,
+    socket_addr: SocketAddr,
+    connection: Option<u64>,
+) {
+    let mut entries = entries.lock().await;
+
+    if let Some(entry) = entries
+        .iter_mut()
+        .find(|e| e.socket_addr == socket_addr && connection.is_none_or(|c| c == e.connection))
+    {
+        entry.disconnect();
+        entry.failures += 1;
+    }
+
+    entries.retain(|e| e.failures < MAX_PEER_FAILURES);
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::{Bytes32, SpendBundle};
+    use chia::puzzles::standard::StandardArgs;
+    use chia_wallet_sdk::{test_secret_key, PeerSimulator};
+
+    use crate::network::TargetNetwork;
+    use crate::wallet::{send_xch, sign_coin_spends};
+
+    use super::*;
+
+    async fn request_solution(
+        peer: Peer,
+        coin_id: Bytes32,
+        height: u32,
+    ) -> Result<(), WalletError> {
+        peer.request_puzzle_and_solution(coin_id, height)
+            .await?
+            .map_err(WalletError::RejectPuzzleSolution)?;
+
+        Ok(())
+    }
+
+    // Spends a new coin on the simulator and returns its id and spent height.
+    async fn spend_coin(sim: &PeerSimulator) -> anyhow::Result<(Bytes32, u32)> {
+        let sk = test_secret_key()?;
+        let pk = sk.public_key();
+        let puzzle_hash = StandardArgs::curry_tree_hash(pk).into();
+        let coin = sim.mint_coin(puzzle_hash, 1).await;
+
+        let coin_spends = send_xch(pk, &[coin], &[(puzzle_hash, 1, Vec::new())], 0)?;
+        let signature = sign_coin_spends(coin_spends.clone(), vec![sk], &TargetNetwork::mainnet())?;
+
+        let peer = sim.connect().await?;
+        let ack = peer
+            .send_transaction(SpendBundle::new(coin_spends, signature))
+            .await?;
+        assert_eq!(ack.error, None);
+
+        let spent_height = sim
+            .coin_state(coin.coin_id())
+            .await
+            .and_then(|cs| cs.spent_height)
+            .expect("coin should be spent");
+
+        Ok((coin.coin_id(), spent_height))
+    }
+
+    // The simulator answers unknown puzzle solution requests with a malformed message,
+    // so the peer that hasn't seen the spend behaves like a broken connection.
+    #[tokio::test]
+    async fn test_failover() -> anyhow::Result<()> {
+        let behind = PeerSimulator::new().await?;
+        let synced = PeerSimulator::new().await?;
+        let (coin_id, height) = spend_coin(&synced).await?;
+
+        let pool = PeerPool::new(NetworkId::Mainnet, None);
+        let (peer, receiver) = behind.connect_split().await?;
+        let behind_addr = peer.socket_addr();
+        pool.insert_peer(peer, receiver).await;
+        let (peer, receiver) = synced.connect_split().await?;
+        pool.insert_peer(peer, receiver).await;
+
+        for _ in 0..4 {
+            pool.request(|peer| request_solution(peer, coin_id, height))
+                .await?;
+        }
+
+        let connected = pool.connected_peers().await;
+        assert_eq!(connected.len(), 1);
+        assert!(!connected.contains(&behind_addr));
+        assert_eq!(pool.peers().await.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failing_peer_is_dropped() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let pool = PeerPool::new(NetworkId::Mainnet, None);
+
+        let (peer, receiver) = sim.connect_split().await?;
+        pool.insert_peer(peer, receiver).await;
+
+        // Without a TLS connector the peer can't be reconnected, so every attempt fails.
+        for _ in 0..MAX_PEER_FAILURES {
+            assert!(pool
+                .request(|peer| request_solution(peer, Bytes32::default(), 0))
+                .await
+                .is_err());
+        }
+        assert!(pool.peers().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flapping_peer_is_dropped() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let pool = PeerPool::new(NetworkId::Mainnet, None);
+
+        // Every connection is accepted, but drops before a request succeeds.
+        for failures in 0..MAX_PEER_FAILURES {
+            let (peer, receiver) = sim.connect_split().await?;
+            let socket_addr = peer.socket_addr();
+            pool.insert_peer(peer, receiver).await;
+            assert_eq!(pool.entries.lock().await[0].failures, failures);
+
+            mark_failure(&pool.entries, socket_addr, None).await;
+        }
+        assert!(pool.peers().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replaced_connection() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let pool = PeerPool::new(NetworkId::Mainnet, None);
+
+        let (peer, receiver) = sim.connect_split().await?;
+        let socket_addr = peer.socket_addr();
+        pool.insert_peer(peer, receiver).await;
+        let replaced = pool.entries.lock().await[0].connection;
+
+        let (peer, receiver) = sim.connect_split().await?;
+        pool.insert_peer(peer, receiver).await;
+
+        // Failures of the replaced connection don't count against the new one.
+        mark_failure(&pool.entries, socket_addr, Some(replaced)).await;
+        tokio::task::yield_now().await;
+        assert_eq!(pool.connected_peers().await, vec![socket_addr]);
+        assert_eq!(pool.entries.lock().await[0].failures, 0);
+
+        assert!(pool.remove_peer(socket_addr).await);
+        assert!(pool.peers().await.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_pool() {
+        let pool = PeerPool::new(NetworkId::Mainnet, None);
+
+        let result = pool
+            .request(|peer| request_solution(peer, Bytes32::default(), 0))
+            .await;
+        assert!(matches!(result, Err(WalletError::NoPeers)));
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use chia::bls::{sign, verify, PublicKey, SecretKey, Signature};
+use chia::protocol::{Bytes, CoinSpend};
+use chia::puzzles::DeriveSynthetic;
+
+use crate::network::TargetNetwork;
+use crate::unsigned::{required_signatures, UnsignedTransaction};
+use crate::wallet::WalletError;
+
+/// Signs the messages a transaction needs, e.g. with keys held in memory, by a hardware
+/// security module or by a wallet over RPC.
+pub trait Signer: Send + Sync {
+    /// Signs the final message with the secret key of the public key, or returns `None`
+    /// if the signer doesn't hold that key.
+    fn sign(
+        &self,
+        public_key: PublicKey,
+        message: Bytes,
+    ) -> impl Future<Output = Result<Option<Signature>, WalletError>> + Send;
+}
+
+/// Signs with secret keys held in memory. Like `sign_coin_spends`, each key also signs for its
+/// synthetic key.
+#[derive(Debug, Clone, Default)]
+pub struct KeySigner {
+    secret_keys: HashMap<PublicKey, SecretKey>,
+}
+
+impl KeySigner {
+    pub fn new(secret_keys: Vec<SecretKey>) -> Self {
+        Self {
+            secret_keys: secret_keys
+                .into_iter()
+                .flat_map(|sk| [sk.derive_synthetic(), sk])
+                .map(|sk| (sk.public_key(), sk))
+                .collect(),
+        }
+    }
+}
+
+impl Signer for KeySigner {
+    async fn sign(
+        &self,
+        public_key: PublicKey,
+        message: Bytes,
+    ) -> Result<Option<Signature>, WalletError> {
+        Ok(self
+            .secret_keys
+            .get(&public_key)
+            .map(|sk| sign(sk, message)))
+    }
+}
+
+/// Asks the signer for every signature the coin spends need and aggregates them.
+///
+/// Unlike `sign_coin_spends`, this fails with `MissingSignatures`, listing the public keys
+/// the signer didn't sign for, instead of returning a signature that doesn't validate. A
+/// signature that doesn't verify fails with `SignatureMismatch`.
+pub async fn sign_with_signer(
+    signer: &impl Signer,
+    coin_spends: &[CoinSpend],
+    network: &TargetNetwork,
+) -> Result<Signature, WalletError> {
+    let mut aggregated_signature = Signature::default();
+    let mut missing = Vec::new();
+
+    for (public_key, message) in required_signatures(coin_spends, network)? {
+        match signer.sign(public_key, message.clone()).await? {
+            Some(signature) if verify(&signature, &public_key, &message) => {
+                aggregated_signature += &signature;
+            }
+            Some(_) => return Err(WalletError::SignatureMismatch(Box::new(public_key))),
+            None => {
+                if !missing.contains(&public_key) {
+                    missing.push(public_key);
+                }
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(WalletError::MissingSignatures(missing));
+    }
+
+    Ok(aggregated_signature)
+}
+
+impl UnsignedTransaction {
+    /// Asks the signer for the missing signatures. Signatures the signer doesn't hold the key
+    /// for stay missing, so that other signers can add them. Returns the number of signatures
+    /// added.
+    pub async fn sign_with(&mut self, signer: &impl Signer) -> Result<usize, WalletError> {
+        let missing = self
+            .required_signatures()
+            .iter()
+            .filter(|request| request.signature.is_none())
+            .map(|request| (request.public_key, request.message.clone()))
+            .collect::<Vec<_>>();
+
+        let mut added = 0;
+
+        for (public_key, message) in missing {
+            if let Some(signature) = signer.sign(public_key, message.clone()).await? {
+                self.add_signature(public_key, &message, signature)?;
+                added += 1;
+            }
+        }
+
+        Ok(added)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::protocol::{Bytes32, Coin, SpendBundle};
+    use chia::puzzles::standard::StandardArgs;
+    use chia_wallet_sdk::{Conditions, SpendContext};
+
+    use super::*;
+    use crate::simulator::ChainSimulator;
+    use crate::wallet::send_spend_bundle;
+
+    // A signer that signs every message with the wrong key.
+    struct WrongKeySigner(SecretKey);
+
+    impl Signer for WrongKeySigner {
+        async fn sign(
+            &self,
+            _public_key: PublicKey,
+            message: Bytes,
+        ) -> Result<Option<Signature>, WalletError> {
+            Ok(Some(sign(&self.0, message)))
+        }
+    }
+
+    fn spend_coins(coins: &[(Coin, PublicKey)]) -> anyhow::Result<Vec<CoinSpend>> {
+        let mut ctx = SpendContext::new();
+
+        for (coin, synthetic_key) in coins {
+            ctx.spend_p2_coin(
+                *coin,
+                *synthetic_key,
+                Conditions::new().reserve_fee(coin.amount),
+            )?;
+        }
+
+        Ok(ctx.take())
+    }
+
+    #[tokio::test]
+    async fn test_sign_with_signer() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+
+        let first = SecretKey::from_seed(&[8; 32]);
+        let second = SecretKey::from_seed(&[9; 32]);
+        let coins = [&first, &second].map(|sk| {
+            let synthetic_key = sk.public_key().derive_synthetic();
+            let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+            (sim.new_coin(puzzle_hash, 100), synthetic_key)
+        });
+        let coin_spends = spend_coins(&coins)?;
+
+        // Signing with only one of the keys fails and names the other one.
+        let result = sign_with_signer(
+            &KeySigner::new(vec![first.clone()]),
+            &coin_spends,
+            sim.network(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(WalletError::MissingSignatures(keys)) if keys == vec![coins[1].1]
+        ));
+
+        let result =
+            sign_with_signer(&WrongKeySigner(first.clone()), &coin_spends, sim.network()).await;
+        assert!(matches!(result, Err(WalletError::SignatureMismatch(_))));
+
+        // An unsigned transaction can be signed by each signer in turn.
+        let mut transaction =
+            UnsignedTransaction::new(coin_spends.clone(), sim.network(), String::new())?;
+        assert_eq!(
+            transaction
+                .sign_with(&KeySigner::new(vec![first.clone()]))
+                .await?,
+            1
+        );
+        assert_eq!(transaction.missing_signatures(), vec![coins[1].1]);
+        assert_eq!(
+            transaction
+                .sign_with(&KeySigner::new(vec![second.clone()]))
+                .await?,
+            1
+        );
+        assert!(transaction.missing_signatures().is_empty());
+
+        let signature = sign_with_signer(
+            &KeySigner::new(vec![first, second]),
+            &coin_spends,
+            sim.network(),
+        )
+        .await?;
+        assert_eq!(signature, transaction.spend_bundle()?.aggregated_signature);
+
+        send_spend_bundle(&sim, SpendBundle::new(coin_spends, signature)).await?;
+
+        Ok(())
+    }
+}
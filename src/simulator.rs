@@ -0,0 +1,1355 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chia::consensus::consensus_constants::ConsensusConstants;
+use chia::consensus::gen::owned_conditions::OwnedSpendBundleConditions;
+use chia::consensus::gen::validation_error::ErrorCode;
+use chia::consensus::spendbundle_validation::validate_clvm_and_signature;
+use chia::protocol::{
+    Bytes32, Coin, CoinState, CoinStateFilters, FeeEstimate, FeeEstimateGroup, FeeRate, Program,
+    PuzzleSolutionResponse, RejectCoinState, RejectHeaderRequest, RejectPuzzleSolution,
+    RejectPuzzleState, RejectStateReason, RespondCoinState, RespondPuzzleState, SpendBundle,
+    TransactionAck,
+};
+use clvmr::sha2::Sha256;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::network::TargetNetwork;
+use crate::wallet::WalletError;
+use crate::wallet_protocol::{BlockHeaderInfo, Response, WalletProtocol};
+
+/// Seconds between the timestamps of consecutive simulated blocks.
+pub const SIMULATOR_BLOCK_TIME: u64 = 20;
+
+/// A block farmed by the simulator, along with the coin states it changed.
+#[derive(Debug, Clone)]
+pub struct BlockUpdate {
+    pub header: BlockHeaderInfo,
+    pub coin_states: Vec<CoinState>,
+}
+
+/// An in-memory blockchain that answers wallet protocol requests like a full node would.
+///
+/// Every block is a transaction block unless made otherwise with `farm_non_transaction_blocks`,
+/// and each accepted spend bundle is farmed into a block of its own, so there is no mempool.
+/// Spend bundles are validated with the consensus rules of the simulated network before they
+/// are applied to the coin set.
+#[derive(Debug)]
+pub struct ChainSimulator {
+    network: TargetNetwork,
+    constants: ConsensusConstants,
+    state: Mutex<ChainState>,
+}
+
+#[derive(Debug, Default)]
+struct ChainState {
+    blocks: Vec<BlockHeaderInfo>,
+    coin_states: HashMap<Bytes32, CoinState>,
+    hints: HashMap<Bytes32, HashSet<Bytes32>>,
+    puzzle_solutions: HashMap<Bytes32, (Program, Program)>,
+    coin_nonce: u64,
+    // Makes blocks farmed after a reorg differ from the orphaned ones at the same height.
+    reorgs: u32,
+    listeners: Vec<UnboundedSender<BlockUpdate>>,
+    refused_connections: u32,
+    failed_requests: u32,
+    non_transaction_blocks: u32,
+    header_requests: u32,
+}
+
+impl ChainSimulator {
+    pub fn new(network: TargetNetwork) -> Self {
+        let genesis_timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+
+        let genesis = BlockHeaderInfo {
+            height: 0,
+            header_hash: block_hash(network.genesis_challenge, 0, 0),
+            prev_header_hash: network.genesis_challenge,
+            timestamp: Some(genesis_timestamp),
+        };
+
+        Self {
+            constants: network.constants(),
+            network,
+            state: Mutex::new(ChainState {
+                blocks: vec![genesis],
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn network(&self) -> &TargetNetwork {
+        &self.network
+    }
+
+    /// The height of the peak block.
+    pub fn height(&self) -> u32 {
+        self.state().peak().height
+    }
+
+    pub fn header_hash(&self, height: u32) -> Option<Bytes32> {
+        self.state()
+            .blocks
+            .get(height as usize)
+            .map(|block| block.header_hash)
+    }
+
+    pub fn coin_state(&self, coin_id: Bytes32) -> Option<CoinState> {
+        self.state().coin_states.get(&coin_id).copied()
+    }
+
+    /// Farms a block that creates a coin out of thin air, for funding test wallets.
+    pub fn new_coin(&self, puzzle_hash: Bytes32, amount: u64) -> Coin {
+        let mut state = self.state();
+
+        state.coin_nonce += 1;
+        let mut hasher = Sha256::new();
+        hasher.update(b"simulator coin");
+        hasher.update(state.coin_nonce.to_be_bytes());
+        let coin = Coin::new(hasher.finalize().into(), puzzle_hash, amount);
+
+        let height = state.peak().height + 1;
+        let coin_state = CoinState::new(coin, None, Some(height));
+        state.coin_states.insert(coin.coin_id(), coin_state);
+        state.farm_block(vec![coin_state]);
+
+        coin
+    }
+
+    /// Farms empty blocks.
+    pub fn farm_blocks(&self, count: u32) {
+        let mut state = self.state();
+
+        for _ in 0..count {
+            state.farm_block(Vec::new());
+        }
+    }
+
+    /// Orphans every block after `fork_height`, undoing their coin changes.
+    ///
+    /// Blocks farmed afterwards have different header hashes than the orphaned ones.
+    pub fn reorg(&self, fork_height: u32) {
+        let mut state = self.state();
+
+        if fork_height >= state.peak().height {
+            return;
+        }
+
+        state.blocks.truncate(fork_height as usize + 1);
+        state.reorgs += 1;
+
+        let mut reverted = Vec::new();
+        let ChainState {
+            coin_states,
+            hints,
+            puzzle_solutions,
+            ..
+        } = &mut *state;
+
+        coin_states.retain(|coin_id, coin_state| {
+            if coin_state
+                .created_height
+                .is_some_and(|height| height > fork_height)
+            {
+                puzzle_solutions.remove(coin_id);
+                return false;
+            }
+
+            if coin_state
+                .spent_height
+                .is_some_and(|height| height > fork_height)
+            {
+                coin_state.spent_height = None;
+                puzzle_solutions.remove(coin_id);
+                reverted.push(*coin_state);
+            }
+
+            true
+        });
+
+        for hinted in hints.values_mut() {
+            hinted.retain(|coin_id| coin_states.contains_key(coin_id));
+        }
+
+        let update = BlockUpdate {
+            header: state.peak(),
+            coin_states: reverted,
+        };
+        state
+            .listeners
+            .retain(|listener| listener.send(update.clone()).is_ok());
+    }
+
+    /// Opens a connection that receives every block farmed from now on, and the new peak after
+    /// a reorg.
+    pub fn connect(&self) -> Result<UnboundedReceiver<BlockUpdate>, WalletError> {
+        let mut state = self.state();
+
+        if state.refused_connections > 0 {
+            state.refused_connections -= 1;
+            return Err(io::Error::from(io::ErrorKind::ConnectionRefused).into());
+        }
+
+        let (sender, receiver) = unbounded_channel();
+        state.listeners.push(sender);
+        Ok(receiver)
+    }
+
+    /// Closes every open connection, like a node going away.
+    pub fn disconnect(&self) {
+        self.state().listeners.clear();
+    }
+
+    /// Makes the next `count` connection attempts fail.
+    pub fn refuse_connections(&self, count: u32) {
+        self.state().refused_connections = count;
+    }
+
+    /// Makes the next `count` wallet protocol requests fail, as if the connection dropped.
+    pub fn fail_requests(&self, count: u32) {
+        self.state().failed_requests = count;
+    }
+
+    /// Farms the next `count` blocks as non-transaction blocks, which have no timestamp.
+    pub fn farm_non_transaction_blocks(&self, count: u32) {
+        self.state().non_transaction_blocks = count;
+    }
+
+    /// The number of block headers requested so far.
+    pub fn header_requests(&self) -> u32 {
+        self.state().header_requests
+    }
+
+    /// Validates a spend bundle against the peak and farms it into a new block.
+    ///
+    /// Returns the coin states changed by the spend bundle.
+    pub fn push_transaction(&self, spend_bundle: SpendBundle) -> Result<Vec<CoinState>, ErrorCode> {
+        if spend_bundle.coin_spends.is_empty() {
+            return Err(ErrorCode::InvalidSpendBundle);
+        }
+
+        let mut state = self.state();
+        let peak = state.peak();
+        let height = peak.height + 1;
+
+        let (conditions, _, _) = validate_clvm_and_signature(
+            &spend_bundle,
+            self.constants.max_block_cost_clvm,
+            &self.constants,
+            height,
+        )?;
+
+        state.check_conditions(&conditions, peak)?;
+
+        let mut coin_states = Vec::new();
+
+        for spend in &conditions.spends {
+            for (puzzle_hash, amount, hint) in &spend.create_coin {
+                let coin = Coin::new(spend.coin_id, *puzzle_hash, *amount);
+                let coin_id = coin.coin_id();
+
+                if let Some(hint) = hint.as_ref().filter(|hint| hint.len() == 32) {
+                    let hint = Bytes32::try_from(hint.as_ref()).expect("hint is 32 bytes");
+                    state.hints.entry(hint).or_default().insert(coin_id);
+                }
+
+                let coin_state = CoinState::new(coin, None, Some(height));
+                state.coin_states.insert(coin_id, coin_state);
+                coin_states.push(coin_state);
+            }
+        }
+
+        for coin_spend in spend_bundle.coin_spends {
+            let coin_id = coin_spend.coin.coin_id();
+
+            let coin_state = state
+                .coin_states
+                .get_mut(&coin_id)
+                .expect("removals are checked before the block is farmed");
+            coin_state.spent_height = Some(height);
+            let coin_state = *coin_state;
+
+            state
+                .puzzle_solutions
+                .insert(coin_id, (coin_spend.puzzle_reveal, coin_spend.solution));
+
+            match coin_states
+                .iter_mut()
+                .find(|existing| existing.coin.coin_id() == coin_id)
+            {
+                Some(existing) => *existing = coin_state,
+                None => coin_states.push(coin_state),
+            }
+        }
+
+        state.farm_block(coin_states.clone());
+
+        Ok(coin_states)
+    }
+
+    fn state(&self) -> MutexGuard<'_, ChainState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Fails the request if it was made to fail with `fail_requests`.
+    fn check_request(&self) -> Result<(), WalletError> {
+        let mut state = self.state();
+
+        if state.failed_requests > 0 {
+            state.failed_requests -= 1;
+            return Err(io::Error::from(io::ErrorKind::ConnectionReset).into());
+        }
+
+        Ok(())
+    }
+
+    // A node only answers requests whose previous block is still part of its chain.
+    fn is_known_block(
+        &self,
+        state: &ChainState,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+    ) -> bool {
+        match previous_height {
+            Some(height) => state
+                .blocks
+                .get(height as usize)
+                .is_some_and(|block| block.header_hash == header_hash),
+            None => header_hash == self.network.genesis_challenge,
+        }
+    }
+}
+
+impl ChainState {
+    fn peak(&self) -> BlockHeaderInfo {
+        *self.blocks.last().expect("the genesis block always exists")
+    }
+
+    // The timestamp of the last transaction block at or before `height`, which time locks are
+    // checked against.
+    fn timestamp(&self, height: u32) -> u64 {
+        self.blocks[..=height as usize]
+            .iter()
+            .rev()
+            .find_map(|block| block.timestamp)
+            .expect("the genesis block is a transaction block")
+    }
+
+    fn farm_block(&mut self, coin_states: Vec<CoinState>) {
+        let peak = self.peak();
+        let height = peak.height + 1;
+
+        let timestamp = if self.non_transaction_blocks > 0 {
+            self.non_transaction_blocks -= 1;
+            None
+        } else {
+            self.blocks[0].timestamp.map(|genesis_timestamp| {
+                genesis_timestamp + SIMULATOR_BLOCK_TIME * u64::from(height)
+            })
+        };
+
+        let header = BlockHeaderInfo {
+            height,
+            header_hash: block_hash(peak.header_hash, height, self.reorgs),
+            prev_header_hash: peak.header_hash,
+            timestamp,
+        };
+        self.blocks.push(header);
+
+        let update = BlockUpdate {
+            header,
+            coin_states,
+        };
+        self.listeners
+            .retain(|listener| listener.send(update.clone()).is_ok());
+    }
+
+    // Checks what `validate_clvm_and_signature` can't know about: the coin set and the peak.
+    fn check_conditions(
+        &self,
+        conditions: &OwnedSpendBundleConditions,
+        peak: BlockHeaderInfo,
+    ) -> Result<(), ErrorCode> {
+        let peak_timestamp = self.timestamp(peak.height);
+
+        let mut removals = HashSet::new();
+        let additions: HashSet<Bytes32> = conditions
+            .spends
+            .iter()
+            .flat_map(|spend| {
+                spend.create_coin.iter().map(|(puzzle_hash, amount, _)| {
+                    Coin::new(spend.coin_id, *puzzle_hash, *amount).coin_id()
+                })
+            })
+            .collect();
+
+        for addition in &additions {
+            if self.coin_states.contains_key(addition) {
+                return Err(ErrorCode::DuplicateOutput);
+            }
+        }
+
+        for spend in &conditions.spends {
+            if !removals.insert(spend.coin_id) {
+                return Err(ErrorCode::DoubleSpend);
+            }
+
+            let (created_height, created_timestamp) = match self.coin_states.get(&spend.coin_id) {
+                Some(coin_state) => {
+                    if coin_state.spent_height.is_some() {
+                        return Err(ErrorCode::DoubleSpend);
+                    }
+
+                    let created_height = coin_state.created_height.unwrap_or_default();
+                    let created_timestamp = self.timestamp(created_height);
+                    (created_height, created_timestamp)
+                }
+                None if additions.contains(&spend.coin_id) => (peak.height + 1, peak_timestamp),
+                None => return Err(ErrorCode::UnknownUnspent),
+            };
+
+            if spend
+                .height_relative
+                .is_some_and(|relative| created_height + relative > peak.height)
+            {
+                return Err(ErrorCode::AssertHeightRelativeFailed);
+            }
+
+            if spend
+                .seconds_relative
+                .is_some_and(|relative| created_timestamp + relative > peak_timestamp)
+            {
+                return Err(ErrorCode::AssertSecondsRelativeFailed);
+            }
+
+            if spend
+                .before_height_relative
+                .is_some_and(|relative| created_height + relative <= peak.height)
+            {
+                return Err(ErrorCode::AssertBeforeHeightRelativeFailed);
+            }
+
+            if spend
+                .before_seconds_relative
+                .is_some_and(|relative| created_timestamp + relative <= peak_timestamp)
+            {
+                return Err(ErrorCode::AssertBeforeSecondsRelativeFailed);
+            }
+
+            if spend
+                .birth_height
+                .is_some_and(|height| height != created_height)
+            {
+                return Err(ErrorCode::AssertMyBirthHeightFailed);
+            }
+
+            if spend
+                .birth_seconds
+                .is_some_and(|seconds| seconds != created_timestamp)
+            {
+                return Err(ErrorCode::AssertMyBirthSecondsFailed);
+            }
+        }
+
+        if conditions.height_absolute > peak.height {
+            return Err(ErrorCode::AssertHeightAbsoluteFailed);
+        }
+
+        if conditions.seconds_absolute > peak_timestamp {
+            return Err(ErrorCode::AssertSecondsAbsoluteFailed);
+        }
+
+        if conditions
+            .before_height_absolute
+            .is_some_and(|height| height <= peak.height)
+        {
+            return Err(ErrorCode::AssertBeforeHeightAbsoluteFailed);
+        }
+
+        if conditions
+            .before_seconds_absolute
+            .is_some_and(|seconds| seconds <= peak_timestamp)
+        {
+            return Err(ErrorCode::AssertBeforeSecondsAbsoluteFailed);
+        }
+
+        Ok(())
+    }
+}
+
+fn block_hash(prev_header_hash: Bytes32, height: u32, reorgs: u32) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_header_hash);
+    hasher.update(height.to_be_bytes());
+    hasher.update(reorgs.to_be_bytes());
+    hasher.finalize().into()
+}
+
+// Coin states are only returned if they changed after the previous height.
+fn changed_since(coin_state: &CoinState, min_height: u32) -> bool {
+    coin_state
+        .created_height
+        .max(coin_state.spent_height)
+        .is_some_and(|height| height >= min_height)
+}
+
+impl WalletProtocol for ChainSimulator {
+    async fn request_puzzle_state(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        filters: CoinStateFilters,
+        _subscribe_when_finished: bool,
+    ) -> Result<Response<RespondPuzzleState, RejectPuzzleState>, WalletError> {
+        self.check_request()?;
+        let state = self.state();
+
+        if !self.is_known_block(&state, previous_height, header_hash) {
+            return Ok(Err(RejectPuzzleState::new(RejectStateReason::Reorg)));
+        }
+
+        let min_height = previous_height.map_or(0, |height| height + 1);
+        let mut coin_ids = HashSet::new();
+
+        for puzzle_hash in &puzzle_hashes {
+            if filters.include_hinted {
+                if let Some(hinted) = state.hints.get(puzzle_hash) {
+                    coin_ids.extend(hinted.iter().copied());
+                }
+            }
+        }
+
+        let mut coin_states: Vec<CoinState> = state
+            .coin_states
+            .values()
+            .filter(|coin_state| {
+                puzzle_hashes.contains(&coin_state.coin.puzzle_hash)
+                    || coin_ids.contains(&coin_state.coin.coin_id())
+            })
+            .filter(|coin_state| {
+                if coin_state.spent_height.is_some() {
+                    filters.include_spent
+                } else {
+                    filters.include_unspent
+                }
+            })
+            .filter(|coin_state| coin_state.coin.amount >= filters.min_amount)
+            .filter(|coin_state| changed_since(coin_state, min_height))
+            .copied()
+            .collect();
+        coin_states
+            .sort_by_key(|coin_state| coin_state.created_height.max(coin_state.spent_height));
+
+        let peak = state.peak();
+
+        Ok(Ok(RespondPuzzleState::new(
+            puzzle_hashes,
+            peak.height,
+            peak.header_hash,
+            true,
+            coin_states,
+        )))
+    }
+
+    async fn request_coin_state(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        _subscribe: bool,
+    ) -> Result<Response<RespondCoinState, RejectCoinState>, WalletError> {
+        self.check_request()?;
+        let state = self.state();
+
+        if !self.is_known_block(&state, previous_height, header_hash) {
+            return Ok(Err(RejectCoinState::new(RejectStateReason::Reorg)));
+        }
+
+        let min_height = previous_height.map_or(0, |height| height + 1);
+
+        let coin_states = coin_ids
+            .iter()
+            .filter_map(|coin_id| state.coin_states.get(coin_id))
+            .filter(|coin_state| changed_since(coin_state, min_height))
+            .copied()
+            .collect();
+
+        Ok(Ok(RespondCoinState::new(coin_ids, coin_states)))
+    }
+
+    async fn request_puzzle_and_solution(
+        &self,
+        coin_id: Bytes32,
+        height: u32,
+    ) -> Result<Response<PuzzleSolutionResponse, RejectPuzzleSolution>, WalletError> {
+        self.check_request()?;
+        let state = self.state();
+
+        let spent_at_height = state
+            .coin_states
+            .get(&coin_id)
+            .is_some_and(|coin_state| coin_state.spent_height == Some(height));
+
+        match state.puzzle_solutions.get(&coin_id) {
+            Some((puzzle, solution)) if spent_at_height => Ok(Ok(PuzzleSolutionResponse::new(
+                coin_id,
+                height,
+                puzzle.clone(),
+                solution.clone(),
+            ))),
+            _ => Ok(Err(RejectPuzzleSolution::new(coin_id, height))),
+        }
+    }
+
+    async fn request_block_header(
+        &self,
+        height: u32,
+    ) -> Result<Response<BlockHeaderInfo, RejectHeaderRequest>, WalletError> {
+        self.check_request()?;
+
+        let mut state = self.state();
+        state.header_requests += 1;
+
+        Ok(state
+            .blocks
+            .get(height as usize)
+            .copied()
+            .ok_or(RejectHeaderRequest::new(height)))
+    }
+
+    async fn request_fee_estimates(
+        &self,
+        time_targets: Vec<u64>,
+    ) -> Result<FeeEstimateGroup, WalletError> {
+        self.check_request()?;
+
+        // Blocks are never full, so no fee is needed.
+        Ok(FeeEstimateGroup::new(
+            None,
+            time_targets
+                .into_iter()
+                .map(|time_target| FeeEstimate::new(None, time_target, FeeRate::new(0)))
+                .collect(),
+        ))
+    }
+
+    async fn send_transaction(
+        &self,
+        spend_bundle: SpendBundle,
+    ) -> Result<TransactionAck, WalletError> {
+        self.check_request()?;
+        let txid = spend_bundle.name();
+
+        Ok(match self.push_transaction(spend_bundle) {
+            Ok(_) => TransactionAck::new(txid, 1, None),
+            Err(error) => TransactionAck::new(txid, 3, Some(format!("{error:?}"))),
+        })
+    }
+
+    async fn remove_coin_subscriptions(
+        &self,
+        _coin_ids: Option<Vec<Bytes32>>,
+    ) -> Result<(), WalletError> {
+        self.check_request()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl ChainSimulator {
+    /// Signs the coin spends with the secret key and farms them into a new block.
+    pub(crate) fn sign_and_push(
+        &self,
+        sk: &chia::bls::SecretKey,
+        coin_spends: Vec<chia::protocol::CoinSpend>,
+    ) {
+        let signature =
+            crate::wallet::sign_coin_spends(coin_spends.clone(), vec![sk.clone()], self.network())
+                .unwrap();
+        self.push_transaction(SpendBundle::new(coin_spends, signature))
+            .unwrap();
+    }
+
+    /// The number of open connections.
+    pub(crate) fn connections(&self) -> usize {
+        self.state().listeners.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::{SecretKey, Signature};
+    use chia::protocol::CoinSpend;
+    use chia::puzzles::standard::StandardArgs;
+    use chia::puzzles::DeriveSynthetic;
+    use chia_wallet_sdk::{Conditions, DataStore, DelegatedPuzzle, SpendContext};
+
+    use super::*;
+    use crate::wallet::{
+        get_store_update, melt_store, mint_store, send_spend_bundle, sign_coin_spends, sync_store,
+        sync_store_using_launcher_id, update_store_metadata, update_store_ownership,
+        validate_spend_bundle, validate_spend_bundle_on_chain, DataStoreInnerSpend, StoreReorg,
+        StoreSpendKind, MAX_TIMESTAMP_LOOKBACK,
+    };
+
+    fn secret_key() -> SecretKey {
+        SecretKey::from_seed(&[7; 32])
+    }
+
+    fn push(sim: &ChainSimulator, coin_spends: Vec<CoinSpend>) -> Result<(), ErrorCode> {
+        push_signed(sim, coin_spends, vec![secret_key()])
+    }
+
+    fn push_signed(
+        sim: &ChainSimulator,
+        coin_spends: Vec<CoinSpend>,
+        secret_keys: Vec<SecretKey>,
+    ) -> Result<(), ErrorCode> {
+        let signature: Signature =
+            sign_coin_spends(coin_spends.clone(), secret_keys, sim.network()).unwrap();
+        sim.push_transaction(SpendBundle::new(coin_spends, signature))
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn test_store_lifecycle() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let genesis_challenge = sim.network().genesis_challenge;
+
+        let synthetic_key = secret_key().public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1000);
+
+        let minted = mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            Some("label".to_string()),
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            10,
+        )?;
+        assert_eq!(push(&sim, minted.coin_spends), Ok(()));
+        let launcher_id = minted.new_datastore.info.launcher_id;
+
+        let updated = update_store_metadata(
+            minted.new_datastore,
+            Bytes32::new([2; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Owner(synthetic_key),
+        )?;
+        assert_eq!(push(&sim, updated.coin_spends), Ok(()));
+        sim.farm_blocks(3);
+
+        let synced =
+            sync_store_using_launcher_id(&sim, launcher_id, None, genesis_challenge, true, &[])
+                .await?;
+        assert_eq!(
+            synced.latest_store.info.metadata.root_hash,
+            Bytes32::new([2; 32])
+        );
+        assert_eq!(synced.latest_store.coin, updated.new_datastore.coin);
+        assert_eq!(
+            synced
+                .root_hash_history
+                .unwrap()
+                .into_iter()
+                .map(|(root_hash, _)| root_hash)
+                .collect::<Vec<_>>(),
+            vec![Bytes32::new([1; 32]), Bytes32::new([2; 32])]
+        );
+
+        assert_eq!(
+            push(
+                &sim,
+                melt_store(synced.latest_store.clone(), synthetic_key)?
+            ),
+            Ok(())
+        );
+
+        let melted = sim.coin_state(synced.latest_store.coin.coin_id()).unwrap();
+        assert_eq!(melted.spent_height, Some(sim.height()));
+
+        // Melting the store doesn't create a new state.
+        assert!(get_store_update(&sim, &synced.latest_store, sim.height())
+            .await?
+            .is_none());
+
+        // A melted store can't be synced past its last state.
+        assert!(sync_store(
+            &sim,
+            &synced.latest_store,
+            None,
+            genesis_challenge,
+            false,
+            &[]
+        )
+        .await
+        .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_store_history() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let genesis_challenge = sim.network().genesis_challenge;
+
+        let owner_key = secret_key().public_key().derive_synthetic();
+        let owner_puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(owner_key).into();
+        let admin_sk = SecretKey::from_seed(&[8; 32]);
+        let admin_key = admin_sk.public_key().derive_synthetic();
+        let writer_sk = SecretKey::from_seed(&[9; 32]);
+        let writer_key = writer_sk.public_key().derive_synthetic();
+
+        let admin = DelegatedPuzzle::Admin(StandardArgs::curry_tree_hash(admin_key));
+        let writer = DelegatedPuzzle::Writer(StandardArgs::curry_tree_hash(writer_key));
+
+        let coin = sim.new_coin(owner_puzzle_hash, 1);
+        let minted = mint_store(
+            owner_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            owner_puzzle_hash,
+            vec![admin, writer],
+            0,
+        )?;
+        assert_eq!(push(&sim, minted.coin_spends), Ok(()));
+        let launcher_id = minted.new_datastore.info.launcher_id;
+
+        let written = update_store_metadata(
+            minted.new_datastore,
+            Bytes32::new([2; 32]),
+            Some("label".to_string()),
+            None,
+            None,
+            DataStoreInnerSpend::Writer(writer_key),
+        )?;
+        assert_eq!(
+            push_signed(&sim, written.coin_spends, vec![writer_sk]),
+            Ok(())
+        );
+
+        let administered = update_store_ownership(
+            written.new_datastore,
+            owner_puzzle_hash,
+            vec![admin],
+            DataStoreInnerSpend::Admin(admin_key),
+        )?;
+        assert_eq!(
+            push_signed(&sim, administered.coin_spends, vec![admin_sk]),
+            Ok(())
+        );
+
+        let owned = update_store_metadata(
+            administered.new_datastore,
+            Bytes32::new([3; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Owner(owner_key),
+        )?;
+        assert_eq!(push(&sim, owned.coin_spends), Ok(()));
+
+        let synced =
+            sync_store_using_launcher_id(&sim, launcher_id, None, genesis_challenge, true, &[])
+                .await?;
+        let history = synced.history.unwrap();
+
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| (entry.kind, entry.delegated_puzzle))
+                .collect::<Vec<_>>(),
+            vec![
+                (StoreSpendKind::Launch, None),
+                (StoreSpendKind::Writer, Some(writer)),
+                (StoreSpendKind::Admin, Some(admin)),
+                (StoreSpendKind::Owner, None),
+            ]
+        );
+        assert_eq!(history[0].coin_id, launcher_id);
+        assert!(history[0].previous_info.is_none());
+
+        for (previous, entry) in history.iter().zip(history.iter().skip(1)) {
+            assert_eq!(entry.previous_info.as_ref(), Some(&previous.info));
+            assert_eq!(entry.height, previous.height + 1);
+            assert_eq!(entry.header_hash, sim.header_hash(entry.height).unwrap());
+        }
+
+        assert_eq!(history[1].info.metadata.label, Some("label".to_string()));
+        assert!(history[1].metadata_changed() && !history[1].ownership_changed());
+        assert_eq!(history[2].info.delegated_puzzles, vec![admin]);
+        assert!(!history[2].metadata_changed() && history[2].ownership_changed());
+        assert!(history[3].metadata_changed() && !history[3].ownership_changed());
+
+        assert_eq!(
+            synced.root_hash_history.unwrap(),
+            history
+                .iter()
+                .map(|entry| (entry.info.metadata.root_hash, entry.timestamp))
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_history_timestamps() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let genesis_challenge = sim.network().genesis_challenge;
+
+        let synthetic_key = secret_key().public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1);
+
+        let minted = mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            0,
+        )?;
+        assert_eq!(push(&sim, minted.coin_spends), Ok(()));
+        let launcher_id = minted.new_datastore.info.launcher_id;
+        let launch_height = sim.height();
+
+        let update = |store: DataStore, root_hash: u8| {
+            update_store_metadata(
+                store,
+                Bytes32::new([root_hash; 32]),
+                None,
+                None,
+                None,
+                DataStoreInnerSpend::Owner(synthetic_key),
+            )
+        };
+
+        // Both updates fall back to the timestamp of the launch block.
+        sim.farm_non_transaction_blocks(2);
+        let mut store = minted.new_datastore;
+        for root_hash in [2, 3] {
+            let updated = update(store, root_hash)?;
+            assert_eq!(push(&sim, updated.coin_spends), Ok(()));
+            store = updated.new_datastore;
+        }
+
+        let header_requests = sim.header_requests();
+        let synced =
+            sync_store_using_launcher_id(&sim, launcher_id, None, genesis_challenge, true, &[])
+                .await?;
+        let launch_timestamp = sim.state().blocks[launch_height as usize].timestamp;
+
+        assert_eq!(
+            synced
+                .history
+                .unwrap()
+                .into_iter()
+                .map(|entry| (entry.height, Some(entry.timestamp)))
+                .collect::<Vec<_>>(),
+            vec![
+                (launch_height, launch_timestamp),
+                (launch_height + 1, launch_timestamp),
+                (launch_height + 2, launch_timestamp),
+            ]
+        );
+        assert_eq!(sim.header_requests() - header_requests, 3);
+
+        // The walk back to a transaction block gives up after `MAX_TIMESTAMP_LOOKBACK` blocks.
+        sim.farm_non_transaction_blocks(MAX_TIMESTAMP_LOOKBACK + 1);
+        sim.farm_blocks(MAX_TIMESTAMP_LOOKBACK);
+        assert_eq!(push(&sim, update(store, 4)?.coin_spends), Ok(()));
+        assert!(matches!(
+            sync_store_using_launcher_id(&sim, launcher_id, None, genesis_challenge, true, &[]).await,
+            Err(WalletError::MissingTimestamp(height)) if height == sim.height()
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_after_reorg() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let genesis_challenge = sim.network().genesis_challenge;
+
+        let synthetic_key = secret_key().public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1);
+
+        let minted = mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            0,
+        )?;
+        assert_eq!(push(&sim, minted.coin_spends), Ok(()));
+        let fork_height = sim.height();
+
+        let update = |root_hash: Bytes32| -> anyhow::Result<Vec<CoinSpend>> {
+            Ok(update_store_metadata(
+                minted.new_datastore.clone(),
+                root_hash,
+                None,
+                None,
+                None,
+                DataStoreInnerSpend::Owner(synthetic_key),
+            )?
+            .coin_spends)
+        };
+
+        assert_eq!(push(&sim, update(Bytes32::new([2; 32]))?), Ok(()));
+        sim.farm_blocks(2);
+
+        let synced = sync_store_using_launcher_id(
+            &sim,
+            minted.new_datastore.info.launcher_id,
+            None,
+            genesis_challenge,
+            true,
+            &[],
+        )
+        .await?;
+        let known_history = synced.history.unwrap();
+        let last_height = sim.height();
+        let last_header_hash = sim.header_hash(last_height).unwrap();
+
+        // The update to the second root hash is orphaned and replaced by another one.
+        sim.reorg(fork_height);
+        assert_eq!(push(&sim, update(Bytes32::new([3; 32]))?), Ok(()));
+        sim.farm_blocks(5);
+
+        let resynced = sync_store(
+            &sim,
+            &synced.latest_store,
+            Some(last_height),
+            last_header_hash,
+            true,
+            &known_history,
+        )
+        .await?;
+
+        assert_eq!(
+            resynced.latest_store.info.metadata.root_hash,
+            Bytes32::new([3; 32])
+        );
+        assert_eq!(
+            resynced.reorg,
+            Some(StoreReorg {
+                fork_height: Some(fork_height),
+                orphaned_root_hashes: vec![Bytes32::new([2; 32])],
+            })
+        );
+        let resynced_history = resynced.history.unwrap();
+        assert_eq!(resynced_history[0], known_history[0]);
+        assert_eq!(
+            resynced
+                .root_hash_history
+                .unwrap()
+                .into_iter()
+                .map(|(root_hash, _)| root_hash)
+                .collect::<Vec<_>>(),
+            vec![Bytes32::new([1; 32]), Bytes32::new([3; 32])]
+        );
+
+        // The known history doesn't have to start at the launch. If none of it is still part of
+        // the chain, the store is synced from its launcher.
+        let resynced_from_launcher = sync_store(
+            &sim,
+            &synced.latest_store,
+            Some(last_height),
+            last_header_hash,
+            false,
+            &known_history[1..],
+        )
+        .await?;
+        assert_eq!(
+            resynced_from_launcher.reorg,
+            Some(StoreReorg {
+                fork_height: None,
+                orphaned_root_hashes: vec![Bytes32::new([2; 32])],
+            })
+        );
+        assert!(resynced_from_launcher.history.is_none());
+
+        // A chain that is shorter than the last height is a reorg, even if nothing known changed.
+        let resynced_again = sync_store(
+            &sim,
+            &resynced.latest_store,
+            Some(sim.height() + 1),
+            last_header_hash,
+            false,
+            &resynced_history,
+        )
+        .await?;
+        assert_eq!(
+            resynced_again.reorg,
+            Some(StoreReorg {
+                fork_height: Some(resynced_history[1].height),
+                orphaned_root_hashes: vec![],
+            })
+        );
+        assert_eq!(resynced_again.latest_store, resynced.latest_store);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reorg_includes_spend_again() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let genesis_challenge = sim.network().genesis_challenge;
+
+        let synthetic_key = secret_key().public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1);
+
+        let minted = mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            0,
+        )?;
+        assert_eq!(push(&sim, minted.coin_spends), Ok(()));
+        let fork_height = sim.height();
+
+        let updated = update_store_metadata(
+            minted.new_datastore.clone(),
+            Bytes32::new([2; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Owner(synthetic_key),
+        )?;
+        assert_eq!(push(&sim, updated.coin_spends.clone()), Ok(()));
+        sim.farm_blocks(2);
+
+        let synced = sync_store_using_launcher_id(
+            &sim,
+            minted.new_datastore.info.launcher_id,
+            None,
+            genesis_challenge,
+            true,
+            &[],
+        )
+        .await?;
+        let known_history = synced.history.unwrap();
+        let last_height = sim.height();
+        let last_header_hash = sim.header_hash(last_height).unwrap();
+
+        // The update is orphaned, then included again in a later block of the new chain.
+        sim.reorg(fork_height);
+        sim.farm_blocks(1);
+        assert_eq!(push(&sim, updated.coin_spends), Ok(()));
+        sim.farm_blocks(5);
+
+        let resynced = sync_store(
+            &sim,
+            &synced.latest_store,
+            Some(last_height),
+            last_header_hash,
+            true,
+            &known_history,
+        )
+        .await?;
+
+        assert_eq!(
+            resynced.reorg,
+            Some(StoreReorg {
+                fork_height: Some(fork_height),
+                orphaned_root_hashes: vec![],
+            })
+        );
+        let resynced_history = resynced.history.unwrap();
+        assert_eq!(resynced_history.len(), 2);
+        assert_eq!(resynced_history[1].coin_id, known_history[1].coin_id);
+        assert_eq!(resynced_history[1].height, fork_height + 2);
+        assert_eq!(resynced.latest_store, synced.latest_store);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_spends() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+
+        let synthetic_key = secret_key().public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1000);
+
+        let spend = |conditions: Conditions| -> anyhow::Result<Vec<CoinSpend>> {
+            let mut ctx = SpendContext::new();
+            ctx.spend_p2_coin(coin, synthetic_key, conditions)?;
+            Ok(ctx.take())
+        };
+
+        assert_eq!(
+            push(
+                &sim,
+                spend(Conditions::new().create_coin(puzzle_hash, 2000, vec![]))?
+            ),
+            Err(ErrorCode::MintingCoin)
+        );
+        assert_eq!(
+            push(&sim, spend(Conditions::new().assert_height_relative(5))?),
+            Err(ErrorCode::AssertHeightRelativeFailed)
+        );
+
+        let unsigned = SpendBundle::new(spend(Conditions::new())?, Signature::default());
+        assert_eq!(
+            sim.push_transaction(unsigned.clone()),
+            Err(ErrorCode::BadAggregateSignature)
+        );
+        assert!(matches!(
+            send_spend_bundle(&sim, unsigned).await,
+            Err(WalletError::TransactionRejected { status: 3, error })
+                if error == "BadAggregateSignature"
+        ));
+
+        sim.farm_blocks(5);
+        assert_eq!(
+            push(&sim, spend(Conditions::new().assert_height_relative(5))?),
+            Ok(())
+        );
+        assert_eq!(
+            push(&sim, spend(Conditions::new())?),
+            Err(ErrorCode::DoubleSpend)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validate_spend_bundle() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+
+        let synthetic_key = secret_key().public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1000);
+
+        let spend = |coin: Coin, conditions: Conditions| -> anyhow::Result<SpendBundle> {
+            let mut ctx = SpendContext::new();
+            ctx.spend_p2_coin(coin, synthetic_key, conditions)?;
+            let coin_spends = ctx.take();
+            let signature =
+                sign_coin_spends(coin_spends.clone(), vec![secret_key()], sim.network())?;
+            Ok(SpendBundle::new(coin_spends, signature))
+        };
+
+        let spend_bundle = spend(
+            coin,
+            Conditions::new()
+                .create_coin(puzzle_hash, 900, vec![])
+                .reserve_fee(100)
+                .assert_height_relative(0),
+        )?;
+        let conditions = validate_spend_bundle_on_chain(&sim, &spend_bundle, sim.network()).await?;
+        assert_eq!(conditions.spends.len(), 1);
+        assert_eq!(conditions.spends[0].coin_id, coin.coin_id());
+        assert_eq!(conditions.spends[0].create_coin.len(), 1);
+        assert_eq!(conditions.spends[0].height_relative, Some(0));
+        assert_eq!(conditions.removal_amount, 1000);
+        assert_eq!(conditions.addition_amount, 900);
+        assert_eq!(conditions.reserve_fee, 100);
+
+        // An assertion no spend in the bundle satisfies.
+        let unannounced = spend(
+            coin,
+            Conditions::new().assert_coin_announcement(Bytes32::new([1; 32])),
+        )?;
+        assert!(matches!(
+            validate_spend_bundle(&unannounced, sim.network()),
+            Err(WalletError::InvalidSpendBundle(
+                ErrorCode::AssertCoinAnnouncementFailed
+            ))
+        ));
+
+        let unsigned = SpendBundle::new(spend_bundle.coin_spends.clone(), Signature::default());
+        assert!(matches!(
+            validate_spend_bundle(&unsigned, sim.network()),
+            Err(WalletError::InvalidSpendBundle(
+                ErrorCode::BadAggregateSignature
+            ))
+        ));
+
+        // A coin the chain doesn't know about is valid on its own, but not on chain.
+        let unknown = spend(
+            Coin::new(Bytes32::new([2; 32]), puzzle_hash, 1000),
+            Conditions::new(),
+        )?;
+        validate_spend_bundle(&unknown, sim.network())?;
+        assert!(matches!(
+            validate_spend_bundle_on_chain(&sim, &unknown, sim.network()).await,
+            Err(WalletError::UnknownCoin(_))
+        ));
+
+        sim.push_transaction(spend_bundle.clone()).unwrap();
+        let height = sim.height();
+        assert!(matches!(
+            validate_spend_bundle_on_chain(&sim, &spend_bundle, sim.network()).await,
+            Err(WalletError::CoinAlreadySpent { coin_id, height: spent_height })
+                if coin_id == coin.coin_id() && spent_height == height
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_coin_state_requests() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let genesis_challenge = sim.network().genesis_challenge;
+        let coin = sim.new_coin(Bytes32::new([3; 32]), 1);
+        let height = sim.height();
+
+        let response = sim
+            .request_coin_state(vec![coin.coin_id()], None, genesis_challenge, false)
+            .await?
+            .unwrap();
+        assert_eq!(response.coin_states.len(), 1);
+
+        // Nothing changed after the coin was created.
+        let response = sim
+            .request_coin_state(
+                vec![coin.coin_id()],
+                Some(height),
+                sim.header_hash(height).unwrap(),
+                false,
+            )
+            .await?
+            .unwrap();
+        assert!(response.coin_states.is_empty());
+
+        let rejection = sim
+            .request_coin_state(vec![coin.coin_id()], Some(height), genesis_challenge, false)
+            .await?
+            .unwrap_err();
+        assert_eq!(rejection.reason, RejectStateReason::Reorg);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+use chia::protocol::{Bytes32, CoinSpend};
+
+use crate::wallet::WalletError;
+use crate::wallet_protocol::WalletProtocol;
+
+/// Default number of blocks a spend bundle needs to be buried under before it's confirmed.
+/// One means it's confirmed as soon as it's part of a block.
+pub const DEFAULT_CONFIRMATIONS: u32 = 1;
+
+/// Default number of blocks after which a spend bundle that didn't make it into a block is
+/// considered dropped from the mempool. About an hour on mainnet.
+pub const DEFAULT_EXPIRY_BLOCKS: u32 = 200;
+
+/// Where a submitted spend bundle stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpendStatus {
+    /// Not in a block yet, or not buried deep enough to be confirmed.
+    Pending,
+    /// Part of the block at `height`, with enough blocks on top of it.
+    Confirmed { height: u32 },
+    /// One of the coins was spent at `height` by a different spend, so the bundle can't be
+    /// included anymore.
+    Conflicted { coin_id: Bytes32, height: u32 },
+    /// Didn't make it into a block before it expired.
+    Dropped,
+}
+
+impl SpendStatus {
+    /// Whether the status can't change anymore.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Self::Pending)
+    }
+}
+
+/// Whether the coins of a spend bundle were spent, and by whom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inclusion {
+    NotIncluded,
+    Included(u32),
+    Conflicted { coin_id: Bytes32, height: u32 },
+}
+
+/// The coins spent by a spend bundle that exist before it. The others are created by the bundle
+/// itself, since their parent is spent in it.
+pub fn existing_coin_ids(coin_spends: &[CoinSpend]) -> Vec<Bytes32> {
+    let coin_ids: Vec<Bytes32> = coin_spends.iter().map(|cs| cs.coin.coin_id()).collect();
+
+    coin_spends
+        .iter()
+        .filter(|cs| !coin_ids.contains(&cs.coin.parent_coin_info))
+        .map(|cs| cs.coin.coin_id())
+        .collect()
+}
+
+/// Looks up the coins spent by a spend bundle, and subscribes to their states if `subscribe` is
+/// set. Only the coins that exist before the bundle are looked up.
+pub async fn get_inclusion(
+    peer: &impl WalletProtocol,
+    coin_spends: &[CoinSpend],
+    genesis_challenge: Bytes32,
+    subscribe: bool,
+) -> Result<Inclusion, WalletError> {
+    let coin_ids = existing_coin_ids(coin_spends);
+    let response = peer
+        .request_coin_state(coin_ids, None, genesis_challenge, subscribe)
+        .await?
+        .map_err(WalletError::RejectCoinState)?;
+
+    let mut included_height = None;
+
+    for coin_state in response.coin_states {
+        let Some(spent_height) = coin_state.spent_height else {
+            continue;
+        };
+
+        let coin_id = coin_state.coin.coin_id();
+        let Some(coin_spend) = coin_spends.iter().find(|cs| cs.coin.coin_id() == coin_id) else {
+            continue;
+        };
+
+        let puzzle_and_solution = peer
+            .request_puzzle_and_solution(coin_id, spent_height)
+            .await?
+            .map_err(WalletError::RejectPuzzleSolution)?;
+
+        if puzzle_and_solution.puzzle != coin_spend.puzzle_reveal
+            || puzzle_and_solution.solution != coin_spend.solution
+        {
+            return Ok(Inclusion::Conflicted {
+                coin_id,
+                height: spent_height,
+            });
+        }
+
+        included_height = Some(spent_height);
+    }
+
+    Ok(included_height.map_or(Inclusion::NotIncluded, Inclusion::Included))
+}
+
+/// Follows a submitted spend bundle until it's confirmed, conflicted or dropped.
+///
+/// The bundle's coins are subscribed to on the first update. Until one of them is spent, which
+/// the caller reports with `coin_spent` when the peer sends the coin state update, new peaks
+/// only move the expiry along without asking the peer again.
+#[derive(Debug, Clone)]
+pub struct SpendTracker {
+    coin_spends: Vec<CoinSpend>,
+    confirmations: u32,
+    expiry_blocks: u32,
+    expiry_height: Option<u32>,
+    subscribed: bool,
+    inclusion: Option<Inclusion>,
+    status: Option<SpendStatus>,
+}
+
+impl SpendTracker {
+    /// Tracks a spend bundle that needs `confirmations` blocks (at least one) and expires
+    /// `expiry_blocks` blocks after the first update.
+    pub fn new(coin_spends: Vec<CoinSpend>, confirmations: u32, expiry_blocks: u32) -> Self {
+        Self {
+            coin_spends,
+            confirmations: confirmations.max(1),
+            expiry_blocks,
+            expiry_height: None,
+            subscribed: false,
+            inclusion: None,
+            status: None,
+        }
+    }
+
+    /// The coins that are subscribed to, which are the ones that exist before the bundle.
+    pub fn coin_ids(&self) -> Vec<Bytes32> {
+        existing_coin_ids(&self.coin_spends)
+    }
+
+    /// Marks the bundle's coins as changed, so the next update checks them again.
+    pub fn coin_spent(&mut self) {
+        self.inclusion = None;
+    }
+
+    /// Checks the bundle against the chain at `peak`. Returns the new status if it changed.
+    pub async fn update(
+        &mut self,
+        peer: &impl WalletProtocol,
+        genesis_challenge: Bytes32,
+        peak: u32,
+    ) -> Result<Option<SpendStatus>, WalletError> {
+        if self.status.is_some_and(|status| status.is_final()) {
+            return Ok(None);
+        }
+
+        let inclusion = match self.inclusion {
+            Some(Inclusion::NotIncluded) => Inclusion::NotIncluded,
+            // Checked again at every peak until it's confirmed, since the block could be orphaned.
+            _ => {
+                let inclusion =
+                    get_inclusion(peer, &self.coin_spends, genesis_challenge, !self.subscribed)
+                        .await?;
+                self.subscribed = true;
+                inclusion
+            }
+        };
+        self.inclusion = Some(inclusion);
+
+        let status = self.status_at(inclusion, peak);
+
+        if self.status == Some(status) {
+            return Ok(None);
+        }
+
+        self.status = Some(status);
+        Ok(Some(status))
+    }
+
+    fn status_at(&mut self, inclusion: Inclusion, peak: u32) -> SpendStatus {
+        let expiry_height = *self
+            .expiry_height
+            .get_or_insert(peak.saturating_add(self.expiry_blocks));
+
+        match inclusion {
+            Inclusion::Included(height) if peak >= height + self.confirmations - 1 => {
+                SpendStatus::Confirmed { height }
+            }
+            Inclusion::Included(_) => SpendStatus::Pending,
+            Inclusion::Conflicted { coin_id, height } => {
+                SpendStatus::Conflicted { coin_id, height }
+            }
+            Inclusion::NotIncluded if peak >= expiry_height => SpendStatus::Dropped,
+            Inclusion::NotIncluded => SpendStatus::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::{PublicKey, SecretKey};
+    use chia::protocol::Coin;
+    use chia::puzzles::standard::StandardArgs;
+    use chia::puzzles::DeriveSynthetic;
+
+    use super::*;
+    use crate::network::TargetNetwork;
+    use crate::simulator::ChainSimulator;
+    use crate::wallet::mint_store;
+
+    fn mint(synthetic_key: PublicKey, coin: Coin, fee: u64) -> Vec<CoinSpend> {
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            fee,
+        )
+        .unwrap()
+        .coin_spends
+    }
+
+    #[tokio::test]
+    async fn test_spend_tracker() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let genesis_challenge = sim.network().genesis_challenge;
+        let sk = SecretKey::from_seed(&[7; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1_000);
+
+        let submitted = mint(synthetic_key, coin, 0);
+        let mut tracker = SpendTracker::new(submitted.clone(), 3, 5);
+        let mut conflicted = SpendTracker::new(mint(synthetic_key, coin, 1), 1, 5);
+        // The launcher is created by the bundle, so only the funding coin is subscribed to.
+        assert_eq!(tracker.coin_ids(), vec![coin.coin_id()]);
+        let height = sim.height();
+        assert_eq!(
+            tracker.update(&sim, genesis_challenge, height).await?,
+            Some(SpendStatus::Pending)
+        );
+        assert_eq!(
+            conflicted.update(&sim, genesis_challenge, height).await?,
+            Some(SpendStatus::Pending)
+        );
+
+        sim.sign_and_push(&sk, submitted);
+        let height = sim.height();
+        assert_eq!(tracker.update(&sim, genesis_challenge, height).await?, None);
+        assert_eq!(
+            conflicted.update(&sim, genesis_challenge, height).await?,
+            None
+        );
+
+        // The coins are only checked again once the spend is reported.
+        tracker.coin_spent();
+        conflicted.coin_spent();
+        assert_eq!(tracker.update(&sim, genesis_challenge, height).await?, None);
+        assert_eq!(
+            conflicted.update(&sim, genesis_challenge, height).await?,
+            Some(SpendStatus::Conflicted {
+                coin_id: coin.coin_id(),
+                height
+            })
+        );
+
+        sim.farm_blocks(2);
+        assert_eq!(
+            tracker
+                .update(&sim, genesis_challenge, sim.height())
+                .await?,
+            Some(SpendStatus::Confirmed { height })
+        );
+
+        // A bundle that never makes it into a block is dropped once it expires.
+        let coin = sim.new_coin(puzzle_hash, 1_000);
+        let mut dropped = SpendTracker::new(mint(synthetic_key, coin, 0), 1, 5);
+        dropped
+            .update(&sim, genesis_challenge, sim.height())
+            .await?;
+        sim.farm_blocks(4);
+        assert_eq!(
+            dropped
+                .update(&sim, genesis_challenge, sim.height())
+                .await?,
+            None
+        );
+        sim.farm_blocks(1);
+        assert_eq!(
+            dropped
+                .update(&sim, genesis_challenge, sim.height())
+                .await?,
+            Some(SpendStatus::Dropped)
+        );
+
+        Ok(())
+    }
+}
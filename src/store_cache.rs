@@ -0,0 +1,644 @@
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use chia::clvm_traits::{self, FromClvm, ToClvm};
+use chia::protocol::{Bytes, Bytes32, Coin};
+use chia::puzzles::Proof;
+use chia_wallet_sdk::{DataStore, DataStoreInfo, DataStoreMetadata, DelegatedPuzzle};
+use clvmr::serde::{node_from_bytes, node_to_bytes};
+use clvmr::Allocator;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::wallet::{
+    get_genesis_challenge, resync_store, sync_store, sync_store_using_launcher_id, HeaderCache,
+    StoreHistoryEntry, StoreSpendKind, SyncStoreResponse, WalletError,
+};
+use crate::wallet_protocol::WalletProtocol;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS stores (
+        launcher_id BLOB PRIMARY KEY,
+        coin BLOB NOT NULL,
+        proof BLOB NOT NULL,
+        info BLOB NOT NULL,
+        height INTEGER NOT NULL,
+        checkpoint_height INTEGER NOT NULL,
+        checkpoint_header_hash BLOB NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS history (
+        launcher_id BLOB NOT NULL,
+        position INTEGER NOT NULL,
+        kind INTEGER NOT NULL,
+        delegated_puzzle BLOB,
+        coin_id BLOB NOT NULL,
+        height INTEGER NOT NULL,
+        header_hash BLOB NOT NULL,
+        timestamp INTEGER NOT NULL,
+        previous_info BLOB,
+        info BLOB NOT NULL,
+        PRIMARY KEY (launcher_id, position)
+    );
+";
+
+/// A synced store, as saved in a `StoreCache`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedStore {
+    pub store: DataStore,
+    /// Height of the block the store's coin was created in.
+    pub height: u32,
+    /// The block before `height`, which syncing resumes from. A coin created in the block a
+    /// sync starts from wouldn't be reported by the node.
+    pub checkpoint_height: u32,
+    pub checkpoint_header_hash: Bytes32,
+    /// Every spend of the store, starting at its launch.
+    pub history: Vec<StoreHistoryEntry>,
+}
+
+/// An on-disk cache of synced stores, kept in a SQLite database.
+///
+/// Stores are saved with their full history and the block syncing resumes from, so a restart
+/// doesn't replay every store's spends from its launcher.
+pub struct StoreCache {
+    connection: Mutex<Connection>,
+}
+
+impl StoreCache {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, WalletError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, WalletError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, WalletError> {
+        connection.execute_batch(SCHEMA)?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    // a panic can't leave the database half written, since writes are made in transactions
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn launcher_ids(&self) -> Result<Vec<Bytes32>, WalletError> {
+        let connection = self.connection();
+        let mut statement = connection.prepare("SELECT launcher_id FROM stores")?;
+
+        let rows = statement
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))?
+            .collect::<rusqlite::Result<Vec<Vec<u8>>>>()?;
+
+        let mut launcher_ids = Vec::with_capacity(rows.len());
+        for launcher_id in rows {
+            launcher_ids.push(bytes32(&launcher_id)?);
+        }
+
+        Ok(launcher_ids)
+    }
+
+    pub fn get(&self, launcher_id: Bytes32) -> Result<Option<CachedStore>, WalletError> {
+        let connection = self.connection();
+
+        let Some((coin, proof, info, height, checkpoint_height, checkpoint_header_hash)) =
+            connection
+                .query_row(
+                    "SELECT coin, proof, info, height, checkpoint_height, checkpoint_header_hash
+                    FROM stores WHERE launcher_id = ?1",
+                    params![launcher_id.to_vec()],
+                    |row| {
+                        Ok((
+                            row.get::<_, Vec<u8>>(0)?,
+                            row.get::<_, Vec<u8>>(1)?,
+                            row.get::<_, Vec<u8>>(2)?,
+                            row.get::<_, u32>(3)?,
+                            row.get::<_, u32>(4)?,
+                            row.get::<_, Vec<u8>>(5)?,
+                        ))
+                    },
+                )
+                .optional()?
+        else {
+            return Ok(None);
+        };
+
+        let mut statement = connection.prepare(
+            "SELECT kind, delegated_puzzle, coin_id, height, header_hash, timestamp,
+            previous_info, info FROM history WHERE launcher_id = ?1 ORDER BY position",
+        )?;
+        let rows = statement
+            .query_map(params![launcher_id.to_vec()], |row| {
+                Ok((
+                    row.get::<_, u8>(0)?,
+                    row.get::<_, Option<Vec<u8>>>(1)?,
+                    row.get::<_, Vec<u8>>(2)?,
+                    row.get::<_, u32>(3)?,
+                    row.get::<_, Vec<u8>>(4)?,
+                    row.get::<_, u64>(5)?,
+                    row.get::<_, Option<Vec<u8>>>(6)?,
+                    row.get::<_, Vec<u8>>(7)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut history = Vec::with_capacity(rows.len());
+        for (
+            kind,
+            delegated_puzzle,
+            coin_id,
+            height,
+            header_hash,
+            timestamp,
+            previous_info,
+            info,
+        ) in rows
+        {
+            history.push(StoreHistoryEntry {
+                kind: spend_kind_from_u8(kind)?,
+                delegated_puzzle: match delegated_puzzle {
+                    Some(delegated_puzzle) => Some(decode_delegated_puzzle(&delegated_puzzle)?),
+                    None => None,
+                },
+                coin_id: bytes32(&coin_id)?,
+                height,
+                header_hash: bytes32(&header_hash)?,
+                timestamp,
+                previous_info: match previous_info {
+                    Some(previous_info) => Some(decode_info(launcher_id, &previous_info)?),
+                    None => None,
+                },
+                info: decode_info(launcher_id, &info)?,
+            });
+        }
+
+        Ok(Some(CachedStore {
+            store: DataStore {
+                coin: decode::<Coin>(&coin)?,
+                proof: decode::<Proof>(&proof)?,
+                info: decode_info(launcher_id, &info)?,
+            },
+            height,
+            checkpoint_height,
+            checkpoint_header_hash: bytes32(&checkpoint_header_hash)?,
+            history,
+        }))
+    }
+
+    /// Saves a store, replacing what was cached for it.
+    pub fn insert(&self, cached: &CachedStore) -> Result<(), WalletError> {
+        self.save(cached, 0, true)
+    }
+
+    /// Saves a store's new state, appending the history entries after the ones already cached.
+    pub fn update(&self, cached: &CachedStore, cached_entries: usize) -> Result<(), WalletError> {
+        self.save(cached, cached_entries, false)
+    }
+
+    pub fn remove(&self, launcher_id: Bytes32) -> Result<(), WalletError> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+
+        transaction.execute(
+            "DELETE FROM history WHERE launcher_id = ?1",
+            params![launcher_id.to_vec()],
+        )?;
+        transaction.execute(
+            "DELETE FROM stores WHERE launcher_id = ?1",
+            params![launcher_id.to_vec()],
+        )?;
+
+        Ok(transaction.commit()?)
+    }
+
+    fn save(
+        &self,
+        cached: &CachedStore,
+        cached_entries: usize,
+        replace_history: bool,
+    ) -> Result<(), WalletError> {
+        let launcher_id = cached.store.info.launcher_id.to_vec();
+
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+
+        transaction.execute(
+            "INSERT OR REPLACE INTO stores (launcher_id, coin, proof, info, height,
+            checkpoint_height, checkpoint_header_hash) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                launcher_id,
+                encode(cached.store.coin)?,
+                encode(cached.store.proof)?,
+                encode_info(&cached.store.info)?,
+                cached.height,
+                cached.checkpoint_height,
+                cached.checkpoint_header_hash.to_vec(),
+            ],
+        )?;
+
+        if replace_history {
+            transaction.execute(
+                "DELETE FROM history WHERE launcher_id = ?1",
+                params![launcher_id],
+            )?;
+        }
+
+        for (position, entry) in cached.history.iter().enumerate().skip(cached_entries) {
+            let delegated_puzzle = match entry.delegated_puzzle {
+                Some(delegated_puzzle) => {
+                    Some(encode(delegated_puzzle_memos(&[delegated_puzzle]))?)
+                }
+                None => None,
+            };
+            let previous_info = match &entry.previous_info {
+                Some(previous_info) => Some(encode_info(previous_info)?),
+                None => None,
+            };
+
+            transaction.execute(
+                "INSERT OR REPLACE INTO history (launcher_id, position, kind, delegated_puzzle,
+                coin_id, height, header_hash, timestamp, previous_info, info)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    launcher_id,
+                    position,
+                    spend_kind_to_u8(entry.kind),
+                    delegated_puzzle,
+                    entry.coin_id.to_vec(),
+                    entry.height,
+                    entry.header_hash.to_vec(),
+                    entry.timestamp,
+                    previous_info,
+                    encode_info(&entry.info)?,
+                ],
+            )?;
+        }
+
+        Ok(transaction.commit()?)
+    }
+}
+
+/// Syncs a store, resuming from its cached state, and saves the result to the cache.
+///
+/// Stores that aren't cached yet are synced from their launcher. If a reorg orphaned the block
+/// the cached state resumes from or the cached coin itself, the store is synced again from the
+/// newest cached state that is still part of the chain, and the cached states after it are
+/// replaced with the current chain's.
+pub async fn sync_cached_store(
+    peer: &impl WalletProtocol,
+    cache: &StoreCache,
+    launcher_id: Bytes32,
+    with_history: bool,
+) -> Result<SyncStoreResponse, WalletError> {
+    let (response, history, cached_entries) = match cache.get(launcher_id)? {
+        Some(cached) => match sync_store(
+            peer,
+            &cached.store,
+            Some(cached.checkpoint_height),
+            cached.checkpoint_header_hash,
+            true,
+            &cached.history,
+        )
+        .await
+        {
+            Ok(response) if response.reorg.is_none() => {
+                let cached_entries = cached.history.len();
+                let mut history = cached.history;
+                history.extend(response.history.clone().unwrap_or_default());
+                (response, history, cached_entries)
+            }
+            Ok(response) => {
+                let history = response.history.clone().unwrap_or_default();
+                (response, history, 0)
+            }
+            // the cached coin was orphaned, but the checkpoint wasn't
+            Err(WalletError::UnknownCoin(_)) => {
+                let response =
+                    resync_store(peer, &HeaderCache::new(), launcher_id, &cached.history).await?;
+                let history = response.history.clone().unwrap_or_default();
+                (response, history, 0)
+            }
+            Err(error) => return Err(error),
+        },
+        None => {
+            let response = sync_from_launcher(peer, launcher_id).await?;
+            let history = response.history.clone().unwrap_or_default();
+            (response, history, 0)
+        }
+    };
+
+    let created_block = HeaderCache::new()
+        .get_header(peer, response.latest_height)
+        .await?;
+    let cached = CachedStore {
+        store: response.latest_store.clone(),
+        height: response.latest_height,
+        checkpoint_height: response.latest_height.saturating_sub(1),
+        checkpoint_header_hash: created_block.prev_header_hash,
+        history,
+    };
+
+    if cached_entries == 0 {
+        cache.insert(&cached)?;
+    } else {
+        cache.update(&cached, cached_entries)?;
+    }
+
+    Ok(SyncStoreResponse {
+        reorg: response.reorg,
+        ..SyncStoreResponse::new(
+            cached.store,
+            cached.height,
+            with_history.then_some(cached.history),
+        )
+    })
+}
+
+async fn sync_from_launcher(
+    peer: &impl WalletProtocol,
+    launcher_id: Bytes32,
+) -> Result<SyncStoreResponse, WalletError> {
+    let genesis_challenge = get_genesis_challenge(peer).await?;
+
+    sync_store_using_launcher_id(peer, launcher_id, None, genesis_challenge, true, &[]).await
+}
+
+// The parts of a store's info besides its launcher id, which the cache keys rows by.
+#[derive(ToClvm, FromClvm)]
+#[clvm(list)]
+struct EncodedInfo {
+    metadata: DataStoreMetadata,
+    owner_puzzle_hash: Bytes32,
+    delegated_puzzle_memos: Vec<Bytes>,
+}
+
+pub(crate) fn encode(value: impl ToClvm<Allocator>) -> Result<Vec<u8>, WalletError> {
+    let mut allocator = Allocator::new();
+    let ptr = value.to_clvm(&mut allocator)?;
+
+    Ok(node_to_bytes(&allocator, ptr)?)
+}
+
+pub(crate) fn decode<T: FromClvm<Allocator>>(bytes: &[u8]) -> Result<T, WalletError> {
+    let mut allocator = Allocator::new();
+    let ptr = node_from_bytes(&mut allocator, bytes)?;
+
+    Ok(T::from_clvm(&allocator, ptr)?)
+}
+
+fn encode_info(info: &DataStoreInfo) -> Result<Vec<u8>, WalletError> {
+    encode(EncodedInfo {
+        metadata: info.metadata.clone(),
+        owner_puzzle_hash: info.owner_puzzle_hash,
+        delegated_puzzle_memos: delegated_puzzle_memos(&info.delegated_puzzles),
+    })
+}
+
+fn decode_info(launcher_id: Bytes32, bytes: &[u8]) -> Result<DataStoreInfo, WalletError> {
+    let info = decode::<EncodedInfo>(bytes)?;
+
+    Ok(DataStoreInfo {
+        launcher_id,
+        metadata: info.metadata,
+        owner_puzzle_hash: info.owner_puzzle_hash,
+        delegated_puzzles: decode_delegated_puzzles(info.delegated_puzzle_memos)?,
+    })
+}
+
+// Delegated puzzles are saved in the format of the memos that recreate a store.
+fn delegated_puzzle_memos(delegated_puzzles: &[DelegatedPuzzle]) -> Vec<Bytes> {
+    DataStore::<DataStoreMetadata>::get_recreation_memos(
+        Bytes32::default(),
+        Bytes32::default().into(),
+        delegated_puzzles.to_vec(),
+    )
+    .split_off(2)
+}
+
+fn decode_delegated_puzzles(mut memos: Vec<Bytes>) -> Result<Vec<DelegatedPuzzle>, WalletError> {
+    let mut delegated_puzzles = vec![];
+
+    while !memos.is_empty() {
+        delegated_puzzles.push(DelegatedPuzzle::from_memos(&mut memos)?);
+    }
+
+    Ok(delegated_puzzles)
+}
+
+fn decode_delegated_puzzle(bytes: &[u8]) -> Result<DelegatedPuzzle, WalletError> {
+    decode_delegated_puzzles(decode(bytes)?)?
+        .pop()
+        .ok_or(WalletError::Parse)
+}
+
+fn spend_kind_to_u8(kind: StoreSpendKind) -> u8 {
+    match kind {
+        StoreSpendKind::Launch => 0,
+        StoreSpendKind::Owner => 1,
+        StoreSpendKind::Admin => 2,
+        StoreSpendKind::Writer => 3,
+        StoreSpendKind::Oracle => 4,
+    }
+}
+
+fn spend_kind_from_u8(kind: u8) -> Result<StoreSpendKind, WalletError> {
+    Ok(match kind {
+        0 => StoreSpendKind::Launch,
+        1 => StoreSpendKind::Owner,
+        2 => StoreSpendKind::Admin,
+        3 => StoreSpendKind::Writer,
+        4 => StoreSpendKind::Oracle,
+        _ => return Err(WalletError::Parse),
+    })
+}
+
+fn bytes32(bytes: &[u8]) -> Result<Bytes32, WalletError> {
+    Ok(Bytes32::new(
+        bytes.try_into().map_err(|_| WalletError::Parse)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use chia::protocol::CoinSpend;
+    use chia::puzzles::standard::StandardArgs;
+    use chia::puzzles::DeriveSynthetic;
+
+    use super::*;
+    use crate::network::TargetNetwork;
+    use crate::simulator::ChainSimulator;
+    use crate::wallet::{mint_store, update_store_metadata, DataStoreInnerSpend};
+
+    fn root_hashes(history: &[StoreHistoryEntry]) -> Vec<Bytes32> {
+        history
+            .iter()
+            .map(|entry| entry.info.metadata.root_hash)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_cache_round_trip() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[4; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        let delegated_puzzles = vec![
+            DelegatedPuzzle::Admin(Bytes32::new([5; 32]).into()),
+            DelegatedPuzzle::Writer(puzzle_hash.into()),
+            DelegatedPuzzle::Oracle(Bytes32::new([6; 32]), 1000),
+        ];
+
+        let coin = sim.new_coin(puzzle_hash, 1);
+        let minted = mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            Some("label".to_string()),
+            Some("description".to_string()),
+            Some(1024),
+            puzzle_hash,
+            delegated_puzzles,
+            0,
+        )?;
+        sim.sign_and_push(&sk, minted.coin_spends);
+        let launcher_id = minted.new_datastore.info.launcher_id;
+
+        let updated = update_store_metadata(
+            minted.new_datastore,
+            Bytes32::new([2; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Writer(synthetic_key),
+        )?;
+        sim.sign_and_push(&sk, updated.coin_spends);
+
+        let path = std::env::temp_dir().join(format!("store-cache-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let synced = {
+            let cache = StoreCache::open(&path)?;
+            sync_cached_store(&sim, &cache, launcher_id, true).await?
+        };
+
+        let cache = StoreCache::open(&path)?;
+        let cached = cache.get(launcher_id)?.unwrap();
+
+        assert_eq!(cache.launcher_ids()?, vec![launcher_id]);
+        assert_eq!(cached.store, synced.latest_store);
+        assert_eq!(cached.store, updated.new_datastore);
+        assert_eq!(cached.height, sim.height());
+        assert_eq!(cached.checkpoint_height, sim.height() - 1);
+        assert_eq!(
+            Some(cached.checkpoint_header_hash),
+            sim.header_hash(sim.height() - 1)
+        );
+        assert_eq!(Some(cached.history), synced.history);
+
+        cache.remove(launcher_id)?;
+        assert!(cache.get(launcher_id)?.is_none());
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_reorgs() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[4; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let cache = StoreCache::open_in_memory()?;
+
+        let coin = sim.new_coin(puzzle_hash, 1);
+        let minted = mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            0,
+        )?;
+        sim.sign_and_push(&sk, minted.coin_spends);
+        let launcher_id = minted.new_datastore.info.launcher_id;
+
+        let update = |store: DataStore, root_hash: u8| -> anyhow::Result<Vec<CoinSpend>> {
+            Ok(update_store_metadata(
+                store,
+                Bytes32::new([root_hash; 32]),
+                None,
+                None,
+                None,
+                DataStoreInnerSpend::Owner(synthetic_key),
+            )?
+            .coin_spends)
+        };
+
+        let synced = sync_cached_store(&sim, &cache, launcher_id, false).await?;
+        assert!(synced.history.is_none());
+        let fork_height = sim.height();
+
+        sim.farm_blocks(2);
+        sim.sign_and_push(&sk, update(synced.latest_store.clone(), 2)?);
+        let resumed = sync_cached_store(&sim, &cache, launcher_id, true).await?;
+        assert!(resumed.reorg.is_none());
+        assert_eq!(
+            root_hashes(&cache.get(launcher_id)?.unwrap().history),
+            vec![Bytes32::new([1; 32]), Bytes32::new([2; 32])]
+        );
+
+        // The checkpoint block is orphaned, and the node rejects the request.
+        sim.reorg(fork_height);
+        sim.sign_and_push(&sk, update(synced.latest_store.clone(), 3)?);
+        sim.farm_blocks(5);
+        let resynced = sync_cached_store(&sim, &cache, launcher_id, true).await?;
+        assert_eq!(
+            resynced.reorg.unwrap().orphaned_root_hashes,
+            vec![Bytes32::new([2; 32])]
+        );
+        assert_eq!(
+            root_hashes(&cache.get(launcher_id)?.unwrap().history),
+            vec![Bytes32::new([1; 32]), Bytes32::new([3; 32])]
+        );
+
+        // Only the cached coin is orphaned, so the node doesn't know about it.
+        let fork_height = sim.height();
+        sim.sign_and_push(&sk, update(resynced.latest_store.clone(), 4)?);
+        sync_cached_store(&sim, &cache, launcher_id, false).await?;
+        sim.reorg(fork_height);
+        sim.sign_and_push(&sk, update(resynced.latest_store, 5)?);
+
+        let resynced = sync_cached_store(&sim, &cache, launcher_id, true).await?;
+        assert!(resynced.reorg.is_some());
+        assert_eq!(
+            root_hashes(&resynced.history.unwrap()),
+            vec![
+                Bytes32::new([1; 32]),
+                Bytes32::new([3; 32]),
+                Bytes32::new([5; 32])
+            ]
+        );
+        assert_eq!(
+            cache
+                .get(launcher_id)?
+                .unwrap()
+                .store
+                .info
+                .metadata
+                .root_hash,
+            Bytes32::new([5; 32])
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+
+use chia::bls::PublicKey;
+use chia::protocol::{Bytes, Bytes32, Coin, CoinSpend};
+use chia::puzzles::standard::StandardArgs;
+use chia_wallet_sdk::{
+    announcement_id, Conditions, DataStore, DelegatedPuzzle, RequiredSignature, SpendContext,
+};
+use clvmr::Allocator;
+
+use crate::fees::{FeePolicy, MAX_FEE_ATTEMPTS};
+use crate::network::TargetNetwork;
+use crate::wallet::{
+    get_cost, melt_store, select_coins, spend_store_as_oracle, update_store_metadata,
+    update_store_ownership, DataStoreInnerSpend, WalletError,
+};
+
+/// Accumulates store operations and XCH payments into a single spend bundle.
+///
+/// Operations on the same store are chained, each spending the store the previous one created.
+/// When the transaction is built, coins are selected once to pay for the outputs, oracle fees and
+/// the transaction fee; the first selected coin sends the change back to the wallet and asserts
+/// that every store coin is spent along with it.
+pub struct TransactionBuilder {
+    synthetic_key: PublicKey,
+    store_spends: Vec<CoinSpend>,
+    launcher_ids: Vec<Bytes32>,
+    // The latest state of each store, or `None` once it's melted.
+    stores: HashMap<Bytes32, Option<DataStore>>,
+    announcements: Vec<Bytes32>,
+    outputs: Vec<(Bytes32, u64, Vec<Bytes>)>,
+    oracle_fees: u64,
+    melted_mojos: u64,
+    fee: u64,
+}
+
+/// A built transaction, ready to be signed.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub coin_spends: Vec<CoinSpend>,
+    /// The stores' states after the transaction, in the order they were first used. Melted stores are left out.
+    pub new_stores: Vec<DataStore>,
+    pub selected_coins: Vec<Coin>,
+    pub change: u64,
+    /// The fee paid, including the mojos of melted stores.
+    pub fee: u64,
+    /// The CLVM cost of the transaction.
+    pub cost: u64,
+    pub required_signatures: Vec<RequiredSignature>,
+}
+
+impl TransactionBuilder {
+    /// Creates a builder for the wallet with the given synthetic key, which pays for the transaction.
+    pub fn new(synthetic_key: PublicKey) -> Self {
+        Self {
+            synthetic_key,
+            store_spends: Vec::new(),
+            launcher_ids: Vec::new(),
+            stores: HashMap::new(),
+            announcements: Vec::new(),
+            outputs: Vec::new(),
+            oracle_fees: 0,
+            melted_mojos: 0,
+            fee: 0,
+        }
+    }
+
+    pub fn set_fee(&mut self, fee: u64) {
+        self.fee = fee;
+    }
+
+    pub fn update_store_metadata(
+        &mut self,
+        store: DataStore,
+        new_root_hash: Bytes32,
+        new_label: Option<String>,
+        new_description: Option<String>,
+        new_bytes: Option<u64>,
+        inner_spend_info: DataStoreInnerSpend,
+    ) -> Result<DataStore, WalletError> {
+        let store = self.current_store(store)?;
+        let response = update_store_metadata(
+            store,
+            new_root_hash,
+            new_label,
+            new_description,
+            new_bytes,
+            inner_spend_info,
+        )?;
+
+        self.store_spends.extend(response.coin_spends);
+        Ok(self.set_store(response.new_datastore))
+    }
+
+    pub fn update_store_ownership(
+        &mut self,
+        store: DataStore,
+        new_owner_puzzle_hash: Bytes32,
+        new_delegated_puzzles: Vec<DelegatedPuzzle>,
+        inner_spend_info: DataStoreInnerSpend,
+    ) -> Result<DataStore, WalletError> {
+        let store = self.current_store(store)?;
+        let response = update_store_ownership(
+            store,
+            new_owner_puzzle_hash,
+            new_delegated_puzzles,
+            inner_spend_info,
+        )?;
+
+        self.store_spends.extend(response.coin_spends);
+        Ok(self.set_store(response.new_datastore))
+    }
+
+    pub fn oracle_spend(&mut self, store: DataStore) -> Result<DataStore, WalletError> {
+        let store = self.current_store(store)?;
+        let store_puzzle_hash = store.coin.puzzle_hash;
+
+        let mut ctx = SpendContext::new();
+        let (spend, new_store, oracle_fee) = spend_store_as_oracle(&mut ctx, store)?;
+
+        self.store_spends.push(spend);
+        self.announcements
+            .push(announcement_id(store_puzzle_hash, Bytes::new("$".into())));
+        self.oracle_fees += oracle_fee;
+        Ok(self.set_store(new_store))
+    }
+
+    pub fn melt_store(&mut self, store: DataStore, owner_pk: PublicKey) -> Result<(), WalletError> {
+        let store = self.current_store(store)?;
+        let launcher_id = store.info.launcher_id;
+        let amount = store.coin.amount;
+
+        self.store_spends.extend(melt_store(store, owner_pk)?);
+        self.stores.insert(launcher_id, None);
+        self.melted_mojos += amount;
+        Ok(())
+    }
+
+    pub fn send_xch(&mut self, puzzle_hash: Bytes32, amount: u64, memos: Vec<Bytes>) {
+        self.outputs.push((puzzle_hash, amount, memos));
+    }
+
+    /// The amount the selected coins have to cover: outputs, oracle fees and the fee.
+    pub fn required_amount(&self) -> u64 {
+        self.outputs.iter().map(|output| output.1).sum::<u64>() + self.oracle_fees + self.fee
+    }
+
+    /// Selects coins out of `coins` and builds the transaction. No coins are spent if the
+    /// transaction doesn't need any XCH.
+    pub fn build(
+        &self,
+        coins: Vec<Coin>,
+        network: &TargetNetwork,
+    ) -> Result<Transaction, WalletError> {
+        let required_amount = self.required_amount();
+        let mut ctx = SpendContext::new();
+
+        let selected_coins = if required_amount > 0 {
+            select_coins(coins, required_amount)?
+        } else {
+            Vec::new()
+        };
+        let change = selected_coins.iter().map(|coin| coin.amount).sum::<u64>() - required_amount;
+
+        if let Some(lead_coin) = selected_coins.first() {
+            let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(self.synthetic_key).into();
+            let mut conditions = Conditions::new();
+
+            for (puzzle_hash, amount, memos) in &self.outputs {
+                conditions = conditions.create_coin(*puzzle_hash, *amount, memos.clone());
+            }
+            if change > 0 {
+                conditions = conditions.create_coin(puzzle_hash, change, vec![puzzle_hash.into()]);
+            }
+            if self.fee > 0 {
+                conditions = conditions.reserve_fee(self.fee);
+            }
+            for announcement in &self.announcements {
+                conditions = conditions.assert_puzzle_announcement(*announcement);
+            }
+            for coin_spend in &self.store_spends {
+                conditions = conditions.assert_concurrent_spend(coin_spend.coin.coin_id());
+            }
+
+            ctx.spend_p2_coin(*lead_coin, self.synthetic_key, conditions)?;
+
+            for coin in selected_coins.iter().skip(1) {
+                ctx.spend_p2_coin(
+                    *coin,
+                    self.synthetic_key,
+                    Conditions::new().assert_concurrent_spend(lead_coin.coin_id()),
+                )?;
+            }
+        }
+
+        let mut coin_spends = self.store_spends.clone();
+        coin_spends.extend(ctx.take());
+
+        let cost = get_cost(coin_spends.clone(), network)?;
+        let required_signatures = RequiredSignature::from_coin_spends(
+            &mut Allocator::new(),
+            &coin_spends,
+            &network.constants(),
+        )?;
+
+        Ok(Transaction {
+            coin_spends,
+            new_stores: self
+                .launcher_ids
+                .iter()
+                .filter_map(|launcher_id| self.stores[launcher_id].clone())
+                .collect(),
+            selected_coins,
+            change,
+            fee: self.fee + self.melted_mojos,
+            cost,
+            required_signatures,
+        })
+    }
+
+    /// Builds the transaction like `build`, raising the fee until it covers the transaction's
+    /// cost. The fee set with `set_fee` is replaced.
+    pub fn build_with_fee_policy(
+        &mut self,
+        coins: Vec<Coin>,
+        network: &TargetNetwork,
+        fee_policy: FeePolicy,
+    ) -> Result<Transaction, WalletError> {
+        self.fee = fee_policy.initial_fee();
+
+        for _ in 0..MAX_FEE_ATTEMPTS {
+            let transaction = self.build(coins.clone(), network)?;
+            let required_fee = fee_policy.fee_for_cost(transaction.cost);
+
+            if transaction.fee >= required_fee {
+                return Ok(transaction);
+            }
+
+            // Melted stores already pay part of the fee.
+            self.fee = required_fee - self.melted_mojos;
+        }
+
+        Err(WalletError::FeeNotConverged(MAX_FEE_ATTEMPTS))
+    }
+
+    // The state an operation on the store should spend.
+    fn current_store(&self, store: DataStore) -> Result<DataStore, WalletError> {
+        match self.stores.get(&store.info.launcher_id) {
+            None => Ok(store),
+            Some(Some(current)) => Ok(current.clone()),
+            Some(None) => Err(WalletError::MeltedStore(store.info.launcher_id)),
+        }
+    }
+
+    fn set_store(&mut self, store: DataStore) -> DataStore {
+        let launcher_id = store.info.launcher_id;
+
+        if !self.stores.contains_key(&launcher_id) {
+            self.launcher_ids.push(launcher_id);
+        }
+        self.stores.insert(launcher_id, Some(store.clone()));
+
+        store
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::bls::SecretKey;
+    use chia::puzzles::DeriveSynthetic;
+
+    use super::*;
+    use crate::fees::mint_store_with_fee_policy;
+    use crate::simulator::ChainSimulator;
+    use crate::wallet::{mint_store, sync_store};
+
+    #[tokio::test]
+    async fn test_transaction_builder() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[4; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let oracle_puzzle_hash = Bytes32::new([7; 32]);
+
+        let mut stores = Vec::new();
+        for i in 0..3 {
+            let minted = mint_store(
+                synthetic_key,
+                vec![sim.new_coin(puzzle_hash, 1)],
+                Bytes32::new([i; 32]),
+                None,
+                None,
+                None,
+                puzzle_hash,
+                vec![DelegatedPuzzle::Oracle(oracle_puzzle_hash, 100)],
+                0,
+            )?;
+            sim.sign_and_push(&sk, minted.coin_spends);
+            stores.push(minted.new_datastore);
+        }
+
+        let coins = vec![
+            sim.new_coin(puzzle_hash, 600),
+            sim.new_coin(puzzle_hash, 600),
+        ];
+
+        let mut builder = TransactionBuilder::new(synthetic_key);
+        builder.update_store_metadata(
+            stores[0].clone(),
+            Bytes32::new([10; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Owner(synthetic_key),
+        )?;
+        builder.oracle_spend(stores[1].clone())?;
+        builder.melt_store(stores[2].clone(), synthetic_key)?;
+        builder.send_xch(Bytes32::new([8; 32]), 250, Vec::new());
+        builder.set_fee(50);
+
+        // The second update spends the store the first one created.
+        builder.update_store_metadata(
+            stores[0].clone(),
+            Bytes32::new([11; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Owner(synthetic_key),
+        )?;
+        assert!(matches!(
+            builder.oracle_spend(stores[2].clone()),
+            Err(WalletError::MeltedStore(_))
+        ));
+
+        assert_eq!(builder.required_amount(), 400);
+        let transaction = builder.build(coins.clone(), sim.network())?;
+
+        assert_eq!(transaction.new_stores.len(), 2);
+        assert_eq!(transaction.fee, 51);
+        assert_eq!(
+            transaction
+                .selected_coins
+                .iter()
+                .map(|coin| coin.amount)
+                .sum::<u64>(),
+            transaction.change + 400
+        );
+        assert!(!transaction.required_signatures.is_empty());
+
+        sim.sign_and_push(&sk, transaction.coin_spends);
+
+        let synced = sync_store(
+            &sim,
+            &stores[0],
+            None,
+            sim.network().genesis_challenge,
+            false,
+            &[],
+        )
+        .await?;
+        assert_eq!(
+            synced.latest_store.info.metadata.root_hash,
+            Bytes32::new([11; 32])
+        );
+        assert_eq!(synced.latest_store.coin, transaction.new_stores[0].coin);
+
+        // The oracle got paid by the store it was spent through.
+        let oracle_payment = Coin::new(stores[1].coin.coin_id(), oracle_puzzle_hash, 100);
+        assert!(sim.coin_state(oracle_payment.coin_id()).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fee_policy() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::mainnet());
+        let sk = SecretKey::from_seed(&[5; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        let coins: Vec<Coin> = (0..5)
+            .map(|_| sim.new_coin(puzzle_hash, 2_000_000_000))
+            .collect();
+
+        let minted = mint_store_with_fee_policy(
+            synthetic_key,
+            coins.clone(),
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![],
+            FeePolicy::MojosPerCost(5),
+            sim.network(),
+        )?;
+        assert_eq!(
+            minted.cost,
+            get_cost(minted.response.coin_spends.clone(), sim.network())?
+        );
+        assert!(minted.fee >= minted.cost * 5);
+        sim.sign_and_push(&sk, minted.response.coin_spends);
+
+        let mut builder = TransactionBuilder::new(synthetic_key);
+        builder.update_store_metadata(
+            minted.response.new_datastore,
+            Bytes32::new([2; 32]),
+            None,
+            None,
+            None,
+            DataStoreInnerSpend::Owner(synthetic_key),
+        )?;
+
+        // Paying more than one coin holds makes the fee grow with the extra coin spends.
+        builder.send_xch(Bytes32::new([9; 32]), 3_000_000_000, Vec::new());
+        let transaction = builder.build_with_fee_policy(
+            coins[1..].to_vec(),
+            sim.network(),
+            FeePolicy::MojosPerCost(10),
+        )?;
+        assert!(transaction.fee >= transaction.cost * 10);
+        assert_eq!(
+            transaction.cost,
+            get_cost(transaction.coin_spends.clone(), sim.network())?
+        );
+
+        let fixed = builder.build_with_fee_policy(
+            coins[1..].to_vec(),
+            sim.network(),
+            FeePolicy::Fixed(123),
+        )?;
+        assert_eq!(fixed.fee, 123);
+
+        sim.sign_and_push(&sk, transaction.coin_spends);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use chia::bls::{sign, verify, PublicKey, SecretKey, Signature};
+use chia::protocol::{Bytes, Bytes32, CoinSpend, SpendBundle};
+use chia::puzzles::DeriveSynthetic;
+use chia::traits::Streamable;
+use chia_wallet_sdk::RequiredSignature;
+use clvmr::Allocator;
+
+use crate::network::TargetNetwork;
+use crate::wallet::WalletError;
+
+/// Version of the serialized format, written as its first byte.
+const FORMAT_VERSION: u8 = 1;
+
+/// A signature an unsigned transaction needs, along with the signature once it's been added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureRequest {
+    pub public_key: PublicKey,
+    /// Final message to sign, including the `AGG_SIG_*` additional data.
+    pub message: Bytes,
+    pub signature: Option<Signature>,
+}
+
+/// Coin spends waiting to be signed, e.g. on an air-gapped machine that holds the keys.
+///
+/// The transaction is exported with [`UnsignedTransaction::to_bytes`] and imported on the
+/// signing machine, which adds its signatures with [`UnsignedTransaction::sign`] and exports it
+/// again. Back online, [`UnsignedTransaction::import_signatures`] merges the signatures of each
+/// signer, and [`UnsignedTransaction::spend_bundle`] assembles the spend bundle to broadcast.
+///
+/// The required signatures are computed from the coin spends, both when the transaction is
+/// created and when it's imported, so signers only sign what the coin spends ask for. The intent
+/// is free text from the transaction's creator and isn't checked against the coin spends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    network: TargetNetwork,
+    coin_spends: Vec<CoinSpend>,
+    intent: String,
+    required_signatures: Vec<SignatureRequest>,
+}
+
+impl UnsignedTransaction {
+    /// Computes the signatures the coin spends need on the network.
+    pub fn new(
+        coin_spends: Vec<CoinSpend>,
+        network: &TargetNetwork,
+        intent: String,
+    ) -> Result<Self, WalletError> {
+        let required_signatures = required_signatures(&coin_spends, network)?
+            .into_iter()
+            .map(|(public_key, message)| SignatureRequest {
+                public_key,
+                message,
+                signature: None,
+            })
+            .collect();
+
+        Ok(Self {
+            network: network.clone(),
+            coin_spends,
+            intent,
+            required_signatures,
+        })
+    }
+
+    /// The network the coin spends are signed for. Only its id, genesis challenge and
+    /// `AGG_SIG_ME` additional data are kept when the transaction is exported.
+    pub fn network(&self) -> &TargetNetwork {
+        &self.network
+    }
+
+    pub fn coin_spends(&self) -> &[CoinSpend] {
+        &self.coin_spends
+    }
+
+    /// Human-readable description of what the transaction does.
+    pub fn intent(&self) -> &str {
+        &self.intent
+    }
+
+    pub fn required_signatures(&self) -> &[SignatureRequest] {
+        &self.required_signatures
+    }
+
+    /// Public keys of the signatures that haven't been added yet, without duplicates.
+    pub fn missing_signatures(&self) -> Vec<PublicKey> {
+        let mut public_keys = Vec::new();
+
+        for request in &self.required_signatures {
+            if request.signature.is_none() && !public_keys.contains(&request.public_key) {
+                public_keys.push(request.public_key);
+            }
+        }
+
+        public_keys
+    }
+
+    /// Signs the missing signatures whose public key is one of the keys or its synthetic key,
+    /// like `sign_coin_spends`. Returns the number of signatures added.
+    pub fn sign(&mut self, secret_keys: &[SecretKey]) -> usize {
+        let key_pairs = secret_keys
+            .iter()
+            .flat_map(|sk| [sk.clone(), sk.derive_synthetic()])
+            .map(|sk| (sk.public_key(), sk))
+            .collect::<HashMap<PublicKey, SecretKey>>();
+
+        let mut added = 0;
+
+        for request in &mut self.required_signatures {
+            if request.signature.is_some() {
+                continue;
+            }
+
+            if let Some(sk) = key_pairs.get(&request.public_key) {
+                request.signature = Some(sign(sk, &request.message));
+                added += 1;
+            }
+        }
+
+        added
+    }
+
+    /// Adds a signature made elsewhere, e.g. by a hardware wallet, for the required signatures
+    /// with the public key and message. Fails with `SignatureMismatch` if the signature isn't
+    /// required or doesn't verify.
+    pub fn add_signature(
+        &mut self,
+        public_key: PublicKey,
+        message: &[u8],
+        signature: Signature,
+    ) -> Result<(), WalletError> {
+        let mut requests = self
+            .required_signatures
+            .iter_mut()
+            .filter(|request| {
+                request.public_key == public_key && request.message.as_ref() == message
+            })
+            .peekable();
+
+        if requests.peek().is_none() || !verify(&signature, &public_key, message) {
+            return Err(WalletError::SignatureMismatch(Box::new(public_key)));
+        }
+
+        for request in requests {
+            request.signature = Some(signature.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Copies the signatures of another copy of the transaction, e.g. one signed offline.
+    /// Returns the number of signatures added.
+    pub fn import_signatures(
+        &mut self,
+        signed: &UnsignedTransaction,
+    ) -> Result<usize, WalletError> {
+        let network = &self.network;
+        if signed.coin_spends != self.coin_spends
+            || signed.network.genesis_challenge != network.genesis_challenge
+            || signed.network.agg_sig_me != network.agg_sig_me
+        {
+            return Err(WalletError::InvalidUnsignedTransaction(
+                "the signed transaction has different coin spends or network".to_string(),
+            ));
+        }
+
+        let mut added = 0;
+
+        for (request, signed_request) in self
+            .required_signatures
+            .iter_mut()
+            .zip(&signed.required_signatures)
+        {
+            if request.signature.is_none() && signed_request.signature.is_some() {
+                request.signature.clone_from(&signed_request.signature);
+                added += 1;
+            }
+        }
+
+        Ok(added)
+    }
+
+    /// Aggregates the signatures into a spend bundle. Fails with `MissingSignatures` if any
+    /// required signature hasn't been added.
+    pub fn spend_bundle(&self) -> Result<SpendBundle, WalletError> {
+        let missing = self.missing_signatures();
+        if !missing.is_empty() {
+            return Err(WalletError::MissingSignatures(missing));
+        }
+
+        let mut aggregated_signature = Signature::default();
+        for request in &self.required_signatures {
+            if let Some(signature) = &request.signature {
+                aggregated_signature += signature;
+            }
+        }
+
+        Ok(SpendBundle::new(
+            self.coin_spends.clone(),
+            aggregated_signature,
+        ))
+    }
+
+    /// Serializes the transaction, with the signatures added so far.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WalletError> {
+        let mut out = vec![FORMAT_VERSION];
+
+        self.network
+            .network_id
+            .to_string()
+            .stream(&mut out)
+            .and_then(|()| self.network.genesis_challenge.stream(&mut out))
+            .and_then(|()| self.network.agg_sig_me.stream(&mut out))
+            .and_then(|()| self.coin_spends.stream(&mut out))
+            .and_then(|()| self.intent.stream(&mut out))
+            .and_then(|()| {
+                self.required_signatures
+                    .iter()
+                    .map(|request| {
+                        (
+                            request.public_key,
+                            request.message.clone(),
+                            request.signature.clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .stream(&mut out)
+            })
+            .map_err(invalid)?;
+
+        Ok(out)
+    }
+
+    /// Parses a serialized transaction. The required signatures are computed again from the
+    /// coin spends and have to match the serialized ones, and every signature has to verify.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WalletError> {
+        let Some((&version, bytes)) = bytes.split_first() else {
+            return Err(invalid("the transaction is empty"));
+        };
+
+        if version != FORMAT_VERSION {
+            return Err(invalid(format!("unsupported format version {version}")));
+        }
+
+        let mut input = Cursor::new(bytes);
+        let network_id = String::parse::<false>(&mut input).map_err(invalid)?;
+        let genesis_challenge = Bytes32::parse::<false>(&mut input).map_err(invalid)?;
+        let agg_sig_me = Bytes32::parse::<false>(&mut input).map_err(invalid)?;
+        let coin_spends = Vec::<CoinSpend>::parse::<false>(&mut input).map_err(invalid)?;
+        let intent = String::parse::<false>(&mut input).map_err(invalid)?;
+        let requests = Vec::<(PublicKey, Bytes, Option<Signature>)>::parse::<false>(&mut input)
+            .map_err(invalid)?;
+
+        if input.position() as usize != bytes.len() {
+            return Err(invalid("unexpected bytes after the transaction"));
+        }
+
+        let network = network_from_parts(&network_id, genesis_challenge, agg_sig_me);
+        let mut transaction = Self::new(coin_spends, &network, intent)?;
+
+        let expected = transaction
+            .required_signatures
+            .iter()
+            .map(|request| (&request.public_key, &request.message));
+        if !expected.eq(requests
+            .iter()
+            .map(|(public_key, message, _)| (public_key, message)))
+        {
+            return Err(invalid(
+                "the required signatures don't match the coin spends",
+            ));
+        }
+
+        for (public_key, message, signature) in requests {
+            if let Some(signature) = signature {
+                transaction.add_signature(public_key, &message, signature)?;
+            }
+        }
+
+        Ok(transaction)
+    }
+}
+
+// The public key and final message of every signature the coin spends need.
+pub(crate) fn required_signatures(
+    coin_spends: &[CoinSpend],
+    network: &TargetNetwork,
+) -> Result<Vec<(PublicKey, Bytes)>, WalletError> {
+    let mut allocator = Allocator::new();
+
+    Ok(
+        RequiredSignature::from_coin_spends(&mut allocator, coin_spends, &network.constants())?
+            .into_iter()
+            .map(|required| (required.public_key(), required.final_message().into()))
+            .collect(),
+    )
+}
+
+// Only the fields that affect signing are serialized. The others are taken from the public
+// network with the same id, or from mainnet for other networks.
+fn network_from_parts(
+    network_id: &str,
+    genesis_challenge: Bytes32,
+    agg_sig_me: Bytes32,
+) -> TargetNetwork {
+    let base = match network_id {
+        "testnet11" => TargetNetwork::testnet11(),
+        _ => TargetNetwork::mainnet(),
+    };
+
+    TargetNetwork {
+        network_id: TargetNetwork::network_id_from_str(network_id),
+        genesis_challenge,
+        agg_sig_me,
+        ..base
+    }
+}
+
+fn invalid(error: impl ToString) -> WalletError {
+    WalletError::InvalidUnsignedTransaction(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia::puzzles::standard::StandardArgs;
+    use chia_wallet_sdk::DelegatedPuzzle;
+
+    use super::*;
+    use crate::simulator::ChainSimulator;
+    use crate::wallet::{mint_store, send_spend_bundle};
+
+    #[tokio::test]
+    async fn test_offline_signing() -> anyhow::Result<()> {
+        let sim = ChainSimulator::new(TargetNetwork::testnet11());
+
+        let owner = SecretKey::from_seed(&[5; 32]);
+        let admin = SecretKey::from_seed(&[6; 32]);
+        let owner_key = owner.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(owner_key).into();
+        let coin = sim.new_coin(puzzle_hash, 1_000);
+
+        let minted = mint_store(
+            owner_key,
+            vec![coin],
+            Bytes32::new([1; 32]),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            vec![DelegatedPuzzle::Admin(StandardArgs::curry_tree_hash(
+                admin.public_key(),
+            ))],
+            10,
+        )?;
+
+        let mut online = UnsignedTransaction::new(
+            minted.coin_spends,
+            sim.network(),
+            "Mint a store".to_string(),
+        )?;
+        assert_eq!(online.missing_signatures(), vec![owner_key]);
+        assert!(matches!(
+            online.spend_bundle(),
+            Err(WalletError::MissingSignatures(keys)) if keys == vec![owner_key]
+        ));
+
+        // The offline machine doesn't know about the network or the coin spends beforehand.
+        let mut offline = UnsignedTransaction::from_bytes(&online.to_bytes()?)?;
+        assert_eq!(offline, online);
+        assert_eq!(offline.intent(), "Mint a store");
+        assert_eq!(offline.network(), sim.network());
+        assert_eq!(offline.sign(&[admin]), 0);
+        assert_eq!(offline.sign(&[owner]), 1);
+
+        let signed = UnsignedTransaction::from_bytes(&offline.to_bytes()?)?;
+        assert_eq!(online.import_signatures(&signed)?, 1);
+        assert!(online.missing_signatures().is_empty());
+
+        send_spend_bundle(&sim, online.spend_bundle()?).await?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_tampered_transactions() -> anyhow::Result<()> {
+        let network = TargetNetwork::mainnet();
+        let sk = SecretKey::from_seed(&[5; 32]);
+        let synthetic_key = sk.public_key().derive_synthetic();
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+        let coin = chia::protocol::Coin::new(Bytes32::default(), puzzle_hash, 1_000);
+
+        let minted = mint_store(
+            synthetic_key,
+            vec![coin],
+            Bytes32::default(),
+            None,
+            None,
+            None,
+            puzzle_hash,
+            Vec::new(),
+            0,
+        )?;
+        let mut transaction =
+            UnsignedTransaction::new(minted.coin_spends, &network, String::new())?;
+        let message = transaction.required_signatures()[0].message.clone();
+
+        // Signatures are checked against the required ones.
+        let other = SecretKey::from_seed(&[6; 32]);
+        assert!(matches!(
+            transaction.add_signature(other.public_key(), &message, sign(&other, &message)),
+            Err(WalletError::SignatureMismatch(_))
+        ));
+        assert!(matches!(
+            transaction.add_signature(synthetic_key, &message, sign(&other, &message)),
+            Err(WalletError::SignatureMismatch(_))
+        ));
+
+        // The message to sign can't be swapped for another one.
+        let bytes = transaction.to_bytes()?;
+        let position = bytes
+            .windows(message.len())
+            .position(|window| window == message.as_ref())
+            .unwrap();
+        let mut tampered = bytes.clone();
+        tampered[position] ^= 1;
+        assert!(matches!(
+            UnsignedTransaction::from_bytes(&tampered),
+            Err(WalletError::InvalidUnsignedTransaction(_))
+        ));
+
+        let mut unknown_version = bytes;
+        unknown_version[0] = 2;
+        assert!(matches!(
+            UnsignedTransaction::from_bytes(&unknown_version),
+            Err(WalletError::InvalidUnsignedTransaction(_))
+        ));
+
+        transaction.add_signature(
+            synthetic_key,
+            &message,
+            sign(&sk.derive_synthetic(), &message),
+        )?;
+        assert!(transaction.spend_bundle().is_ok());
+
+        Ok(())
+    }
+}
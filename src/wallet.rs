@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 use chia::bls::sign;
 use chia::bls::verify;
@@ -7,43 +8,53 @@ use chia::bls::SecretKey;
 use chia::bls::Signature;
 use chia::clvm_traits::clvm_tuple;
 use chia::clvm_traits::FromClvm;
+use chia::clvm_traits::FromClvmError;
 use chia::clvm_traits::ToClvm;
+use chia::clvm_traits::ToClvmError;
 use chia::clvm_utils::tree_hash;
 use chia::clvm_utils::CurriedProgram;
-use chia::consensus::consensus_constants::ConsensusConstants;
 use chia::consensus::gen::{
-    conditions::EmptyVisitor, flags::MEMPOOL_MODE, owned_conditions::OwnedSpendBundleConditions,
-    run_block_generator::run_block_generator, solution_generator::solution_generator,
-    validation_error::ValidationErr,
+    conditions::EmptyVisitor,
+    flags::MEMPOOL_MODE,
+    owned_conditions::OwnedSpendBundleConditions,
+    run_block_generator::run_block_generator,
+    solution_generator::solution_generator,
+    validation_error::{ErrorCode, ValidationErr},
 };
+use chia::consensus::spendbundle_validation::validate_clvm_and_signature;
 use chia::protocol::CoinState;
 use chia::protocol::{
-    Bytes, Bytes32, Coin, CoinSpend, CoinStateFilters, RejectHeaderRequest, RequestBlockHeader,
-    RequestFeeEstimates, RespondBlockHeader, RespondFeeEstimates, SpendBundle, TransactionAck,
+    Bytes, Bytes32, Coin, CoinSpend, CoinStateFilters, RejectCoinState, RejectHeaderRequest,
+    RejectPuzzleSolution, RejectPuzzleState, RejectStateReason, SpendBundle, TransactionAck,
 };
-use chia::puzzles::singleton::SINGLETON_LAUNCHER_PUZZLE_HASH;
+use chia::puzzles::nft::NftStateLayerSolution;
+use chia::puzzles::singleton::{SingletonSolution, SINGLETON_LAUNCHER_PUZZLE_HASH};
 use chia::puzzles::standard::StandardArgs;
 use chia::puzzles::standard::StandardSolution;
 use chia::puzzles::DeriveSynthetic;
 use chia_wallet_sdk::announcement_id;
 use chia_wallet_sdk::CreateCoin;
-use chia_wallet_sdk::TESTNET11_CONSTANTS;
 use chia_wallet_sdk::{
     get_merkle_tree, select_coins as select_coins_algo, ClientError, CoinSelectionError, Condition,
-    Conditions, DataStore, DataStoreMetadata, DelegatedPuzzle, DriverError, Launcher, Layer,
-    MeltSingleton, OracleLayer, Peer, RequiredSignature, SignerError, SpendContext, StandardLayer,
-    UpdateDataStoreMerkleRoot, WriterLayer, MAINNET_CONSTANTS,
+    Conditions, DataStore, DataStoreInfo, DataStoreMetadata, DelegatedPuzzle,
+    DelegationLayerSolution, DriverError, Launcher, Layer, MeltSingleton, OracleLayer,
+    RequiredSignature, SignerError, SpendContext, StandardLayer, UpdateDataStoreMerkleRoot,
+    WriterLayer, WriterLayerArgs,
 };
-use clvmr::Allocator;
+use clvmr::reduction::EvalErr;
+use clvmr::{Allocator, NodePtr};
 use hex_literal::hex;
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
-use crate::rust::ServerCoin;
+use crate::batch_sync::MAX_COIN_IDS_PER_REQUEST;
+use crate::network::TargetNetwork;
 use crate::server_coin::urls_from_conditions;
 use crate::server_coin::MirrorArgs;
 use crate::server_coin::MirrorExt;
 use crate::server_coin::MirrorSolution;
+use crate::server_coin::ServerCoin;
+use crate::wallet_protocol::{BlockHeaderInfo, Response, WalletProtocol};
 
 /* echo -n 'datastore' | sha256sum */
 pub const DATASTORE_LAUNCHER_HINT: Bytes32 = Bytes32::new(hex!(
@@ -61,19 +72,19 @@ pub struct SuccessResponse {
 #[derive(Debug, Error)]
 pub enum WalletError {
     #[error("{0:?}")]
-    Client(#[from] ClientError),
+    Client(Box<ClientError>),
 
-    #[error("RejectPuzzleState")]
-    RejectPuzzleState,
+    #[error("RejectPuzzleState: {:?}", .0.reason)]
+    RejectPuzzleState(RejectPuzzleState),
 
-    #[error("RejectCoinState")]
-    RejectCoinState,
+    #[error("RejectCoinState: {:?}", .0.reason)]
+    RejectCoinState(RejectCoinState),
 
-    #[error("RejectPuzzleSolution")]
-    RejectPuzzleSolution,
+    #[error("RejectPuzzleSolution: coin {} at height {}", .0.coin_name, .0.height)]
+    RejectPuzzleSolution(RejectPuzzleSolution),
 
-    #[error("RejectHeaderRequest")]
-    RejectHeaderRequest,
+    #[error("RejectHeaderRequest: height {}", .0.height)]
+    RejectHeaderRequest(RejectHeaderRequest),
 
     #[error("{0:?}")]
     Driver(#[from] DriverError),
@@ -81,23 +92,101 @@ pub enum WalletError {
     #[error("ParseError")]
     Parse,
 
-    #[error("UnknownCoin")]
-    UnknownCoin,
+    #[error("Couldn't parse CLVM: {0}")]
+    FromClvm(#[from] FromClvmError),
 
-    #[error("Clvm error")]
-    Clvm,
+    #[error("Couldn't serialize to CLVM: {0}")]
+    ToClvm(#[from] ToClvmError),
+
+    #[error("Unknown coin {0}")]
+    UnknownCoin(Bytes32),
+
+    #[error("Clvm error: {0}")]
+    Clvm(#[from] EvalErr),
 
     #[error("Permission error: puzzle can't perform this action")]
     Permission,
 
     #[error("Io error: {0}")]
-    Io(std::io::Error),
+    Io(#[from] std::io::Error),
 
     #[error("Validation error: {0}")]
     Validation(#[from] ValidationErr),
 
     #[error("Fee estimation rejection: {0}")]
     FeeEstimateRejection(String),
+
+    #[error("No peers available")]
+    NoPeers,
+
+    #[error("Peer disconnected and could not be reconnected")]
+    Disconnected,
+
+    #[error(
+        "No transaction block at or up to {lookback} blocks before height {0}",
+        lookback = MAX_TIMESTAMP_LOOKBACK
+    )]
+    MissingTimestamp(u32),
+
+    #[error("Cache error: {0}")]
+    Cache(#[from] rusqlite::Error),
+
+    #[error("Delta doesn't apply to this tree")]
+    InvalidDelta,
+
+    #[error("Coin selection error: {0}")]
+    CoinSelection(#[from] CoinSelectionError),
+
+    #[error("Signer error: {0}")]
+    Signer(#[from] SignerError),
+
+    #[error("Store {0} is melted in this transaction")]
+    MeltedStore(Bytes32),
+
+    #[error("Fee didn't cover the cost after {0} attempts")]
+    FeeNotConverged(usize),
+
+    #[error("Transaction rejected by the mempool: {error}")]
+    TransactionRejected { status: u8, error: String },
+
+    #[error("Missing signatures by {}", .0.iter().map(|pk| hex::encode(pk.to_bytes())).collect::<Vec<_>>().join(", "))]
+    MissingSignatures(Vec<PublicKey>),
+
+    #[error("Signature by {} doesn't match a required signature", hex::encode(.0.to_bytes()))]
+    SignatureMismatch(Box<PublicKey>),
+
+    #[error("Invalid unsigned transaction: {0}")]
+    InvalidUnsignedTransaction(String),
+
+    #[error("Signer failed: {0}")]
+    SignerFailed(String),
+
+    #[error("Invalid spend bundle: {0:?}")]
+    InvalidSpendBundle(ErrorCode),
+
+    #[error("Coin {coin_id} was already spent at height {height}")]
+    CoinAlreadySpent { coin_id: Bytes32, height: u32 },
+}
+
+// Boxed, since client errors are large enough to make every `Result` returning a `WalletError`
+// noticeably bigger.
+impl From<ClientError> for WalletError {
+    fn from(error: ClientError) -> Self {
+        Self::Client(Box::new(error))
+    }
+}
+
+impl WalletError {
+    /// Whether the error is a rejection sent by the peer, which another peer might not send.
+    pub fn is_peer_rejection(&self) -> bool {
+        matches!(
+            self,
+            WalletError::RejectPuzzleState(_)
+                | WalletError::RejectCoinState(_)
+                | WalletError::RejectPuzzleSolution(_)
+                | WalletError::RejectHeaderRequest(_)
+        )
+    }
 }
 
 pub struct UnspentCoinStates {
@@ -107,7 +196,7 @@ pub struct UnspentCoinStates {
 }
 
 pub async fn get_unspent_coin_states(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     puzzle_hash: Bytes32,
     previous_height: Option<u32>,
     previous_header_hash: Bytes32,
@@ -136,9 +225,8 @@ pub async fn get_unspent_coin_states(
                 },
                 false,
             )
-            .await
-            .map_err(WalletError::Client)?
-            .map_err(|_| WalletError::RejectPuzzleState)?;
+            .await?
+            .map_err(WalletError::RejectPuzzleState)?;
 
         last_height = response.height;
         last_header_hash = response.header_hash;
@@ -273,11 +361,11 @@ pub fn create_server_coin(
 }
 
 pub async fn spend_server_coins(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     synthetic_key: PublicKey,
     selected_coins: Vec<Coin>,
     total_fee: u64,
-    network: TargetNetwork,
+    network: &TargetNetwork,
 ) -> Result<Vec<CoinSpend>, WalletError> {
     let puzzle_hash = StandardArgs::curry_tree_hash(synthetic_key).into();
 
@@ -302,14 +390,11 @@ pub async fn spend_server_coins(
         .request_coin_state(
             server_coins.iter().map(|sc| sc.parent_coin_info).collect(),
             None,
-            match network {
-                TargetNetwork::Mainnet => MAINNET_CONSTANTS.genesis_challenge,
-                TargetNetwork::Testnet11 => TESTNET11_CONSTANTS.genesis_challenge,
-            },
+            network.genesis_challenge,
             false,
         )
         .await?
-        .map_err(|_| WalletError::RejectCoinState)?
+        .map_err(WalletError::RejectCoinState)?
         .coin_states;
 
     let mut ctx = SpendContext::new();
@@ -330,7 +415,7 @@ pub async fn spend_server_coins(
             .iter()
             .find(|cs| cs.coin.coin_id() == server_coin.parent_coin_info)
             .copied()
-            .ok_or(WalletError::UnknownCoin)?;
+            .ok_or(WalletError::UnknownCoin(server_coin.parent_coin_info))?;
 
         if parent_coin.coin.puzzle_hash != puzzle_hash {
             return Err(WalletError::Permission);
@@ -369,31 +454,25 @@ pub async fn spend_server_coins(
 }
 
 pub async fn fetch_server_coin(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     coin_state: CoinState,
     max_cost: u64,
 ) -> Result<ServerCoin, WalletError> {
     let Some(created_height) = coin_state.created_height else {
-        return Err(WalletError::UnknownCoin);
+        return Err(WalletError::UnknownCoin(coin_state.coin.coin_id()));
     };
 
     let spend = peer
         .request_puzzle_and_solution(coin_state.coin.parent_coin_info, created_height)
         .await?
-        .map_err(|_| WalletError::RejectPuzzleSolution)?;
+        .map_err(WalletError::RejectPuzzleSolution)?;
 
     let mut allocator = Allocator::new();
 
-    let Ok(output) = spend
+    let output = spend
         .puzzle
-        .run(&mut allocator, 0, max_cost, &spend.solution)
-    else {
-        return Err(WalletError::Clvm);
-    };
-
-    let Ok(conditions) = Vec::<Condition>::from_clvm(&allocator, output.1) else {
-        return Err(WalletError::Parse);
-    };
+        .run(&mut allocator, 0, max_cost, &spend.solution)?;
+    let conditions = Vec::<Condition>::from_clvm(&allocator, output.1)?;
 
     let Some(urls) = urls_from_conditions(&coin_state.coin, &conditions) else {
         return Err(WalletError::Parse);
@@ -490,34 +569,491 @@ pub fn mint_store(
 pub struct SyncStoreResponse {
     pub latest_store: DataStore,
     pub latest_height: u32,
+    /// After a reorg, this starts where the known history started, or at the store's launch.
     pub root_hash_history: Option<Vec<(Bytes32, u64)>>,
+    /// The spends behind `root_hash_history`, one entry per root hash.
+    pub history: Option<Vec<StoreHistoryEntry>>,
+    pub reorg: Option<StoreReorg>,
+}
+
+impl SyncStoreResponse {
+    pub fn new(
+        latest_store: DataStore,
+        latest_height: u32,
+        history: Option<Vec<StoreHistoryEntry>>,
+    ) -> Self {
+        Self {
+            latest_store,
+            latest_height,
+            root_hash_history: history.as_ref().map(|history| {
+                history
+                    .iter()
+                    .map(|entry| (entry.info.metadata.root_hash, entry.timestamp))
+                    .collect()
+            }),
+            history,
+            reorg: None,
+        }
+    }
+}
+
+/// What authorized a store spend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreSpendKind {
+    Launch,
+    Owner,
+    Admin,
+    Writer,
+    Oracle,
 }
 
+/// A confirmed spend of a store, with the states it was spent from and into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreHistoryEntry {
+    pub kind: StoreSpendKind,
+    /// The delegated puzzle that authorized the spend. `None` for launches and owner spends.
+    pub delegated_puzzle: Option<DelegatedPuzzle>,
+    /// The spent coin, i.e. the launcher or the store's previous coin.
+    pub coin_id: Bytes32,
+    pub height: u32,
+    pub header_hash: Bytes32,
+    pub timestamp: u64,
+    /// The state the store was spent from. `None` for launches.
+    pub previous_info: Option<DataStoreInfo>,
+    pub info: DataStoreInfo,
+}
+
+impl StoreHistoryEntry {
+    pub fn metadata_changed(&self) -> bool {
+        self.previous_info
+            .as_ref()
+            .is_none_or(|previous| previous.metadata != self.info.metadata)
+    }
+
+    pub fn ownership_changed(&self) -> bool {
+        self.previous_info.as_ref().is_none_or(|previous| {
+            previous.owner_puzzle_hash != self.info.owner_puzzle_hash
+                || previous.delegated_puzzles != self.info.delegated_puzzles
+        })
+    }
+}
+
+// Finds which puzzle authorized a store spend, given the delegated puzzles of the spent state.
+fn get_store_spend_kind(
+    ctx: &mut SpendContext,
+    cs: &CoinSpend,
+    parent_delegated_puzzles: &[DelegatedPuzzle],
+) -> Result<(StoreSpendKind, Option<DelegatedPuzzle>), WalletError> {
+    if cs.coin.puzzle_hash == SINGLETON_LAUNCHER_PUZZLE_HASH.into() {
+        return Ok((StoreSpendKind::Launch, None));
+    }
+
+    // without a delegation layer, only the owner can spend the store
+    if parent_delegated_puzzles.is_empty() {
+        return Ok((StoreSpendKind::Owner, None));
+    }
+
+    let solution_ptr = cs
+        .solution
+        .to_clvm(&mut ctx.allocator)
+        .map_err(DriverError::ToClvm)?;
+    let solution = SingletonSolution::<
+        NftStateLayerSolution<DelegationLayerSolution<NodePtr, NodePtr>>,
+    >::from_clvm(&ctx.allocator, solution_ptr)?;
+    let delegation_layer_solution = solution.inner_solution.inner_solution;
+
+    // the owner puzzle is run without a merkle proof
+    if delegation_layer_solution.merkle_proof.is_none() {
+        return Ok((StoreSpendKind::Owner, None));
+    }
+
+    let delegated_puzzle_hash: Bytes32 = ctx
+        .tree_hash(delegation_layer_solution.puzzle_reveal)
+        .into();
+
+    for delegated_puzzle in parent_delegated_puzzles {
+        let (kind, puzzle_hash): (StoreSpendKind, Bytes32) = match delegated_puzzle {
+            DelegatedPuzzle::Admin(puzzle_hash) => (StoreSpendKind::Admin, (*puzzle_hash).into()),
+            DelegatedPuzzle::Writer(inner_puzzle_hash) => (
+                StoreSpendKind::Writer,
+                WriterLayerArgs::curry_tree_hash(*inner_puzzle_hash).into(),
+            ),
+            DelegatedPuzzle::Oracle(oracle_puzzle_hash, oracle_fee) => {
+                let oracle_puzzle = OracleLayer::new(*oracle_puzzle_hash, *oracle_fee)
+                    .ok_or(DriverError::OddOracleFee)?
+                    .construct_puzzle(ctx)?;
+
+                (StoreSpendKind::Oracle, ctx.tree_hash(oracle_puzzle).into())
+            }
+        };
+
+        if puzzle_hash == delegated_puzzle_hash {
+            return Ok((kind, Some(*delegated_puzzle)));
+        }
+    }
+
+    Err(WalletError::Parse)
+}
+
+/// How many blocks `HeaderCache::get_timestamp` walks back to find a transaction block, which
+/// are usually only a few blocks apart.
+pub const MAX_TIMESTAMP_LOOKBACK: u32 = 32;
+
+/// Block headers requested during a sync, by height.
+///
+/// Consecutive store spends are often confirmed in the same block, and looking up a timestamp
+/// can walk back over several blocks, so headers are only requested once.
+#[derive(Debug, Default)]
+pub struct HeaderCache {
+    headers: Mutex<HashMap<u32, BlockHeaderInfo>>,
+}
+
+impl HeaderCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get_header(
+        &self,
+        peer: &impl WalletProtocol,
+        height: u32,
+    ) -> Result<BlockHeaderInfo, WalletError> {
+        let cached = self.headers().get(&height).copied();
+        if let Some(header) = cached {
+            return Ok(header);
+        }
+
+        let header = peer
+            .request_block_header(height)
+            .await?
+            .map_err(WalletError::RejectHeaderRequest)?;
+        self.headers().insert(height, header);
+
+        Ok(header)
+    }
+
+    /// Timestamp of the block at `height`. Only transaction blocks have one, so for other
+    /// blocks this is the timestamp of the last transaction block before it, looking back at
+    /// most `MAX_TIMESTAMP_LOOKBACK` blocks.
+    pub async fn get_timestamp(
+        &self,
+        peer: &impl WalletProtocol,
+        height: u32,
+    ) -> Result<u64, WalletError> {
+        for block_height in (height.saturating_sub(MAX_TIMESTAMP_LOOKBACK)..=height).rev() {
+            if let Some(timestamp) = self.get_header(peer, block_height).await?.timestamp {
+                return Ok(timestamp);
+            }
+        }
+
+        Err(WalletError::MissingTimestamp(height))
+    }
+
+    fn headers(&self) -> MutexGuard<'_, HashMap<u32, BlockHeaderInfo>> {
+        self.headers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Describes a store spend, looking up the block it was confirmed in.
+pub async fn get_store_history_entry(
+    peer: &impl WalletProtocol,
+    headers: &HeaderCache,
+    cs: &CoinSpend,
+    spent_height: u32,
+    previous_store: Option<&DataStore>,
+    new_store: &DataStore,
+) -> Result<StoreHistoryEntry, WalletError> {
+    let (kind, delegated_puzzle) = get_store_spend_kind(
+        &mut SpendContext::new(),
+        cs,
+        previous_store.map_or(&[], |store| &store.info.delegated_puzzles),
+    )?;
+
+    let block_header = headers.get_header(peer, spent_height).await?;
+
+    Ok(StoreHistoryEntry {
+        kind,
+        delegated_puzzle,
+        coin_id: cs.coin.coin_id(),
+        height: spent_height,
+        header_hash: block_header.header_hash,
+        timestamp: headers.get_timestamp(peer, spent_height).await?,
+        previous_info: previous_store.map(|store| store.info.clone()),
+        info: new_store.info.clone(),
+    })
+}
+
+/// Reported by a sync whose starting block is no longer part of the chain.
+///
+/// The sync walks back through the history the caller synced before to the newest state whose
+/// block is still part of the chain, and resumes from there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreReorg {
+    /// Height of the newest known state that is still part of the chain, or `None` if none of
+    /// them are and the store was synced again from its launcher.
+    pub fork_height: Option<u32>,
+    /// Root hashes of the known history that are no longer part of the chain, oldest first.
+    /// Spends that were included again on the new chain aren't listed.
+    pub orphaned_root_hashes: Vec<Bytes32>,
+}
+
+/// Syncs a store from its current state.
+///
+/// `known_history` is the history synced before, oldest first, e.g. from the `history` of
+/// previous responses. It doesn't have to start at the store's launch. If a reorg orphaned the
+/// block at `last_height`, the history returned replaces it: it keeps the known states that
+/// are still part of the chain and continues with the current chain's.
+///
+/// It doesn't read or write a `StoreCache`; use `sync_cached_store` to resume from one.
 pub async fn sync_store(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     store: &DataStore,
     last_height: Option<u32>,
     last_header_hash: Bytes32,
     with_history: bool,
+    known_history: &[StoreHistoryEntry],
+) -> Result<SyncStoreResponse, WalletError> {
+    let headers = HeaderCache::new();
+
+    match follow_store(
+        peer,
+        &headers,
+        store,
+        last_height,
+        last_header_hash,
+        with_history,
+    )
+    .await?
+    {
+        Ok(response) => Ok(response),
+        Err(rejection) => {
+            sync_after_rejection(
+                peer,
+                &headers,
+                rejection,
+                store.info.launcher_id,
+                last_height,
+                last_header_hash,
+                known_history,
+                with_history,
+            )
+            .await
+        }
+    }
+}
+
+/// Syncs a store from its launch. See `sync_store` for `known_history`.
+pub async fn sync_store_using_launcher_id(
+    peer: &impl WalletProtocol,
+    launcher_id: Bytes32,
+    last_height: Option<u32>,
+    last_header_hash: Bytes32,
+    with_history: bool,
+    known_history: &[StoreHistoryEntry],
+) -> Result<SyncStoreResponse, WalletError> {
+    let headers = HeaderCache::new();
+
+    match follow_launcher(
+        peer,
+        &headers,
+        launcher_id,
+        last_height,
+        last_header_hash,
+        with_history,
+    )
+    .await?
+    {
+        Ok(response) => Ok(response),
+        Err(rejection) => {
+            sync_after_rejection(
+                peer,
+                &headers,
+                rejection,
+                launcher_id,
+                last_height,
+                last_header_hash,
+                known_history,
+                with_history,
+            )
+            .await
+        }
+    }
+}
+
+// The node rejects with `Reorg` when `last_header_hash` isn't the header hash at `last_height`
+// on its chain, which includes the chain being shorter than `last_height`.
+#[allow(clippy::too_many_arguments)]
+async fn sync_after_rejection(
+    peer: &impl WalletProtocol,
+    headers: &HeaderCache,
+    rejection: RejectCoinState,
+    launcher_id: Bytes32,
+    last_height: Option<u32>,
+    last_header_hash: Bytes32,
+    known_history: &[StoreHistoryEntry],
+    with_history: bool,
 ) -> Result<SyncStoreResponse, WalletError> {
+    if rejection.reason != RejectStateReason::Reorg {
+        return Err(WalletError::RejectCoinState(rejection));
+    }
+
+    if let Some(last_height) = last_height {
+        if let Ok(header) = peer.request_block_header(last_height).await? {
+            if header.header_hash == last_header_hash {
+                return Err(WalletError::RejectCoinState(rejection));
+            }
+        }
+    }
+
+    let mut response = resync_store(peer, headers, launcher_id, known_history).await?;
+    if !with_history {
+        response.root_hash_history = None;
+        response.history = None;
+    }
+
+    Ok(response)
+}
+
+/// Syncs a store again after a reorg, resuming from the newest state of `known_history` whose
+/// block is still part of the chain, or from the store's launcher if there is none. The
+/// returned history starts with the known states that are still part of the chain.
+pub async fn resync_store(
+    peer: &impl WalletProtocol,
+    headers: &HeaderCache,
+    launcher_id: Bytes32,
+    known_history: &[StoreHistoryEntry],
+) -> Result<SyncStoreResponse, WalletError> {
+    let mut common_entries = 0;
+
+    for (index, entry) in known_history.iter().enumerate().rev() {
+        match headers.get_header(peer, entry.height).await {
+            Ok(header) if header.header_hash == entry.header_hash => {
+                common_entries = index + 1;
+                break;
+            }
+            Ok(_) | Err(WalletError::RejectHeaderRequest(_)) => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    let genesis_challenge = get_genesis_challenge(peer).await?;
+
+    let (fork_height, response) = match known_history[..common_entries].last() {
+        Some(entry) => {
+            let store = get_created_store(peer, entry, genesis_challenge).await?;
+            let mut response = follow_store(peer, headers, &store, None, genesis_challenge, true)
+                .await?
+                .map_err(WalletError::RejectCoinState)?;
+
+            let mut history = known_history[..common_entries].to_vec();
+            history.extend(response.history.take().unwrap_or_default());
+
+            (
+                Some(entry.height),
+                SyncStoreResponse::new(
+                    response.latest_store,
+                    response.latest_height,
+                    Some(history),
+                ),
+            )
+        }
+        None => (
+            None,
+            follow_launcher(peer, headers, launcher_id, None, genesis_challenge, true)
+                .await?
+                .map_err(WalletError::RejectCoinState)?,
+        ),
+    };
+
+    // The orphaned spends are often included again on the new chain, and then they're still
+    // part of it.
+    let history = response.history.as_deref().unwrap_or_default();
+    let orphaned_root_hashes = known_history[common_entries..]
+        .iter()
+        .filter(|entry| {
+            !history.iter().any(|new_entry| {
+                new_entry.coin_id == entry.coin_id
+                    && new_entry.info.metadata.root_hash == entry.info.metadata.root_hash
+            })
+        })
+        .map(|entry| entry.info.metadata.root_hash)
+        .collect();
+
+    Ok(SyncStoreResponse {
+        reorg: Some(StoreReorg {
+            fork_height,
+            orphaned_root_hashes,
+        }),
+        ..response
+    })
+}
+
+// Parses the state a history entry's spend created.
+async fn get_created_store(
+    peer: &impl WalletProtocol,
+    entry: &StoreHistoryEntry,
+    genesis_challenge: Bytes32,
+) -> Result<DataStore, WalletError> {
+    let coin = peer
+        .request_coin_state(vec![entry.coin_id], None, genesis_challenge, false)
+        .await?
+        .map_err(WalletError::RejectCoinState)?
+        .coin_states
+        .into_iter()
+        .next()
+        .ok_or(WalletError::UnknownCoin(entry.coin_id))?
+        .coin;
+    let puzzle_and_solution = peer
+        .request_puzzle_and_solution(entry.coin_id, entry.height)
+        .await?
+        .map_err(WalletError::RejectPuzzleSolution)?;
+
+    let cs = CoinSpend {
+        coin,
+        puzzle_reveal: puzzle_and_solution.puzzle,
+        solution: puzzle_and_solution.solution,
+    };
+
+    DataStore::<DataStoreMetadata>::from_spend(
+        &mut SpendContext::new().allocator,
+        &cs,
+        entry
+            .previous_info
+            .as_ref()
+            .map_or(&[], |info| &info.delegated_puzzles),
+    )?
+    .ok_or(WalletError::Parse)
+}
+
+// Syncs a store from its current state, returning the node's rejection of `last_header_hash`.
+async fn follow_store(
+    peer: &impl WalletProtocol,
+    headers: &HeaderCache,
+    store: &DataStore,
+    last_height: Option<u32>,
+    last_header_hash: Bytes32,
+    with_history: bool,
+) -> Result<Response<SyncStoreResponse, RejectCoinState>, WalletError> {
     let mut latest_store = store.clone();
     let mut history = vec![];
 
-    let response = peer
+    let response = match peer
         .request_coin_state(
             vec![store.coin.coin_id()],
             last_height,
             last_header_hash,
             false,
         )
-        .await
-        .map_err(WalletError::Client)?
-        .map_err(|_| WalletError::RejectCoinState)?;
+        .await?
+    {
+        Ok(response) => response,
+        Err(rejection) => return Ok(Err(rejection)),
+    };
     let mut last_coin_record = response
         .coin_states
         .into_iter()
         .next()
-        .ok_or(WalletError::UnknownCoin)?;
+        .ok_or(WalletError::UnknownCoin(store.coin.coin_id()))?;
 
     let mut ctx = SpendContext::new(); // just to run puzzles more easily
 
@@ -527,9 +1063,8 @@ pub async fn sync_store(
                 last_coin_record.coin.coin_id(),
                 last_coin_record.spent_height.unwrap(),
             )
-            .await
-            .map_err(WalletError::Client)?
-            .map_err(|_| WalletError::RejectPuzzleSolution)?;
+            .await?
+            .map_err(WalletError::RejectPuzzleSolution)?;
 
         let cs = CoinSpend {
             coin: last_coin_record.coin,
@@ -541,27 +1076,21 @@ pub async fn sync_store(
             &mut ctx.allocator,
             &cs,
             &latest_store.info.delegated_puzzles,
-        )
-        .map_err(|_| WalletError::Parse)?
+        )?
         .ok_or(WalletError::Parse)?;
 
         if with_history {
-            let resp: Result<RespondBlockHeader, RejectHeaderRequest> = peer
-                .request_fallible(RequestBlockHeader {
-                    height: last_coin_record.spent_height.unwrap(),
-                })
-                .await
-                .map_err(WalletError::Client)?;
-            let block_header = resp.map_err(|_| WalletError::RejectHeaderRequest)?;
-
-            history.push((
-                new_store.info.metadata.root_hash,
-                block_header
-                    .header_block
-                    .foliage_transaction_block
-                    .unwrap()
-                    .timestamp,
-            ));
+            history.push(
+                get_store_history_entry(
+                    peer,
+                    headers,
+                    &cs,
+                    last_coin_record.spent_height.unwrap(),
+                    Some(&latest_store),
+                    &new_store,
+                )
+                .await?,
+            );
         }
 
         let response = peer
@@ -571,57 +1100,57 @@ pub async fn sync_store(
                 last_header_hash,
                 false,
             )
-            .await
-            .map_err(WalletError::Client)?
-            .map_err(|_| WalletError::RejectCoinState)?;
+            .await?
+            .map_err(WalletError::RejectCoinState)?;
 
         last_coin_record = response
             .coin_states
             .into_iter()
             .next()
-            .ok_or(WalletError::UnknownCoin)?;
+            .ok_or(WalletError::UnknownCoin(new_store.coin.coin_id()))?;
         latest_store = new_store;
     }
 
-    Ok(SyncStoreResponse {
+    Ok(Ok(SyncStoreResponse::new(
         latest_store,
-        latest_height: last_coin_record
+        last_coin_record
             .created_height
-            .ok_or(WalletError::UnknownCoin)?,
-        root_hash_history: if with_history { Some(history) } else { None },
-    })
+            .ok_or(WalletError::UnknownCoin(last_coin_record.coin.coin_id()))?,
+        if with_history { Some(history) } else { None },
+    )))
 }
 
-pub async fn sync_store_using_launcher_id(
-    peer: &Peer,
+// Syncs a store from its launch, returning the node's rejection of `last_header_hash`.
+async fn follow_launcher(
+    peer: &impl WalletProtocol,
+    headers: &HeaderCache,
     launcher_id: Bytes32,
     last_height: Option<u32>,
     last_header_hash: Bytes32,
     with_history: bool,
-) -> Result<SyncStoreResponse, WalletError> {
-    let response = peer
+) -> Result<Response<SyncStoreResponse, RejectCoinState>, WalletError> {
+    let response = match peer
         .request_coin_state(vec![launcher_id], last_height, last_header_hash, false)
-        .await
-        .map_err(WalletError::Client)?
-        .map_err(|_| WalletError::RejectCoinState)?;
+        .await?
+    {
+        Ok(response) => response,
+        Err(rejection) => return Ok(Err(rejection)),
+    };
     let last_coin_record = response
         .coin_states
         .into_iter()
         .next()
-        .ok_or(WalletError::UnknownCoin)?;
+        .ok_or(WalletError::UnknownCoin(launcher_id))?;
 
     let mut ctx = SpendContext::new(); // just to run puzzles more easily
 
+    let spent_height = last_coin_record
+        .spent_height
+        .ok_or(WalletError::UnknownCoin(launcher_id))?;
     let puzzle_and_solution_req = peer
-        .request_puzzle_and_solution(
-            last_coin_record.coin.coin_id(),
-            last_coin_record
-                .spent_height
-                .ok_or(WalletError::UnknownCoin)?,
-        )
-        .await
-        .map_err(WalletError::Client)?
-        .map_err(|_| WalletError::RejectPuzzleSolution)?;
+        .request_puzzle_and_solution(last_coin_record.coin.coin_id(), spent_height)
+        .await?
+        .map_err(WalletError::RejectPuzzleSolution)?;
 
     let cs = CoinSpend {
         coin: last_coin_record.coin,
@@ -629,71 +1158,106 @@ pub async fn sync_store_using_launcher_id(
         solution: puzzle_and_solution_req.solution,
     };
 
-    let first_store = DataStore::<DataStoreMetadata>::from_spend(&mut ctx.allocator, &cs, &[])
-        .map_err(|_| WalletError::Parse)?
+    let first_store = DataStore::<DataStoreMetadata>::from_spend(&mut ctx.allocator, &cs, &[])?
         .ok_or(WalletError::Parse)?;
 
-    let res = sync_store(
+    let res = match follow_store(
         peer,
+        headers,
         &first_store,
         last_height,
         last_header_hash,
         with_history,
     )
-    .await?;
-
-    // prepend root hash from launch
-    let root_hash_history = if let Some(mut res_root_hash_history) = res.root_hash_history {
-        let spent_timestamp = if let Some(spent_height) = last_coin_record.spent_height {
-            let resp: Result<RespondBlockHeader, RejectHeaderRequest> = peer
-                .request_fallible(RequestBlockHeader {
-                    height: spent_height,
-                })
-                .await
-                .map_err(WalletError::Client)?;
-            let resp = resp.map_err(|_| WalletError::RejectHeaderRequest)?;
-
-            resp.header_block
-                .foliage_transaction_block
-                .unwrap()
-                .timestamp
-        } else {
-            0
-        };
+    .await?
+    {
+        Ok(res) => res,
+        Err(rejection) => return Ok(Err(rejection)),
+    };
 
-        res_root_hash_history.insert(0, (first_store.info.metadata.root_hash, spent_timestamp));
-        Some(res_root_hash_history)
+    // prepend the launch
+    let history = if let Some(mut res_history) = res.history {
+        res_history.insert(
+            0,
+            get_store_history_entry(peer, headers, &cs, spent_height, None, &first_store).await?,
+        );
+        Some(res_history)
     } else {
         None
     };
 
-    Ok(SyncStoreResponse {
-        latest_store: res.latest_store,
-        latest_height: res.latest_height,
-        root_hash_history,
-    })
+    Ok(Ok(SyncStoreResponse::new(
+        res.latest_store,
+        res.latest_height,
+        history,
+    )))
+}
+
+/// A new state of a store, as confirmed on chain.
+pub struct StoreUpdate {
+    pub store: DataStore,
+    pub height: u32,
+    pub timestamp: u64,
+}
+
+/// Parses the state a store's coin was spent into. Returns `None` if the store was melted.
+pub async fn get_store_update(
+    peer: &impl WalletProtocol,
+    store: &DataStore,
+    spent_height: u32,
+) -> Result<Option<StoreUpdate>, WalletError> {
+    let puzzle_and_solution_req = peer
+        .request_puzzle_and_solution(store.coin.coin_id(), spent_height)
+        .await?
+        .map_err(WalletError::RejectPuzzleSolution)?;
+
+    let cs = CoinSpend {
+        coin: store.coin,
+        puzzle_reveal: puzzle_and_solution_req.puzzle,
+        solution: puzzle_and_solution_req.solution,
+    };
+
+    let mut ctx = SpendContext::new(); // just to run puzzles more easily
+
+    // Melting spends the singleton without creating a child.
+    let new_store = match DataStore::<DataStoreMetadata>::from_spend(
+        &mut ctx.allocator,
+        &cs,
+        &store.info.delegated_puzzles,
+    ) {
+        Ok(new_store) => new_store.ok_or(WalletError::Parse)?,
+        Err(DriverError::MissingChild) => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+
+    let timestamp = HeaderCache::new().get_timestamp(peer, spent_height).await?;
+
+    Ok(Some(StoreUpdate {
+        store: new_store,
+        height: spent_height,
+        timestamp,
+    }))
 }
 
 pub async fn get_store_creation_height(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     launcher_id: Bytes32,
     last_height: Option<u32>,
     last_header_hash: Bytes32,
 ) -> Result<u32, WalletError> {
     let response = peer
         .request_coin_state(vec![launcher_id], last_height, last_header_hash, false)
-        .await
-        .map_err(WalletError::Client)?
-        .map_err(|_| WalletError::RejectCoinState)?;
+        .await?
+        .map_err(WalletError::RejectCoinState)?;
     let last_coin_record = response
         .coin_states
         .into_iter()
         .next()
-        .ok_or(WalletError::UnknownCoin)?;
+        .ok_or(WalletError::UnknownCoin(launcher_id))?;
 
     last_coin_record
         .created_height
-        .ok_or(WalletError::UnknownCoin)
+        .ok_or(WalletError::UnknownCoin(launcher_id))
 }
 
 pub enum DataStoreInnerSpend {
@@ -863,21 +1427,15 @@ pub fn oracle_spend(
     datastore: DataStore,
     fee: u64,
 ) -> Result<SuccessResponse, WalletError> {
-    let Some(DelegatedPuzzle::Oracle(oracle_ph, oracle_fee)) = datastore
-        .info
-        .delegated_puzzles
-        .iter()
-        .find(|dp| matches!(dp, DelegatedPuzzle::Oracle(_, _)))
-    else {
-        return Err(WalletError::Permission);
-    };
+    let ctx = &mut SpendContext::new();
+
+    let store_puzzle_hash = datastore.coin.puzzle_hash;
+    let (new_spend, new_datastore, oracle_fee) = spend_store_as_oracle(ctx, datastore)?;
 
     let spender_puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(spender_synthetic_key).into();
 
     let total_amount = oracle_fee + fee;
 
-    let ctx = &mut SpendContext::new();
-
     let lead_coin = selected_coins[0];
     let lead_coin_name = lead_coin.coin_id();
 
@@ -890,10 +1448,8 @@ pub fn oracle_spend(
         )?;
     }
 
-    let assert_oracle_conds = Conditions::new().assert_puzzle_announcement(announcement_id(
-        datastore.coin.puzzle_hash,
-        Bytes::new("$".into()),
-    ));
+    let assert_oracle_conds = Conditions::new()
+        .assert_puzzle_announcement(announcement_id(store_puzzle_hash, Bytes::new("$".into())));
 
     let mut lead_coin_conditions = assert_oracle_conds;
     if total_amount_from_coins > total_amount {
@@ -908,7 +1464,31 @@ pub fn oracle_spend(
     }
     ctx.spend_p2_coin(lead_coin, spender_synthetic_key, lead_coin_conditions)?;
 
-    let inner_datastore_spend = OracleLayer::new(*oracle_ph, *oracle_fee)
+    ctx.insert(new_spend);
+
+    Ok(SuccessResponse {
+        coin_spends: ctx.take(),
+        new_datastore,
+    })
+}
+
+// Spends a store through its oracle delegated puzzle. Also returns the new store and the fee
+// the oracle charges, which the spender has to pay for and assert the store's announcement.
+pub(crate) fn spend_store_as_oracle(
+    ctx: &mut SpendContext,
+    datastore: DataStore,
+) -> Result<(CoinSpend, DataStore, u64), WalletError> {
+    let Some(DelegatedPuzzle::Oracle(oracle_ph, oracle_fee)) = datastore
+        .info
+        .delegated_puzzles
+        .iter()
+        .find(|dp| matches!(dp, DelegatedPuzzle::Oracle(_, _)))
+        .copied()
+    else {
+        return Err(WalletError::Permission);
+    };
+
+    let inner_datastore_spend = OracleLayer::new(oracle_ph, oracle_fee)
         .ok_or(DriverError::OddOracleFee)?
         .construct_spend(ctx, ())?;
 
@@ -918,12 +1498,8 @@ pub fn oracle_spend(
     let new_datastore =
         DataStore::from_spend(&mut ctx.allocator, &new_spend, &parent_delegated_puzzles)?
             .ok_or(WalletError::Parse)?;
-    ctx.insert(new_spend.clone());
 
-    Ok(SuccessResponse {
-        coin_spends: ctx.take(),
-        new_datastore,
-    })
+    Ok((new_spend, new_datastore, oracle_fee))
 }
 
 pub fn add_fee(
@@ -973,30 +1549,15 @@ pub fn secret_key_to_synthetic_key(sk: SecretKey) -> SecretKey {
     sk.derive_synthetic()
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum TargetNetwork {
-    Mainnet,
-    Testnet11,
-}
-
-impl TargetNetwork {
-    fn get_constants(&self) -> &ConsensusConstants {
-        match self {
-            TargetNetwork::Mainnet => &MAINNET_CONSTANTS,
-            TargetNetwork::Testnet11 => &TESTNET11_CONSTANTS,
-        }
-    }
-}
-
 pub fn sign_coin_spends(
     coin_spends: Vec<CoinSpend>,
     private_keys: Vec<SecretKey>,
-    network: TargetNetwork,
+    network: &TargetNetwork,
 ) -> Result<Signature, SignerError> {
     let mut allocator = Allocator::new();
 
     let required_signatures =
-        RequiredSignature::from_coin_spends(&mut allocator, &coin_spends, network.get_constants())?;
+        RequiredSignature::from_coin_spends(&mut allocator, &coin_spends, &network.constants())?;
 
     let key_pairs = private_keys
         .iter()
@@ -1025,38 +1586,67 @@ pub fn sign_coin_spends(
 }
 
 pub async fn broadcast_spend_bundle(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     spend_bundle: SpendBundle,
 ) -> Result<TransactionAck, WalletError> {
-    peer.send_transaction(spend_bundle)
-        .await
-        .map_err(WalletError::Client)
+    peer.send_transaction(spend_bundle).await
 }
 
-pub async fn get_header_hash(peer: &Peer, height: u32) -> Result<Bytes32, WalletError> {
-    let resp: Result<RespondBlockHeader, RejectHeaderRequest> = peer
-        .request_fallible(RequestBlockHeader { height })
-        .await
-        .map_err(WalletError::Client)?;
+/// Mempool inclusion status of a transaction the node keeps in its pending pool.
+pub(crate) const MEMPOOL_INCLUSION_PENDING: u8 = 2;
+
+/// Mempool inclusion status of a transaction the node refused.
+pub(crate) const MEMPOOL_INCLUSION_FAILED: u8 = 3;
+
+/// Broadcasts a spend bundle and returns its id, failing with `TransactionRejected` if the
+/// mempool refused it. Bundles the node keeps in its pending pool are considered accepted.
+pub async fn send_spend_bundle(
+    peer: &impl WalletProtocol,
+    spend_bundle: SpendBundle,
+) -> Result<Bytes32, WalletError> {
+    let ack = peer.send_transaction(spend_bundle).await?;
+
+    if ack.status == MEMPOOL_INCLUSION_FAILED {
+        return Err(WalletError::TransactionRejected {
+            status: ack.status,
+            error: ack.error.unwrap_or_default(),
+        });
+    }
+
+    Ok(ack.txid)
+}
 
-    resp.map_err(|_| WalletError::RejectHeaderRequest)
-        .map(|resp| resp.header_block.header_hash())
+pub async fn get_header_hash(
+    peer: &impl WalletProtocol,
+    height: u32,
+) -> Result<Bytes32, WalletError> {
+    peer.request_block_header(height)
+        .await?
+        .map_err(WalletError::RejectHeaderRequest)
+        .map(|header| header.header_hash)
 }
 
-pub async fn get_fee_estimate(peer: &Peer, target_time_seconds: u64) -> Result<u64, WalletError> {
+/// The genesis challenge of the peer's network, which is the previous header hash of block 0.
+pub async fn get_genesis_challenge(peer: &impl WalletProtocol) -> Result<Bytes32, WalletError> {
+    peer.request_block_header(0)
+        .await?
+        .map_err(WalletError::RejectHeaderRequest)
+        .map(|header| header.prev_header_hash)
+}
+
+pub async fn get_fee_estimate(
+    peer: &impl WalletProtocol,
+    target_time_seconds: u64,
+) -> Result<u64, WalletError> {
     let target_time_seconds = target_time_seconds
         + SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
             .as_secs();
 
-    let resp: RespondFeeEstimates = peer
-        .request_infallible(RequestFeeEstimates {
-            time_targets: vec![target_time_seconds],
-        })
-        .await
-        .map_err(WalletError::Client)?;
-    let fee_estimate_group = resp.estimates;
+    let fee_estimate_group = peer
+        .request_fee_estimates(vec![target_time_seconds])
+        .await?;
 
     if let Some(error_message) = fee_estimate_group.error {
         return Err(WalletError::FeeEstimateRejection(error_message));
@@ -1076,16 +1666,15 @@ pub async fn get_fee_estimate(peer: &Peer, target_time_seconds: u64) -> Result<u
 }
 
 pub async fn is_coin_spent(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     coin_id: Bytes32,
     last_height: Option<u32>,
     last_header_hash: Bytes32,
 ) -> Result<bool, WalletError> {
     let response = peer
         .request_coin_state(vec![coin_id], last_height, last_header_hash, false)
-        .await
-        .map_err(WalletError::Client)?
-        .map_err(|_| WalletError::RejectCoinState)?;
+        .await?
+        .map_err(WalletError::RejectCoinState)?;
 
     if let Some(coin_state) = response.coin_states.first() {
         return Ok(coin_state.spent_height.is_some());
@@ -1116,7 +1705,15 @@ pub fn verify_signature(
     Ok(verify(&sig, &pk, make_message(message)?))
 }
 
-pub fn get_cost(coin_spends: Vec<CoinSpend>) -> Result<u64, WalletError> {
+pub fn get_cost(coin_spends: Vec<CoinSpend>, network: &TargetNetwork) -> Result<u64, WalletError> {
+    Ok(get_fee_and_cost(coin_spends, network)?.1)
+}
+
+/// The fee (removals minus additions) and the cost of the given coin spends.
+pub fn get_fee_and_cost(
+    coin_spends: Vec<CoinSpend>,
+    network: &TargetNetwork,
+) -> Result<(u64, u64), WalletError> {
     let mut alloc = Allocator::new();
 
     let generator = solution_generator(
@@ -1132,12 +1729,87 @@ pub fn get_cost(coin_spends: Vec<CoinSpend>) -> Result<u64, WalletError> {
         [],
         u64::MAX,
         MEMPOOL_MODE,
-        TargetNetwork::Mainnet.get_constants(),
+        &network.constants(),
     )?;
 
     let conds = OwnedSpendBundleConditions::from(&alloc, conds);
+    let fee = conds.removal_amount.saturating_sub(conds.addition_amount);
 
-    Ok(conds.cost)
+    Ok((fee.try_into().map_err(|_| WalletError::Parse)?, conds.cost))
+}
+
+/// Validates a spend bundle like the mempool would, except for the coin set: runs its puzzles,
+/// checks their conditions, including announcements and concurrent spend assertions, and
+/// verifies the aggregated signature.
+///
+/// Returns the conditions, with the additions, removals, fees and time locks the node would
+/// check against its peak.
+pub fn validate_spend_bundle(
+    spend_bundle: &SpendBundle,
+    network: &TargetNetwork,
+) -> Result<OwnedSpendBundleConditions, WalletError> {
+    let constants = network.constants();
+
+    // the rules of the latest fork apply, since the bundle would be included after the peak
+    let (conditions, _, _) = validate_clvm_and_signature(
+        spend_bundle,
+        constants.max_block_cost_clvm,
+        &constants,
+        u32::MAX,
+    )
+    .map_err(WalletError::InvalidSpendBundle)?;
+
+    Ok(conditions)
+}
+
+/// Validates a spend bundle with `validate_spend_bundle`, and checks that the peer knows every
+/// coin it spends, except for the ones it creates itself, and that none of them are spent. Coin
+/// states are requested in chunks of `MAX_COIN_IDS_PER_REQUEST`.
+pub async fn validate_spend_bundle_on_chain(
+    peer: &impl WalletProtocol,
+    spend_bundle: &SpendBundle,
+    network: &TargetNetwork,
+) -> Result<OwnedSpendBundleConditions, WalletError> {
+    let conditions = validate_spend_bundle(spend_bundle, network)?;
+
+    let additions: HashSet<Bytes32> = conditions
+        .spends
+        .iter()
+        .flat_map(|spend| {
+            spend
+                .create_coin
+                .iter()
+                .map(|(puzzle_hash, amount, _)| Coin::new(spend.coin_id, *puzzle_hash, *amount))
+        })
+        .map(|coin| coin.coin_id())
+        .collect();
+    let removals: Vec<Bytes32> = conditions
+        .spends
+        .iter()
+        .map(|spend| spend.coin_id)
+        .filter(|coin_id| !additions.contains(coin_id))
+        .collect();
+
+    for chunk in removals.chunks(MAX_COIN_IDS_PER_REQUEST) {
+        let response = peer
+            .request_coin_state(chunk.to_vec(), None, network.genesis_challenge, false)
+            .await?
+            .map_err(WalletError::RejectCoinState)?;
+
+        for &coin_id in chunk {
+            let coin_state = response
+                .coin_states
+                .iter()
+                .find(|coin_state| coin_state.coin.coin_id() == coin_id)
+                .ok_or(WalletError::UnknownCoin(coin_id))?;
+
+            if let Some(height) = coin_state.spent_height {
+                return Err(WalletError::CoinAlreadySpent { coin_id, height });
+            }
+        }
+    }
+
+    Ok(conditions)
 }
 
 pub struct PossibleLaunchersResponse {
@@ -1147,7 +1819,7 @@ pub struct PossibleLaunchersResponse {
 }
 
 pub async fn look_up_possible_launchers(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     previous_height: Option<u32>,
     previous_header_hash: Bytes32,
 ) -> Result<PossibleLaunchersResponse, WalletError> {
@@ -1178,31 +1850,26 @@ pub async fn look_up_possible_launchers(
 }
 
 pub async fn subscribe_to_coin_states(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     coin_id: Bytes32,
     previous_height: Option<u32>,
     previous_header_hash: Bytes32,
 ) -> Result<Option<u32>, WalletError> {
     let response = peer
         .request_coin_state(vec![coin_id], previous_height, previous_header_hash, true)
-        .await
-        .map_err(WalletError::Client)?
-        .map_err(|_| WalletError::RejectCoinState)?;
+        .await?
+        .map_err(WalletError::RejectCoinState)?;
 
     if let Some(coin_state) = response.coin_states.first() {
         return Ok(coin_state.spent_height);
     }
 
-    Err(WalletError::UnknownCoin)
+    Err(WalletError::UnknownCoin(coin_id))
 }
 
 pub async fn unsubscribe_from_coin_states(
-    peer: &Peer,
+    peer: &impl WalletProtocol,
     coin_id: Bytes32,
 ) -> Result<(), WalletError> {
-    peer.remove_coin_subscriptions(Some(vec![coin_id]))
-        .await
-        .map_err(WalletError::Client)?;
-
-    Ok(())
+    peer.remove_coin_subscriptions(Some(vec![coin_id])).await
 }
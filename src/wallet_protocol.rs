@@ -0,0 +1,290 @@
+use std::future::Future;
+
+use chia::protocol::{
+    Bytes32, CoinStateFilters, FeeEstimateGroup, HeaderBlock, PuzzleSolutionResponse,
+    RejectCoinState, RejectHeaderRequest, RejectPuzzleSolution, RejectPuzzleState,
+    RequestBlockHeader, RequestFeeEstimates, RespondBlockHeader, RespondCoinState,
+    RespondFeeEstimates, RespondPuzzleState, SpendBundle, TransactionAck,
+};
+use chia_wallet_sdk::Peer;
+use std::sync::Arc;
+
+use crate::simulator::ChainSimulator;
+use crate::wallet::WalletError;
+
+/// Either the response to a request or the node's rejection of it.
+pub type Response<T, E> = std::result::Result<T, E>;
+
+/// The parts of a block header the driver needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeaderInfo {
+    pub height: u32,
+    pub header_hash: Bytes32,
+    pub prev_header_hash: Bytes32,
+    /// Only transaction blocks have a timestamp.
+    pub timestamp: Option<u64>,
+}
+
+impl From<&HeaderBlock> for BlockHeaderInfo {
+    fn from(header_block: &HeaderBlock) -> Self {
+        Self {
+            height: header_block.height(),
+            header_hash: header_block.header_hash(),
+            prev_header_hash: header_block.prev_header_hash(),
+            timestamp: header_block
+                .foliage_transaction_block
+                .as_ref()
+                .map(|block| block.timestamp),
+        }
+    }
+}
+
+/// The wallet protocol requests the driver makes to a full node.
+///
+/// Implemented by `Peer` for real nodes and by `ChainSimulator` for tests. Requests the node
+/// can reject return the rejection as the inner error, like `Peer` does.
+pub trait WalletProtocol: Send + Sync {
+    fn request_puzzle_state(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        filters: CoinStateFilters,
+        subscribe_when_finished: bool,
+    ) -> impl Future<Output = Result<Response<RespondPuzzleState, RejectPuzzleState>, WalletError>> + Send;
+
+    fn request_coin_state(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        subscribe: bool,
+    ) -> impl Future<Output = Result<Response<RespondCoinState, RejectCoinState>, WalletError>> + Send;
+
+    fn request_puzzle_and_solution(
+        &self,
+        coin_id: Bytes32,
+        height: u32,
+    ) -> impl Future<
+        Output = Result<Response<PuzzleSolutionResponse, RejectPuzzleSolution>, WalletError>,
+    > + Send;
+
+    fn request_block_header(
+        &self,
+        height: u32,
+    ) -> impl Future<Output = Result<Response<BlockHeaderInfo, RejectHeaderRequest>, WalletError>> + Send;
+
+    fn request_fee_estimates(
+        &self,
+        time_targets: Vec<u64>,
+    ) -> impl Future<Output = Result<FeeEstimateGroup, WalletError>> + Send;
+
+    fn send_transaction(
+        &self,
+        spend_bundle: SpendBundle,
+    ) -> impl Future<Output = Result<TransactionAck, WalletError>> + Send;
+
+    fn remove_coin_subscriptions(
+        &self,
+        coin_ids: Option<Vec<Bytes32>>,
+    ) -> impl Future<Output = Result<(), WalletError>> + Send;
+}
+
+impl WalletProtocol for Peer {
+    async fn request_puzzle_state(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        filters: CoinStateFilters,
+        subscribe_when_finished: bool,
+    ) -> Result<Response<RespondPuzzleState, RejectPuzzleState>, WalletError> {
+        Ok(Peer::request_puzzle_state(
+            self,
+            puzzle_hashes,
+            previous_height,
+            header_hash,
+            filters,
+            subscribe_when_finished,
+        )
+        .await?)
+    }
+
+    async fn request_coin_state(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        subscribe: bool,
+    ) -> Result<Response<RespondCoinState, RejectCoinState>, WalletError> {
+        Ok(
+            Peer::request_coin_state(self, coin_ids, previous_height, header_hash, subscribe)
+                .await?,
+        )
+    }
+
+    async fn request_puzzle_and_solution(
+        &self,
+        coin_id: Bytes32,
+        height: u32,
+    ) -> Result<Response<PuzzleSolutionResponse, RejectPuzzleSolution>, WalletError> {
+        Ok(Peer::request_puzzle_and_solution(self, coin_id, height).await?)
+    }
+
+    async fn request_block_header(
+        &self,
+        height: u32,
+    ) -> Result<Response<BlockHeaderInfo, RejectHeaderRequest>, WalletError> {
+        let response: Response<RespondBlockHeader, RejectHeaderRequest> =
+            self.request_fallible(RequestBlockHeader { height }).await?;
+
+        Ok(response.map(|response| BlockHeaderInfo::from(&response.header_block)))
+    }
+
+    async fn request_fee_estimates(
+        &self,
+        time_targets: Vec<u64>,
+    ) -> Result<FeeEstimateGroup, WalletError> {
+        let response: RespondFeeEstimates = self
+            .request_infallible(RequestFeeEstimates { time_targets })
+            .await?;
+
+        Ok(response.estimates)
+    }
+
+    async fn send_transaction(
+        &self,
+        spend_bundle: SpendBundle,
+    ) -> Result<TransactionAck, WalletError> {
+        Ok(Peer::send_transaction(self, spend_bundle).await?)
+    }
+
+    async fn remove_coin_subscriptions(
+        &self,
+        coin_ids: Option<Vec<Bytes32>>,
+    ) -> Result<(), WalletError> {
+        Peer::remove_coin_subscriptions(self, coin_ids).await?;
+
+        Ok(())
+    }
+}
+
+/// The chain a `ReconnectingPeer` talks to: a full node, or a simulator for tests.
+#[derive(Debug, Clone)]
+pub enum PeerBackend {
+    Node(Peer),
+    Simulator(Arc<ChainSimulator>),
+}
+
+impl WalletProtocol for PeerBackend {
+    async fn request_puzzle_state(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        filters: CoinStateFilters,
+        subscribe_when_finished: bool,
+    ) -> Result<Response<RespondPuzzleState, RejectPuzzleState>, WalletError> {
+        match self {
+            Self::Node(peer) => {
+                WalletProtocol::request_puzzle_state(
+                    peer,
+                    puzzle_hashes,
+                    previous_height,
+                    header_hash,
+                    filters,
+                    subscribe_when_finished,
+                )
+                .await
+            }
+            Self::Simulator(sim) => {
+                sim.request_puzzle_state(
+                    puzzle_hashes,
+                    previous_height,
+                    header_hash,
+                    filters,
+                    subscribe_when_finished,
+                )
+                .await
+            }
+        }
+    }
+
+    async fn request_coin_state(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        previous_height: Option<u32>,
+        header_hash: Bytes32,
+        subscribe: bool,
+    ) -> Result<Response<RespondCoinState, RejectCoinState>, WalletError> {
+        match self {
+            Self::Node(peer) => {
+                WalletProtocol::request_coin_state(
+                    peer,
+                    coin_ids,
+                    previous_height,
+                    header_hash,
+                    subscribe,
+                )
+                .await
+            }
+            Self::Simulator(sim) => {
+                sim.request_coin_state(coin_ids, previous_height, header_hash, subscribe)
+                    .await
+            }
+        }
+    }
+
+    async fn request_puzzle_and_solution(
+        &self,
+        coin_id: Bytes32,
+        height: u32,
+    ) -> Result<Response<PuzzleSolutionResponse, RejectPuzzleSolution>, WalletError> {
+        match self {
+            Self::Node(peer) => {
+                WalletProtocol::request_puzzle_and_solution(peer, coin_id, height).await
+            }
+            Self::Simulator(sim) => sim.request_puzzle_and_solution(coin_id, height).await,
+        }
+    }
+
+    async fn request_block_header(
+        &self,
+        height: u32,
+    ) -> Result<Response<BlockHeaderInfo, RejectHeaderRequest>, WalletError> {
+        match self {
+            Self::Node(peer) => peer.request_block_header(height).await,
+            Self::Simulator(sim) => sim.request_block_header(height).await,
+        }
+    }
+
+    async fn request_fee_estimates(
+        &self,
+        time_targets: Vec<u64>,
+    ) -> Result<FeeEstimateGroup, WalletError> {
+        match self {
+            Self::Node(peer) => peer.request_fee_estimates(time_targets).await,
+            Self::Simulator(sim) => sim.request_fee_estimates(time_targets).await,
+        }
+    }
+
+    async fn send_transaction(
+        &self,
+        spend_bundle: SpendBundle,
+    ) -> Result<TransactionAck, WalletError> {
+        match self {
+            Self::Node(peer) => WalletProtocol::send_transaction(peer, spend_bundle).await,
+            Self::Simulator(sim) => sim.send_transaction(spend_bundle).await,
+        }
+    }
+
+    async fn remove_coin_subscriptions(
+        &self,
+        coin_ids: Option<Vec<Bytes32>>,
+    ) -> Result<(), WalletError> {
+        match self {
+            Self::Node(peer) => WalletProtocol::remove_coin_subscriptions(peer, coin_ids).await,
+            Self::Simulator(sim) => sim.remove_coin_subscriptions(coin_ids).await,
+        }
+    }
+}